@@ -0,0 +1,44 @@
+//! Compares the time spent parsing instance documents with serde_json and
+//! simd-json.
+//!
+//! Run with `cargo bench -p grill --features simd --bench parse`.
+
+use grill::backend::{SerdeJson, SimdJson, ValueBackend};
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 200;
+
+fn document() -> String {
+    let items: Vec<String> = (0..5_000)
+        .map(|i| {
+            format!(
+                r#"{{"id":{},"name":"item {}","tags":["a","b","c"],"price":{}.5,"active":{}}}"#,
+                i,
+                i,
+                i,
+                i % 2 == 0
+            )
+        })
+        .collect();
+    format!(r#"{{"items":[{}]}}"#, items.join(","))
+}
+
+fn bench<B: ValueBackend>(doc: &str) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut buf = doc.as_bytes().to_vec();
+        std::hint::black_box(B::from_slice(&mut buf).unwrap());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let doc = document();
+    println!("document: {} bytes", doc.len());
+    for (name, elapsed) in [
+        (SerdeJson::name(), bench::<SerdeJson>(&doc)),
+        (SimdJson::name(), bench::<SimdJson>(&doc)),
+    ] {
+        println!("{:>12}: {:?} per parse", name, elapsed);
+    }
+}
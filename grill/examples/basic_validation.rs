@@ -0,0 +1,37 @@
+//! Compiles a schema from an in-memory source document and validates a valid
+//! and an invalid instance against it.
+//!
+//! Run with `cargo run -p grill --example basic_validation`.
+
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt, Uri};
+use serde_json::json;
+
+const ID: &str = "https://example.com/person.json";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let interrogator = Interrogator::builder()
+        .json_schema_2020_12()
+        .source(
+            AbsoluteUri::parse(ID)?,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$id": ID,
+                "patternProperties": {
+                    "^name$": { "pattern": "^[A-Z][a-z]+$" },
+                    "^age$": { "minimum": 0, "maximum": 150 }
+                }
+            }),
+        )
+        .compile(ID)
+        .build()?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    for instance in [
+        json!({ "name": "Ada", "age": 36 }),
+        json!({ "name": "ada", "age": -1 }),
+    ] {
+        let eval = schema.evaluate(&instance, OutputFmt::Basic)?;
+        println!("{} is valid: {}", instance, eval.is_valid());
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! Compares numbers which are equal as `f64`s but not as written, which
+//! requires the `big_num` feature.
+//!
+//! Run with `cargo run -p grill --example big_num --features big_num`.
+
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt, Precision, Uri};
+use serde_json::Value;
+
+const ID: &str = "https://example.com/balance.json";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let source: Value = serde_json::from_str(&format!(
+        r#"{{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": "{}",
+            "const": 12345678901234567890.5
+        }}"#,
+        ID
+    ))?;
+    let interrogator = Interrogator::builder()
+        .json_schema_2020_12()
+        .source(AbsoluteUri::parse(ID)?, source)
+        .build()?;
+    // precision applies to schemas compiled after it is set
+    interrogator.set_precision(Precision::Arbitrary)?;
+    interrogator.compile_all([ID])?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    for instance in ["12345678901234567890.5", "12345678901234567890.6"] {
+        let eval = schema.evaluate(&serde_json::from_str::<Value>(instance)?, OutputFmt::Basic)?;
+        println!("{} is valid: {}", instance, eval.is_valid());
+    }
+    Ok(())
+}
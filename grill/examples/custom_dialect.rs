@@ -0,0 +1,75 @@
+//! Derives a dialect from 2020-12 which adds a `"trimmed"` assertion,
+//! registers the source of its meta schema, and validates instances against
+//! a schema which declares the dialect with `"$schema"`.
+//!
+//! Run with `cargo run -p grill --example custom_dialect`.
+
+use grill::{
+    draft::SCHEMA_2020_12_URI, keyword::custom, plugin::Registrar, uri::AbsoluteUri, Error,
+    Interrogator, OutputFmt, Uri, Vocabulary,
+};
+use serde_json::{json, Value};
+
+const DIALECT: &str = "https://example.com/dialect/trimmed";
+const VOCABULARY: &str = "https://example.com/vocab/trimmed";
+const ID: &str = "https://example.com/username.json";
+
+/// Registers the dialect `DIALECT`, which is 2020-12 with the vocabulary
+/// `VOCABULARY`.
+fn trimmed(registrar: &mut Registrar) -> Result<(), Error> {
+    let mut vocabulary = Vocabulary::new(Uri::parse(VOCABULARY)?);
+    vocabulary.push(custom(
+        "trimmed",
+        |_, _, value: &Value| Ok(value.as_bool().unwrap_or(false)),
+        |trimmed: &bool, value: &Value, eval| {
+            if let Some(s) = value.as_str() {
+                if *trimmed && s.trim() != s {
+                    eval.set_error(format!("{:?} has leading or trailing whitespace", s));
+                }
+            }
+            Ok(())
+        },
+    ));
+    registrar.meta_schema_source(
+        AbsoluteUri::parse(DIALECT)?,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": DIALECT,
+            "$vocabulary": {
+                "https://json-schema.org/draft/2020-12/vocab/core": true,
+                "https://json-schema.org/draft/2020-12/vocab/applicator": true,
+                "https://json-schema.org/draft/2020-12/vocab/validation": true,
+                VOCABULARY: true
+            },
+            "$dynamicAnchor": "meta",
+            "allOf": [{ "$ref": "https://json-schema.org/draft/2020-12/schema" }],
+            "properties": { "trimmed": { "type": "boolean" } }
+        }),
+    );
+    registrar.derive_dialect(SCHEMA_2020_12_URI.clone(), Uri::parse(DIALECT)?, vocabulary);
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let interrogator = Interrogator::builder()
+        .json_schema_2020_12()
+        .plugin(trimmed)
+        .source(
+            AbsoluteUri::parse(ID)?,
+            json!({
+                "$schema": DIALECT,
+                "$id": ID,
+                "type": "string",
+                "trimmed": true
+            }),
+        )
+        .compile(ID)
+        .build()?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    for instance in [json!("ada"), json!(" ada ")] {
+        let eval = schema.evaluate(&instance, OutputFmt::Basic)?;
+        println!("{} is valid: {}", instance, eval.is_valid());
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Registers a `"format"` which is not part of any draft and validates
+//! instances against a schema which uses it.
+//!
+//! Run with `cargo run -p grill --example custom_format`.
+
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt, Uri};
+use serde_json::json;
+
+const ID: &str = "https://example.com/sku.json";
+
+/// Returns `true` if `value` is a SKU such as `"ABC-1234"`.
+fn is_sku(value: &str) -> bool {
+    match value.split_once('-') {
+        Some((prefix, number)) => {
+            prefix.len() == 3
+                && prefix.bytes().all(|b| b.is_ascii_uppercase())
+                && number.len() == 4
+                && number.bytes().all(|b| b.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let interrogator = Interrogator::builder()
+        .source(
+            AbsoluteUri::parse(ID)?,
+            json!({ "$id": ID, "type": "string", "format": "sku" }),
+        )
+        .build()?;
+    interrogator.formats().insert("sku", is_sku);
+    interrogator.compile_all([ID])?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    for instance in [json!("ABC-1234"), json!("abc-12")] {
+        let eval = schema.evaluate(&instance, OutputFmt::Basic)?;
+        println!("{} is a sku: {}", instance, eval.is_valid());
+    }
+    Ok(())
+}
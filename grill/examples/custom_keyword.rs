@@ -0,0 +1,60 @@
+//! Creates a keyword which is not part of any draft with
+//! [`keyword::custom`](grill::keyword::custom), attaches it to the 2020-12
+//! dialect with a plugin, and reports its annotation.
+//!
+//! Run with `cargo run -p grill --example custom_keyword`.
+
+use grill::{
+    draft::SCHEMA_2020_12_URI, error::InvalidKeywordError, keyword::custom, output::Verbose,
+    plugin::Registrar, uri::AbsoluteUri, Error, Interrogator, OutputFmt, Schema, Uri, Vocabulary,
+};
+use serde_json::{json, Value};
+
+const ID: &str = "https://example.com/order.json";
+
+/// Adds `"x-internal-routing"`, which annotates each instance with the
+/// queue it is routed to.
+fn routing(registrar: &mut Registrar) -> Result<(), Error> {
+    let mut vocabulary = Vocabulary::new(Uri::parse("https://example.com/vocab/routing")?);
+    vocabulary.push(custom(
+        "x-internal-routing",
+        |_, schema: &Schema, value: &Value| match value.as_str() {
+            Some(queue) => Ok(queue.to_string()),
+            None => Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "x-internal-routing".to_string(),
+                expected: "a string".to_string(),
+                value: value.clone(),
+            }
+            .into()),
+        },
+        |queue: &String, _: &Value, eval| {
+            eval.insert("queue".to_string(), queue)?;
+            Ok(())
+        },
+    ));
+    registrar.extend_dialect(SCHEMA_2020_12_URI.clone(), vocabulary);
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let interrogator = Interrogator::builder()
+        .json_schema_2020_12()
+        .plugin(routing)
+        .source(
+            AbsoluteUri::parse(ID)?,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$id": ID,
+                "type": "object",
+                "x-internal-routing": "orders"
+            }),
+        )
+        .compile(ID)
+        .build()?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    let eval = schema.evaluate(&json!({ "id": 7 }), OutputFmt::Verbose)?;
+    println!("{}", serde_json::to_string_pretty(&Verbose::from(&eval))?);
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! Lists the optional features grill was compiled with and shows the error
+//! returned when a capability of a disabled feature is requested.
+//!
+//! This example requires no features, so it also builds with
+//! `--no-default-features` and under any combination of features.
+//!
+//! Run with `cargo run -p grill --example features --no-default-features`.
+
+use grill::{Feature, Interrogator, Precision};
+
+fn main() {
+    for feature in Feature::ALL {
+        let state = if feature.is_enabled() {
+            "enabled"
+        } else {
+            "disabled"
+        };
+        println!("{:<14} {}", feature.name(), state);
+    }
+
+    let interrogator = Interrogator::new();
+    match interrogator.set_precision(Precision::Arbitrary) {
+        Ok(_) => println!("numbers are compared with arbitrary precision"),
+        Err(err) => println!("{}", err),
+    }
+}
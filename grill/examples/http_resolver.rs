@@ -0,0 +1,32 @@
+//! Resolves a schema, and the documents it references, over HTTP with an
+//! [`HttpResolver`] restricted to a single host, then validates an instance
+//! against it.
+//!
+//! Run with `cargo run -p grill --features http --example http_resolver`.
+
+use grill::{HttpResolver, Interrogator, OutputFmt, Uri};
+use serde_json::json;
+use std::time::Duration;
+
+const ID: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let resolver = HttpResolver::new()
+        .allow_host("json-schema.org")
+        .timeout(Duration::from_secs(10));
+    let interrogator = Interrogator::builder()
+        .json_schema_2020_12()
+        .async_resolver(resolver)
+        .compile(ID)
+        .finish()
+        .await?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+
+    // the 2020-12 meta schema validates schemas
+    for instance in [json!({ "type": "string" }), json!({ "type": 7 })] {
+        let eval = schema.evaluate(&instance, OutputFmt::Basic)?;
+        println!("{} is a valid schema: {}", instance, eval.is_valid());
+    }
+    Ok(())
+}
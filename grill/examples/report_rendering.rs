@@ -0,0 +1,53 @@
+//! Renders the evaluation of an invalid instance as a [`Report`] in Markdown,
+//! HTML, and, with the `cli` feature, for a terminal, along with the
+//! standard output formats.
+//!
+//! Run with `cargo run -p grill --example report_rendering`, adding
+//! `--features cli` to render it for a terminal.
+
+#[cfg(feature = "cli")]
+use grill::report::TerminalOptions;
+use grill::{
+    output::{Basic, Detailed, Verbose},
+    report::Report,
+    uri::AbsoluteUri,
+    Interrogator, OutputFmt, Uri,
+};
+use serde_json::json;
+
+const ID: &str = "https://example.com/address.json";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let source = json!({
+        "$id": ID,
+        "patternProperties": {
+            "^zip$": { "pattern": "^[0-9]{5}$" },
+            "^country$": { "enum": ["CA", "MX", "US"] }
+        }
+    });
+    let interrogator = Interrogator::builder()
+        .source(AbsoluteUri::parse(ID)?, source.clone())
+        .compile(ID)
+        .build()?;
+    let schema = interrogator.schema(&Uri::parse(ID)?).expect("compiled");
+    let instance = json!({ "zip": "9021", "country": "UK" });
+    let eval = schema.evaluate(&instance, OutputFmt::Verbose)?;
+
+    let report = Report::from(&eval);
+    println!("{} error(s)\n", report.error_count());
+    println!("{}", report.to_markdown());
+    println!("{}", report.to_html());
+    #[cfg(feature = "cli")]
+    {
+        let options = TerminalOptions::new().color(true).schema(source);
+        println!("{}", report.render_terminal(&options));
+    }
+
+    let verbose = Verbose::from(&eval);
+    let detailed = Detailed::from(verbose.clone());
+    let basic = Basic::from(detailed.clone());
+    println!("{}", serde_json::to_string_pretty(&verbose)?);
+    println!("{}", serde_json::to_string_pretty(&detailed)?);
+    println!("{}", serde_json::to_string_pretty(&basic)?);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Seals the source documents and settings of an [`Interrogator`], writes
+//! them as JSON, and restores them into a new `Interrogator`, verifying the
+//! signature of the snapshot.
+//!
+//! The [`Signer`] and [`Verifier`] below are for illustration only; use a
+//! real signature scheme, such as HMAC-SHA256 or Ed25519, in practice.
+//!
+//! Run with `cargo run -p grill --features state --example state_serialization`.
+
+use grill::{
+    state::{Sealed, Signer, Verifier},
+    uri::AbsoluteUri,
+    Interrogator, OutputFmt, Uri,
+};
+use serde_json::json;
+
+const ID: &str = "https://example.com/port.json";
+const KEY: &[u8] = b"not-a-secret";
+
+/// "Signs" a digest by XORing it with `KEY`.
+struct Xor;
+
+impl Signer for Xor {
+    fn key_id(&self) -> String {
+        "xor".to_string()
+    }
+
+    fn sign(&self, digest: &[u8]) -> Vec<u8> {
+        xor(digest)
+    }
+}
+
+impl Verifier for Xor {
+    fn verify(&self, key_id: &str, digest: &[u8], signature: &[u8]) -> bool {
+        key_id == "xor" && xor(digest) == signature
+    }
+}
+
+fn xor(digest: &[u8]) -> Vec<u8> {
+    digest
+        .iter()
+        .zip(KEY.iter().cycle())
+        .map(|(d, k)| d ^ k)
+        .collect()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let original = Interrogator::builder()
+        .source(
+            AbsoluteUri::parse(ID)?,
+            json!({ "$id": ID, "type": "integer", "minimum": 1, "maximum": 65535 }),
+        )
+        .build()?;
+    let json = serde_json::to_string_pretty(&original.seal(Some(&Xor))?)?;
+    println!("{}", json);
+
+    let sealed: Sealed = serde_json::from_str(&json)?;
+    let restored = Interrogator::new();
+    restored.restore(&sealed, Some(&Xor))?;
+    restored.compile_all([ID])?;
+    let schema = restored.schema(&Uri::parse(ID)?).expect("compiled");
+
+    let eval = schema.evaluate(&json!(8080), OutputFmt::Flag)?;
+    println!("8080 is a valid port: {}", eval.is_valid());
+    Ok(())
+}
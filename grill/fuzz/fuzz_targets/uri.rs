@@ -0,0 +1,25 @@
+//! Parses arbitrary text as a URI and exercises each accessor grill relies
+//! on, none of which may panic, including on multi-byte UTF-8.
+#![no_main]
+
+use grill::uri::{AbsoluteUri, Uri};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(uri) = Uri::parse(text) {
+        let _ = uri.scheme();
+        let _ = uri.fragment();
+        let formatted = uri.to_string();
+        let _ = Uri::parse(&formatted);
+        let base = Uri::parse("https://example.com/ä/ß.json#/€").unwrap();
+        let _ = base.resolve(&uri).to_string();
+    }
+    if let Ok(uri) = AbsoluteUri::parse(text) {
+        let _ = uri.host_to_string();
+        let _ = uri.port();
+        let _ = uri.path();
+        let mut without = uri.clone();
+        without.set_fragment(None);
+        let _ = without.to_string();
+    }
+});
@@ -0,0 +1,53 @@
+//! Assembles a URI from arbitrary components, each of which may contain
+//! multi-byte UTF-8, so that every component is exercised rather than only
+//! those a random string happens to delimit.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use grill::uri::{AbsoluteUri, Uri};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Debug, Arbitrary)]
+struct Components<'a> {
+    scheme: &'a str,
+    authority: &'a str,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl Components<'_> {
+    fn to_text(&self) -> String {
+        let mut text = format!("{}://{}/{}", self.scheme, self.authority, self.path);
+        if let Some(query) = self.query {
+            text.push('?');
+            text.push_str(query);
+        }
+        if let Some(fragment) = self.fragment {
+            text.push('#');
+            text.push_str(fragment);
+        }
+        text
+    }
+}
+
+fuzz_target!(|components: Components<'_>| {
+    let text = components.to_text();
+    if let Ok(uri) = AbsoluteUri::parse(&text) {
+        let _ = uri.host_to_string();
+        let _ = uri.port();
+        let _ = uri.path();
+        let _ = uri.fragment();
+        let _ = uri.to_string();
+    }
+    if let Ok(uri) = Uri::parse(&text) {
+        let relative = format!(
+            "{}?{}",
+            components.path,
+            components.query.unwrap_or_default()
+        );
+        if let Ok(relative) = Uri::parse(&relative) {
+            let _ = uri.resolve(&relative).to_string();
+        }
+    }
+});
@@ -0,0 +1,436 @@
+//! Experimental intersection and union of schemas.
+//!
+//! [`intersect_values`] produces a schema accepting the instances which both
+//! schemas accept, and [`union_values`] one accepting the instances which
+//! either accepts; see [`Schema::intersect`] and [`Schema::union`] for
+//! compiled [`Schema`]s. This is useful for computing the effective schema of
+//! layered configuration.
+//!
+//! Where the keywords of both schemas can be combined without changing their
+//! meaning (e.g. two `"type"`s, two ranges, or two sets of `"required"`
+//! properties), a single flat schema is produced. Otherwise the schemas are
+//! embedded as the members of an `"allOf"` or `"anyOf"`. References by JSON
+//! Pointer within an embedded schema which does not declare its own `"$id"`
+//! (e.g. `"#/$defs/name"`) are rewritten to point within the member, and
+//! schemas containing such references are never flattened, as a reference
+//! may point to a subschema which the combination changes.
+//!
+//! Values of `"enum"` are compared as by JSON Schema, so that `1` and `1.0`
+//! are the same value. A draft 4 `"exclusiveMinimum"` or
+//! `"exclusiveMaximum"`, which is a boolean modifying `"minimum"` or
+//! `"maximum"`, is only combined with an equal bound.
+//!
+//! Embedding a schema changes its dynamic scope, so the result is flagged as
+//! approximate, with [`Combined::exact`] set to `false`, if either schema
+//! contains `"$dynamicRef"` or `"$recursiveRef"`.
+//!
+//! ## Example
+//! ```
+//! use grill::algebra::{intersect_values, union_values};
+//! use serde_json::json;
+//!
+//! let base = json!({ "type": ["string", "null"], "maxLength": 64 });
+//! let overlay = json!({ "type": "string", "maxLength": 16, "minLength": 1 });
+//!
+//! let both = intersect_values(&base, &overlay);
+//! assert!(both.exact);
+//! assert_eq!(
+//!     both.value,
+//!     json!({ "type": "string", "minLength": 1, "maxLength": 16 })
+//! );
+//!
+//! let either = union_values(&json!({ "type": "string" }), &json!({ "type": "null" }));
+//! assert_eq!(either.value, json!({ "type": ["null", "string"] }));
+//! ```
+
+use crate::{keyword::equals, schema::contains_dynamic_reference, Schema};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// Keywords whose values combine under intersection by taking the greatest.
+const LOWER_BOUNDS: &[&str] = &[
+    "minimum",
+    "exclusiveMinimum",
+    "minLength",
+    "minItems",
+    "minProperties",
+    "minContains",
+];
+
+/// Keywords whose values combine under intersection by taking the least.
+const UPPER_BOUNDS: &[&str] = &[
+    "maximum",
+    "exclusiveMaximum",
+    "maxLength",
+    "maxItems",
+    "maxProperties",
+    "maxContains",
+];
+
+/// Keywords whose meaning depends on the location of the schema or on its
+/// sibling keywords, which prevent a flat combination.
+const CONTEXTUAL_KEYWORDS: &[&str] = &[
+    "$id",
+    "id",
+    "$schema",
+    "$ref",
+    "$dynamicRef",
+    "$recursiveRef",
+    "$anchor",
+    "$dynamicAnchor",
+    "$recursiveAnchor",
+    "$defs",
+    "definitions",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Groups of keywords which affect one another, e.g. `"additionalProperties"`
+/// applies to properties not listed in `"properties"`.
+const DEPENDENT_KEYWORDS: &[&[&str]] = &[
+    &["properties", "patternProperties", "additionalProperties"],
+    &["prefixItems", "items", "additionalItems"],
+    &["contains", "minContains", "maxContains"],
+    &["if", "then", "else"],
+];
+
+/// Bounds which, in draft 4, are modified by a boolean exclusive keyword.
+const DRAFT_4_BOUNDS: &[&[&str]] = &[
+    &["minimum", "exclusiveMinimum"],
+    &["maximum", "exclusiveMaximum"],
+];
+
+/// Keywords whose value is a reference which may be a JSON Pointer fragment.
+/// `"$recursiveRef"` is always `"#"`, which is handled as a dynamic reference.
+const POINTER_REFERENCE_KEYWORDS: &[&str] = &["$ref", "$dynamicRef"];
+
+/// Keywords whose value is instance data rather than schema.
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// The seven primitive types of JSON Schema.
+const TYPES: &[&str] = &[
+    "array", "boolean", "integer", "null", "number", "object", "string",
+];
+
+/// The result of combining two schemas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Combined {
+    /// The combined schema.
+    pub value: Value,
+    /// Whether `value` is equivalent to the combination. If `false`, `value`
+    /// is an approximation.
+    pub exact: bool,
+}
+
+/// Returns a schema accepting the instances which the [`Schema`]s `a` and `b`
+/// both accept.
+///
+/// If either [`Schema`] has an id which its source does not declare, the id
+/// is added as `"$id"` so that its references resolve the same when
+/// embedded.
+pub fn intersect(a: &Schema, b: &Schema) -> Combined {
+    intersect_values(&identified(a), &identified(b))
+}
+
+/// Returns a schema accepting the instances which either of the [`Schema`]s
+/// `a` and `b` accept. See [`intersect`] regarding ids.
+pub fn union(a: &Schema, b: &Schema) -> Combined {
+    union_values(&identified(a), &identified(b))
+}
+
+/// Returns a schema accepting the instances which the schema sources `a` and
+/// `b` both accept.
+///
+/// `a` and `b` are taken to be root schemas: references by JSON Pointer are
+/// rewritten relative to the root of the result.
+pub fn intersect_values(a: &Value, b: &Value) -> Combined {
+    let exact = !contains_dynamic_reference(a) && !contains_dynamic_reference(b);
+    let value = match (a, b) {
+        (Value::Bool(false), _) | (_, Value::Bool(false)) => Value::Bool(false),
+        (Value::Bool(true), other) | (other, Value::Bool(true)) => other.clone(),
+        _ if a == b => a.clone(),
+        (Value::Object(x), Value::Object(y)) => merge_all(x, y).unwrap_or_else(|| all_of(a, b)),
+        _ => all_of(a, b),
+    };
+    Combined { value, exact }
+}
+
+/// Returns a schema accepting the instances which either of the schema
+/// sources `a` and `b` accept. See [`intersect_values`] regarding references.
+pub fn union_values(a: &Value, b: &Value) -> Combined {
+    let exact = !contains_dynamic_reference(a) && !contains_dynamic_reference(b);
+    let value = match (a, b) {
+        (Value::Bool(true), _) | (_, Value::Bool(true)) => Value::Bool(true),
+        (Value::Bool(false), other) | (other, Value::Bool(false)) => other.clone(),
+        _ if a == b => a.clone(),
+        (Value::Object(x), Value::Object(y)) => merge_any(x, y).unwrap_or_else(|| any_of(a, b)),
+        _ => any_of(a, b),
+    };
+    Combined { value, exact }
+}
+
+fn identified(schema: &Schema) -> Value {
+    let mut source = (*schema.source()).clone();
+    if let (Some(id), Value::Object(obj)) = (schema.id(), &mut source) {
+        if !obj.contains_key("$id") && !obj.contains_key("id") {
+            obj.insert("$id".into(), id.to_string().into());
+        }
+    }
+    source
+}
+
+fn all_of(a: &Value, b: &Value) -> Value {
+    let mut obj = Map::new();
+    let members = vec![embed(a, "/allOf/0"), embed(b, "/allOf/1")];
+    obj.insert("allOf".into(), Value::Array(members));
+    Value::Object(obj)
+}
+
+fn any_of(a: &Value, b: &Value) -> Value {
+    let mut obj = Map::new();
+    let members = vec![embed(a, "/anyOf/0"), embed(b, "/anyOf/1")];
+    obj.insert("anyOf".into(), Value::Array(members));
+    Value::Object(obj)
+}
+
+/// Returns a copy of the root schema `value` to be embedded at `location`,
+/// with its references by JSON Pointer rewritten to point within it, unless
+/// it declares its own `"$id"`, against which they continue to resolve.
+fn embed(value: &Value, location: &str) -> Value {
+    let mut value = value.clone();
+    if !is_resource(&value) {
+        rebase(&mut value, location);
+    }
+    value
+}
+
+/// Returns `true` if `value` is a schema with its own base URI.
+fn is_resource(value: &Value) -> bool {
+    ["$id", "id"].iter().any(
+        |keyword| matches!(value.get(*keyword), Some(Value::String(id)) if !id.starts_with('#')),
+    )
+}
+
+/// Prefixes each reference by JSON Pointer within `value`, outside of
+/// embedded resources, with `location`.
+fn rebase(value: &mut Value, location: &str) {
+    match value {
+        Value::Object(obj) => {
+            for (keyword, value) in obj.iter_mut() {
+                if DATA_KEYWORDS.contains(&keyword.as_str()) {
+                    continue;
+                }
+                match value {
+                    Value::String(reference)
+                        if POINTER_REFERENCE_KEYWORDS.contains(&keyword.as_str()) =>
+                    {
+                        if let Some(pointer) = reference.strip_prefix('#') {
+                            if pointer.is_empty() || pointer.starts_with('/') {
+                                *reference = format!("#{}{}", location, pointer);
+                            }
+                        }
+                    }
+                    value if !is_resource(value) => rebase(value, location),
+                    _ => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut().filter(|item| !is_resource(item)) {
+                rebase(item, location);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns `true` if `value` contains a reference by JSON Pointer outside of
+/// embedded resources.
+fn has_pointer_reference(value: &Value) -> bool {
+    match value {
+        Value::Object(obj) => obj.iter().any(|(keyword, value)| {
+            if DATA_KEYWORDS.contains(&keyword.as_str()) {
+                return false;
+            }
+            match value {
+                Value::String(reference)
+                    if POINTER_REFERENCE_KEYWORDS.contains(&keyword.as_str()) =>
+                {
+                    reference == "#" || reference.starts_with("#/")
+                }
+                value => !is_resource(value) && has_pointer_reference(value),
+            }
+        }),
+        Value::Array(items) => items
+            .iter()
+            .any(|item| !is_resource(item) && has_pointer_reference(item)),
+        _ => false,
+    }
+}
+
+fn is_contextual(obj: &Map<String, Value>) -> bool {
+    obj.keys()
+        .any(|k| CONTEXTUAL_KEYWORDS.contains(&k.as_str()))
+        || obj.values().any(has_pointer_reference)
+}
+
+/// Returns `true` if the keywords of `x` and `y` can be combined without one
+/// altering the meaning of the other.
+fn is_separable(x: &Map<String, Value>, y: &Map<String, Value>) -> bool {
+    let draft_4 = |obj: &Map<String, Value>| {
+        ["exclusiveMinimum", "exclusiveMaximum"]
+            .iter()
+            .any(|k| matches!(obj.get(*k), Some(Value::Bool(_))))
+    };
+    let draft_4_bounds = if draft_4(x) || draft_4(y) {
+        DRAFT_4_BOUNDS
+    } else {
+        &[]
+    };
+    let mut groups = DEPENDENT_KEYWORDS.iter().chain(draft_4_bounds);
+    groups.all(|group| {
+        let uses = |obj: &Map<String, Value>| group.iter().any(|k| obj.contains_key(*k));
+        let only_properties = |obj: &Map<String, Value>| {
+            group
+                .iter()
+                .all(|k| *k == "properties" || !obj.contains_key(*k))
+        };
+        !uses(x)
+            || !uses(y)
+            || group.iter().all(|k| x.get(*k) == y.get(*k))
+            // properties listed by both are intersected
+            || (only_properties(x) && only_properties(y))
+    })
+}
+
+/// Combines `x` and `y` into a single schema object under intersection, or
+/// returns `None` if a pair of keywords can not be combined exactly.
+fn merge_all(x: &Map<String, Value>, y: &Map<String, Value>) -> Option<Value> {
+    if is_contextual(x) || is_contextual(y) || !is_separable(x, y) {
+        return None;
+    }
+    let mut merged = x.clone();
+    for (keyword, b) in y {
+        let a = match x.get(keyword) {
+            None => {
+                merged.insert(keyword.clone(), b.clone());
+                continue;
+            }
+            Some(a) if a == b => continue,
+            Some(a) => a,
+        };
+        let combined = match keyword.as_str() {
+            "type" => {
+                let types = intersect_types(&type_set(a)?, &type_set(b)?);
+                if types.is_empty() {
+                    return Some(Value::Bool(false));
+                }
+                type_value(types)
+            }
+            "required" => {
+                let mut required = a.as_array()?.clone();
+                for name in b.as_array()? {
+                    if !required.contains(name) {
+                        required.push(name.clone());
+                    }
+                }
+                Value::Array(required)
+            }
+            "enum" => {
+                let b = b.as_array()?;
+                let values: Vec<Value> = a
+                    .as_array()?
+                    .iter()
+                    .filter(|v| b.iter().any(|b| equals(v, b)))
+                    .cloned()
+                    .collect();
+                if values.is_empty() {
+                    return Some(Value::Bool(false));
+                }
+                Value::Array(values)
+            }
+            "properties" => {
+                let mut properties = a.as_object()?.clone();
+                for (name, b) in b.as_object()? {
+                    let value = match properties.get(name) {
+                        Some(a) => intersect_values(a, b).value,
+                        None => b.clone(),
+                    };
+                    properties.insert(name.clone(), value);
+                }
+                Value::Object(properties)
+            }
+            "allOf" => Value::Array(a.as_array()?.iter().chain(b.as_array()?).cloned().collect()),
+            k if LOWER_BOUNDS.contains(&k) => bound(a, b, |x, y| x >= y)?,
+            k if UPPER_BOUNDS.contains(&k) => bound(a, b, |x, y| x <= y)?,
+            _ => return None,
+        };
+        merged.insert(keyword.clone(), combined);
+    }
+    Some(Value::Object(merged))
+}
+
+/// Combines `x` and `y` into a single schema object under union, or returns
+/// `None` if they can not be combined exactly.
+fn merge_any(x: &Map<String, Value>, y: &Map<String, Value>) -> Option<Value> {
+    if x.len() != 1 || y.len() != 1 {
+        return None;
+    }
+    let (keyword, a) = x.iter().next()?;
+    let b = y.get(keyword)?;
+    let combined = match keyword.as_str() {
+        "type" => {
+            let types: BTreeSet<&str> = type_set(a)?.union(&type_set(b)?).copied().collect();
+            if TYPES.iter().all(|t| types.contains(t) || *t == "integer") {
+                return Some(Value::Bool(true));
+            }
+            type_value(types)
+        }
+        "enum" => {
+            let mut values = a.as_array()?.clone();
+            for value in b.as_array()? {
+                if !values.iter().any(|v| equals(v, value)) {
+                    values.push(value.clone());
+                }
+            }
+            Value::Array(values)
+        }
+        _ => return None,
+    };
+    let mut obj = Map::new();
+    obj.insert(keyword.clone(), combined);
+    Some(Value::Object(obj))
+}
+
+fn type_set(value: &Value) -> Option<BTreeSet<&str>> {
+    match value {
+        Value::String(t) => Some(std::iter::once(t.as_str()).collect()),
+        Value::Array(ts) => ts.iter().map(Value::as_str).collect(),
+        _ => None,
+    }
+}
+
+fn intersect_types<'a>(a: &BTreeSet<&'a str>, b: &BTreeSet<&'a str>) -> BTreeSet<&'a str> {
+    let mut types: BTreeSet<&str> = a.intersection(b).copied().collect();
+    // integers are numbers
+    if (a.contains("number") && b.contains("integer"))
+        || (a.contains("integer") && b.contains("number"))
+    {
+        types.insert("integer");
+    }
+    types
+}
+
+fn type_value(types: BTreeSet<&str>) -> Value {
+    let mut types: Vec<Value> = types.into_iter().map(Value::from).collect();
+    if types.len() == 1 {
+        types.remove(0)
+    } else {
+        Value::Array(types)
+    }
+}
+
+fn bound(a: &Value, b: &Value, keep_a: impl Fn(f64, f64) -> bool) -> Option<Value> {
+    let (x, y) = (a.as_f64()?, b.as_f64()?);
+    Some(if keep_a(x, y) { a.clone() } else { b.clone() })
+}
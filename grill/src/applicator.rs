@@ -96,6 +96,17 @@ pub trait Applicator: DynClone + Send + Sync {
         interrogator: Interrogator,
         schema: Schema,
     ) -> Result<Option<Box<SetupFn>>, Error>;
+
+    /// Returns the keyword this `Applicator` implements, if any.
+    ///
+    /// The keyword is used to order and group compiled keywords according to
+    /// the [`KeywordOrder`](crate::dialect::KeywordOrder) of a
+    /// [`Dialect`](crate::Dialect). Use
+    /// [`Keyword`](crate::keyword::Keyword) to associate a keyword with an
+    /// `Applicator` which is a function.
+    fn keyword(&self) -> Option<&'static str> {
+        None
+    }
 }
 clone_trait_object!(Applicator);
 
@@ -0,0 +1,256 @@
+//! Abstraction over the JSON document model used for sources and instances.
+//!
+//! grill's keywords operate on [`serde_json::Value`]. A [`ValueBackend`]
+//! describes an alternative document model (e.g. a SIMD parser's owned
+//! values) in terms of how it is parsed and how it is viewed as a
+//! [`serde_json::Value`], so that instances and sources produced by it can be
+//! used without changes to keyword implementations.
+//!
+//! The [`Json`] trait is a read-only view of a JSON value which backends can
+//! implement to be inspected without conversion.
+//!
+//! With the `"simd"` feature enabled, `SimdJson` parses documents with
+//! simd-json.
+//!
+//! ## Example
+//! ```
+//! use grill::backend::{Json, Kind, SerdeJson, ValueBackend};
+//!
+//! let value = SerdeJson::from_str(r#"{"name": "grill"}"#).unwrap();
+//! assert_eq!(value.kind(), Kind::Object);
+//! assert_eq!(value.get("name").and_then(Json::as_str), Some("grill"));
+//! assert_eq!(SerdeJson::to_json(&value).unwrap()["name"], "grill");
+//! ```
+
+use crate::Error;
+use serde_json::Value;
+use std::{borrow::Cow, fmt};
+
+/// The type of a JSON value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool,
+    /// An integer or floating point number.
+    Number,
+    /// A string.
+    String,
+    /// An array of values.
+    Array,
+    /// An object of key/value pairs.
+    Object,
+}
+
+impl Kind {
+    /// Returns the name of the JSON type, as used by the `"type"` keyword.
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Bool => "boolean",
+            Kind::Number => "number",
+            Kind::String => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A read-only view of a JSON value.
+pub trait Json {
+    /// Returns the [`Kind`] of the value.
+    fn kind(&self) -> Kind;
+    /// Returns the value if it is a boolean.
+    fn as_bool(&self) -> Option<bool>;
+    /// Returns the value if it is a number which fits in an `f64`.
+    fn as_f64(&self) -> Option<f64>;
+    /// Returns the value if it is a string.
+    fn as_str(&self) -> Option<&str>;
+    /// Returns the length of the value if it is an array or object.
+    fn len(&self) -> Option<usize>;
+    /// Returns the member `key` if the value is an object.
+    fn get(&self, key: &str) -> Option<&Self>;
+    /// Returns the element at `index` if the value is an array.
+    fn index(&self, index: usize) -> Option<&Self>;
+
+    /// Returns `true` if the value is an empty array or object.
+    fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Returns `true` if the value is `null`.
+    fn is_null(&self) -> bool {
+        self.kind() == Kind::Null
+    }
+}
+
+impl Json for Value {
+    fn kind(&self) -> Kind {
+        match self {
+            Value::Null => Kind::Null,
+            Value::Bool(_) => Kind::Bool,
+            Value::Number(_) => Kind::Number,
+            Value::String(_) => Kind::String,
+            Value::Array(_) => Kind::Array,
+            Value::Object(_) => Kind::Object,
+        }
+    }
+    fn as_bool(&self) -> Option<bool> {
+        Value::as_bool(self)
+    }
+    fn as_f64(&self) -> Option<f64> {
+        Value::as_f64(self)
+    }
+    fn as_str(&self) -> Option<&str> {
+        Value::as_str(self)
+    }
+    fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(arr) => Some(arr.len()),
+            Value::Object(obj) => Some(obj.len()),
+            _ => None,
+        }
+    }
+    fn get(&self, key: &str) -> Option<&Self> {
+        self.as_object()?.get(key)
+    }
+    fn index(&self, index: usize) -> Option<&Self> {
+        self.as_array()?.get(index)
+    }
+}
+
+/// A JSON document model which can be used for sources and instances.
+pub trait ValueBackend: Send + Sync + 'static {
+    /// The owned value type of the backend.
+    type Value: Json + Clone + fmt::Debug + Send + Sync;
+
+    /// Returns the name of the backend.
+    fn name() -> &'static str;
+
+    /// Parses a JSON string.
+    fn from_str(s: &str) -> Result<Self::Value, Error>;
+
+    /// Parses JSON bytes. The buffer may be used as scratch space by
+    /// backends which parse in place.
+    fn from_slice(v: &mut [u8]) -> Result<Self::Value, Error>;
+
+    /// Returns `value` as a [`serde_json::Value`], borrowing it if the
+    /// representations are the same.
+    ///
+    /// Fails if `value` can not be represented as a [`serde_json::Value`].
+    fn to_json(value: &Self::Value) -> Result<Cow<'_, Value>, Error>;
+
+    /// Converts a [`serde_json::Value`] into the backend's representation.
+    ///
+    /// Fails if the backend can not represent `value`, e.g. a number beyond
+    /// the range of its numeric types.
+    fn from_json(value: Value) -> Result<Self::Value, Error>;
+}
+
+/// The [`ValueBackend`] of [`serde_json::Value`], used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerdeJson;
+
+impl ValueBackend for SerdeJson {
+    type Value = Value;
+
+    fn name() -> &'static str {
+        "serde_json"
+    }
+    fn from_str(s: &str) -> Result<Value, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+    fn from_slice(v: &mut [u8]) -> Result<Value, Error> {
+        Ok(serde_json::from_slice(v)?)
+    }
+    fn to_json(value: &Value) -> Result<Cow<'_, Value>, Error> {
+        Ok(Cow::Borrowed(value))
+    }
+    fn from_json(value: Value) -> Result<Value, Error> {
+        Ok(value)
+    }
+}
+
+/// The [`ValueBackend`] of simd-json's owned values, which are parsed with
+/// SIMD-accelerated UTF-8 and structural validation.
+///
+/// Values are converted to a [`serde_json::Value`] upon evaluation.
+#[cfg(feature = "simd")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SimdJson;
+
+#[cfg(feature = "simd")]
+impl ValueBackend for SimdJson {
+    type Value = simd_json::OwnedValue;
+
+    fn name() -> &'static str {
+        "simd-json"
+    }
+    fn from_str(s: &str) -> Result<Self::Value, Error> {
+        Self::from_slice(&mut s.as_bytes().to_vec())
+    }
+    fn from_slice(v: &mut [u8]) -> Result<Self::Value, Error> {
+        simd_json::to_owned_value(v).map_err(simd_error)
+    }
+    fn to_json(value: &Self::Value) -> Result<Cow<'_, Value>, Error> {
+        Ok(Cow::Owned(serde_json::to_value(value)?))
+    }
+    fn from_json(value: Value) -> Result<Self::Value, Error> {
+        simd_json::OwnedValue::try_from(value).map_err(simd_error)
+    }
+}
+
+#[cfg(feature = "simd")]
+pub(crate) fn simd_error(err: impl fmt::Display) -> Error {
+    <serde_json::Error as serde::de::Error>::custom(err).into()
+}
+
+#[cfg(feature = "simd")]
+impl Json for simd_json::OwnedValue {
+    fn kind(&self) -> Kind {
+        use simd_json::{OwnedValue, StaticNode};
+        match self {
+            OwnedValue::Static(StaticNode::Null) => Kind::Null,
+            OwnedValue::Static(StaticNode::Bool(_)) => Kind::Bool,
+            OwnedValue::Static(_) => Kind::Number,
+            OwnedValue::String(_) => Kind::String,
+            OwnedValue::Array(_) => Kind::Array,
+            OwnedValue::Object(_) => Kind::Object,
+        }
+    }
+    fn as_bool(&self) -> Option<bool> {
+        simd_json::prelude::ValueAsScalar::as_bool(self)
+    }
+    fn as_f64(&self) -> Option<f64> {
+        simd_json::prelude::ValueAsScalar::cast_f64(self)
+    }
+    fn as_str(&self) -> Option<&str> {
+        simd_json::prelude::ValueAsScalar::as_str(self)
+    }
+    fn len(&self) -> Option<usize> {
+        match self {
+            simd_json::OwnedValue::Array(arr) => Some(arr.len()),
+            simd_json::OwnedValue::Object(obj) => Some(obj.len()),
+            _ => None,
+        }
+    }
+    fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            simd_json::OwnedValue::Object(obj) => obj.get(key),
+            _ => None,
+        }
+    }
+    fn index(&self, index: usize) -> Option<&Self> {
+        match self {
+            simd_json::OwnedValue::Array(arr) => arr.get(index),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Cooperative cancellation of schema compilation.
+//!
+//! A [`CancellationToken`] is passed to
+//! [`Interrogator::insert_schema_cancellable`](crate::Interrogator::insert_schema_cancellable),
+//! which checks it before setting up each affected [`Schema`](crate::Schema).
+//! Once the token is cancelled or its deadline passes, compilation stops,
+//! the schemas and graph of the [`Interrogator`](crate::Interrogator) are
+//! restored to the state they were in before the call, each existing schema
+//! which was set up again is set up against the restored state, and
+//! [`Error::Cancelled`](crate::Error::Cancelled) is returned.
+//!
+//! This bounds request-scoped compiles in servers, e.g. by cancelling when
+//! the client disconnects.
+//!
+//! ## Example
+//! ```
+//! use grill::cancel::CancellationToken;
+//! use std::time::Duration;
+//!
+//! let token = CancellationToken::new();
+//! let handle = token.clone();
+//! assert!(token.check().is_ok());
+//! handle.cancel();
+//! assert!(token.is_cancelled());
+//! assert!(token.check().is_err());
+//!
+//! let expired = CancellationToken::with_timeout(Duration::ZERO);
+//! assert!(expired.is_cancelled());
+//! ```
+
+use crate::error::{CancelReason, CancelledError};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Signals that an operation should stop, either when
+/// [`cancel`](Self::cancel) is called or once a deadline passes.
+///
+/// Cloning a `CancellationToken` is cheap; clones share the same state, so a
+/// clone may be handed to another task in order to cancel.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// Creates and returns a new `CancellationToken` without a deadline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates and returns a new `CancellationToken` which is cancelled once
+    /// `deadline` passes.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// Creates and returns a new `CancellationToken` which is cancelled once
+    /// `timeout` elapses. A `timeout` too large for an [`Instant`] to
+    /// represent never elapses.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => Self::with_deadline(deadline),
+            None => Self::new(),
+        }
+    }
+
+    /// Returns the deadline of this `CancellationToken`, if one is set.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Cancels this `CancellationToken` and each of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns `true` if [`cancel`](Self::cancel) has been called or the
+    /// deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.check().is_err()
+    }
+
+    /// Returns a [`CancelledError`] if this `CancellationToken` has been
+    /// cancelled or its deadline has passed.
+    pub fn check(&self) -> Result<(), CancelledError> {
+        if self.cancelled.load(Ordering::Acquire) {
+            return Err(CancelledError {
+                reason: CancelReason::Cancelled,
+            });
+        }
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(CancelledError {
+                reason: CancelReason::DeadlineExceeded,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
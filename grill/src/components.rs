@@ -0,0 +1,223 @@
+//! Exports the [`Schema`]s of an [`Interrogator`] as a flat set of named,
+//! deduplicated definitions in the style of OpenAPI's `components/schemas`.
+//!
+//! Each top-level [`Schema`] and each member of its `"$defs"` or
+//! `"definitions"` becomes a definition. Definitions which are identical once
+//! their `"$ref"`s are made absolute are merged. `"$ref"`s to a definition,
+//! by pointer or by the `"$anchor"` it declares, are rewritten to point to
+//! its exported location, e.g. `"#/components/schemas/Address"`.
+//!
+//! Names are derived from the last token of the JSON Pointer fragment of a
+//! definition or, for a top-level [`Schema`], the last segment of the path of
+//! its `$id`, converted to `UpperCamelCase`. Collisions are resolved by
+//! appending a numeric suffix. Definitions are ordered by URI so names are
+//! stable between exports of the same schemas.
+//!
+//! ## Example
+//! ```
+//! use grill::components::Components;
+//! use grill::Uri;
+//! use serde_json::json;
+//!
+//! let user = json!({
+//!     "$id": "https://example.com/schemas/user.json",
+//!     "properties": { "address": { "$ref": "#/$defs/address" } },
+//!     "$defs": { "address": { "type": "object" } }
+//! });
+//! let id = Uri::parse("https://example.com/schemas/user.json").unwrap();
+//! let components = Components::from_sources(&[(id, user)]);
+//! assert_eq!(
+//!     components.schemas()["User"]["properties"]["address"]["$ref"],
+//!     "#/components/schemas/Address"
+//! );
+//! ```
+
+use crate::{
+    pointer::{self, Pointer},
+    Interrogator, Schema,
+};
+use heck::ToUpperCamelCase;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use uniresid::Uri;
+
+/// The prefix of each rewritten `"$ref"`.
+pub const COMPONENTS_PREFIX: &str = "#/components/schemas/";
+
+/// Returns the [`Components`] of each [`Schema`] in `interrogator`.
+pub fn export(interrogator: &Interrogator) -> Components {
+    Components::from_schemas(&interrogator.schemas())
+}
+
+/// A set of named, deduplicated schema definitions.
+#[derive(Debug, Clone, Default)]
+pub struct Components {
+    schemas: BTreeMap<String, Value>,
+    names: HashMap<String, String>,
+}
+
+impl Components {
+    /// Exports the given identified [`Schema`]s. Unidentified [`Schema`]s are
+    /// skipped.
+    pub fn from_schemas(schemas: &[Schema]) -> Self {
+        let sources: Vec<(Uri, Value)> = schemas
+            .iter()
+            .filter_map(|s| Some((s.id()?.as_ref().clone(), s.source().as_ref().clone())))
+            .collect();
+        Self::from_sources(&sources)
+    }
+
+    /// Exports the given schema sources, each paired with its `$id`.
+    pub fn from_sources(sources: &[(Uri, Value)]) -> Self {
+        let mut candidates = Vec::new();
+        for (id, source) in sources {
+            collect(id, source, &mut candidates);
+        }
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // deduplicate by the canonical form, in which refs are absolute
+        let mut representatives: Vec<(String, Value)> = Vec::new();
+        let mut aliases: HashMap<String, usize> = HashMap::new();
+        for (uri, value) in candidates {
+            if let Some(i) = representatives.iter().position(|(_, v)| *v == value) {
+                aliases.insert(uri, i);
+            } else {
+                aliases.insert(uri.clone(), representatives.len());
+                representatives.push((uri, value));
+            }
+        }
+
+        let mut taken: HashMap<String, usize> = HashMap::new();
+        let assigned: Vec<String> = representatives
+            .iter()
+            .map(|(uri, _)| {
+                let base = derive_name(uri);
+                let count = taken.entry(base.clone()).or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    base
+                } else {
+                    format!("{}{}", base, count)
+                }
+            })
+            .collect();
+        let names: HashMap<String, String> = aliases
+            .into_iter()
+            .map(|(uri, i)| (uri, assigned[i].clone()))
+            .collect();
+
+        let schemas = representatives
+            .into_iter()
+            .zip(assigned)
+            .map(|((_, mut value), name)| {
+                rewrite_refs(&mut value, &names);
+                (name, value)
+            })
+            .collect();
+        Components { schemas, names }
+    }
+
+    /// Returns the definitions, keyed by name.
+    pub fn schemas(&self) -> &BTreeMap<String, Value> {
+        &self.schemas
+    }
+
+    /// Returns the name of the definition exported for the schema at `uri`,
+    /// if one exists.
+    pub fn name_of(&self, uri: &str) -> Option<&str> {
+        self.names
+            .get(uri.trim_end_matches('#'))
+            .map(String::as_str)
+    }
+
+    /// Returns the definitions as a JSON object, keyed by name.
+    pub fn to_value(&self) -> Value {
+        Value::Object(self.schemas.clone().into_iter().collect())
+    }
+}
+
+/// Collects the definitions of `source`, with `"$defs"` and `"definitions"`
+/// removed and refs made absolute.
+fn collect(id: &Uri, source: &Value, candidates: &mut Vec<(String, Value)>) {
+    let mut root = source.clone();
+    absolutize_refs(&mut root, id);
+    let obj = if let Some(obj) = root.as_object_mut() {
+        obj
+    } else {
+        candidates.push((key(id), root));
+        return;
+    };
+    for keyword in ["$defs", "definitions"] {
+        if let Some(Value::Object(defs)) = obj.remove(keyword) {
+            for (name, def) in defs {
+                let location = Pointer::new(&[keyword, name.as_str()]);
+                let uri = format!("{}{}", key(id), pointer::to_fragment(&location));
+                push_anchored(&uri, id, def, candidates);
+            }
+        }
+    }
+    push_anchored(&key(id), id, root, candidates);
+}
+
+/// Pushes `value` at `uri` as well as under its `"$anchor"`, if present.
+fn push_anchored(uri: &str, id: &Uri, value: Value, candidates: &mut Vec<(String, Value)>) {
+    if let Some(anchor) = value.get("$anchor").and_then(Value::as_str) {
+        candidates.push((format!("{}#{}", key(id), anchor), value.clone()));
+    }
+    candidates.push((uri.to_string(), value));
+}
+
+fn absolutize_refs(value: &mut Value, id: &Uri) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(reference)) = obj.get_mut("$ref") {
+                if let Ok(uri) = Uri::parse(reference.as_str()) {
+                    *reference = key(&id.resolve(&uri));
+                }
+            }
+            obj.values_mut().for_each(|v| absolutize_refs(v, id));
+        }
+        Value::Array(arr) => arr.iter_mut().for_each(|v| absolutize_refs(v, id)),
+        _ => {}
+    }
+}
+
+fn rewrite_refs(value: &mut Value, names: &HashMap<String, String>) {
+    match value {
+        Value::Object(obj) => {
+            rewrite_ref(obj, names);
+            obj.values_mut().for_each(|v| rewrite_refs(v, names));
+        }
+        Value::Array(arr) => arr.iter_mut().for_each(|v| rewrite_refs(v, names)),
+        _ => {}
+    }
+}
+
+fn rewrite_ref(obj: &mut Map<String, Value>, names: &HashMap<String, String>) {
+    if let Some(Value::String(reference)) = obj.get_mut("$ref") {
+        if let Some(name) = names.get(reference.as_str()) {
+            *reference = format!("{}{}", COMPONENTS_PREFIX, name);
+        }
+    }
+}
+
+/// Returns the string form of `uri` without an empty fragment.
+fn key(uri: &Uri) -> String {
+    uri.to_string().trim_end_matches('#').to_string()
+}
+
+fn derive_name(uri: &str) -> String {
+    let (base, fragment) = uri.split_once('#').unwrap_or((uri, ""));
+    let raw = if fragment.is_empty() {
+        let segment = base.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+        segment.split('.').next().unwrap_or(segment)
+    } else {
+        fragment.rsplit('/').next().unwrap_or(fragment)
+    };
+    let name = raw.to_upper_camel_case();
+    if name.is_empty() {
+        "Schema".to_string()
+    } else {
+        name
+    }
+}
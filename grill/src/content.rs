@@ -0,0 +1,249 @@
+//! Decoders and parsers of the content vocabulary.
+//!
+//! [`Contents`] is the registry an [`Interrogator`](crate::Interrogator)
+//! consults when asserting `"contentEncoding"`, `"contentMediaType"`, and
+//! `"contentSchema"` with the [`content`](crate::keyword::content) keyword.
+//! A string instance is decoded by the [`DecodeFn`] registered for its
+//! `"contentEncoding"`, parsed by the [`ParseFn`] registered for its
+//! `"contentMediaType"`, and the parsed value is evaluated against the
+//! `"contentSchema"`.
+//!
+//! Asserting the content keywords is opt-in, with the
+//! [`content_assertion`](crate::keyword::content_assertion) plugin; by
+//! default, as the specification prescribes, they are annotations only, as
+//! applied by [`content_annotation`](crate::keyword::content_annotation).
+//!
+//! [`Contents::standard`] registers the `"base64"` and `"base16"` encodings
+//! and the `"application/json"` media type. Others, such as
+//! `"gzip+base64"` or `"application/yaml"`, can be registered with
+//! [`Contents::insert_decoder`] and [`Contents::insert_parser`].
+//!
+//! ## Example
+//! ```
+//! use grill::content::Contents;
+//! use serde_json::json;
+//!
+//! let contents = Contents::standard();
+//! assert_eq!(contents.decode("base64", "eyJhIjoxfQ==").unwrap().unwrap(), br#"{"a":1}"#);
+//! assert_eq!(
+//!     contents.parse("application/json; charset=utf-8", br#"{"a":1}"#).unwrap().unwrap(),
+//!     json!({ "a": 1 })
+//! );
+//! assert!(contents.decode("gzip+base64", "").is_none());
+//!
+//! contents.insert_parser("text/csv", |bytes: &[u8]| {
+//!     let text = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+//!     Ok(text.lines().map(|line| line.split(',').collect::<Vec<_>>()).collect::<Vec<_>>().into())
+//! });
+//! assert_eq!(contents.parse("text/csv", b"a,b").unwrap().unwrap(), json!([["a", "b"]]));
+//! ```
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// A function which decodes a string of a `"contentEncoding"` into bytes,
+/// returning a description of the failure if the string is malformed.
+pub type DecodeFn = dyn Fn(&str) -> Result<Vec<u8>, String> + Send + Sync;
+
+/// A function which parses the bytes of a `"contentMediaType"` into a
+/// [`Value`], returning a description of the failure if they are malformed.
+pub type ParseFn = dyn Fn(&[u8]) -> Result<Value, String> + Send + Sync;
+
+/// A registry of decoders, keyed by `"contentEncoding"`, and parsers, keyed
+/// by `"contentMediaType"`.
+///
+/// Cloning `Contents` is cheap; clones share the same registry.
+#[derive(Clone, Default)]
+pub struct Contents {
+    registry: Arc<RwLock<Arc<Registry>>>,
+}
+
+#[derive(Clone, Default)]
+struct Registry {
+    decoders: HashMap<String, Arc<DecodeFn>>,
+    parsers: HashMap<String, Arc<ParseFn>>,
+}
+
+impl Contents {
+    /// Creates and returns a new, empty `Contents` registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates and returns a `Contents` registry containing the `"base64"`
+    /// and `"base16"` encodings and the `"application/json"` media type.
+    pub fn standard() -> Self {
+        let contents = Self::new();
+        contents.insert_decoder("base64", decode_base64);
+        contents.insert_decoder("base16", decode_base16);
+        contents.insert_parser("application/json", |bytes: &[u8]| {
+            serde_json::from_slice(bytes).map_err(|err| err.to_string())
+        });
+        contents
+    }
+
+    /// Returns an independent copy of this `Contents` registry.
+    ///
+    /// Forking is O(1): the decoders and parsers are shared until either
+    /// registry is next modified, at which point that registry copies its
+    /// index of them.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            registry: Arc::new(RwLock::new(self.read())),
+        }
+    }
+
+    fn read(&self) -> Arc<Registry> {
+        self.registry.read().clone()
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut registry = self.registry.write();
+        f(Arc::make_mut(&mut registry))
+    }
+
+    /// Registers `decode` as the decoder of the `"contentEncoding"`
+    /// `encoding`, returning the decoder it replaced, if any.
+    pub fn insert_decoder(
+        &self,
+        encoding: impl Into<String>,
+        decode: impl Fn(&str) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    ) -> Option<Arc<DecodeFn>> {
+        let encoding = encoding.into().to_ascii_lowercase();
+        self.write(|registry| registry.decoders.insert(encoding, Arc::new(decode)))
+    }
+
+    /// Registers `parse` as the parser of the `"contentMediaType"`
+    /// `media_type`, returning the parser it replaced, if any.
+    pub fn insert_parser(
+        &self,
+        media_type: impl Into<String>,
+        parse: impl Fn(&[u8]) -> Result<Value, String> + Send + Sync + 'static,
+    ) -> Option<Arc<ParseFn>> {
+        let media_type = essence(&media_type.into());
+        self.write(|registry| registry.parsers.insert(media_type, Arc::new(parse)))
+    }
+
+    /// Removes and returns the decoder of `encoding`, if registered.
+    pub fn remove_decoder(&self, encoding: &str) -> Option<Arc<DecodeFn>> {
+        let encoding = encoding.to_ascii_lowercase();
+        self.write(|registry| registry.decoders.remove(&encoding))
+    }
+
+    /// Removes and returns the parser of `media_type`, if registered.
+    pub fn remove_parser(&self, media_type: &str) -> Option<Arc<ParseFn>> {
+        let media_type = essence(media_type);
+        self.write(|registry| registry.parsers.remove(&media_type))
+    }
+
+    /// Returns the decoder of `encoding`, if registered. Encodings are
+    /// matched case-insensitively.
+    pub fn decoder(&self, encoding: &str) -> Option<Arc<DecodeFn>> {
+        self.registry
+            .read()
+            .decoders
+            .get(&encoding.to_ascii_lowercase())
+            .cloned()
+    }
+
+    /// Returns the parser of `media_type`, if registered. Media types are
+    /// matched case-insensitively and without parameters, e.g.
+    /// `"application/json; charset=utf-8"` matches `"application/json"`.
+    pub fn parser(&self, media_type: &str) -> Option<Arc<ParseFn>> {
+        self.registry
+            .read()
+            .parsers
+            .get(&essence(media_type))
+            .cloned()
+    }
+
+    /// Decodes `value` with the decoder of `encoding`, returning `None` if
+    /// no decoder is registered.
+    pub fn decode(&self, encoding: &str, value: &str) -> Option<Result<Vec<u8>, String>> {
+        self.decoder(encoding).map(|decode| decode(value))
+    }
+
+    /// Parses `bytes` with the parser of `media_type`, returning `None` if no
+    /// parser is registered.
+    pub fn parse(&self, media_type: &str, bytes: &[u8]) -> Option<Result<Value, String>> {
+        self.parser(media_type).map(|parse| parse(bytes))
+    }
+}
+
+impl fmt::Debug for Contents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.read();
+        let mut encodings: Vec<&String> = registry.decoders.keys().collect();
+        let mut media_types: Vec<&String> = registry.parsers.keys().collect();
+        encodings.sort();
+        media_types.sort();
+        f.debug_struct("Contents")
+            .field("encodings", &encodings)
+            .field("media_types", &media_types)
+            .finish()
+    }
+}
+
+/// Returns the type and subtype of `media_type`, lowercased and without
+/// parameters.
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Decodes base64 as defined by RFC 4648, ignoring whitespace as permitted
+/// by RFC 2045.
+fn decode_base64(value: &str) -> Result<Vec<u8>, String> {
+    let symbols: Vec<u8> = value.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if symbols.len() % 4 != 0 {
+        return Err("base64 length is not a multiple of 4".to_string());
+    }
+    let mut bytes = Vec::with_capacity(symbols.len() / 4 * 3);
+    let quads = symbols.len() / 4;
+    for (i, quad) in symbols.chunks(4).enumerate() {
+        let padding = quad.iter().rev().take_while(|b| **b == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != quads) {
+            return Err("base64 padding is malformed".to_string());
+        }
+        let mut n: u32 = 0;
+        for &b in &quad[..4 - padding] {
+            let sextet = match b {
+                b'A'..=b'Z' => b - b'A',
+                b'a'..=b'z' => b - b'a' + 26,
+                b'0'..=b'9' => b - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => return Err(format!("'{}' is not a base64 character", b as char)),
+            };
+            n = (n << 6) | u32::from(sextet);
+        }
+        n <<= 6 * padding as u32;
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        bytes.extend_from_slice(&decoded[..3 - padding]);
+    }
+    Ok(bytes)
+}
+
+/// Decodes base16 as defined by RFC 4648, accepting either case.
+fn decode_base16(value: &str) -> Result<Vec<u8>, String> {
+    if value.len() % 2 != 0 {
+        return Err("base16 length is not even".to_string());
+    }
+    value
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("\"{}\" is not base16", String::from_utf8_lossy(pair)))
+        })
+        .collect()
+}
@@ -0,0 +1,128 @@
+//! Static cost estimates of compiled keywords, used to bound the work of an
+//! evaluation.
+//!
+//! Each keyword of a [`Schema`](crate::Schema) is assigned a cost when the
+//! [`Schema`](crate::Schema) is set up, derived from the keyword's value: the
+//! size of a regular expression, the number of subschemas, the number of
+//! candidates of `"enum"`, and so on. Each time a keyword is applied to an
+//! instance node, its cost is charged against the budget set with
+//! [`Options::budget`](crate::evaluation::Options::budget). Evaluation aborts
+//! with [`Error::BudgetExceeded`](crate::Error::BudgetExceeded) once the
+//! budget is spent.
+//!
+//! ## Example
+//! ```
+//! use grill::cost::{estimate, schema_cost};
+//! use serde_json::json;
+//!
+//! assert_eq!(estimate("type", &json!("string")), 1);
+//! assert_eq!(estimate("anyOf", &json!([{}, {}, {}])), 4);
+//! let email = json!("^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\\.[a-zA-Z]{2,}$");
+//! assert!(estimate("pattern", &email) > estimate("pattern", &json!("^a$")));
+//! assert_eq!(schema_cost(&json!({ "type": "string", "minLength": 1 })), 2);
+//! ```
+
+use crate::{
+    applicator::ExecutorFn, error::BudgetExceededError, number::usize_to_u64, Error, Evaluation,
+    Next,
+};
+use serde_json::Value;
+use std::time::Instant;
+
+/// The cost of a keyword which is not otherwise estimated.
+pub const BASE_COST: u64 = 1;
+
+/// The number of bytes of a regular expression charged as a single unit.
+const REGEX_BYTES_PER_UNIT: u64 = 8;
+
+/// Returns the estimated cost of applying `keyword`, with the given `value`,
+/// to a single instance node.
+pub fn estimate(keyword: &str, value: &Value) -> u64 {
+    let extra = match keyword {
+        "pattern" => value.as_str().map_or(0, regex_cost),
+        "patternProperties" => value
+            .as_object()
+            .map_or(0, |obj| obj.keys().map(|k| regex_cost(k) + 1).sum()),
+        "enum" => value.as_array().map_or(0, |a| len(a.len())),
+        "allOf" | "anyOf" | "oneOf" | "prefixItems" | "items" => match value {
+            Value::Array(subs) => len(subs.len()),
+            Value::Object(_) | Value::Bool(_) => 1,
+            _ => 0,
+        },
+        "properties" | "dependentSchemas" | "dependentRequired" => {
+            value.as_object().map_or(0, |obj| len(obj.len()))
+        }
+        "not"
+        | "if"
+        | "then"
+        | "else"
+        | "contains"
+        | "propertyNames"
+        | "additionalProperties"
+        | "additionalItems"
+        | "unevaluatedProperties"
+        | "unevaluatedItems"
+        | "$ref"
+        | "$dynamicRef"
+        | "$recursiveRef" => 1,
+        // compares each pair of items
+        "uniqueItems" if value == &Value::Bool(true) => 4,
+        _ => 0,
+    };
+    BASE_COST.saturating_add(extra)
+}
+
+/// Returns the sum of the estimated cost of each keyword of `source`.
+///
+/// Subschemas are not included beyond the cost their parent keyword assigns
+/// to descending into them.
+pub fn schema_cost(source: &Value) -> u64 {
+    match source.as_object() {
+        Some(obj) => obj
+            .iter()
+            .fold(0, |acc: u64, (k, v)| acc.saturating_add(estimate(k, v))),
+        None => BASE_COST,
+    }
+}
+
+fn regex_cost(pattern: &str) -> u64 {
+    len(pattern.len()) / REGEX_BYTES_PER_UNIT + 1
+}
+
+fn len(n: usize) -> u64 {
+    usize_to_u64(n, "keyword").unwrap_or(u64::MAX)
+}
+
+/// Wraps `f` so that `cost` is charged against the budget of the evaluation,
+/// if any, each time it is applied. The deadline of the evaluation, if any,
+/// is checked beforehand; once it has passed, `f` and the keywords after it
+/// are skipped so that the evaluation unwinds with what it has collected.
+pub(crate) fn metered(cost: u64, f: Box<ExecutorFn>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Some(deadline) = eval.state().deadline() {
+                if eval.state().is_timed_out() {
+                    return Ok(eval);
+                }
+                if Instant::now() >= deadline {
+                    let state = eval.state();
+                    state.time_out(eval.instance_location(), eval.keyword_location());
+                    return Ok(eval);
+                }
+            }
+            if let Some(budget) = eval.state().options().budget_limit() {
+                let spent = eval.state().charge(cost);
+                if spent > budget {
+                    return Err(BudgetExceededError {
+                        budget,
+                        spent,
+                        instance_location: eval.instance_location().clone(),
+                        keyword_location: eval.keyword_location().clone(),
+                    }
+                    .into());
+                }
+            }
+            f(value, eval, next)
+        },
+    )
+}
@@ -0,0 +1,174 @@
+//! Measurement of which compiled keywords are exercised by a corpus of
+//! instances.
+//!
+//! When [`Options::coverage`](crate::evaluation::Options::coverage) is
+//! enabled, each application of a compiled keyword is counted.
+//! [`Interrogator::coverage`](crate::Interrogator::coverage) reports the
+//! counts for each keyword of each top-level [`Schema`], and
+//! [`Interrogator::reset_coverage`](crate::Interrogator::reset_coverage)
+//! clears them. Keywords with no hits are branches of a schema which the
+//! corpus never reached, e.g. the `"else"` of an `"if"` which always
+//! matched.
+//!
+//! Hits are counted by the id of the [`Schema`] a keyword was compiled into
+//! and the keyword's location within it, so the counts of a `Schema` which
+//! is compiled again under the same id carry over.
+//!
+//! ## Example
+//! ```
+//! use grill::coverage::{KeywordCoverage, SchemaCoverage};
+//! use grill::schema::CompiledKeyword;
+//! use jsonptr::Pointer;
+//!
+//! let keyword = |name: &'static str, hits| KeywordCoverage {
+//!     keyword: CompiledKeyword {
+//!         name: Some(name),
+//!         location: Pointer::default(),
+//!         is_applicator: false,
+//!     },
+//!     hits,
+//! };
+//! let coverage = SchemaCoverage {
+//!     id: None,
+//!     keywords: vec![keyword("type", 3), keyword("minLength", 0)],
+//! };
+//! assert_eq!(coverage.ratio(), 0.5);
+//! assert_eq!(coverage.uncovered().count(), 1);
+//! ```
+
+use crate::{applicator::ExecutorFn, schema::CompiledKeyword, Error, Evaluation, Next, Schema};
+use jsonptr::Pointer;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use uniresid::Uri;
+
+/// Identifies a compiled keyword by the id of the [`Schema`] it was compiled
+/// into and its location within that `Schema`.
+type CoverageKey = (Option<Uri>, Pointer);
+
+/// The hit counts of compiled keywords, shared by an
+/// [`Interrogator`](crate::Interrogator) and the [`Schema`]s it sets up.
+///
+/// Each keyword is counted by its own atomic counter, created when the
+/// keyword is setup, so recording a hit takes no lock.
+#[derive(Clone, Default)]
+pub(crate) struct Recorder {
+    counters: Arc<RwLock<HashMap<CoverageKey, Arc<AtomicU64>>>>,
+}
+
+impl Recorder {
+    pub(crate) fn hits(&self, schema_id: Option<&Uri>, location: &Pointer) -> u64 {
+        self.counters
+            .read()
+            .get(&(schema_id.cloned(), location.clone()))
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn reset(&self) {
+        for counter in self.counters.read().values() {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Drops the hit counts of the keywords of each `Schema` whose id is in
+    /// `schema_ids`.
+    pub(crate) fn forget(&self, schema_ids: &HashSet<Uri>) {
+        self.counters.write().retain(|(schema_id, _), _| {
+            !schema_id
+                .as_ref()
+                .map_or(false, |id| schema_ids.contains(id))
+        });
+    }
+
+    fn counter(&self, key: CoverageKey) -> Arc<AtomicU64> {
+        if let Some(counter) = self.counters.read().get(&key) {
+            return counter.clone();
+        }
+        self.counters.write().entry(key).or_default().clone()
+    }
+
+    /// Wraps `f` so that each application is counted if coverage is enabled
+    /// for the evaluation.
+    pub(crate) fn recorded(
+        &self,
+        schema: &Schema,
+        keyword: Option<&'static str>,
+        f: Box<ExecutorFn>,
+    ) -> Box<ExecutorFn> {
+        let counter = self.counter(coverage_key(schema, keyword));
+        Box::new(
+            move |value: &Value, eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+                if eval.state().options().is_recording_coverage() {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+                f(value, eval, next)
+            },
+        )
+    }
+}
+
+/// Returns the [`CoverageKey`] of `keyword` within `schema`.
+fn coverage_key(schema: &Schema, keyword: Option<&'static str>) -> CoverageKey {
+    let mut location = Pointer::default();
+    if let Some(keyword) = keyword {
+        location.push_back(keyword.into());
+    }
+    (schema.id().map(|id| (*id).clone()), location)
+}
+
+/// The number of times a compiled keyword was applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeywordCoverage {
+    /// The compiled keyword.
+    pub keyword: CompiledKeyword,
+    /// The number of times the keyword was applied.
+    pub hits: u64,
+}
+
+/// The coverage of each compiled keyword of a top-level [`Schema`] and its
+/// subschemas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaCoverage {
+    /// The id of the [`Schema`].
+    pub id: Option<Arc<Uri>>,
+    /// The coverage of each keyword, in the order of [`Schema::keywords`].
+    pub keywords: Vec<KeywordCoverage>,
+}
+
+impl SchemaCoverage {
+    pub(crate) fn of(schema: &Schema, recorder: &Recorder) -> Self {
+        let mut keywords = Vec::new();
+        schema.visit_keywords(&mut |owner, keyword| {
+            let (id, location) = coverage_key(owner, keyword.name);
+            let hits = recorder.hits(id.as_ref(), &location);
+            keywords.push(KeywordCoverage { keyword, hits });
+        });
+        Self {
+            id: schema.id(),
+            keywords,
+        }
+    }
+
+    /// Returns an iterator over the keywords which were never applied.
+    pub fn uncovered(&self) -> impl Iterator<Item = &KeywordCoverage> {
+        self.keywords.iter().filter(|k| k.hits == 0)
+    }
+
+    /// Returns the fraction of keywords which were applied at least once, or
+    /// `1.0` if the [`Schema`] has no keywords.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> f64 {
+        if self.keywords.is_empty() {
+            return 1.0;
+        }
+        let covered = self.keywords.len() - self.uncovered().count();
+        covered as f64 / self.keywords.len() as f64
+    }
+}
@@ -0,0 +1,208 @@
+//! Generates concise, human-readable summaries of the constraints of a
+//! [`Schema`] at a given location, e.g. `"string, 3–20 chars, matching
+//! ^[a-z]+$, required"`.
+//!
+//! Summaries are composed of the output of each [`Describer`]. Keyword
+//! implementations provide their own (see [`keyword`](crate::keyword)); the
+//! remainder are provided by this module.
+//!
+//! ## Example
+//! ```
+//! use grill::{describe::describe_value, Pointer};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "required": ["name"],
+//!     "properties": {
+//!         "name": { "type": "string", "minLength": 3, "maxLength": 20, "pattern": "^[a-z]+$" }
+//!     }
+//! });
+//! let location = Pointer::new(&["properties", "name"]);
+//! assert_eq!(
+//!     describe_value(&schema, &location).as_deref(),
+//!     Some("string, 3–20 chars, matching ^[a-z]+$, required")
+//! );
+//! ```
+
+use crate::{keyword, Schema};
+use jsonptr::Pointer;
+use serde_json::{Map, Value};
+
+/// Summarizes the constraints a keyword, or a group of related keywords,
+/// imposes upon an instance.
+pub trait Describer: Send + Sync {
+    /// Returns a concise summary of the constraints of `object` relevant to
+    /// this `Describer`, or `None` if there are none.
+    fn describe(&self, object: &Map<String, Value>) -> Option<String>;
+}
+
+impl<F> Describer for F
+where
+    F: Send + Sync + Fn(&Map<String, Value>) -> Option<String>,
+{
+    fn describe(&self, object: &Map<String, Value>) -> Option<String> {
+        self(object)
+    }
+}
+
+/// Returns the default [`Describer`]s, in the order their summaries appear.
+pub fn describers() -> Vec<&'static dyn Describer> {
+    vec![
+        &describe_type,
+        &keyword::describe_bson_type,
+        &describe_enum,
+        &describe_const,
+        &keyword::describe_range,
+        &describe_multiple_of,
+        &describe_length,
+        &describe_pattern,
+        &describe_format,
+        &keyword::describe_format_range,
+        &describe_items,
+        &keyword::describe_array_extension,
+        &describe_properties,
+    ]
+}
+
+/// Summarizes the constraints of the schema object at `location` within
+/// `schema` with the default [`Describer`]s.
+///
+/// Returns `None` if `location` does not exist or has no constraints.
+pub fn describe(schema: &Schema, location: &Pointer) -> Option<String> {
+    describe_value(&schema.source(), location)
+}
+
+/// Summarizes the constraints of the schema object at `location` within the
+/// schema `source` with the default [`Describer`]s.
+pub fn describe_value(source: &Value, location: &Pointer) -> Option<String> {
+    describe_with(source, location, &describers())
+}
+
+/// Summarizes the constraints of the schema object at `location` within the
+/// schema `source` with the given `describers`.
+///
+/// If the location is a property which the parent schema object lists in
+/// `"required"`, the summary ends with `"required"`.
+pub fn describe_with(
+    source: &Value,
+    location: &Pointer,
+    describers: &[&dyn Describer],
+) -> Option<String> {
+    let mut parts = match location.resolve(source).ok()? {
+        Value::Bool(true) => vec!["any value".to_string()],
+        Value::Bool(false) => vec!["no value".to_string()],
+        Value::Object(object) => describers
+            .iter()
+            .filter_map(|d| d.describe(object))
+            .collect(),
+        _ => return None,
+    };
+    if is_required(source, location) {
+        parts.push("required".to_string());
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
+/// Returns `true` if `location` is of the form `.../properties/<name>` and
+/// the parent schema object lists `<name>` in `"required"`.
+fn is_required(source: &Value, location: &Pointer) -> bool {
+    let tokens: Vec<String> = location.tokens().map(|t| t.as_str().to_string()).collect();
+    let (name, parent) = match tokens.as_slice() {
+        [parent @ .., properties, name] if properties == "properties" => (name, parent),
+        _ => return false,
+    };
+    let required = Pointer::new(parent)
+        .resolve(source)
+        .ok()
+        .and_then(|p| p.get("required"))
+        .and_then(Value::as_array);
+    matches!(required, Some(r) if r.iter().any(|v| v.as_str() == Some(name)))
+}
+
+/// [`Describer`] for the `"type"` keyword, e.g. `"string or null"`.
+pub fn describe_type(object: &Map<String, Value>) -> Option<String> {
+    match object.get("type")? {
+        Value::String(t) => Some(t.clone()),
+        Value::Array(types) => {
+            let types: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+            if types.is_empty() {
+                None
+            } else {
+                Some(types.join(" or "))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// [`Describer`] for the `"enum"` keyword, e.g. `"one of \"a\", \"b\""`.
+pub fn describe_enum(object: &Map<String, Value>) -> Option<String> {
+    let values = object.get("enum")?.as_array()?;
+    let values: Vec<String> = values.iter().map(Value::to_string).collect();
+    Some(format!("one of {}", values.join(", ")))
+}
+
+/// [`Describer`] for the `"const"` keyword, e.g. `"equal to 5"`.
+pub fn describe_const(object: &Map<String, Value>) -> Option<String> {
+    Some(format!("equal to {}", object.get("const")?))
+}
+
+/// [`Describer`] for the `"multipleOf"` keyword, e.g. `"multiple of 2"`.
+pub fn describe_multiple_of(object: &Map<String, Value>) -> Option<String> {
+    match object.get("multipleOf")? {
+        Value::Number(n) => Some(format!("multiple of {}", n)),
+        _ => None,
+    }
+}
+
+/// [`Describer`] for the `"minLength"` and `"maxLength"` keywords, e.g.
+/// `"3–20 chars"`.
+pub fn describe_length(object: &Map<String, Value>) -> Option<String> {
+    describe_count(object, "minLength", "maxLength", "chars")
+}
+
+/// [`Describer`] for the `"pattern"` keyword, e.g. `"matching ^[a-z]+$"`.
+pub fn describe_pattern(object: &Map<String, Value>) -> Option<String> {
+    Some(format!("matching {}", object.get("pattern")?.as_str()?))
+}
+
+/// [`Describer`] for the `"format"` keyword, e.g. `"formatted as email"`.
+pub fn describe_format(object: &Map<String, Value>) -> Option<String> {
+    Some(format!("formatted as {}", object.get("format")?.as_str()?))
+}
+
+/// [`Describer`] for the `"minItems"`, `"maxItems"` and `"uniqueItems"`
+/// keywords, e.g. `"1–5 items, unique"`.
+pub fn describe_items(object: &Map<String, Value>) -> Option<String> {
+    let count = describe_count(object, "minItems", "maxItems", "items");
+    let unique = object.get("uniqueItems").and_then(Value::as_bool) == Some(true);
+    match (count, unique) {
+        (Some(count), true) => Some(format!("{}, unique", count)),
+        (Some(count), false) => Some(count),
+        (None, true) => Some("unique items".to_string()),
+        (None, false) => None,
+    }
+}
+
+/// [`Describer`] for the `"minProperties"` and `"maxProperties"` keywords,
+/// e.g. `"at most 10 properties"`.
+pub fn describe_properties(object: &Map<String, Value>) -> Option<String> {
+    describe_count(object, "minProperties", "maxProperties", "properties")
+}
+
+fn describe_count(object: &Map<String, Value>, min: &str, max: &str, unit: &str) -> Option<String> {
+    let min = object.get(min).and_then(Value::as_u64);
+    let max = object.get(max).and_then(Value::as_u64);
+    match (min, max) {
+        (Some(min), Some(max)) if min == max => Some(format!("exactly {} {}", min, unit)),
+        (Some(min), Some(max)) => Some(format!("{}–{} {}", min, max, unit)),
+        (Some(min), None) => Some(format!("at least {} {}", min, unit)),
+        (None, Some(max)) => Some(format!("at most {} {}", max, unit)),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1,145 @@
+//! Deserialization which validates the input against a [`Schema`] before
+//! producing a value.
+//!
+//! [`ValidatedDeserializer`] is a [`DeserializeSeed`] which reads the input
+//! once, evaluates it, and only then deserializes `T`. Invalid input yields
+//! the [`Evaluation`] rather than a deserialization error, so the report can
+//! be returned to the client as-is.
+//!
+//! ## Example
+//! ```no_run
+//! use grill::{deserialize, Interrogator, OutputFmt, Schema};
+//! use serde::Deserialize;
+//! use serde_json::json;
+//!
+//! #[derive(Deserialize)]
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! let interrogator = Interrogator::new();
+//! let schema = Schema::new(
+//!     json!({ "type": "object", "required": ["name"] }),
+//!     &interrogator,
+//! )
+//! .unwrap();
+//! match deserialize::from_str::<User>(&schema, r#"{"name": "ann"}"#, OutputFmt::Basic) {
+//!     Ok(Ok(user)) => assert_eq!(user.name, "ann"),
+//!     Ok(Err(report)) => panic!("invalid: {:?}", report),
+//!     Err(err) => panic!("{}", err),
+//! }
+//! ```
+
+use crate::{evaluation::Options, Error, Evaluation, OutputFmt, Schema};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed, Error as _},
+    Deserialize, Deserializer,
+};
+use serde_json::Value;
+use std::{fmt, marker::PhantomData};
+
+/// A [`DeserializeSeed`] which evaluates the input against a [`Schema`]
+/// before deserializing it into `T`.
+///
+/// The seed's value is `Ok(T)` if the input is valid and `Err(Evaluation)`
+/// otherwise. Errors raised by the [`Schema`] during evaluation are reported
+/// as errors of the [`Deserializer`].
+///
+/// The input is buffered before `T` is deserialized, so `T` cannot borrow
+/// from it and must be [`DeserializeOwned`].
+pub struct ValidatedDeserializer<T> {
+    schema: Schema,
+    output: OutputFmt,
+    options: Options,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ValidatedDeserializer<T> {
+    /// Creates a new `ValidatedDeserializer` which evaluates input against
+    /// `schema`, reporting in the given [`OutputFmt`].
+    pub fn new(schema: Schema, output: OutputFmt) -> Self {
+        Self {
+            schema,
+            output,
+            options: Options::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the [`Options`] of the evaluation.
+    #[must_use]
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl<T> Clone for ValidatedDeserializer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            output: self.output.clone(),
+            options: self.options.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for ValidatedDeserializer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatedDeserializer")
+            .field("schema", &self.schema)
+            .field("output", &self.output)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for ValidatedDeserializer<T>
+where
+    T: DeserializeOwned,
+{
+    type Value = Result<T, Evaluation>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let evaluation = self
+            .schema
+            .evaluate_with_options(&value, self.output, self.options)
+            .map_err(D::Error::custom)?;
+        if !evaluation.is_valid() {
+            return Ok(Err(evaluation));
+        }
+        T::deserialize(value).map(Ok).map_err(D::Error::custom)
+    }
+}
+
+/// Deserializes `T` from the JSON string `s` if it is valid against `schema`.
+///
+/// Returns `Ok(Err(Evaluation))` if `s` is well-formed JSON but not valid.
+pub fn from_str<T: DeserializeOwned>(
+    schema: &Schema,
+    s: &str,
+    output: OutputFmt,
+) -> Result<Result<T, Evaluation>, Error> {
+    from_slice(schema, s.as_bytes(), output)
+}
+
+/// Deserializes `T` from the JSON bytes `v` if they are valid against
+/// `schema`.
+///
+/// Returns `Ok(Err(Evaluation))` if `v` is well-formed JSON but not valid.
+pub fn from_slice<T: DeserializeOwned>(
+    schema: &Schema,
+    v: &[u8],
+    output: OutputFmt,
+) -> Result<Result<T, Evaluation>, Error> {
+    let mut deserializer = serde_json::Deserializer::from_slice(v);
+    let result =
+        ValidatedDeserializer::new(schema.clone(), output).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(result)
+}
@@ -0,0 +1,363 @@
+//! Deserializers of source documents written in languages other than JSON.
+//!
+//! [`Deserializers`] is the registry an [`Interrogator`](crate::Interrogator)
+//! consults when a source is added with
+//! [`Interrogator::source_bytes`](crate::Interrogator::source_bytes),
+//! [`source_str`](crate::Interrogator::source_str), or
+//! [`source_reader`](crate::Interrogator::source_reader). Each
+//! [`DeserializeFn`] is registered for a media type and, optionally, the
+//! file extensions which imply it. The deserializer of a document is chosen
+//! by the media type hint, if one is given, and otherwise by the extension
+//! of the document's URI, falling back to JSON.
+//!
+//! [`Deserializers::standard`] registers `"application/json"` and, with the
+//! `"yaml"` feature enabled, `"application/yaml"` for the extensions `yaml`
+//! and `yml`. With the `"toml"` feature enabled, it registers
+//! `"application/toml"` for the extension `toml`; tables keep the order of
+//! their keys, as the feature enables `preserve_order` of `serde_json`, and
+//! dates and times are deserialized as RFC 3339 strings.
+//!
+//! A document which fails to deserialize is reported as a
+//! [`DeserializeError`] with the line and column of the failure within the
+//! original document, rather than within its JSON equivalent.
+//!
+//! ## Example
+//! ```
+//! use grill::{deserializer::{DeserializeError, Deserializers}, uri::AbsoluteUri};
+//! use serde_json::json;
+//!
+//! let deserializers = Deserializers::standard();
+//! deserializers.insert("text/plain", &["txt"], |bytes: &[u8]| {
+//!     let text = std::str::from_utf8(bytes).map_err(|err| DeserializeError::new(err.to_string()))?;
+//!     Ok(json!({ "const": text.trim() }))
+//! });
+//!
+//! let uri = AbsoluteUri::parse("https://example.com/const.txt").unwrap();
+//! assert_eq!(
+//!     deserializers.deserialize(&uri, None, b"grill\n").unwrap(),
+//!     json!({ "const": "grill" })
+//! );
+//! let err = deserializers
+//!     .deserialize(&uri, Some("application/json"), b"{\n  \"type\": }")
+//!     .unwrap_err();
+//! assert_eq!(err.line, Some(2));
+//! ```
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::{collections::HashMap, error::Error as StdError, fmt, sync::Arc};
+use uniresid::AbsoluteUri;
+
+/// The media type of JSON, used when no other is hinted or implied.
+pub const JSON_MEDIA_TYPE: &str = "application/json";
+
+/// The media type of YAML.
+pub const YAML_MEDIA_TYPE: &str = "application/yaml";
+
+/// The media type of TOML.
+pub const TOML_MEDIA_TYPE: &str = "application/toml";
+
+/// A function which deserializes the bytes of a source document into a
+/// [`Value`].
+pub type DeserializeFn = dyn Fn(&[u8]) -> Result<Value, DeserializeError> + Send + Sync;
+
+/// A registry of deserializers, keyed by media type, and of the media type
+/// implied by each file extension.
+///
+/// Cloning `Deserializers` is cheap; clones share the same registry.
+#[derive(Clone, Default)]
+pub struct Deserializers {
+    registry: Arc<RwLock<Arc<Registry>>>,
+}
+
+#[derive(Clone, Default)]
+struct Registry {
+    deserializers: HashMap<String, Arc<DeserializeFn>>,
+    extensions: HashMap<String, String>,
+}
+
+impl Deserializers {
+    /// Creates and returns a new, empty `Deserializers` registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates and returns a `Deserializers` registry containing
+    /// `"application/json"` and, with their features enabled,
+    /// `"application/yaml"` and `"application/toml"`.
+    pub fn standard() -> Self {
+        let deserializers = Self::new();
+        deserializers.insert(JSON_MEDIA_TYPE, &["json"], deserialize_json);
+        #[cfg(feature = "yaml")]
+        deserializers.insert(YAML_MEDIA_TYPE, &["yaml", "yml"], deserialize_yaml);
+        #[cfg(feature = "toml")]
+        deserializers.insert(TOML_MEDIA_TYPE, &["toml"], deserialize_toml);
+        deserializers
+    }
+
+    /// Returns an independent copy of this `Deserializers` registry.
+    ///
+    /// Forking is O(1): the deserializers are shared until either registry is
+    /// next modified, at which point that registry copies its index of them.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            registry: Arc::new(RwLock::new(self.read())),
+        }
+    }
+
+    fn read(&self) -> Arc<Registry> {
+        self.registry.read().clone()
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Registry) -> T) -> T {
+        let mut registry = self.registry.write();
+        f(Arc::make_mut(&mut registry))
+    }
+
+    /// Registers `deserialize` as the deserializer of `media_type`, implied
+    /// by each of `extensions`, returning the deserializer it replaced, if
+    /// any.
+    pub fn insert(
+        &self,
+        media_type: impl Into<String>,
+        extensions: &[&str],
+        deserialize: impl Fn(&[u8]) -> Result<Value, DeserializeError> + Send + Sync + 'static,
+    ) -> Option<Arc<DeserializeFn>> {
+        let media_type = essence(&media_type.into());
+        self.write(|registry| {
+            for extension in extensions {
+                registry.extensions.insert(
+                    extension.trim_start_matches('.').to_ascii_lowercase(),
+                    media_type.clone(),
+                );
+            }
+            registry
+                .deserializers
+                .insert(media_type, Arc::new(deserialize))
+        })
+    }
+
+    /// Removes and returns the deserializer of `media_type`, along with each
+    /// extension which implies it.
+    pub fn remove(&self, media_type: &str) -> Option<Arc<DeserializeFn>> {
+        let media_type = essence(media_type);
+        self.write(|registry| {
+            registry
+                .extensions
+                .retain(|_, implied| *implied != media_type);
+            registry.deserializers.remove(&media_type)
+        })
+    }
+
+    /// Returns the deserializer of `media_type`, if registered. Media types
+    /// are matched case-insensitively and without parameters.
+    pub fn get(&self, media_type: &str) -> Option<Arc<DeserializeFn>> {
+        self.registry
+            .read()
+            .deserializers
+            .get(&essence(media_type))
+            .cloned()
+    }
+
+    /// Returns the media type implied by the extension of the path of `uri`,
+    /// if one is registered.
+    pub fn media_type_of(&self, uri: &AbsoluteUri) -> Option<String> {
+        let uri = uri.to_string();
+        let path = uri.split(['?', '#']).next().unwrap_or_default();
+        let last = path.rsplit('/').next().unwrap_or_default();
+        let (_, extension) = last.rsplit_once('.')?;
+        self.registry
+            .read()
+            .extensions
+            .get(&extension.to_ascii_lowercase())
+            .cloned()
+    }
+
+    /// Deserializes `bytes`, the document at `uri`, with the deserializer of
+    /// `media_type` or, if `None`, of the media type implied by the
+    /// extension of `uri`, falling back to JSON.
+    ///
+    /// # Errors
+    /// Returns a [`DeserializeError`] if no deserializer is registered for
+    /// the media type or if `bytes` are malformed.
+    pub fn deserialize(
+        &self,
+        uri: &AbsoluteUri,
+        media_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<Value, DeserializeError> {
+        let media_type = match media_type {
+            Some(media_type) => essence(media_type),
+            None => self
+                .media_type_of(uri)
+                .unwrap_or_else(|| JSON_MEDIA_TYPE.to_string()),
+        };
+        let deserialize = match self.get(&media_type) {
+            Some(deserialize) => deserialize,
+            None if media_type == JSON_MEDIA_TYPE => Arc::new(deserialize_json),
+            None => {
+                return Err(DeserializeError::new(format!(
+                    "no deserializer is registered for \"{}\"",
+                    media_type
+                ))
+                .with_source(uri, &media_type))
+            }
+        };
+        deserialize(bytes).map_err(|err| err.with_source(uri, &media_type))
+    }
+}
+
+impl fmt::Debug for Deserializers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.read();
+        let mut media_types: Vec<&String> = registry.deserializers.keys().collect();
+        let mut extensions: Vec<(&String, &String)> = registry.extensions.iter().collect();
+        media_types.sort();
+        extensions.sort();
+        f.debug_struct("Deserializers")
+            .field("media_types", &media_types)
+            .field("extensions", &extensions)
+            .finish()
+    }
+}
+
+/// A source document could not be deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    /// The URI of the document, if known.
+    pub uri: Option<String>,
+    /// The media type the document was deserialized as, if known.
+    pub media_type: Option<String>,
+    /// A description of the failure.
+    pub message: String,
+    /// The line of the failure within the original document, starting at
+    /// `1`, if known.
+    pub line: Option<usize>,
+    /// The column of the failure within the original document, starting at
+    /// `1`, if known.
+    pub column: Option<usize>,
+}
+
+impl DeserializeError {
+    /// Creates and returns a new `DeserializeError` with the given `message`
+    /// and no position.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            uri: None,
+            media_type: None,
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Sets the line and column of the failure, each starting at `1`.
+    #[must_use]
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    fn with_source(mut self, uri: &AbsoluteUri, media_type: &str) -> Self {
+        self.uri = Some(uri.to_string());
+        self.media_type = Some(media_type.to_string());
+        self
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to deserialize")?;
+        if let Some(uri) = &self.uri {
+            write!(f, " \"{}\"", uri)?;
+        }
+        if let Some(media_type) = &self.media_type {
+            write!(f, " as {}", media_type)?;
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, " at line {} column {}", line, column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl StdError for DeserializeError {}
+
+/// Returns the type and subtype of `media_type`, lowercased and without
+/// parameters.
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn deserialize_json(bytes: &[u8]) -> Result<Value, DeserializeError> {
+    serde_json::from_slice(bytes)
+        .map_err(|err| DeserializeError::new(err.to_string()).at(err.line(), err.column()))
+}
+
+#[cfg(feature = "yaml")]
+fn deserialize_yaml(bytes: &[u8]) -> Result<Value, DeserializeError> {
+    serde_yaml::from_slice(bytes).map_err(|err| {
+        let location = err.location();
+        let err = DeserializeError::new(err.to_string());
+        match location {
+            Some(location) => err.at(location.line(), location.column()),
+            None => err,
+        }
+    })
+}
+
+#[cfg(feature = "toml")]
+fn deserialize_toml(bytes: &[u8]) -> Result<Value, DeserializeError> {
+    let text = std::str::from_utf8(bytes).map_err(|err| {
+        let (line, column) = line_column(bytes, err.valid_up_to());
+        DeserializeError::new(err.to_string()).at(line, column)
+    })?;
+    let table: toml::Table = text.parse().map_err(|err: toml::de::Error| {
+        let span = err.span();
+        let err = DeserializeError::new(err.message());
+        match span {
+            Some(span) => {
+                let (line, column) = line_column(bytes, span.start);
+                err.at(line, column)
+            }
+            None => err,
+        }
+    })?;
+    Ok(toml_to_json(toml::Value::Table(table)))
+}
+
+#[cfg(feature = "toml")]
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(a) => Value::Array(a.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(t) => {
+            Value::Object(t.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect())
+        }
+    }
+}
+
+/// Returns the line and column, each starting at `1`, of the byte at
+/// `offset` within `bytes`.
+#[cfg(feature = "toml")]
+fn line_column(bytes: &[u8], offset: usize) -> (usize, usize) {
+    let before = &bytes[..offset.min(bytes.len())];
+    let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let column = String::from_utf8_lossy(&before[start..]).chars().count() + 1;
+    (line, column)
+}
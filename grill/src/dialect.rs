@@ -1,15 +1,220 @@
 use std::collections::HashMap;
 
-use crate::Vocabulary;
+use crate::{applicator::ExecutorFn, Error, Evaluation, Next, Vocabulary};
+use serde_json::Value;
+
 /// A composition of [`Vocabulary`].
+#[derive(Clone)]
 pub struct Dialect {
     /// The [`Vocabulary`]s in this `Dialect` mapped to a `bool` indicating
     /// whether they are required.
     pub vocabularies: HashMap<Vocabulary, bool>,
+    /// The order in which the keywords of a [`Schema`](crate::Schema) of
+    /// this `Dialect` are evaluated.
+    pub order: KeywordOrder,
 }
 
 impl Dialect {
     pub fn new(vocabularies: HashMap<Vocabulary, bool>) -> Self {
-        Dialect { vocabularies }
+        Dialect {
+            vocabularies,
+            order: KeywordOrder::default(),
+        }
+    }
+
+    /// Sets the [`KeywordOrder`] of this `Dialect`.
+    #[must_use]
+    pub fn with_order(mut self, order: KeywordOrder) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+/// Keywords which are skipped by [`KeywordOrder::standard`] when `"type"`
+/// fails.
+const TYPE_GATED_KEYWORDS: &[&str] = &[
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "propertyNames",
+    "dependentSchemas",
+    "unevaluatedProperties",
+    "items",
+    "prefixItems",
+    "additionalItems",
+    "contains",
+    "unevaluatedItems",
+];
+
+/// A group of keywords which are skipped if the `gate` keyword has already
+/// failed for the instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortCircuit {
+    /// The keyword which, upon failure, short-circuits `skip`.
+    pub gate: String,
+    /// The keywords which are skipped.
+    pub skip: Vec<String>,
+}
+
+/// The order in which the keywords of a [`Schema`](crate::Schema) are
+/// evaluated along with groups of keywords which are skipped once another has
+/// failed.
+///
+/// Keywords are compiled in the order listed. Keywords which are not listed,
+/// or whose [`Applicator`](crate::Applicator) does not report a
+/// [`keyword`](crate::Applicator::keyword), are evaluated afterward in the
+/// order they were registered.
+///
+/// A [`ShortCircuit`] only takes effect if its gate is ordered before the
+/// keywords it skips.
+///
+/// ## Example
+/// ```
+/// use grill::dialect::KeywordOrder;
+///
+/// let order = KeywordOrder::new()
+///     .order(["type", "enum", "properties"])
+///     .short_circuit("type", ["properties"]);
+/// assert!(order.rank("type") < order.rank("properties"));
+/// assert!(order.skips("type", "properties"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeywordOrder {
+    order: Vec<String>,
+    short_circuits: Vec<ShortCircuit>,
+}
+
+impl KeywordOrder {
+    /// Creates and returns a new, empty `KeywordOrder`, which retains the
+    /// registration order of keywords.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `KeywordOrder` which evaluates `"type"` first, followed by
+    /// the remaining assertions and lastly the applicators. Applicators which
+    /// descend into an object or array are skipped when `"type"` fails.
+    pub fn standard() -> Self {
+        Self::new()
+            .order([
+                "type",
+                "enum",
+                "const",
+                "multipleOf",
+                "maximum",
+                "exclusiveMaximum",
+                "minimum",
+                "exclusiveMinimum",
+                "maxLength",
+                "minLength",
+                "pattern",
+                "format",
+                "maxItems",
+                "minItems",
+                "uniqueItems",
+                "maxProperties",
+                "minProperties",
+                "required",
+                "dependentRequired",
+            ])
+            .short_circuit("type", TYPE_GATED_KEYWORDS.iter().copied())
+    }
+
+    /// Appends `keywords` to the order.
+    #[must_use]
+    pub fn order<I, S>(mut self, keywords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for keyword in keywords {
+            let keyword = keyword.into();
+            if !self.order.contains(&keyword) {
+                self.order.push(keyword);
+            }
+        }
+        self
+    }
+
+    /// Adds a [`ShortCircuit`] which skips `skip` if `gate` has failed.
+    #[must_use]
+    pub fn short_circuit<I, S>(mut self, gate: impl Into<String>, skip: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.short_circuits.push(ShortCircuit {
+            gate: gate.into(),
+            skip: skip.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Returns the position of `keyword` in the order, or `None` if it is not
+    /// listed.
+    pub fn rank(&self, keyword: &str) -> Option<usize> {
+        self.order.iter().position(|k| k == keyword)
+    }
+
+    /// Returns the [`ShortCircuit`]s of this `KeywordOrder`.
+    pub fn short_circuits(&self) -> &[ShortCircuit] {
+        &self.short_circuits
+    }
+
+    /// Returns `true` if `keyword` is skipped when `gate` fails.
+    pub fn skips(&self, gate: &str, keyword: &str) -> bool {
+        self.short_circuits
+            .iter()
+            .any(|sc| sc.gate == gate && sc.skip.iter().any(|k| k == keyword))
+    }
+
+    /// Returns the position `keyword` is sorted to when compiled. Sorting is
+    /// stable; unlisted keywords retain their registration order.
+    pub(crate) fn sort_key(&self, keyword: Option<&str>) -> usize {
+        keyword.and_then(|k| self.rank(k)).unwrap_or(usize::MAX)
+    }
+
+    /// Sorts compiled keywords, wrapping those which are subject to a
+    /// [`ShortCircuit`].
+    pub(crate) fn compile(
+        &self,
+        mut fns: Vec<(Option<&'static str>, Box<ExecutorFn>)>,
+    ) -> Vec<Box<ExecutorFn>> {
+        fns.sort_by_key(|(keyword, _)| self.sort_key(*keyword));
+        self.gated(fns)
+    }
+
+    /// Wraps the compiled keywords which are subject to a [`ShortCircuit`],
+    /// retaining their order.
+    pub(crate) fn gated(
+        &self,
+        fns: Vec<(Option<&'static str>, Box<ExecutorFn>)>,
+    ) -> Vec<Box<ExecutorFn>> {
+        fns.into_iter()
+            .map(|(keyword, f)| match keyword {
+                Some(keyword) => self.gate(keyword, f),
+                None => f,
+            })
+            .collect()
+    }
+
+    fn gate(&self, keyword: &str, f: Box<ExecutorFn>) -> Box<ExecutorFn> {
+        let gates: Vec<String> = self
+            .short_circuits
+            .iter()
+            .filter(|sc| sc.skip.iter().any(|k| k == keyword))
+            .map(|sc| sc.gate.clone())
+            .collect();
+        if gates.is_empty() {
+            return f;
+        }
+        Box::new(
+            move |value: &Value, eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+                if gates.iter().any(|gate| eval.has_failed(gate)) {
+                    return next.call(value, eval);
+                }
+                f(value, eval, next)
+            },
+        )
     }
 }
@@ -0,0 +1,360 @@
+//! Extracts documentation from a [`Schema`] in a form suitable for rendering
+//! API docs.
+//!
+//! ## Example
+//! ```
+//! use grill::docs::{Docs, Segment};
+//! use serde_json::json;
+//!
+//! let docs = Docs::from_value(&json!({
+//!     "title": "User",
+//!     "type": "object",
+//!     "required": ["name"],
+//!     "properties": {
+//!         "name": { "$ref": "#/$defs/name", "description": "Display name" }
+//!     },
+//!     "$defs": {
+//!         "name": { "type": "string", "maxLength": 20 }
+//!     }
+//! }));
+//! let name = &docs.entries()[1];
+//! assert_eq!(name.path, vec![Segment::Property("name".to_string())]);
+//! assert_eq!(name.description.as_deref(), Some("Display name"));
+//! assert!(name.required);
+//! assert_eq!(name.constraints["maxLength"], 20);
+//! ```
+
+use crate::{fragment, Schema};
+use jsonptr::{Pointer, Token};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Keywords which constrain an instance and are collected into
+/// [`Entry::constraints`].
+pub const CONSTRAINT_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "multipleOf",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "format",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "maxContains",
+    "minContains",
+    "maxProperties",
+    "minProperties",
+    "required",
+    "dependentRequired",
+    "contentEncoding",
+    "contentMediaType",
+];
+
+/// Returns the [`Docs`] of `schema`.
+pub fn extract(schema: &Schema) -> Docs {
+    Docs::from_value(&schema.source())
+}
+
+/// Documentation of a [`Schema`], consisting of an [`Entry`] per documented
+/// location in the order they appear.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Docs {
+    entries: Vec<Entry>,
+}
+
+impl Docs {
+    /// Extracts `Docs` from the source of a schema.
+    ///
+    /// `"properties"`, `"patternProperties"`, `"additionalProperties"`,
+    /// `"items"` and `"prefixItems"` are followed. The subschemas of
+    /// `"allOf"` and the targets of fragment-only `"$ref"`s (e.g.
+    /// `"#/$defs/name"`) describe the same instance location as the schema
+    /// object they appear in and are merged into its [`Entry`]. Other
+    /// references are not resolved, and a reference to a schema object
+    /// which encloses it is not followed.
+    pub fn from_value(source: &Value) -> Self {
+        let mut docs = Docs::default();
+        docs.walk(source, vec![(source, Pointer::default())], &[], false, &[]);
+        docs
+    }
+
+    /// Returns the documented entries.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Returns the [`Entry`] at the given instance `path`, if one exists.
+    pub fn get(&self, path: &[Segment]) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.path == path)
+    }
+
+    /// Documents the instance location `path`, described by each of
+    /// `schemas` and their locations.
+    fn walk(
+        &mut self,
+        root: &Value,
+        schemas: Vec<(&Value, Pointer)>,
+        path: &[Segment],
+        required: bool,
+        ancestors: &[Pointer],
+    ) {
+        let mut layers = Vec::new();
+        for (value, location) in schemas {
+            expand(root, value, location, ancestors, &mut layers);
+        }
+        let ((first, location), rest) = match layers.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        let mut entry = Entry::new(first, path.to_vec(), location.clone(), required);
+        for (obj, _) in rest {
+            entry.merge(obj);
+        }
+        self.entries.push(entry);
+
+        let required: Vec<&str> = layers
+            .iter()
+            .filter_map(|(obj, _)| obj.get("required").and_then(Value::as_array))
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let mut children = Children::default();
+        for (obj, location) in &layers {
+            if let Some(props) = obj.get("properties").and_then(Value::as_object) {
+                for (name, prop) in props {
+                    children.push(
+                        Segment::Property(name.clone()),
+                        prop,
+                        child_location(location, &["properties", name]),
+                    );
+                }
+            }
+            if let Some(props) = obj.get("patternProperties").and_then(Value::as_object) {
+                for (pattern, prop) in props {
+                    children.push(
+                        Segment::PatternProperty(pattern.clone()),
+                        prop,
+                        child_location(location, &["patternProperties", pattern]),
+                    );
+                }
+            }
+            if let Some(additional) = obj.get("additionalProperties") {
+                children.push(
+                    Segment::AdditionalProperty,
+                    additional,
+                    child_location(location, &["additionalProperties"]),
+                );
+            }
+            let tuple_keyword = if obj.contains_key("prefixItems") {
+                "prefixItems"
+            } else {
+                "items"
+            };
+            if let Some(tuple) = obj.get(tuple_keyword).and_then(Value::as_array) {
+                for (i, item) in tuple.iter().enumerate() {
+                    children.push(
+                        Segment::Index(i),
+                        item,
+                        child_location(location, &[tuple_keyword, &i.to_string()]),
+                    );
+                }
+            }
+            if let Some(items) = obj.get("items").filter(|v| v.is_object()) {
+                children.push(Segment::Item, items, child_location(location, &["items"]));
+            }
+        }
+
+        let mut ancestors = ancestors.to_vec();
+        ancestors.extend(layers.iter().map(|(_, location)| location.clone()));
+        for (segment, schemas) in children.0 {
+            let required =
+                matches!(&segment, Segment::Property(name) if required.contains(&name.as_str()));
+            self.walk(root, schemas, &child(path, segment), required, &ancestors);
+        }
+    }
+}
+
+/// The subschemas which describe each child of an instance location, in the
+/// order the children first appear.
+#[derive(Default)]
+struct Children<'a>(Vec<(Segment, Vec<(&'a Value, Pointer)>)>);
+
+impl<'a> Children<'a> {
+    fn push(&mut self, segment: Segment, value: &'a Value, location: Pointer) {
+        match self.0.iter_mut().find(|(s, _)| *s == segment) {
+            Some((_, schemas)) => schemas.push((value, location)),
+            None => self.0.push((segment, vec![(value, location)])),
+        }
+    }
+}
+
+/// Pushes the schema object `value` at `location` onto `layers`, followed by
+/// the subschemas of its `"allOf"` and the target of its `"$ref"` if it is
+/// fragment-only.
+///
+/// Schema objects in `ancestors` or already in `layers` are skipped so that
+/// recursive references terminate.
+fn expand<'a>(
+    root: &'a Value,
+    value: &'a Value,
+    location: Pointer,
+    ancestors: &[Pointer],
+    layers: &mut Vec<(&'a Map<String, Value>, Pointer)>,
+) {
+    let obj = match value.as_object() {
+        Some(obj) => obj,
+        None => return,
+    };
+    if ancestors.contains(&location) || layers.iter().any(|(_, l)| *l == location) {
+        return;
+    }
+    layers.push((obj, location.clone()));
+    if let Some(Value::Array(subs)) = obj.get("allOf") {
+        for (i, sub) in subs.iter().enumerate() {
+            let sub_location = child_location(&location, &["allOf", &i.to_string()]);
+            expand(root, sub, sub_location, ancestors, layers);
+        }
+    }
+    let reference = obj
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix('#'));
+    // a malformed fragment is not followed
+    let located = reference.and_then(|f| fragment::locate(root, f, false).ok().flatten());
+    if let Some(located) = located {
+        if let Some(target) = root.pointer(located.location.as_str()) {
+            expand(root, target, located.location, ancestors, layers);
+        }
+    }
+}
+
+/// Documentation of a single location within a [`Schema`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Entry {
+    /// The path of instance values described by this entry.
+    pub path: Vec<Segment>,
+    /// The location of the documented schema object, relative to the root of
+    /// the [`Schema`].
+    pub keyword_location: Pointer,
+    /// The value of `"title"`.
+    pub title: Option<String>,
+    /// The value of `"description"`.
+    pub description: Option<String>,
+    /// The values of `"examples"`.
+    pub examples: Vec<Value>,
+    /// The value of `"default"`.
+    pub default: Option<Value>,
+    /// `true` if the value of `"deprecated"` is `true`.
+    pub deprecated: bool,
+    /// `true` if the property is listed in the `"required"` keyword of the
+    /// parent schema object.
+    pub required: bool,
+    /// The constraint keywords, as listed in [`CONSTRAINT_KEYWORDS`], present
+    /// in the schema object.
+    pub constraints: Map<String, Value>,
+}
+
+impl Entry {
+    fn new(
+        obj: &Map<String, Value>,
+        path: Vec<Segment>,
+        location: Pointer,
+        required: bool,
+    ) -> Self {
+        let string = |keyword: &str| obj.get(keyword).and_then(Value::as_str).map(String::from);
+        Entry {
+            path,
+            keyword_location: location,
+            title: string("title"),
+            description: string("description"),
+            examples: obj
+                .get("examples")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            default: obj.get("default").cloned(),
+            deprecated: obj.get("deprecated").and_then(Value::as_bool) == Some(true),
+            required,
+            constraints: CONSTRAINT_KEYWORDS
+                .iter()
+                .filter_map(|k| obj.get(*k).map(|v| ((*k).to_string(), v.clone())))
+                .collect(),
+        }
+    }
+
+    /// Fills in the documentation of this entry from `obj`, a schema object
+    /// which applies to the same location. Values already present are kept.
+    fn merge(&mut self, obj: &Map<String, Value>) {
+        let string = |keyword: &str| obj.get(keyword).and_then(Value::as_str).map(String::from);
+        if self.title.is_none() {
+            self.title = string("title");
+        }
+        if self.description.is_none() {
+            self.description = string("description");
+        }
+        if let Some(Value::Array(examples)) = obj.get("examples") {
+            self.examples.extend(examples.iter().cloned());
+        }
+        if self.default.is_none() {
+            self.default = obj.get("default").cloned();
+        }
+        self.deprecated |= obj.get("deprecated").and_then(Value::as_bool) == Some(true);
+        for keyword in CONSTRAINT_KEYWORDS {
+            if let Some(value) = obj.get(*keyword) {
+                self.constraints
+                    .entry((*keyword).to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
+
+/// A segment of the instance path of an [`Entry`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum Segment {
+    /// A property named in `"properties"`.
+    Property(String),
+    /// Properties matching a pattern of `"patternProperties"`.
+    PatternProperty(String),
+    /// Properties described by `"additionalProperties"`.
+    AdditionalProperty,
+    /// A positional item described by `"prefixItems"` or a tuple form of
+    /// `"items"`.
+    Index(usize),
+    /// Each item described by `"items"`.
+    Item,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Property(name) => write!(f, ".{}", name),
+            Segment::PatternProperty(pattern) => write!(f, ".<{}>", pattern),
+            Segment::AdditionalProperty => write!(f, ".*"),
+            Segment::Index(i) => write!(f, "[{}]", i),
+            Segment::Item => write!(f, "[]"),
+        }
+    }
+}
+
+fn child(path: &[Segment], segment: Segment) -> Vec<Segment> {
+    let mut path = path.to_vec();
+    path.push(segment);
+    path
+}
+
+fn child_location(location: &Pointer, tokens: &[&str]) -> Pointer {
+    let mut location = location.clone();
+    for token in tokens {
+        location.push_back(Token::new(token));
+    }
+    location
+}
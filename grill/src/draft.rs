@@ -1,27 +1,144 @@
+//! Meta schemas of the drafts of JSON Schema and of other dialects.
+//!
+//! The URIs of each draft are always available. The meta schema documents
+//! of a draft, the functions which create its [`MetaSchema`](crate::MetaSchema)s
+//! and its [`Dialect`](crate::Dialect), and the keywords which only it
+//! defines, such as [`dynamic_ref`](crate::keyword::dynamic_ref) of
+//! 2020-12, are compiled only with its cargo feature: `"draft-04"`,
+//! `"draft-07"`, `"draft-2019-09"`, or `"draft-2020-12"`. Each is enabled by default;
+//! disable default features and select drafts to avoid embedding the meta
+//! schemas of the others, e.g.
+//!
+//! ```toml
+//! grill = { version = "0.0.0", default-features = false, features = ["draft-2020-12"] }
+//! ```
+//!
+//! The [`Dialect`](crate::Dialect) of each enabled draft is registered by
+//! [`Interrogator::new`](crate::Interrogator::new) for the URIs of both its
+//! schema and hyper schema.
+
 mod draft_2020_12;
 
+#[cfg(feature = "draft-2020-12")]
 pub use draft_2020_12::{
-    create_hyper_schema_2020_12, create_schema_2020_12, HYPER_SCHEMA_2020_12,
-    HYPER_SCHEMA_2020_12_URI, SCHEMA_2020_12, SCHEMA_2020_12_URI,
+    create_hyper_schema_2020_12, create_schema_2020_12, dialect_2020_12, HYPER_SCHEMA_2020_12,
+    SCHEMA_2020_12,
 };
+pub use draft_2020_12::{HYPER_SCHEMA_2020_12_URI, SCHEMA_2020_12_URI};
 
 mod draft_2019_09;
 
+#[cfg(feature = "draft-2019-09")]
 pub use draft_2019_09::{
-    create_hyper_schema_2019_09, create_schema_2019_09, HYPER_SCHEMA_2019_09,
-    HYPER_SCHEMA_2019_09_URI, SCHEMA_2019_09, SCHEMA_2019_09_URI,
+    create_hyper_schema_2019_09, create_schema_2019_09, dialect_2019_09, HYPER_SCHEMA_2019_09,
+    SCHEMA_2019_09,
 };
+pub use draft_2019_09::{HYPER_SCHEMA_2019_09_URI, SCHEMA_2019_09_URI};
 
 mod draft_07;
 
+#[cfg(feature = "draft-07")]
 pub use draft_07::{
-    create_hyper_schema_07, create_schema_07, HYPER_SCHEMA_07, HYPER_SCHEMA_07_URI, SCHEMA_07,
-    SCHEMA_07_URI,
+    create_hyper_schema_07, create_schema_07, dialect_07, HYPER_SCHEMA_07, SCHEMA_07,
 };
+pub use draft_07::{HYPER_SCHEMA_07_URI, SCHEMA_07_URI};
 
 mod draft_04;
 
+#[cfg(feature = "draft-04")]
 pub use draft_04::{
-    create_hyper_schema_04, create_schema_04, HYPER_SCHEMA_04, HYPER_SCHEMA_04_URI, SCHEMA_04,
-    SCHEMA_04_URI,
+    create_hyper_schema_04, create_schema_04, dialect_04, HYPER_SCHEMA_04, SCHEMA_04,
 };
+pub use draft_04::{HYPER_SCHEMA_04_URI, SCHEMA_04_URI};
+
+mod mongodb;
+
+pub use mongodb::{
+    mongodb, mongodb_unsupported_keywords, MONGODB, MONGODB_DIALECT, MONGODB_UNSUPPORTED_KEYWORDS,
+    MONGODB_URI, MONGODB_VOCABULARY_URI,
+};
+
+mod negotiate;
+
+pub use negotiate::{negotiate, negotiate_accept, Family, Mismatch, Negotiation};
+
+/// Returns `true` if `id` is the URI of the schema or hyper schema of a
+/// draft, whether or not the draft is enabled.
+pub(crate) fn is_draft(id: &uniresid::Uri) -> bool {
+    [
+        &*SCHEMA_2020_12_URI,
+        &*HYPER_SCHEMA_2020_12_URI,
+        &*SCHEMA_2019_09_URI,
+        &*HYPER_SCHEMA_2019_09_URI,
+        &*SCHEMA_07_URI,
+        &*HYPER_SCHEMA_07_URI,
+        &*SCHEMA_04_URI,
+        &*HYPER_SCHEMA_04_URI,
+    ]
+    .contains(&id)
+}
+
+/// Creates the [`MetaSchema`](crate::MetaSchema) identified by `id` from
+/// `documents`, the meta schemas of a draft, which evaluates schemas with
+/// `dialect`.
+///
+/// # Panics
+/// Panics if `documents` does not contain the meta schema `id` or if it can
+/// not be initialized with the dialect of its draft, either of which is a bug.
+#[cfg(any(
+    feature = "draft-04",
+    feature = "draft-07",
+    feature = "draft-2019-09",
+    feature = "draft-2020-12"
+))]
+fn create_meta_schema(
+    documents: &[serde_json::Value],
+    id: &uniresid::Uri,
+    dialect: crate::Dialect,
+) -> crate::MetaSchema {
+    use crate::{Interrogator, MetaSchema, Schema};
+    use serde_json::Value;
+    use uniresid::Uri;
+
+    let source = documents
+        .iter()
+        .find(|document| {
+            let declared = document
+                .get("$id")
+                .or_else(|| document.get("id"))
+                .and_then(Value::as_str)
+                .and_then(|declared| Uri::parse(declared).ok());
+            declared.as_ref() == Some(id)
+        })
+        .unwrap_or_else(|| panic!("the documents of its draft contain the meta schema {id}"))
+        .clone();
+    let interrogator = Interrogator::new();
+    let schema = Schema::initialized(source, Some(id.clone()), &interrogator)
+        .unwrap_or_else(|err| panic!("failed to initialize the meta schema {id}: {err}"));
+    if schema.id().is_none() {
+        schema.set_id(id.clone());
+    }
+    MetaSchema::new(schema, dialect)
+}
+
+/// Pushes the assertions shared by every draft, as far as grill implements
+/// them, onto `vocabulary`.
+#[cfg(any(
+    feature = "draft-04",
+    feature = "draft-07",
+    feature = "draft-2019-09",
+    feature = "draft-2020-12"
+))]
+fn push_validation_keywords(vocabulary: &mut crate::Vocabulary) {
+    use crate::keyword::{
+        enum_, exclusive_maximum, exclusive_minimum, maximum, minimum, pattern, unique_items,
+        Keyword,
+    };
+    vocabulary.push(Keyword::new("enum", enum_));
+    vocabulary.push(Keyword::new("maximum", maximum));
+    vocabulary.push(Keyword::new("exclusiveMaximum", exclusive_maximum));
+    vocabulary.push(Keyword::new("minimum", minimum));
+    vocabulary.push(Keyword::new("exclusiveMinimum", exclusive_minimum));
+    vocabulary.push(Keyword::new("pattern", pattern));
+    vocabulary.push(Keyword::new("uniqueItems", unique_items));
+}
@@ -1,15 +1,41 @@
 use once_cell::sync::Lazy;
+#[cfg(feature = "draft-04")]
 use serde_json::{json, Value};
 use uniresid::Uri;
 
-use crate::MetaSchema;
+#[cfg(feature = "draft-04")]
+use crate::{
+    dialect::KeywordOrder,
+    keyword::{format, one_of, pattern_properties, push_meta_data_keywords, ref_, Keyword},
+    Dialect, MetaSchema, Vocabulary,
+};
 
+#[cfg(feature = "draft-04")]
+/// Creates the [`MetaSchema`] of the schema of Draft 04,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_schema_04() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&SCHEMA_04, &SCHEMA_04_URI, dialect_04())
 }
 
+#[cfg(feature = "draft-04")]
+/// Creates the [`MetaSchema`] of the hyper schema of Draft 04,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_hyper_schema_04() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&HYPER_SCHEMA_04, &HYPER_SCHEMA_04_URI, dialect_04())
+}
+
+/// Returns the [`Dialect`] of Draft 04, composed of the keywords of its
+/// vocabularies which grill implements.
+#[cfg(feature = "draft-04")]
+pub fn dialect_04() -> Dialect {
+    let mut vocabulary = Vocabulary::new(SCHEMA_04_URI.clone());
+    vocabulary.push(Keyword::new("$ref", ref_));
+    vocabulary.push(Keyword::new("oneOf", one_of));
+    vocabulary.push(Keyword::new("patternProperties", pattern_properties));
+    super::push_validation_keywords(&mut vocabulary);
+    vocabulary.push(Keyword::new("format", format));
+    push_meta_data_keywords(&mut vocabulary, &["title", "description", "default"]);
+    Dialect::new([(vocabulary, true)].into_iter().collect()).with_order(KeywordOrder::standard())
 }
 
 /// The [Uri] of Schema Draft 04.
@@ -20,6 +46,7 @@ pub static SCHEMA_04_URI: Lazy<Uri> =
 pub static HYPER_SCHEMA_04_URI: Lazy<Uri> =
     Lazy::new(|| Uri::parse("http://json-schema.org/draft-04/hyper-schema#").unwrap());
 
+#[cfg(feature = "draft-04")]
 pub static SCHEMA_04: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![json!({
         "id": "http://json-schema.org/draft-04/schema#",
@@ -173,6 +200,7 @@ pub static SCHEMA_04: Lazy<Vec<Value>> = Lazy::new(|| {
     )]
 });
 
+#[cfg(feature = "draft-04")]
 pub static HYPER_SCHEMA_04: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
@@ -1,15 +1,52 @@
 use once_cell::sync::Lazy;
+#[cfg(feature = "draft-07")]
 use serde_json::{json, Value};
 use uniresid::Uri;
 
-use crate::MetaSchema;
+#[cfg(feature = "draft-07")]
+use crate::{
+    dialect::KeywordOrder,
+    keyword::{const_, format, one_of, pattern_properties, push_meta_data_keywords, ref_, Keyword},
+    Dialect, MetaSchema, Vocabulary,
+};
 
+#[cfg(feature = "draft-07")]
+/// Creates the [`MetaSchema`] of the schema of Draft 07,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_schema_07() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&SCHEMA_07, &SCHEMA_07_URI, dialect_07())
 }
 
+#[cfg(feature = "draft-07")]
+/// Creates the [`MetaSchema`] of the hyper schema of Draft 07,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_hyper_schema_07() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&HYPER_SCHEMA_07, &HYPER_SCHEMA_07_URI, dialect_07())
+}
+
+/// Returns the [`Dialect`] of Draft 07, composed of the keywords of its
+/// vocabularies which grill implements.
+#[cfg(feature = "draft-07")]
+pub fn dialect_07() -> Dialect {
+    let mut vocabulary = Vocabulary::new(SCHEMA_07_URI.clone());
+    vocabulary.push(Keyword::new("$ref", ref_));
+    vocabulary.push(Keyword::new("oneOf", one_of));
+    vocabulary.push(Keyword::new("patternProperties", pattern_properties));
+    vocabulary.push(Keyword::new("const", const_));
+    super::push_validation_keywords(&mut vocabulary);
+    vocabulary.push(Keyword::new("format", format));
+    push_meta_data_keywords(
+        &mut vocabulary,
+        &[
+            "title",
+            "description",
+            "default",
+            "readOnly",
+            "writeOnly",
+            "examples",
+        ],
+    );
+    Dialect::new([(vocabulary, true)].into_iter().collect()).with_order(KeywordOrder::standard())
 }
 
 /// [Uri] of Schema Draft 07.
@@ -20,6 +57,7 @@ pub static SCHEMA_07_URI: Lazy<Uri> =
 pub static HYPER_SCHEMA_07_URI: Lazy<Uri> =
     Lazy::new(|| Uri::parse("http://json-schema.org/draft-07/hyper-schema#").unwrap());
 
+#[cfg(feature = "draft-07")]
 pub static SCHEMA_07: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![json!({
         "$schema": "http://json-schema.org/draft-07/schema#",
@@ -196,6 +234,7 @@ pub static SCHEMA_07: Lazy<Vec<Value>> = Lazy::new(|| {
     )]
 });
 
+#[cfg(feature = "draft-07")]
 pub static HYPER_SCHEMA_07: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
@@ -1,14 +1,70 @@
-use crate::MetaSchema;
+#[cfg(feature = "draft-2019-09")]
+use crate::{
+    dialect::KeywordOrder,
+    keyword::{
+        const_, format, one_of, pattern_properties, push_content_annotations,
+        push_meta_data_keywords, ref_, Keyword, META_DATA_KEYWORDS,
+    },
+    Dialect, MetaSchema, Vocabulary,
+};
 use once_cell::sync::Lazy;
+#[cfg(feature = "draft-2019-09")]
 use serde_json::{json, Value};
 use uniresid::Uri;
 
+#[cfg(feature = "draft-2019-09")]
+/// Creates the [`MetaSchema`] of the schema of Draft 2019-09,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_schema_2019_09() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&SCHEMA_2019_09, &SCHEMA_2019_09_URI, dialect_2019_09())
 }
 
+#[cfg(feature = "draft-2019-09")]
+/// Creates the [`MetaSchema`] of the hyper schema of Draft 2019-09,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_hyper_schema_2019_09() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(
+        &HYPER_SCHEMA_2019_09,
+        &HYPER_SCHEMA_2019_09_URI,
+        dialect_2019_09(),
+    )
+}
+
+/// Returns the [`Dialect`] of Draft 2019-09, composed of the keywords of its
+/// vocabularies which grill implements.
+#[cfg(feature = "draft-2019-09")]
+pub fn dialect_2019_09() -> Dialect {
+    let vocab = |name: &str| {
+        Uri::parse(&format!(
+            "https://json-schema.org/draft/2019-09/vocab/{name}"
+        ))
+        .unwrap()
+    };
+    let mut core = Vocabulary::new(vocab("core"));
+    core.push(Keyword::new("$ref", ref_));
+    let mut applicator = Vocabulary::new(vocab("applicator"));
+    applicator.push(Keyword::new("oneOf", one_of));
+    applicator.push(Keyword::new("patternProperties", pattern_properties));
+    let mut validation = Vocabulary::new(vocab("validation"));
+    validation.push(Keyword::new("const", const_));
+    super::push_validation_keywords(&mut validation);
+    let mut format_vocabulary = Vocabulary::new(vocab("format"));
+    format_vocabulary.push(Keyword::new("format", format));
+    let mut content = Vocabulary::new(vocab("content"));
+    push_content_annotations(&mut content);
+    let mut meta_data = Vocabulary::new(vocab("meta-data"));
+    push_meta_data_keywords(&mut meta_data, META_DATA_KEYWORDS);
+    let vocabularies = [
+        (core, true),
+        (applicator, true),
+        (validation, true),
+        (format_vocabulary, false),
+        (content, true),
+        (meta_data, true),
+    ]
+    .into_iter()
+    .collect();
+    Dialect::new(vocabularies).with_order(KeywordOrder::standard())
 }
 
 /// [Uri] of Schema Draft 2019-09.
@@ -19,6 +75,7 @@ pub static SCHEMA_2019_09_URI: Lazy<Uri> =
 pub static HYPER_SCHEMA_2019_09_URI: Lazy<Uri> =
     Lazy::new(|| Uri::parse("https://json-schema.org/draft/2019-09/hyper-schema").unwrap());
 
+#[cfg(feature = "draft-2019-09")]
 pub static SCHEMA_2019_09: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
@@ -308,8 +365,51 @@ pub static SCHEMA_2019_09: Lazy<Vec<Value>> = Lazy::new(|| {
             }
         }
         ),
+        json!({
+            "$schema": "https://json-schema.org/draft/2019-09/schema",
+            "$id": "https://json-schema.org/draft/2019-09/schema",
+            "$vocabulary": {
+                "https://json-schema.org/draft/2019-09/vocab/core": true,
+                "https://json-schema.org/draft/2019-09/vocab/applicator": true,
+                "https://json-schema.org/draft/2019-09/vocab/validation": true,
+                "https://json-schema.org/draft/2019-09/vocab/meta-data": true,
+                "https://json-schema.org/draft/2019-09/vocab/format": false,
+                "https://json-schema.org/draft/2019-09/vocab/content": true
+            },
+            "$recursiveAnchor": true,
+
+            "title": "Core and Validation specifications meta-schema",
+            "allOf": [
+                {"$ref": "meta/core"},
+                {"$ref": "meta/applicator"},
+                {"$ref": "meta/validation"},
+                {"$ref": "meta/meta-data"},
+                {"$ref": "meta/format"},
+                {"$ref": "meta/content"}
+            ],
+            "type": ["object", "boolean"],
+            "properties": {
+                "definitions": {
+                    "$comment": "While no longer an official keyword as it is replaced by $defs, this keyword is retained in the meta-schema to prevent incompatible extensions as it remains in common use.",
+                    "type": "object",
+                    "additionalProperties": { "$recursiveRef": "#" },
+                    "default": {}
+                },
+                "dependencies": {
+                    "$comment": "\"dependencies\" is no longer a keyword, but schema authors should avoid redefining it to facilitate a smooth transition to \"dependentSchemas\" and \"dependentRequired\"",
+                    "type": "object",
+                    "additionalProperties": {
+                        "anyOf": [
+                            { "$recursiveRef": "#" },
+                            { "$ref": "meta/validation#/$defs/stringArray" }
+                        ]
+                    }
+                }
+            }
+        }),
     ]
 });
+#[cfg(feature = "draft-2019-09")]
 pub static HYPER_SCHEMA_2019_09: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
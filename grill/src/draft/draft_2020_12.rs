@@ -1,16 +1,75 @@
-use crate::MetaSchema;
+#[cfg(feature = "draft-2020-12")]
+use crate::{
+    dialect::KeywordOrder,
+    keyword::{
+        const_, dynamic_ref, format, one_of, pattern_properties, push_content_annotations,
+        push_meta_data_keywords, ref_, Keyword, META_DATA_KEYWORDS,
+    },
+    Dialect, MetaSchema, Vocabulary,
+};
 use once_cell::sync::Lazy;
+#[cfg(feature = "draft-2020-12")]
 use serde_json::{json, Value};
 use uniresid::Uri;
 
+#[cfg(feature = "draft-2020-12")]
+/// Creates the [`MetaSchema`] of the schema of Draft 2020-12,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_schema_2020_12() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(&SCHEMA_2020_12, &SCHEMA_2020_12_URI, dialect_2020_12())
 }
 
+#[cfg(feature = "draft-2020-12")]
+/// Creates the [`MetaSchema`] of the hyper schema of Draft 2020-12,
+/// which evaluates schemas with its [`Dialect`].
 pub fn create_hyper_schema_2020_12() -> MetaSchema {
-    todo!()
+    super::create_meta_schema(
+        &HYPER_SCHEMA_2020_12,
+        &HYPER_SCHEMA_2020_12_URI,
+        dialect_2020_12(),
+    )
 }
 
+/// Returns the [`Dialect`] of Draft 2020-12, composed of the keywords of its
+/// vocabularies which grill implements.
+#[cfg(feature = "draft-2020-12")]
+pub fn dialect_2020_12() -> Dialect {
+    let vocab = |name: &str| {
+        Uri::parse(&format!(
+            "https://json-schema.org/draft/2020-12/vocab/{name}"
+        ))
+        .unwrap()
+    };
+    let mut core = Vocabulary::new(vocab("core"));
+    core.push(Keyword::new("$ref", ref_));
+    core.push(Keyword::new("$dynamicRef", dynamic_ref));
+    let mut applicator = Vocabulary::new(vocab("applicator"));
+    applicator.push(Keyword::new("oneOf", one_of));
+    applicator.push(Keyword::new("patternProperties", pattern_properties));
+    let mut validation = Vocabulary::new(vocab("validation"));
+    validation.push(Keyword::new("const", const_));
+    super::push_validation_keywords(&mut validation);
+    let mut format_annotation = Vocabulary::new(vocab("format-annotation"));
+    format_annotation.push(Keyword::new("format", format));
+    let mut content = Vocabulary::new(vocab("content"));
+    push_content_annotations(&mut content);
+    let mut meta_data = Vocabulary::new(vocab("meta-data"));
+    push_meta_data_keywords(&mut meta_data, META_DATA_KEYWORDS);
+    let vocabularies = [
+        core,
+        applicator,
+        validation,
+        format_annotation,
+        content,
+        meta_data,
+    ]
+    .into_iter()
+    .map(|vocabulary| (vocabulary, true))
+    .collect();
+    Dialect::new(vocabularies).with_order(KeywordOrder::standard())
+}
+
+#[cfg(feature = "draft-2020-12")]
 pub static SCHEMA_2020_12: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
@@ -277,6 +336,7 @@ pub static SCHEMA_2020_12: Lazy<Vec<Value>> = Lazy::new(|| {
     ]
 });
 
+#[cfg(feature = "draft-2020-12")]
 pub static HYPER_SCHEMA_2020_12: Lazy<Vec<Value>> = Lazy::new(|| {
     vec![
         json!({
@@ -0,0 +1,192 @@
+use crate::{
+    applicator::SetupFn,
+    dialect::KeywordOrder,
+    error::UnsupportedKeywordError,
+    keyword::{
+        bson_type, exclusive_maximum, exclusive_minimum, maximum, minimum, pattern,
+        pattern_properties, unique_items, Keyword,
+    },
+    plugin::Registrar,
+    Dialect, Error, Interrogator, Schema, Vocabulary,
+};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uniresid::{AbsoluteUri, Uri};
+
+/// The name of the MongoDB `$jsonSchema` dialect, as used in errors.
+pub const MONGODB_DIALECT: &str = "MongoDB $jsonSchema";
+
+/// Keywords of JSON Schema Draft 04 which MongoDB's `$jsonSchema` does not
+/// support.
+pub const MONGODB_UNSUPPORTED_KEYWORDS: &[&str] =
+    &["$ref", "$schema", "default", "definitions", "format", "id"];
+
+/// The [Uri] of the MongoDB `$jsonSchema` meta schema.
+///
+/// MongoDB does not publish a meta schema; this identifier is specific to
+/// grill.
+pub static MONGODB_URI: Lazy<Uri> = Lazy::new(|| {
+    Uri::parse("https://www.mongodb.com/docs/manual/reference/operator/query/jsonSchema/").unwrap()
+});
+
+/// The [Uri] of the vocabulary implementing `"bsonType"` and rejecting
+/// unsupported keywords.
+pub static MONGODB_VOCABULARY_URI: Lazy<Uri> = Lazy::new(|| {
+    Uri::parse("https://www.mongodb.com/docs/manual/reference/operator/query/jsonSchema/#vocab")
+        .unwrap()
+});
+
+/// Meta schema of the subset of Draft 04 supported by MongoDB's `$jsonSchema`,
+/// extended with `"bsonType"`.
+pub static MONGODB: Lazy<Value> = Lazy::new(|| {
+    json!({
+        "$id": "https://www.mongodb.com/docs/manual/reference/operator/query/jsonSchema/",
+        "title": "MongoDB $jsonSchema meta-schema",
+        "type": "object",
+        "definitions": {
+            "bsonType": {
+                "enum": [
+                    "double", "string", "object", "array", "binData", "objectId", "bool",
+                    "date", "null", "regex", "javascript", "int", "timestamp", "long",
+                    "decimal", "minKey", "maxKey", "number"
+                ]
+            },
+            "simpleTypes": {
+                "enum": ["array", "boolean", "null", "number", "object", "string"]
+            },
+            "schemaArray": { "type": "array", "minItems": 1, "items": { "$ref": "#" } },
+            "stringArray": { "type": "array", "items": { "type": "string" }, "minItems": 1, "uniqueItems": true }
+        },
+        "properties": {
+            "bsonType": {
+                "anyOf": [
+                    { "$ref": "#/definitions/bsonType" },
+                    { "type": "array", "items": { "$ref": "#/definitions/bsonType" }, "minItems": 1, "uniqueItems": true }
+                ]
+            },
+            "type": {
+                "anyOf": [
+                    { "$ref": "#/definitions/simpleTypes" },
+                    { "type": "array", "items": { "$ref": "#/definitions/simpleTypes" }, "minItems": 1, "uniqueItems": true }
+                ]
+            },
+            "title": { "type": "string" },
+            "description": { "type": "string" },
+            "enum": { "type": "array", "minItems": 1, "uniqueItems": true },
+            "multipleOf": { "type": "number", "exclusiveMinimum": true, "minimum": 0 },
+            "maximum": { "type": "number" },
+            "exclusiveMaximum": { "type": "boolean" },
+            "minimum": { "type": "number" },
+            "exclusiveMinimum": { "type": "boolean" },
+            "maxLength": { "type": "integer", "minimum": 0 },
+            "minLength": { "type": "integer", "minimum": 0 },
+            "pattern": { "type": "string" },
+            "items": {
+                "anyOf": [{ "$ref": "#" }, { "$ref": "#/definitions/schemaArray" }]
+            },
+            "additionalItems": {
+                "anyOf": [{ "type": "boolean" }, { "$ref": "#" }]
+            },
+            "maxItems": { "type": "integer", "minimum": 0 },
+            "minItems": { "type": "integer", "minimum": 0 },
+            "uniqueItems": { "type": "boolean" },
+            "maxProperties": { "type": "integer", "minimum": 0 },
+            "minProperties": { "type": "integer", "minimum": 0 },
+            "required": { "$ref": "#/definitions/stringArray" },
+            "properties": { "type": "object", "additionalProperties": { "$ref": "#" } },
+            "patternProperties": { "type": "object", "additionalProperties": { "$ref": "#" } },
+            "additionalProperties": {
+                "anyOf": [{ "type": "boolean" }, { "$ref": "#" }]
+            },
+            "dependencies": {
+                "type": "object",
+                "additionalProperties": {
+                    "anyOf": [{ "$ref": "#" }, { "$ref": "#/definitions/stringArray" }]
+                }
+            },
+            "allOf": { "$ref": "#/definitions/schemaArray" },
+            "anyOf": { "$ref": "#/definitions/schemaArray" },
+            "oneOf": { "$ref": "#/definitions/schemaArray" },
+            "not": { "$ref": "#" }
+        },
+        "additionalProperties": false
+    })
+});
+
+/// Plugin which registers the MongoDB `$jsonSchema` compatibility dialect.
+///
+/// The dialect is a subset of Draft 04 extended with `"bsonType"`. Schemas
+/// containing any of [`MONGODB_UNSUPPORTED_KEYWORDS`] or `"type": "integer"`
+/// are rejected with an
+/// [`UnsupportedKeywordError`](crate::error::UnsupportedKeywordError).
+///
+/// ## Example
+/// ```
+/// use grill::{draft::{mongodb, MONGODB_URI}, Interrogator};
+///
+/// let interrogator = Interrogator::builder().plugin(mongodb).build().unwrap();
+/// assert!(interrogator.dialect(&MONGODB_URI).is_some());
+/// ```
+pub fn mongodb(registrar: &mut Registrar) -> Result<(), Error> {
+    let uri = AbsoluteUri::parse(&MONGODB_URI.to_string())?;
+    let mut vocabulary = Vocabulary::new(MONGODB_VOCABULARY_URI.clone());
+    vocabulary.push(mongodb_unsupported_keywords);
+    vocabulary.push(Keyword::new("bsonType", bson_type));
+    vocabulary.push(Keyword::new("maximum", maximum));
+    vocabulary.push(Keyword::new("minimum", minimum));
+    vocabulary.push(Keyword::new("exclusiveMaximum", exclusive_maximum));
+    vocabulary.push(Keyword::new("exclusiveMinimum", exclusive_minimum));
+    vocabulary.push(Keyword::new("pattern", pattern));
+    vocabulary.push(Keyword::new("patternProperties", pattern_properties));
+    vocabulary.push(Keyword::new("uniqueItems", unique_items));
+    let mut vocabularies = HashMap::new();
+    vocabularies.insert(vocabulary, true);
+
+    registrar.meta_schema_source(uri, MONGODB.clone()).dialect(
+        MONGODB_URI.clone(),
+        Dialect::new(vocabularies).with_order(KeywordOrder::new().order([
+            "bsonType",
+            "maximum",
+            "minimum",
+            "exclusiveMaximum",
+            "exclusiveMinimum",
+            "pattern",
+            "patternProperties",
+            "uniqueItems",
+        ])),
+    );
+    Ok(())
+}
+
+/// [`Applicator`](crate::Applicator) which rejects keywords which are not
+/// supported by MongoDB's `$jsonSchema`.
+pub fn mongodb_unsupported_keywords(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    let unsupported = MONGODB_UNSUPPORTED_KEYWORDS
+        .iter()
+        .find(|k| source.get(**k).is_some())
+        .map(|k| (*k).to_string())
+        .or_else(|| {
+            let is_integer = |v: &Value| v.as_str() == Some("integer");
+            match source.get("type") {
+                Some(Value::Array(types)) if types.iter().any(is_integer) => {
+                    Some("type: integer".to_string())
+                }
+                Some(t) if is_integer(t) => Some("type: integer".to_string()),
+                _ => None,
+            }
+        });
+    match unsupported {
+        Some(keyword) => Err(UnsupportedKeywordError {
+            schema: schema.clone(),
+            keyword,
+            dialect: MONGODB_DIALECT.to_string(),
+        }
+        .into()),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,234 @@
+use crate::error::UnsupportedDialectError;
+use std::fmt;
+use uniresid::Uri;
+
+/// A release of the JSON Schema specification, identified from the URI of
+/// one of its meta schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Family {
+    /// Draft 04
+    Draft04,
+    /// Draft 06
+    Draft06,
+    /// Draft 07
+    Draft07,
+    /// Draft 2019-09
+    Draft2019_09,
+    /// Draft 2020-12
+    Draft2020_12,
+}
+
+impl Family {
+    /// Returns the `Family` of the meta schema `uri`, e.g.
+    /// [`Draft2020_12`](Family::Draft2020_12) for both
+    /// `"https://json-schema.org/draft/2020-12/schema"` and
+    /// `"https://json-schema.org/draft/2020-12/hyper-schema"`.
+    pub fn of(uri: &str) -> Option<Family> {
+        let key = normalize(uri);
+        let path = key.strip_prefix("json-schema.org/")?;
+        [
+            ("draft-04/", Family::Draft04),
+            ("draft-06/", Family::Draft06),
+            ("draft-07/", Family::Draft07),
+            ("draft/2019-09/", Family::Draft2019_09),
+            ("draft/2020-12/", Family::Draft2020_12),
+        ]
+        .iter()
+        .find(|(prefix, _)| path.starts_with(prefix))
+        .map(|(_, family)| *family)
+    }
+
+    /// Returns the name of the release, e.g. `"2020-12"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Family::Draft04 => "draft-04",
+            Family::Draft06 => "draft-06",
+            Family::Draft07 => "draft-07",
+            Family::Draft2019_09 => "2019-09",
+            Family::Draft2020_12 => "2020-12",
+        }
+    }
+}
+
+impl fmt::Display for Family {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Describes how the dialect selected by [`negotiate`] differs from the one
+/// requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The URIs differ only in scheme, case of the host, or an empty
+    /// fragment, e.g. `"http://json-schema.org/draft-07/schema"` and
+    /// `"http://json-schema.org/draft-07/schema#"`.
+    Equivalent,
+    /// The selected dialect is another meta schema of the same [`Family`],
+    /// e.g. the schema of a requested hyper-schema.
+    Variant(Family),
+    /// The unversioned `"http://json-schema.org/schema#"` was requested and
+    /// the latest supported [`Family`] was selected.
+    Latest(Family),
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Equivalent => f.write_str("equivalent URI"),
+            Mismatch::Variant(family) => write!(f, "another meta schema of {}", family),
+            Mismatch::Latest(family) => write!(f, "latest supported draft, {}", family),
+        }
+    }
+}
+
+/// The dialect selected by [`negotiate`] or [`negotiate_accept`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiation {
+    /// The requested dialect which was satisfied.
+    pub requested: String,
+    /// The selected dialect, one of those supported.
+    pub selected: Uri,
+    /// How `selected` differs from `requested`, if at all.
+    pub mismatch: Option<Mismatch>,
+}
+
+impl Negotiation {
+    /// Returns `true` if the selected dialect is exactly the one requested.
+    pub fn is_exact(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+/// Selects the dialect among `supported` which best satisfies `requested`,
+/// such as the `$schema` of a schema submitted by a client.
+///
+/// In order of preference, the selected dialect is:
+/// 1. identical to `requested`
+/// 2. equivalent to `requested`, ignoring scheme, case of the host, and an
+///    empty fragment
+/// 3. of the same [`Family`], preferring the meta schema of the same name
+///    (e.g. `"schema"` or `"hyper-schema"`) and then `"schema"`
+/// 4. the latest supported [`Family`], if `requested` is the unversioned
+///    `"http://json-schema.org/schema#"`
+///
+/// ## Example
+/// ```
+/// use grill::draft::{negotiate, Family, Mismatch, SCHEMA_07_URI, SCHEMA_2020_12_URI};
+///
+/// let supported = [SCHEMA_07_URI.clone(), SCHEMA_2020_12_URI.clone()];
+///
+/// let exact = negotiate(&supported, "https://json-schema.org/draft/2020-12/schema").unwrap();
+/// assert!(exact.is_exact());
+///
+/// let hyper = negotiate(&supported, "https://json-schema.org/draft/2020-12/hyper-schema").unwrap();
+/// assert_eq!(hyper.selected, *SCHEMA_2020_12_URI);
+/// assert_eq!(hyper.mismatch, Some(Mismatch::Variant(Family::Draft2020_12)));
+///
+/// assert!(negotiate(&supported, "http://json-schema.org/draft-04/schema#").is_err());
+/// ```
+pub fn negotiate(
+    supported: &[Uri],
+    requested: &str,
+) -> Result<Negotiation, UnsupportedDialectError> {
+    select(supported, requested).ok_or_else(|| UnsupportedDialectError {
+        requested: requested.to_string(),
+        supported: supported.to_vec(),
+    })
+}
+
+/// Selects the dialect among `supported` which best satisfies an
+/// Accept-style list of dialects, e.g.
+/// `"https://json-schema.org/draft/2020-12/schema, http://json-schema.org/draft-07/schema#;q=0.5"`.
+///
+/// Each entry is negotiated as with [`negotiate`] in order of descending
+/// quality (`q`, which defaults to `1`); entries of equal quality are tried
+/// in the order listed. Entries with a quality of `0` are ignored.
+pub fn negotiate_accept(
+    supported: &[Uri],
+    header: &str,
+) -> Result<Negotiation, UnsupportedDialectError> {
+    let mut entries: Vec<(&str, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut params = entry.split(';');
+            let uri = params.next()?.trim();
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!uri.is_empty() && quality > 0.0).then_some((uri, quality))
+        })
+        .collect();
+    // stable; entries of equal quality retain their order
+    entries.sort_by(|a, b| b.1.total_cmp(&a.1));
+    entries
+        .into_iter()
+        .find_map(|(uri, _)| select(supported, uri))
+        .ok_or_else(|| UnsupportedDialectError {
+            requested: header.to_string(),
+            supported: supported.to_vec(),
+        })
+}
+
+fn select(supported: &[Uri], requested: &str) -> Option<Negotiation> {
+    let negotiation = |selected: &Uri, mismatch| Negotiation {
+        requested: requested.to_string(),
+        selected: selected.clone(),
+        mismatch,
+    };
+    if let Some(uri) = supported.iter().find(|uri| uri.to_string() == requested) {
+        return Some(negotiation(uri, None));
+    }
+    let key = normalize(requested);
+    if let Some(uri) = supported
+        .iter()
+        .find(|uri| normalize(&uri.to_string()) == key)
+    {
+        return Some(negotiation(uri, Some(Mismatch::Equivalent)));
+    }
+    if key == "json-schema.org/schema" {
+        let latest = supported
+            .iter()
+            .filter_map(|uri| Some((Family::of(&uri.to_string())?, uri)))
+            .max_by(|a, b| {
+                // prefer "schema" over other meta schemas of the same family
+                a.0.cmp(&b.0)
+                    .then_with(|| is_schema(a.1).cmp(&is_schema(b.1)))
+            })?;
+        return Some(negotiation(latest.1, Some(Mismatch::Latest(latest.0))));
+    }
+    let family = Family::of(requested)?;
+    let name = meta_schema_name(&key);
+    let candidates: Vec<&Uri> = supported
+        .iter()
+        .filter(|uri| Family::of(&uri.to_string()) == Some(family))
+        .collect();
+    let selected = candidates
+        .iter()
+        .find(|uri| meta_schema_name(&normalize(&uri.to_string())) == name)
+        .or_else(|| candidates.iter().find(|uri| is_schema(uri)))
+        .or_else(|| candidates.first())?;
+    Some(negotiation(selected, Some(Mismatch::Variant(family))))
+}
+
+/// Returns `uri` without its scheme or an empty fragment, with the host in
+/// lowercase.
+fn normalize(uri: &str) -> String {
+    let uri = uri.trim();
+    let uri = uri.strip_suffix('#').unwrap_or(uri);
+    let rest = uri.split_once("://").map_or(uri, |(_, rest)| rest);
+    match rest.split_once('/') {
+        Some((host, path)) => format!("{}/{}", host.to_ascii_lowercase(), path),
+        None => rest.to_ascii_lowercase(),
+    }
+}
+
+/// Returns the last segment of the path of a normalized URI, e.g. `"schema"`.
+fn meta_schema_name(key: &str) -> &str {
+    key.rsplit('/').next().unwrap_or(key)
+}
+
+fn is_schema(uri: &Uri) -> bool {
+    meta_schema_name(&normalize(&uri.to_string())) == "schema"
+}
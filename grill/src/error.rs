@@ -1,12 +1,15 @@
+use crate::deserializer::DeserializeError;
 use crate::evaluation::Field;
-use crate::{Evaluation, Schema};
+use crate::lint::Diagnostic;
+use crate::{Evaluation, Feature, Schema};
 
-use jsonptr::{Error as PointerError, MalformedPointerError};
+use jsonptr::{Error as PointerError, MalformedPointerError, Pointer};
+use serde::{ser::SerializeMap, Serialize, Serializer};
 use serde_json::{Error as SerdeError, Value};
 use std::error::Error as StdError;
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
-use uniresid::{Error as UriError, Uri};
+use uniresid::{AbsoluteUri, Error as UriError, Uri};
 
 /// Represents all possible errors that can occur while initializing, setting up
 /// or using an [`Interrogator`] and [`Schema`].
@@ -35,6 +38,90 @@ pub enum Error {
 
     /// Schema is not setup. Add it to the [`Interrogator`] before using it.
     SchemaNotSetup(SchemaNotSetupError),
+
+    /// A keyword of a [`Schema`] contained an invalid value.
+    InvalidKeyword(InvalidKeywordError),
+
+    /// A capability was requested which requires a cargo [`Feature`] that is
+    /// not enabled.
+    FeatureDisabled(FeatureDisabledError),
+
+    /// A keyword is not supported by the dialect of a [`Schema`].
+    UnsupportedKeyword(UnsupportedKeywordError),
+
+    /// An evaluation exceeded its cost budget.
+    BudgetExceeded(BudgetExceededError),
+
+    /// A [`Schema`] was requested by an id which is not known to the
+    /// [`Interrogator`].
+    UnknownSchema(UnknownSchemaError),
+
+    /// A length exceeded the range of the integer type it was converted to.
+    Overflow(OverflowError),
+
+    /// None of the supported dialects satisfies the requested dialect.
+    UnsupportedDialect(UnsupportedDialectError),
+
+    /// A fragment combined an anchor with a JSON Pointer.
+    AnchorPointerFragment(AnchorPointerFragmentError),
+
+    /// A sealed snapshot of the state of an [`Interrogator`](crate::Interrogator)
+    /// failed verification. See `state`.
+    StateVerification(StateVerificationError),
+
+    /// An operation was cancelled through a
+    /// [`CancellationToken`](crate::cancel::CancellationToken).
+    Cancelled(CancelledError),
+
+    /// An evaluation did not complete before its
+    /// [timeout](crate::evaluation::Options::timeout).
+    Timeout(TimeoutError),
+
+    /// The target of a `"$ref"` is neither a schema object nor a boolean
+    /// schema.
+    InvalidRefTarget(InvalidRefTargetError),
+
+    /// A chain of references between documents exceeded the maximum depth
+    /// of the [`Interrogator`](crate::Interrogator).
+    ReferenceDepthExceeded(ReferenceDepthError),
+
+    /// A document was claimed or accessed by a
+    /// [`Namespace`](crate::namespace::Namespace) other than its owner.
+    Namespace(NamespaceError),
+
+    /// A dependency could not be resolved or written while vendoring.
+    Vendor(VendorError),
+
+    /// One or more anchors of a `Schema` are malformed or declared more than
+    /// once within a resource.
+    InvalidAnchor(InvalidAnchorError),
+
+    /// One or more URIs of a batch passed to `Interrogator::compile_all` could
+    /// not be resolved or compiled.
+    CompileAll(CompileAllError),
+
+    /// A source document could not be deserialized.
+    Deserialize(DeserializeError),
+
+    /// A [`Schema`] could not be removed because other schemas reference it.
+    SchemaInUse(SchemaInUseError),
+
+    /// A meta schema requires, with `"$vocabulary"`, a vocabulary which is not
+    /// registered.
+    UnknownVocabulary(UnknownVocabularyError),
+
+    /// A keyword of a [`Schema`] requires random access to an instance which is
+    /// being evaluated from a stream.
+    RandomAccess(RandomAccessError),
+
+    /// A source document imported from another
+    /// [`Interrogator`](crate::Interrogator) conflicts with an existing
+    /// source.
+    ImportConflict(ImportConflictError),
+
+    /// A [`LockedResolver`](crate::vendor::LockedResolver) refused to resolve
+    /// a document.
+    Lock(LockError),
 }
 impl Error {
     /// Wraps a `std::error::Error` in an [`Error::Internal`](Error::Internal).
@@ -98,6 +185,199 @@ impl Error {
     pub fn is_schema_not_setup(&self) -> bool {
         matches!(self, Error::SchemaNotSetup(_))
     }
+
+    /// Returns `true` if the error is an `InvalidKeyword` error.
+    pub fn is_invalid_keyword(&self) -> bool {
+        matches!(self, Error::InvalidKeyword(_))
+    }
+
+    /// Returns `true` if the error is a `FeatureDisabled` error.
+    pub fn is_feature_disabled(&self) -> bool {
+        matches!(self, Error::FeatureDisabled(_))
+    }
+
+    /// Returns `true` if the error is an `UnsupportedKeyword` error.
+    pub fn is_unsupported_keyword(&self) -> bool {
+        matches!(self, Error::UnsupportedKeyword(_))
+    }
+
+    /// Returns `true` if the error is a `BudgetExceeded` error.
+    pub fn is_budget_exceeded(&self) -> bool {
+        matches!(self, Error::BudgetExceeded(_))
+    }
+
+    /// Returns `true` if the error is an `UnknownSchema` error.
+    pub fn is_unknown_schema(&self) -> bool {
+        matches!(self, Error::UnknownSchema(_))
+    }
+
+    /// Returns `true` if the error is an `Overflow` error.
+    pub fn is_overflow(&self) -> bool {
+        matches!(self, Error::Overflow(_))
+    }
+
+    /// Returns `true` if the error is an `UnsupportedDialect` error.
+    pub fn is_unsupported_dialect(&self) -> bool {
+        matches!(self, Error::UnsupportedDialect(_))
+    }
+
+    /// Returns `true` if the error is an `AnchorPointerFragment` error.
+    pub fn is_anchor_pointer_fragment(&self) -> bool {
+        matches!(self, Error::AnchorPointerFragment(_))
+    }
+
+    /// Returns `true` if the error is a `StateVerification` error.
+    pub fn is_state_verification(&self) -> bool {
+        matches!(self, Error::StateVerification(_))
+    }
+
+    /// Returns `true` if the error is a `Cancelled` error.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Error::Cancelled(_))
+    }
+
+    /// Returns `true` if the error is a `Timeout` error.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout(_))
+    }
+
+    /// Returns `true` if the error is an `InvalidRefTarget` error.
+    pub fn is_invalid_ref_target(&self) -> bool {
+        matches!(self, Error::InvalidRefTarget(_))
+    }
+
+    /// Returns `true` if the error is a `ReferenceDepthExceeded` error.
+    pub fn is_reference_depth_exceeded(&self) -> bool {
+        matches!(self, Error::ReferenceDepthExceeded(_))
+    }
+
+    /// Returns `true` if the error is a `Namespace` error.
+    pub fn is_namespace(&self) -> bool {
+        matches!(self, Error::Namespace(_))
+    }
+
+    /// Returns `true` if the error is a `Vendor` error.
+    pub fn is_vendor(&self) -> bool {
+        matches!(self, Error::Vendor(_))
+    }
+
+    /// Returns `true` if the error is an `InvalidAnchor` error.
+    pub fn is_invalid_anchor(&self) -> bool {
+        matches!(self, Error::InvalidAnchor(_))
+    }
+
+    /// Returns `true` if the error is a `CompileAll` error.
+    pub fn is_compile_all(&self) -> bool {
+        matches!(self, Error::CompileAll(_))
+    }
+
+    /// Returns `true` if the error is a `Deserialize` error.
+    pub fn is_deserialize(&self) -> bool {
+        matches!(self, Error::Deserialize(_))
+    }
+
+    /// Returns `true` if the error is a `SchemaInUse` error.
+    pub fn is_schema_in_use(&self) -> bool {
+        matches!(self, Error::SchemaInUse(_))
+    }
+
+    /// Returns `true` if the error is a `UnknownVocabulary` error.
+    pub fn is_unknown_vocabulary(&self) -> bool {
+        matches!(self, Error::UnknownVocabulary(_))
+    }
+
+    /// Returns `true` if the error is a `RandomAccess` error.
+    pub fn is_random_access(&self) -> bool {
+        matches!(self, Error::RandomAccess(_))
+    }
+
+    /// Returns `true` if the error is an `ImportConflict` error.
+    pub fn is_import_conflict(&self) -> bool {
+        matches!(self, Error::ImportConflict(_))
+    }
+
+    /// Returns `true` if the error is a `Lock` error.
+    pub fn is_lock(&self) -> bool {
+        matches!(self, Error::Lock(_))
+    }
+
+    /// Converts the failure of a [`Resolver`](crate::Resolver), classifying
+    /// the refusal of a [`LockedResolver`](crate::vendor::LockedResolver) as
+    /// a [`Lock`](Error::Lock) error and a document which could not be
+    /// deserialized as a [`Deserialize`](Error::Deserialize) error.
+    pub(crate) fn from_resolve(err: crate::ResolveError) -> Self {
+        match err {
+            crate::ResolveError::Internal(err) => match err.downcast::<LockError>() {
+                Ok(err) => Error::Lock(*err),
+                Err(err) => match err.downcast::<DeserializeError>() {
+                    Ok(err) => Error::Deserialize(*err),
+                    Err(err) => Error::new_internal(crate::ResolveError::Internal(err)),
+                },
+            },
+            err => Error::new_internal(err),
+        }
+    }
+
+    /// Returns the stable, machine-readable code of the error.
+    ///
+    /// Codes of errors which occur while compiling or setting up a
+    /// [`Schema`] are prefixed with `GRILL_C`, codes of errors which occur
+    /// while evaluating an instance are prefixed with `GRILL_E`, and codes of
+    /// errors which occur in either, i.e. [`Internal`](Error::Internal) and
+    /// [`Serde`](Error::Serde), are prefixed with `GRILL_X`. A code is never
+    /// changed or reused once assigned, so it is safe to branch on rather
+    /// than on the message of the error. The code is included when the error
+    /// is serialized.
+    ///
+    /// An instance which is not valid is not an `Error`; the code of each of
+    /// its validation errors is given by [`validation_code`].
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{error::UnknownSchemaError, uri::Uri, Error};
+    ///
+    /// let err = Error::from(UnknownSchemaError {
+    ///     id: Uri::parse("https://example.com/missing").unwrap(),
+    /// });
+    /// assert_eq!(err.code(), "GRILL_C0010");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Internal(_) => "GRILL_X0001",
+            Error::Serde(_) => "GRILL_X0002",
+            Error::InvalidSchema(_) => "GRILL_C0001",
+            Error::UnindentifiedSchema(_) => "GRILL_C0002",
+            Error::InvalidPointer(_) => "GRILL_C0003",
+            Error::ExpectedString(_) => "GRILL_E0001",
+            Error::InvalidUri(_) => "GRILL_C0004",
+            Error::MetaSchema(_) => "GRILL_C0005",
+            Error::MissingRequiredVocabulary(_) => "GRILL_C0006",
+            Error::SchemaNotSetup(_) => "GRILL_E0002",
+            Error::InvalidKeyword(_) => "GRILL_C0007",
+            Error::FeatureDisabled(_) => "GRILL_C0008",
+            Error::UnsupportedKeyword(_) => "GRILL_C0009",
+            Error::BudgetExceeded(_) => "GRILL_E0003",
+            Error::UnknownSchema(_) => "GRILL_C0010",
+            Error::Overflow(_) => "GRILL_E0004",
+            Error::UnsupportedDialect(_) => "GRILL_C0011",
+            Error::AnchorPointerFragment(_) => "GRILL_C0012",
+            Error::StateVerification(_) => "GRILL_C0013",
+            Error::Cancelled(_) => "GRILL_E0005",
+            Error::Timeout(_) => "GRILL_E0006",
+            Error::InvalidRefTarget(_) => "GRILL_C0014",
+            Error::ReferenceDepthExceeded(_) => "GRILL_C0015",
+            Error::Namespace(_) => "GRILL_C0016",
+            Error::Vendor(_) => "GRILL_C0017",
+            Error::InvalidAnchor(_) => "GRILL_C0018",
+            Error::CompileAll(_) => "GRILL_C0019",
+            Error::Deserialize(_) => "GRILL_C0020",
+            Error::SchemaInUse(_) => "GRILL_C0021",
+            Error::UnknownVocabulary(_) => "GRILL_C0022",
+            Error::RandomAccess(_) => "GRILL_E0007",
+            Error::ImportConflict(_) => "GRILL_C0023",
+            Error::Lock(_) => "GRILL_C0024",
+        }
+    }
 }
 
 impl From<UnidentifiedSchemaError> for Error {
@@ -169,6 +449,144 @@ impl From<SchemaNotSetupError> for Error {
     }
 }
 
+impl From<InvalidKeywordError> for Error {
+    fn from(err: InvalidKeywordError) -> Self {
+        Error::InvalidKeyword(err)
+    }
+}
+
+impl From<FeatureDisabledError> for Error {
+    fn from(err: FeatureDisabledError) -> Self {
+        Error::FeatureDisabled(err)
+    }
+}
+
+impl From<UnsupportedKeywordError> for Error {
+    fn from(err: UnsupportedKeywordError) -> Self {
+        Error::UnsupportedKeyword(err)
+    }
+}
+
+impl From<BudgetExceededError> for Error {
+    fn from(err: BudgetExceededError) -> Self {
+        Error::BudgetExceeded(err)
+    }
+}
+
+impl From<UnknownSchemaError> for Error {
+    fn from(err: UnknownSchemaError) -> Self {
+        Error::UnknownSchema(err)
+    }
+}
+
+impl From<OverflowError> for Error {
+    fn from(err: OverflowError) -> Self {
+        Error::Overflow(err)
+    }
+}
+
+impl From<UnsupportedDialectError> for Error {
+    fn from(err: UnsupportedDialectError) -> Self {
+        Error::UnsupportedDialect(err)
+    }
+}
+
+impl From<AnchorPointerFragmentError> for Error {
+    fn from(err: AnchorPointerFragmentError) -> Self {
+        Error::AnchorPointerFragment(err)
+    }
+}
+
+impl From<StateVerificationError> for Error {
+    fn from(err: StateVerificationError) -> Self {
+        Error::StateVerification(err)
+    }
+}
+
+impl From<CancelledError> for Error {
+    fn from(err: CancelledError) -> Self {
+        Error::Cancelled(err)
+    }
+}
+
+impl From<TimeoutError> for Error {
+    fn from(err: TimeoutError) -> Self {
+        Error::Timeout(err)
+    }
+}
+
+impl From<InvalidRefTargetError> for Error {
+    fn from(err: InvalidRefTargetError) -> Self {
+        Error::InvalidRefTarget(err)
+    }
+}
+
+impl From<ReferenceDepthError> for Error {
+    fn from(err: ReferenceDepthError) -> Self {
+        Error::ReferenceDepthExceeded(err)
+    }
+}
+
+impl From<NamespaceError> for Error {
+    fn from(err: NamespaceError) -> Self {
+        Error::Namespace(err)
+    }
+}
+
+impl From<VendorError> for Error {
+    fn from(err: VendorError) -> Self {
+        Error::Vendor(err)
+    }
+}
+
+impl From<InvalidAnchorError> for Error {
+    fn from(err: InvalidAnchorError) -> Self {
+        Error::InvalidAnchor(err)
+    }
+}
+
+impl From<CompileAllError> for Error {
+    fn from(err: CompileAllError) -> Self {
+        Error::CompileAll(err)
+    }
+}
+
+impl From<DeserializeError> for Error {
+    fn from(err: DeserializeError) -> Self {
+        Error::Deserialize(err)
+    }
+}
+
+impl From<SchemaInUseError> for Error {
+    fn from(err: SchemaInUseError) -> Self {
+        Error::SchemaInUse(err)
+    }
+}
+
+impl From<UnknownVocabularyError> for Error {
+    fn from(err: UnknownVocabularyError) -> Self {
+        Error::UnknownVocabulary(err)
+    }
+}
+
+impl From<RandomAccessError> for Error {
+    fn from(err: RandomAccessError) -> Self {
+        Error::RandomAccess(err)
+    }
+}
+
+impl From<ImportConflictError> for Error {
+    fn from(err: ImportConflictError) -> Self {
+        Error::ImportConflict(err)
+    }
+}
+
+impl From<LockError> for Error {
+    fn from(err: LockError) -> Self {
+        Error::Lock(err)
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -182,6 +600,29 @@ impl Display for Error {
             Error::MetaSchema(err) => Display::fmt(err, f),
             Error::MissingRequiredVocabulary(err) => Display::fmt(err, f),
             Error::SchemaNotSetup(err) => Display::fmt(err, f),
+            Error::InvalidKeyword(err) => Display::fmt(err, f),
+            Error::FeatureDisabled(err) => Display::fmt(err, f),
+            Error::UnsupportedKeyword(err) => Display::fmt(err, f),
+            Error::BudgetExceeded(err) => Display::fmt(err, f),
+            Error::UnknownSchema(err) => Display::fmt(err, f),
+            Error::Overflow(err) => Display::fmt(err, f),
+            Error::UnsupportedDialect(err) => Display::fmt(err, f),
+            Error::AnchorPointerFragment(err) => Display::fmt(err, f),
+            Error::StateVerification(err) => Display::fmt(err, f),
+            Error::Cancelled(err) => Display::fmt(err, f),
+            Error::Timeout(err) => Display::fmt(err, f),
+            Error::InvalidRefTarget(err) => Display::fmt(err, f),
+            Error::ReferenceDepthExceeded(err) => Display::fmt(err, f),
+            Error::Namespace(err) => Display::fmt(err, f),
+            Error::Vendor(err) => Display::fmt(err, f),
+            Error::InvalidAnchor(err) => Display::fmt(err, f),
+            Error::CompileAll(err) => Display::fmt(err, f),
+            Error::Deserialize(err) => Display::fmt(err, f),
+            Error::SchemaInUse(err) => Display::fmt(err, f),
+            Error::UnknownVocabulary(err) => Display::fmt(err, f),
+            Error::RandomAccess(err) => Display::fmt(err, f),
+            Error::ImportConflict(err) => Display::fmt(err, f),
+            Error::Lock(err) => Display::fmt(err, f),
         }
     }
 }
@@ -199,10 +640,114 @@ impl StdError for Error {
             Error::MetaSchema(err) => Some(err),
             Error::MissingRequiredVocabulary(err) => Some(err),
             Error::SchemaNotSetup(err) => Some(err),
+            Error::InvalidKeyword(err) => Some(err),
+            Error::FeatureDisabled(err) => Some(err),
+            Error::UnsupportedKeyword(err) => Some(err),
+            Error::BudgetExceeded(err) => Some(err),
+            Error::UnknownSchema(err) => Some(err),
+            Error::Overflow(err) => Some(err),
+            Error::UnsupportedDialect(err) => Some(err),
+            Error::AnchorPointerFragment(err) => Some(err),
+            Error::StateVerification(err) => Some(err),
+            Error::Cancelled(err) => Some(err),
+            Error::Timeout(err) => Some(err),
+            Error::InvalidRefTarget(err) => Some(err),
+            Error::ReferenceDepthExceeded(err) => Some(err),
+            Error::Namespace(err) => Some(err),
+            Error::Vendor(err) => Some(err),
+            Error::InvalidAnchor(err) => Some(err),
+            Error::CompileAll(err) => Some(err),
+            Error::Deserialize(err) => Some(err),
+            Error::SchemaInUse(err) => Some(err),
+            Error::UnknownVocabulary(err) => Some(err),
+            Error::RandomAccess(err) => Some(err),
+            Error::ImportConflict(err) => Some(err),
+            Error::Lock(err) => Some(err),
+        }
+    }
+}
+
+/// Serializes as an object with the [`code`](Error::code) and message of the
+/// error and, for [`Error::CompileAll`], each of its failures, e.g.
+/// `{"code": "GRILL_C0010", "message": "schema [...] not found"}`.
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        if let Error::CompileAll(err) = self {
+            map.serialize_entry("failures", &err.failures)?;
         }
+        map.end()
     }
 }
 
+/// The codes of the validation errors of the keywords grill implements.
+const VALIDATION_CODES: &[(&str, &str)] = &[
+    ("type", "GRILL_V0002"),
+    ("enum", "GRILL_V0003"),
+    ("const", "GRILL_V0004"),
+    ("multipleOf", "GRILL_V0005"),
+    ("maximum", "GRILL_V0006"),
+    ("exclusiveMaximum", "GRILL_V0007"),
+    ("minimum", "GRILL_V0008"),
+    ("exclusiveMinimum", "GRILL_V0009"),
+    ("maxLength", "GRILL_V0010"),
+    ("minLength", "GRILL_V0011"),
+    ("pattern", "GRILL_V0012"),
+    ("maxItems", "GRILL_V0013"),
+    ("minItems", "GRILL_V0014"),
+    ("uniqueItems", "GRILL_V0015"),
+    ("maxContains", "GRILL_V0016"),
+    ("minContains", "GRILL_V0017"),
+    ("maxProperties", "GRILL_V0018"),
+    ("minProperties", "GRILL_V0019"),
+    ("required", "GRILL_V0020"),
+    ("dependentRequired", "GRILL_V0021"),
+    ("format", "GRILL_V0022"),
+    ("contentEncoding", "GRILL_V0023"),
+    ("contentMediaType", "GRILL_V0024"),
+    ("contentSchema", "GRILL_V0025"),
+    ("allOf", "GRILL_V0026"),
+    ("anyOf", "GRILL_V0027"),
+    ("oneOf", "GRILL_V0028"),
+    ("not", "GRILL_V0029"),
+    ("patternProperties", "GRILL_V0030"),
+    ("$ref", "GRILL_V0031"),
+    ("$dynamicRef", "GRILL_V0032"),
+    ("bsonType", "GRILL_V0033"),
+    ("formatMinimum", "GRILL_V0034"),
+    ("formatMaximum", "GRILL_V0035"),
+    ("formatExclusiveMinimum", "GRILL_V0036"),
+    ("formatExclusiveMaximum", "GRILL_V0037"),
+    ("uniqueKeys", "GRILL_V0038"),
+    ("sortOrder", "GRILL_V0039"),
+];
+
+/// Returns the stable, machine-readable code of the validation errors of
+/// `keyword`, e.g. `GRILL_V0006` for `"maximum"`.
+///
+/// Codes of validation errors are prefixed with `GRILL_V`. Each keyword
+/// grill implements has its own; the errors of other keywords, e.g. custom
+/// keywords, and of the schema `false` share `GRILL_V0001`. As with
+/// [`Error::code`], a code is never changed or reused once assigned. The
+/// code is included in each output unit and [`Report`](crate::report::Report)
+/// entry with an error; see [`Evaluation::error_code`](crate::Evaluation::error_code).
+///
+/// ## Example
+/// ```
+/// use grill::error::validation_code;
+///
+/// assert_eq!(validation_code("maximum"), "GRILL_V0006");
+/// assert_eq!(validation_code("x-custom"), "GRILL_V0001");
+/// ```
+pub fn validation_code(keyword: &str) -> &'static str {
+    VALIDATION_CODES
+        .iter()
+        .find(|(k, _)| *k == keyword)
+        .map_or("GRILL_V0001", |(_, code)| code)
+}
+
 #[derive(Debug, Clone)]
 pub enum MetaSchemaError {
     /// The `$schema` is not known to the [`Interrogator`].
@@ -337,3 +882,689 @@ impl Display for MissingRequiredVocabularyError {
     }
 }
 impl StdError for MissingRequiredVocabularyError {}
+
+/// Indicates that the value of a keyword within a [`Schema`] was not of the
+/// expected type or form.
+#[derive(Debug, Clone)]
+pub struct InvalidKeywordError {
+    /// The [`Schema`] containing the keyword.
+    pub schema: Schema,
+    /// The keyword with the invalid value.
+    pub keyword: String,
+    /// A description of what was expected, e.g. `"a number"`.
+    pub expected: String,
+    /// The invalid value.
+    pub value: Value,
+}
+impl Display for InvalidKeywordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid value for keyword \"{}\"{}: expected {}, found {}",
+            self.keyword,
+            self.schema
+                .id()
+                .map_or(String::new(), |v| format!(" in schema [{}]", &v)),
+            self.expected,
+            self.value
+        )
+    }
+}
+impl StdError for InvalidKeywordError {}
+
+/// Indicates that a capability was requested which requires a cargo
+/// [`Feature`] that was not enabled at compile time.
+#[derive(Debug, Clone)]
+pub struct FeatureDisabledError {
+    /// The [`Feature`] required by the capability.
+    pub feature: Feature,
+    /// A description of the capability which was requested.
+    pub capability: String,
+}
+impl Display for FeatureDisabledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requires the \"{}\" feature of grill, which is not enabled",
+            self.capability, self.feature
+        )
+    }
+}
+impl StdError for FeatureDisabledError {}
+
+/// Indicates that a [`Schema`] contains a keyword which is not supported by
+/// its dialect, e.g. `"$ref"` within a MongoDB `$jsonSchema`.
+#[derive(Debug, Clone)]
+pub struct UnsupportedKeywordError {
+    /// The [`Schema`] containing the keyword.
+    pub schema: Schema,
+    /// The unsupported keyword.
+    pub keyword: String,
+    /// The name of the dialect.
+    pub dialect: String,
+}
+impl Display for UnsupportedKeywordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "keyword \"{}\"{} is not supported by {}",
+            self.keyword,
+            self.schema
+                .id()
+                .map_or(String::new(), |v| format!(" in schema [{}]", &v)),
+            self.dialect
+        )
+    }
+}
+impl StdError for UnsupportedKeywordError {}
+
+/// Indicates that an evaluation exceeded the budget set with
+/// [`Options::budget`](crate::evaluation::Options::budget).
+#[derive(Debug, Clone)]
+pub struct BudgetExceededError {
+    /// The budget of the evaluation.
+    pub budget: u64,
+    /// The cost spent, including that of the keyword which exceeded the
+    /// budget.
+    pub spent: u64,
+    /// The location of the instance node being evaluated.
+    pub instance_location: Pointer,
+    /// The location of the schema being applied.
+    pub keyword_location: Pointer,
+}
+impl Display for BudgetExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "evaluation budget of {} exceeded ({} spent) at \"{}\" applying \"{}\"",
+            self.budget, self.spent, self.instance_location, self.keyword_location
+        )
+    }
+}
+impl StdError for BudgetExceededError {}
+
+/// Indicates that no [`Schema`] with the given id has been added to the
+/// [`Interrogator`](crate::Interrogator).
+#[derive(Debug, Clone)]
+pub struct UnknownSchemaError {
+    /// The id which was not found.
+    pub id: Uri,
+}
+impl Display for UnknownSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "schema [{}] not found", self.id)
+    }
+}
+impl StdError for UnknownSchemaError {}
+
+/// Indicates that a length could not be represented in the integer type
+/// required by its destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError {
+    /// The offending length.
+    pub len: usize,
+    /// The maximum value of the destination type.
+    pub max: u64,
+    /// A description of what was being measured, e.g. `"source document"`.
+    pub context: String,
+}
+impl Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of length {} exceeds the maximum of {}",
+            self.context, self.len, self.max
+        )
+    }
+}
+impl StdError for OverflowError {}
+
+/// Indicates that a regular expression could not be compiled by a
+/// [`RegexEngine`](crate::pattern::RegexEngine).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// The regular expression.
+    pub pattern: String,
+    /// The engine which failed to compile it.
+    pub engine: crate::pattern::RegexEngine,
+    /// The reason given by the engine.
+    pub reason: String,
+}
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} is not a valid {} regular expression: {}",
+            self.pattern, self.engine, self.reason
+        )
+    }
+}
+impl StdError for PatternError {}
+
+/// Indicates that a JSON Pointer, Relative JSON Pointer, or URI fragment
+/// could not be parsed. See [`pointer`](crate::pointer).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerSyntaxError {
+    /// The text which could not be parsed.
+    pub value: String,
+    /// Why it could not be parsed.
+    pub reason: String,
+}
+impl Display for PointerSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} is not a valid pointer: {}",
+            self.value, self.reason
+        )
+    }
+}
+impl StdError for PointerSyntaxError {}
+
+/// Indicates that a URI reference could not be parsed as a
+/// [`UriStr`](crate::uri::UriStr) or
+/// [`AbsoluteUriStr`](crate::uri::AbsoluteUriStr).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriSyntaxError {
+    /// The text which could not be parsed.
+    pub value: String,
+    /// Why it could not be parsed.
+    pub reason: String,
+}
+impl Display for UriSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a valid URI: {}", self.value, self.reason)
+    }
+}
+impl StdError for UriSyntaxError {}
+
+/// Indicates that none of the supported dialects satisfies the dialect
+/// requested by a client.
+#[derive(Debug, Clone)]
+pub struct UnsupportedDialectError {
+    /// The requested dialect, e.g. the `$schema` of a schema or an
+    /// Accept-style list of dialects.
+    pub requested: String,
+    /// The dialects which are supported.
+    pub supported: Vec<Uri>,
+}
+impl Display for UnsupportedDialectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let supported: Vec<String> = self.supported.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "dialect \"{}\" is not supported; supported dialects are: {}",
+            self.requested,
+            supported.join(", ")
+        )
+    }
+}
+impl StdError for UnsupportedDialectError {}
+
+/// Indicates that a fragment combines a plain-name anchor with a JSON
+/// Pointer, e.g. `"#node/properties/name"`, which the specification does not
+/// permit.
+///
+/// See [`fragment`](crate::fragment) for resolving such fragments leniently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorPointerFragmentError {
+    /// The offending fragment, without the leading `'#'`.
+    pub fragment: String,
+    /// The anchor portion of the fragment.
+    pub anchor: String,
+    /// The JSON Pointer portion of the fragment.
+    pub pointer: String,
+    /// The equivalent JSON Pointer fragment, if the anchor and the location
+    /// it points to could be found.
+    pub equivalent: Option<Pointer>,
+}
+impl Display for AnchorPointerFragmentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fragment \"#{}\" combines the anchor \"{}\" with the JSON Pointer \"{}\"; an anchor may not be followed by a pointer",
+            self.fragment, self.anchor, self.pointer
+        )?;
+        if let Some(equivalent) = &self.equivalent {
+            write!(f, " (use \"#{}\" instead)", equivalent)?;
+        }
+        Ok(())
+    }
+}
+impl StdError for AnchorPointerFragmentError {}
+
+/// Indicates that a sealed snapshot of the state of an
+/// [`Interrogator`](crate::Interrogator) could not be verified and may have
+/// been tampered with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateVerificationError {
+    /// The digest of the snapshot does not match the digest it was sealed
+    /// with.
+    DigestMismatch {
+        /// The hex-encoded digest the snapshot was sealed with.
+        expected: String,
+        /// The hex-encoded digest of the snapshot as restored.
+        actual: String,
+    },
+    /// A verifier was provided but the snapshot is not signed.
+    MissingSignature,
+    /// The signature was rejected by the verifier.
+    InvalidSignature {
+        /// The identifier of the key the snapshot claims to be signed with.
+        key_id: String,
+    },
+    /// The snapshot or its signature could not be decoded.
+    Malformed,
+}
+impl Display for StateVerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateVerificationError::DigestMismatch { expected, actual } => write!(
+                f,
+                "snapshot digest mismatch: sealed with {}, found {}",
+                expected, actual
+            ),
+            StateVerificationError::MissingSignature => {
+                write!(f, "snapshot is not signed but a signature is required")
+            }
+            StateVerificationError::InvalidSignature { key_id } => {
+                write!(f, "snapshot signature by key \"{}\" is invalid", key_id)
+            }
+            StateVerificationError::Malformed => write!(f, "snapshot is malformed"),
+        }
+    }
+}
+impl StdError for StateVerificationError {}
+
+/// The reason a [`CancelledError`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// [`CancellationToken::cancel`](crate::cancel::CancellationToken::cancel)
+    /// was called.
+    Cancelled,
+    /// The deadline of the
+    /// [`CancellationToken`](crate::cancel::CancellationToken) passed.
+    DeadlineExceeded,
+}
+
+/// Indicates that an operation was stopped by a
+/// [`CancellationToken`](crate::cancel::CancellationToken). Any partial
+/// changes were rolled back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelledError {
+    /// Why the operation was cancelled.
+    pub reason: CancelReason,
+}
+impl Display for CancelledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            CancelReason::Cancelled => write!(f, "operation was cancelled"),
+            CancelReason::DeadlineExceeded => write!(f, "operation exceeded its deadline"),
+        }
+    }
+}
+impl StdError for CancelledError {}
+
+/// Indicates that an evaluation did not complete before the timeout set with
+/// [`Options::timeout`](crate::evaluation::Options::timeout).
+#[derive(Debug, Clone)]
+pub struct TimeoutError {
+    /// The location of the instance node being evaluated when the deadline
+    /// passed.
+    pub instance_location: Pointer,
+    /// The location of the keyword which was about to be applied.
+    pub keyword_location: Pointer,
+    /// The root [`Evaluation`], with each evaluation nested within it which
+    /// was collected up to the point the deadline passed.
+    pub partial: Box<Evaluation>,
+}
+impl Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "evaluation timed out at \"{}\" applying \"{}\"",
+            self.instance_location, self.keyword_location
+        )
+    }
+}
+impl StdError for TimeoutError {}
+
+/// Indicates that the target of a `"$ref"` is a value other than a schema
+/// object or a boolean schema, e.g. a number or string.
+#[derive(Debug, Clone)]
+pub struct InvalidRefTargetError {
+    /// The id of the [`Schema`] containing the `"$ref"`, if known.
+    pub schema_id: Option<Uri>,
+    /// The value of the `"$ref"`.
+    pub reference: String,
+    /// The resolved location of the target.
+    pub target: Uri,
+    /// The JSON type of the target, e.g. `"number"`.
+    pub kind: &'static str,
+}
+impl Display for InvalidRefTargetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"$ref\": \"{}\"{} refers to a {} at [{}]; expected a schema object or boolean",
+            self.reference,
+            self.schema_id
+                .as_ref()
+                .map_or(String::new(), |v| format!(" in schema [{}]", v)),
+            self.kind,
+            self.target
+        )
+    }
+}
+impl StdError for InvalidRefTargetError {}
+
+/// Indicates that a chain of references between documents is longer than
+/// permitted by
+/// [`Interrogator::set_max_reference_depth`](crate::Interrogator::set_max_reference_depth).
+#[derive(Debug, Clone)]
+pub struct ReferenceDepthError {
+    /// The maximum number of references permitted in a chain.
+    pub max: usize,
+    /// The chain, beginning with the inserted schema.
+    pub chain: Vec<Uri>,
+}
+impl Display for ReferenceDepthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let chain: Vec<String> = self.chain.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "reference chain exceeds the maximum depth of {}: {}",
+            self.max,
+            chain.join(" -> ")
+        )
+    }
+}
+impl StdError for ReferenceDepthError {}
+
+/// Indicates that a [`Namespace`](crate::namespace::Namespace) attempted to
+/// claim or access a document which belongs to another namespace.
+#[derive(Debug, Clone)]
+pub struct NamespaceError {
+    /// The name of the namespace which attempted the access.
+    pub namespace: String,
+    /// The URI which was accessed.
+    pub uri: Uri,
+    /// The name of the namespace which owns the document.
+    pub owner: String,
+}
+impl Display for NamespaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "namespace \"{}\" can not access \"{}\" which belongs to namespace \"{}\"",
+            self.namespace, self.uri, self.owner
+        )
+    }
+}
+impl StdError for NamespaceError {}
+
+/// Indicates that the dependencies of a set of schemas could not be
+/// vendored; see [`Interrogator::vendor`](crate::Interrogator::vendor).
+#[derive(Debug, Clone)]
+pub enum VendorError {
+    /// A document could not be resolved.
+    Resolve {
+        /// The URI of the document.
+        uri: String,
+        /// A description of the failure.
+        message: String,
+    },
+    /// A file could not be written.
+    Io {
+        /// The path of the file.
+        path: std::path::PathBuf,
+        /// A description of the failure.
+        message: String,
+    },
+    /// The URI of a document does not name a file within the vendor
+    /// directory; see [`vendor_path`](crate::vendor::vendor_path).
+    UnsupportedUri {
+        /// The URI of the document.
+        uri: String,
+    },
+}
+impl Display for VendorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VendorError::Resolve { uri, message } => {
+                write!(f, "failed to vendor \"{}\": {}", uri, message)
+            }
+            VendorError::Io { path, message } => {
+                write!(f, "failed to write \"{}\": {}", path.display(), message)
+            }
+            VendorError::UnsupportedUri { uri } => {
+                write!(f, "\"{}\" does not name a file to vendor", uri)
+            }
+        }
+    }
+}
+impl StdError for VendorError {}
+
+/// One or more anchors of a [`Schema`] are malformed or, unless permitted by
+/// [`DuplicateAnchorPolicy::FirstWins`](crate::fragment::DuplicateAnchorPolicy::FirstWins),
+/// declared more than once within a resource.
+#[derive(Debug, Clone)]
+pub struct InvalidAnchorError {
+    /// The id of the [`Schema`], if known.
+    pub schema_id: Option<Arc<Uri>>,
+    /// A [`Diagnostic`] for each offending anchor.
+    pub diagnostics: Vec<Diagnostic>,
+}
+impl Display for InvalidAnchorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "schema")?;
+        if let Some(id) = &self.schema_id {
+            write!(f, " \"{}\"", id)?;
+        }
+        write!(f, " has invalid anchors:")?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n\t{}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+impl StdError for InvalidAnchorError {}
+
+/// The failure to resolve or compile a single URI of
+/// [`Interrogator::compile_all`](crate::Interrogator::compile_all).
+#[derive(Debug, Clone)]
+pub struct CompileFailure {
+    /// The URI, as provided.
+    pub uri: String,
+    /// The cause of the failure.
+    pub error: Error,
+}
+/// Serializes as an object with the URI and the serialized [`Error`] of the
+/// failure.
+impl Serialize for CompileFailure {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("uri", &self.uri)?;
+        map.serialize_entry("error", &self.error)?;
+        map.end()
+    }
+}
+impl Display for CompileFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\": {}", self.uri, self.error)
+    }
+}
+
+/// One or more URIs of a batch passed to
+/// [`Interrogator::compile_all`](crate::Interrogator::compile_all) could not
+/// be resolved or compiled. No [`Schema`] of the batch was added.
+#[derive(Debug, Clone)]
+pub struct CompileAllError {
+    /// Each failure, in the order the URIs were provided.
+    pub failures: Vec<CompileFailure>,
+}
+impl Display for CompileAllError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to compile {} schema(s):", self.failures.len())?;
+        for failure in &self.failures {
+            write!(f, "\n\t{}", failure)?;
+        }
+        Ok(())
+    }
+}
+impl StdError for CompileAllError {}
+
+/// Indicates that a [`Schema`] could not be removed from the
+/// [`Interrogator`](crate::Interrogator) because other schemas reference it.
+#[derive(Debug, Clone)]
+pub struct SchemaInUseError {
+    /// The id of the schema.
+    pub id: Uri,
+    /// The ids of the schemas which reference it, directly or transitively.
+    pub dependents: Vec<Uri>,
+}
+impl Display for SchemaInUseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dependents: Vec<String> = self.dependents.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "schema [{}] is referenced by [{}]",
+            self.id,
+            dependents.join(", ")
+        )
+    }
+}
+impl StdError for SchemaInUseError {}
+
+/// Indicates that a meta schema requires, with `"$vocabulary"`, a
+/// vocabulary which is not registered.
+#[derive(Debug, Clone)]
+pub struct UnknownVocabularyError {
+    /// The id of the meta schema.
+    pub meta_schema: Uri,
+    /// The URI of the unknown vocabulary.
+    pub vocabulary: String,
+}
+impl Display for UnknownVocabularyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "meta schema \"{}\" requires the unknown vocabulary \"{}\"",
+            self.meta_schema, self.vocabulary
+        )
+    }
+}
+impl StdError for UnknownVocabularyError {}
+
+/// Indicates that a keyword of a [`Schema`] requires random access to an
+/// instance which is being evaluated from a stream. See
+/// [`stream`](crate::stream).
+#[derive(Debug, Clone)]
+pub struct RandomAccessError {
+    /// The id of the schema, if known.
+    pub schema_id: Option<Arc<Uri>>,
+    /// The keyword which requires random access.
+    pub keyword: String,
+}
+impl Display for RandomAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "keyword \"{}\" requires random access to the instance and can not be evaluated against a stream",
+            self.keyword
+        )?;
+        if let Some(id) = &self.schema_id {
+            write!(f, " [{}]", id)?;
+        }
+        Ok(())
+    }
+}
+impl StdError for RandomAccessError {}
+
+/// Indicates that a source document imported from another
+/// [`Interrogator`](crate::Interrogator) would replace a different source at
+/// the same URI. See [`Interrogator::import`](crate::Interrogator::import).
+#[derive(Debug, Clone)]
+pub struct ImportConflictError {
+    /// The URI of the conflicting source.
+    pub uri: AbsoluteUri,
+}
+impl Display for ImportConflictError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the source imported to \"{}\" differs from the existing source",
+            self.uri
+        )
+    }
+}
+impl StdError for ImportConflictError {}
+
+/// The reason a [`LockedResolver`](crate::vendor::LockedResolver) refused to
+/// resolve a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockError {
+    /// The document is not listed in the [`Lockfile`](crate::vendor::Lockfile).
+    Unlisted {
+        /// The URI of the document.
+        uri: String,
+    },
+    /// The path of the document in the
+    /// [`Lockfile`](crate::vendor::Lockfile) is absolute or leaves the vendor
+    /// directory.
+    InvalidPath {
+        /// The URI of the document.
+        uri: String,
+        /// The path recorded in the [`Lockfile`](crate::vendor::Lockfile).
+        path: String,
+    },
+    /// The vendored document could not be read.
+    Io {
+        /// The URI of the document.
+        uri: String,
+        /// The path of the vendored document.
+        path: std::path::PathBuf,
+        /// A description of the failure.
+        message: String,
+    },
+    /// The digest of the vendored document differs from the [`Lockfile`](crate::vendor::Lockfile).
+    DigestMismatch {
+        /// The URI of the document.
+        uri: String,
+        /// The digest recorded in the [`Lockfile`](crate::vendor::Lockfile).
+        expected: String,
+        /// The digest of the vendored document.
+        actual: String,
+    },
+}
+
+impl Display for LockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockError::Unlisted { uri } => write!(f, "\"{}\" is not listed in the lockfile", uri),
+            LockError::InvalidPath { uri, path } => write!(
+                f,
+                "path \"{}\" of \"{}\" in the lockfile is not within the vendor directory",
+                path, uri
+            ),
+            LockError::Io { uri, path, message } => write!(
+                f,
+                "failed to read \"{}\" from \"{}\": {}",
+                uri,
+                path.display(),
+                message
+            ),
+            LockError::DigestMismatch {
+                uri,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "digest of \"{}\" does not match the lockfile; expected {}, found {}",
+                uri, expected, actual
+            ),
+        }
+    }
+}
+
+impl StdError for LockError {}
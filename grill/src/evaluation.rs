@@ -1,5 +1,17 @@
 mod iter;
 pub use iter::Iter;
+
+mod options;
+pub use options::Options;
+
+mod state;
+pub use state::State;
+
+mod cache;
+pub use cache::ResultCache;
+
+pub(crate) mod probe;
+
 use std::{
     borrow::{Borrow, Cow},
     fmt::Display,
@@ -8,6 +20,7 @@ use uniresid::AbsoluteUri;
 
 use crate::{error::ExpectedStringError, Error, OutputFmt};
 use jsonptr::Pointer;
+use once_cell::sync::Lazy;
 use serde::Serialize;
 use serde_json::{to_value, Map, Value};
 
@@ -23,8 +36,12 @@ pub struct Evaluation {
     absolute_keyword_location: Option<AbsoluteUri>,
     nested: Vec<Evaluation>,
     error: Option<String>,
+    /// The kind and parameters of the error, one for each error merged into
+    /// it; not empty if the error is set.
+    error_details: Vec<ErrorDetail>,
     output: OutputFmt,
     data: Map<String, Value>,
+    state: State,
 }
 ///
 impl Evaluation {
@@ -35,11 +52,59 @@ impl Evaluation {
             nested: Vec::new(),
             data: Map::new(),
             error: None,
+            error_details: Vec::new(),
             instance_location,
             keyword_location,
             absolute_keyword_location: None,
+            state: State::default(),
         }
     }
+
+    /// Creates and returns a new root `Evaluation` which shares `state`.
+    pub(crate) fn with_state(output: OutputFmt, state: State) -> Self {
+        let mut eval = Self::new(Pointer::default(), Pointer::default(), output);
+        eval.state = state;
+        eval
+    }
+
+    /// Returns the [`State`] shared by each `Evaluation` of the current call
+    /// to [`Schema::evaluate`](crate::Schema::evaluate).
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+    /// Creates and returns a new `Evaluation` for the given `keyword` which
+    /// shares the `instanceLocation`, `OutputFmt`, and [`State`] of this
+    /// `Evaluation`.
+    ///
+    /// The `keywordLocation` of the returned `Evaluation` is that of this
+    /// `Evaluation` with `keyword` appended. The returned `Evaluation` is not
+    /// nested; use [`push`](Evaluation::push) to append it.
+    pub fn for_keyword(&self, keyword: &str) -> Evaluation {
+        let mut keyword_location = self.keyword_location.clone();
+        keyword_location.push_back(keyword.into());
+        let mut eval = Evaluation::new(
+            self.instance_location.clone(),
+            keyword_location,
+            self.output.clone(),
+        );
+        eval.state = self.state.clone();
+        eval
+    }
+    /// Returns this `Evaluation` with a [fork](State::fork) of its `State`,
+    /// for a branch of the evaluation which runs in parallel.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn fork(mut self) -> Evaluation {
+        self.state = self.state.fork();
+        self
+    }
+    /// Returns `true` if a directly nested `Evaluation` of `keyword` has an
+    /// error set.
+    pub(crate) fn has_failed(&self, keyword: &str) -> bool {
+        self.nested.iter().any(|n| {
+            n.error.is_some()
+                && matches!(n.keyword_location.tokens().last(), Some(t) if t.as_str() == keyword)
+        })
+    }
     /// Returns `true` if this or any nested `Annotation` has an error set
     pub fn is_valid(&self) -> bool {
         self.error.is_none() && self.nested.iter().all(Evaluation::is_valid)
@@ -49,9 +114,94 @@ impl Evaluation {
         self.error.as_deref()
     }
 
-    /// Sets the error message
-    pub fn set_error(&mut self, error: &str) {
-        self.error = Some(error.to_string());
+    /// Sets the error message.
+    ///
+    /// An owned `String` is stored without being copied.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+        self.error_detail();
+    }
+
+    /// Returns the stable, machine-readable code of the error, if one is
+    /// set: the [`validation_code`](crate::error::validation_code) of the
+    /// keyword of this `Evaluation`.
+    pub fn error_code(&self) -> Option<&'static str> {
+        self.error.as_ref()?;
+        let keyword = self.keyword_location.tokens().last();
+        Some(crate::error::validation_code(
+            keyword.as_ref().map_or("", |t| t.as_str()),
+        ))
+    }
+
+    /// Sets the error of a value evaluated against the schema `false`, of
+    /// kind `"false"`.
+    pub(crate) fn set_false_schema_error(&mut self) {
+        self.set_error_kind("false");
+        self.set_error("no value is valid against the schema false");
+    }
+
+    /// Returns the kind of the error, or of the first of the errors merged
+    /// into it, if one was set with [`set_error_kind`](Self::set_error_kind).
+    pub fn error_kind(&self) -> Option<&'static str> {
+        self.error_details.first()?.kind
+    }
+
+    /// Sets the kind of the error, e.g. `"none"` or `"multiple"` for
+    /// `"oneOf"`. Along with the keyword, the kind keys the translation of
+    /// the error by a [`Translator`](crate::report::Translator).
+    pub fn set_error_kind(&mut self, kind: &'static str) {
+        self.error_detail().kind = Some(kind);
+    }
+
+    /// Sets the parameter `name` of the error, e.g. the limit of
+    /// `"maximum"`, with which a [`Translator`](crate::report::Translator)
+    /// fills in its translation.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    /// use serde_json::json;
+    ///
+    /// let eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Basic);
+    /// let mut max = eval.for_keyword("maximum");
+    /// max.set_error_kind("limit");
+    /// max.set_error_param("limit", 10);
+    /// max.set_error("11 is not less than or equal to 10");
+    ///
+    /// let parts = max.error_parts();
+    /// assert_eq!(parts[0].kind, Some("limit"));
+    /// assert_eq!(parts[0].params["limit"], json!(10));
+    /// ```
+    pub fn set_error_param(&mut self, name: &str, value: impl Into<Value>) {
+        let params = &mut self.error_detail().params;
+        params.insert(name.to_string(), value.into());
+    }
+
+    /// Returns the error, split into each error merged into it with
+    /// [`merge`](Self::merge) along with its kind and parameters, or an
+    /// empty `Vec` if no error is set.
+    pub fn error_parts(&self) -> Vec<ErrorPart<'_>> {
+        match &self.error {
+            Some(error) => ErrorPart::split(error, &self.error_details),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the kind and parameters of the error and of each error merged
+    /// into it.
+    pub(crate) fn error_details(&self) -> &[ErrorDetail] {
+        &self.error_details
+    }
+
+    /// Returns the detail of the last error merged into the error, adding
+    /// one if there is none.
+    fn error_detail(&mut self) -> &mut ErrorDetail {
+        if self.error_details.is_empty() {
+            self.error_details.push(ErrorDetail::default());
+        }
+        let last = self.error_details.len() - 1;
+        &mut self.error_details[last]
     }
     /// Returns the specified `OutputFmt`
     pub fn output(&self) -> OutputFmt {
@@ -66,6 +216,138 @@ impl Evaluation {
     pub fn append(&mut self, evals: impl IntoIterator<Item = Evaluation>) {
         self.extend(evals.into_iter())
     }
+    /// Merges `other`, the result of evaluating the same instance against
+    /// another [`Schema`](crate::Schema), into this `Evaluation`.
+    ///
+    /// The nested `Evaluation`s of `other` are appended with their locations
+    /// intact, so the merged `Evaluation` is valid only if both were. If both
+    /// have an error set, the errors are joined. Data of `other` is added
+    /// where this `Evaluation` does not already have a value for the key.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let base = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Basic);
+    /// let mut overlay = base.clone();
+    /// let mut max = overlay.for_keyword("maxLength");
+    /// max.set_error("string is too long");
+    /// overlay.push(max);
+    ///
+    /// let mut merged = base;
+    /// merged.merge(overlay);
+    /// assert!(!merged.is_valid());
+    /// ```
+    pub fn merge(&mut self, other: Evaluation) {
+        let Evaluation {
+            nested,
+            error,
+            error_details,
+            data,
+            ..
+        } = other;
+        self.error = match (self.error.take(), error) {
+            (Some(mut a), Some(b)) if a != b => {
+                // each merged error keeps its kind and parameters
+                let start = a.len() + "; ".len();
+                self.error_details
+                    .extend(error_details.into_iter().map(|detail| ErrorDetail {
+                        start: detail.start + start,
+                        ..detail
+                    }));
+                a.push_str("; ");
+                a.push_str(&b);
+                Some(a)
+            }
+            (Some(a), _) => Some(a),
+            (None, Some(b)) => {
+                self.error_details = error_details;
+                Some(b)
+            }
+            (None, None) => None,
+        };
+        for (k, v) in data {
+            self.data.entry(k).or_insert(v);
+        }
+        self.nested.extend(nested);
+    }
+    /// Prunes this `Evaluation` to the instance subtree at `focus`.
+    ///
+    /// Nested `Evaluation`s whose instance location is within `focus`, or is
+    /// an ancestor of it, are retained in full. Each other nested
+    /// `Evaluation` is reduced to its validity: its nested `Evaluation`s and
+    /// data are discarded and, if it was invalid, it retains an error.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// for name in ["name", "address"] {
+    ///     let mut property = eval.for_keyword("properties");
+    ///     property.set_instance_location(Pointer::try_from(format!("/{}", name).as_str()).unwrap());
+    ///     let mut min = property.for_keyword("minLength");
+    ///     min.set_error("string is too short");
+    ///     property.push(min);
+    ///     eval.push(property);
+    /// }
+    ///
+    /// eval.focus(&Pointer::try_from("/address").unwrap());
+    /// assert!(!eval.is_valid());
+    /// assert!(eval.nested()[0].nested().is_empty());
+    /// assert_eq!(eval.nested()[1].nested().len(), 1);
+    /// ```
+    pub fn focus(&mut self, focus: &Pointer) {
+        for nested in &mut self.nested {
+            if is_within(&nested.instance_location, focus) {
+                continue;
+            }
+            if is_within(focus, &nested.instance_location) {
+                nested.focus(focus);
+            } else {
+                nested.collapse();
+            }
+        }
+    }
+
+    /// Discards the nested `Evaluation`s and data of this `Evaluation`,
+    /// retaining an error if it was invalid.
+    fn collapse(&mut self) {
+        if self.error.is_none() && !self.is_valid() {
+            self.set_error("a nested evaluation had errors");
+        }
+        self.nested.clear();
+        self.data.clear();
+    }
+    /// Moves this `Evaluation` and each nested `Evaluation` from the instance
+    /// and keyword location of this `Evaluation` to those of `to`.
+    pub(crate) fn relocate(&mut self, to: &Evaluation) {
+        let instance = (self.instance_location.clone(), to.instance_location.clone());
+        let keyword = (self.keyword_location.clone(), to.keyword_location.clone());
+        self.rebase(&instance, &keyword);
+        self.state = to.state.clone();
+    }
+
+    fn rebase(&mut self, instance: &(Pointer, Pointer), keyword: &(Pointer, Pointer)) {
+        self.instance_location = rebase(&self.instance_location, &instance.0, &instance.1);
+        self.keyword_location = rebase(&self.keyword_location, &keyword.0, &keyword.1);
+        for nested in &mut self.nested {
+            nested.rebase(instance, keyword);
+        }
+    }
+    /// Returns the directly nested `Evaluation`s.
+    pub fn nested(&self) -> &[Evaluation] {
+        &self.nested
+    }
+
+    /// Returns the custom fields of this `Evaluation`, i.e. those other than
+    /// the locations and the error.
+    pub fn data(&self) -> &Map<String, Value> {
+        &self.data
+    }
+
     /// Returns the field at the given `key` if it exists.
     pub fn get<K>(&self, key: &K) -> Option<Cow<Value>>
     where
@@ -193,13 +475,114 @@ impl Evaluation {
         }
     }
 
-    /// Sets the internal error to `None`
+    /// Sets the internal error, along with its kind and parameters, to `None`
     /// - If the error was previously set, it is returned
     pub fn reset_error(&mut self) -> Option<String> {
+        self.error_details.clear();
         self.error.take()
     }
 }
 
+/// The kind and parameters of the error of an [`Evaluation`], or of one of
+/// the errors merged into it, set with
+/// [`Evaluation::set_error_kind`] and [`Evaluation::set_error_param`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorDetail {
+    /// The byte offset of the error within the merged error.
+    start: usize,
+    kind: Option<&'static str>,
+    params: Map<String, Value>,
+}
+
+impl ErrorDetail {
+    /// Joins `errors`, each of which takes the place of the error described
+    /// by the detail of `details` at the same index, returning the merged
+    /// error and the details of its parts.
+    pub(crate) fn join<'e>(
+        errors: impl IntoIterator<Item = &'e str>,
+        details: &[ErrorDetail],
+    ) -> (String, Vec<ErrorDetail>) {
+        let mut joined = String::new();
+        let mut joined_details = Vec::with_capacity(details.len());
+        for (i, error) in errors.into_iter().enumerate() {
+            if i > 0 {
+                joined.push_str("; ");
+            }
+            if let Some(detail) = details.get(i) {
+                joined_details.push(ErrorDetail {
+                    start: joined.len(),
+                    ..detail.clone()
+                });
+            }
+            joined.push_str(error);
+        }
+        (joined, joined_details)
+    }
+}
+
+/// An error of an [`Evaluation`], or one of the errors merged into it, along
+/// with its kind and parameters, by which a
+/// [`Translator`](crate::report::Translator) translates it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorPart<'a> {
+    /// The error as produced by the keyword.
+    pub error: &'a str,
+    /// The kind of the error, if the keyword set one.
+    pub kind: Option<&'static str>,
+    /// The parameters of the error, e.g. the limit of `"maximum"`.
+    pub params: &'a Map<String, Value>,
+}
+
+impl<'a> ErrorPart<'a> {
+    /// Splits `error` into the errors merged into it, as described by
+    /// `details`. Without details, `error` is a single part without a kind
+    /// or parameters.
+    pub(crate) fn split(error: &'a str, details: &'a [ErrorDetail]) -> Vec<ErrorPart<'a>> {
+        let ends = details
+            .iter()
+            .skip(1)
+            .map(|next| next.start - "; ".len())
+            .chain([error.len()]);
+        let mut parts: Vec<ErrorPart<'a>> = details
+            .iter()
+            .zip(ends)
+            .map(|(detail, end)| ErrorPart {
+                error: &error[detail.start..end],
+                kind: detail.kind,
+                params: &detail.params,
+            })
+            .collect();
+        if parts.is_empty() {
+            parts.push(ErrorPart {
+                error,
+                kind: None,
+                params: &EMPTY_PARAMS,
+            });
+        }
+        parts
+    }
+}
+
+/// The parameters of an error without details.
+static EMPTY_PARAMS: Lazy<Map<String, Value>> = Lazy::new(Map::new);
+
+/// Returns `true` if `location` is `ancestor` or is nested within it.
+fn is_within(location: &Pointer, ancestor: &Pointer) -> bool {
+    match location.as_str().strip_prefix(ancestor.as_str()) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// Replaces the prefix `from` of `location` with `to`.
+fn rebase(location: &Pointer, from: &Pointer, to: &Pointer) -> Pointer {
+    match location.as_str().strip_prefix(from.as_str()) {
+        Some(rest) => Pointer::try_from(format!("{}{}", to, rest).as_str())
+            .unwrap_or_else(|_| location.clone()),
+        None => location.clone(),
+    }
+}
+
 impl<E> Extend<E> for Evaluation
 where
     E: Borrow<Evaluation>,
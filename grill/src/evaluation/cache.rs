@@ -0,0 +1,243 @@
+use super::{Evaluation, Options, State};
+use crate::OutputFmt;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Identifies the application of a [`Schema`](crate::Schema) to an instance
+/// subtree by the schema's key, the context key of the evaluation, the hash
+/// of the [`Options`] and [`OutputFmt`] it was applied with, and the hash of
+/// the subtree.
+type CacheKey = (usize, u64, u64, u64);
+
+/// A bounded cache of the [`Evaluation`]s of [`Schema`](crate::Schema)s
+/// which is shared across evaluations.
+///
+/// Unlike [memoization](super::Options::memoize), which lasts for a single
+/// evaluation and is keyed by instance location, a `ResultCache` is keyed by
+/// the content of the instance subtree, along with the schema and the options
+/// and output format which affect its result. Workloads which repeatedly validate
+/// near-identical payloads (e.g. retries, or batch imports with shared
+/// sub-objects) evaluate each distinct subtree once per schema. Cached
+/// `Evaluation`s are relocated to the instance and keyword location at which
+/// they are reused.
+///
+/// Only schemas whose result depends solely on the instance are cached;
+/// schemas which contain `"$dynamicRef"` or `"$recursiveRef"`, or which
+/// reference, directly or transitively, a schema which does, are always
+/// evaluated. The result of an evaluation given a user-defined context, e.g.
+/// with [`Schema::evaluate_with_context`](crate::Schema::evaluate_with_context),
+/// is only reused by that evaluation, as keywords may depend on the context.
+/// Once `capacity` results are cached, the oldest is evicted.
+///
+/// Each [`Schema`](crate::Schema) is cached under a key which no other
+/// `Schema` is given, so a `ResultCache` may outlive the schemas it is used
+/// with; results of schemas which no longer exist are evicted in turn. Cloning a `ResultCache` is cheap; clones share the same cache.
+///
+/// ## Example
+/// ```
+/// use grill::evaluation::{Options, ResultCache};
+///
+/// let cache = ResultCache::new(1024);
+/// let options = Options::new().cache(cache.clone());
+/// assert!(options.result_cache().is_some());
+/// assert_eq!(cache.len(), 0);
+/// ```
+#[derive(Clone)]
+pub struct ResultCache {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<CacheKey, (Value, Evaluation)>,
+    order: VecDeque<CacheKey>,
+}
+
+impl ResultCache {
+    /// Creates and returns a new `ResultCache` which holds at most
+    /// `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                entries: Mutex::new(Entries::default()),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of results held.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Returns the number of results held.
+    pub fn len(&self) -> usize {
+        self.inner.entries.lock().map.len()
+    }
+
+    /// Returns `true` if no results are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which found a cached result.
+    pub fn hits(&self) -> u64 {
+        self.inner.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups which did not find a cached result.
+    pub fn misses(&self) -> u64 {
+        self.inner.misses.load(Ordering::Relaxed)
+    }
+
+    /// Removes each cached result.
+    pub fn clear(&self) {
+        let mut entries = self.inner.entries.lock();
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    /// Returns the cached `Evaluation` of the schema identified by
+    /// `schema_key` for `value` under the context and options of `state` and
+    /// `output`, if one exists.
+    pub(crate) fn get(
+        &self,
+        schema_key: usize,
+        state: &State,
+        output: &OutputFmt,
+        value: &Value,
+    ) -> Option<Evaluation> {
+        let key = cache_key(schema_key, state, output, value);
+        let found = match self.inner.entries.lock().map.get(&key) {
+            // hashes may collide; the subtree itself is compared
+            Some((cached, eval)) if cached == value => Some(eval.clone()),
+            _ => None,
+        };
+        let counter = if found.is_some() {
+            &self.inner.hits
+        } else {
+            &self.inner.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    /// Caches `evaluation` as the result of the schema identified by
+    /// `schema_key` for `value` under the context and options of `state` and
+    /// `output`, evicting the oldest result if the cache is full.
+    pub(crate) fn insert(
+        &self,
+        schema_key: usize,
+        state: &State,
+        output: &OutputFmt,
+        value: &Value,
+        evaluation: Evaluation,
+    ) {
+        if self.inner.capacity == 0 {
+            return;
+        }
+        let key = cache_key(schema_key, state, output, value);
+        let mut entries = self.inner.entries.lock();
+        if entries
+            .map
+            .insert(key, (value.clone(), evaluation))
+            .is_some()
+        {
+            return;
+        }
+        entries.order.push_back(key);
+        while entries.order.len() > self.inner.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.map.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ResultCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCache")
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+fn cache_key(schema_key: usize, state: &State, output: &OutputFmt, value: &Value) -> CacheKey {
+    (
+        schema_key,
+        state.context_key(),
+        variant(state.options(), output),
+        hash_value(value),
+    )
+}
+
+/// Returns a hash of the parts of `options` and `output` which affect the
+/// result of an evaluation.
+fn variant(options: &Options, output: &OutputFmt) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    options.fingerprint().hash(&mut hasher);
+    mem::discriminant(output).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns a hash of `value` which is consistent between evaluations.
+pub(crate) fn hash_value(value: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_into(value, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_into<H: Hasher>(value: &Value, hasher: &mut H) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(b) => {
+            1u8.hash(hasher);
+            b.hash(hasher);
+        }
+        Value::Number(n) => {
+            2u8.hash(hasher);
+            n.to_string().hash(hasher);
+        }
+        Value::String(s) => {
+            3u8.hash(hasher);
+            s.hash(hasher);
+        }
+        Value::Array(items) => {
+            4u8.hash(hasher);
+            items.len().hash(hasher);
+            for item in items {
+                hash_into(item, hasher);
+            }
+        }
+        Value::Object(obj) => {
+            5u8.hash(hasher);
+            obj.len().hash(hasher);
+            for (k, v) in obj {
+                k.hash(hasher);
+                hash_into(v, hasher);
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use super::ResultCache;
+use std::time::Duration;
+
+/// Options which apply to a single call to
+/// [`Schema::evaluate_with_options`](crate::Schema::evaluate_with_options).
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    memoize: bool,
+    suggest: bool,
+    budget: Option<u64>,
+    timeout: Option<Duration>,
+    cache: Option<ResultCache>,
+    coverage: bool,
+    parallel: bool,
+    exact_numbers: bool,
+}
+
+impl Options {
+    /// Creates and returns a new `Options` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the results of applying a [`Schema`](crate::Schema) to an
+    /// instance node are memoized for the remainder of the evaluation.
+    ///
+    /// When enabled, a [`Schema`](crate::Schema) reached more than once for
+    /// the same node of the instance (e.g. through diamond-shaped applicator
+    /// graphs) is only evaluated once. Subsequent applications reuse the
+    /// first result, including its `keywordLocation`s, which differ by path.
+    /// As such, memoization is disabled by default.
+    #[must_use]
+    pub fn memoize(mut self, memoize: bool) -> Self {
+        self.memoize = memoize;
+        self
+    }
+
+    /// Returns `true` if memoization is enabled.
+    pub fn is_memoized(&self) -> bool {
+        self.memoize
+    }
+
+    /// Returns a value which differs between `Options` under which a
+    /// [`Schema`](crate::Schema) may produce a different
+    /// [`Evaluation`](crate::Evaluation) of the same instance: memoization
+    /// changes keyword locations, suggestions add to errors, and exact
+    /// comparison changes the results of numeric keywords.
+    pub(crate) fn fingerprint(&self) -> u64 {
+        u64::from(self.memoize) | u64::from(self.suggest) << 1 | u64::from(self.exact_numbers) << 2
+    }
+
+    /// Sets whether keywords which compare a string against a set of values
+    /// (e.g. `"enum"`, `"const"`) attach the nearest matches to their error
+    /// under the `"suggestions"` field.
+    ///
+    /// Computing suggestions requires an edit distance per candidate and is
+    /// disabled by default.
+    #[must_use]
+    pub fn suggest(mut self, suggest: bool) -> Self {
+        self.suggest = suggest;
+        self
+    }
+
+    /// Returns `true` if suggestions are enabled.
+    pub fn is_suggesting(&self) -> bool {
+        self.suggest
+    }
+
+    /// Sets the maximum total [cost](crate::cost) of the keywords applied
+    /// during the evaluation. Once exceeded, evaluation aborts with
+    /// [`Error::BudgetExceeded`](crate::Error::BudgetExceeded).
+    ///
+    /// Evaluations are unbounded by default.
+    #[must_use]
+    pub fn budget(mut self, budget: u64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Returns the budget of the evaluation, if one is set.
+    pub fn budget_limit(&self) -> Option<u64> {
+        self.budget
+    }
+
+    /// Sets the maximum duration of the evaluation, measured from when it
+    /// begins. The deadline is checked before each keyword is applied; once
+    /// it passes, no further keywords are applied and the evaluation ends
+    /// with [`Error::Timeout`](crate::Error::Timeout), which carries the
+    /// partial [`Evaluation`](crate::Evaluation) of the whole instance
+    /// collected so far. The results of applicators within it reflect only
+    /// the keywords which were applied. A timeout too large for an
+    /// [`Instant`](std::time::Instant) to represent never passes.
+    ///
+    /// Evaluations are unbounded by default.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the timeout of the evaluation, if one is set.
+    pub fn timeout_limit(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets the [`ResultCache`] consulted before, and populated after, each
+    /// [`Schema`](crate::Schema) is applied to an instance subtree.
+    ///
+    /// The same `ResultCache` should be passed to each evaluation it is to
+    /// be shared across. No cache is used by default.
+    #[must_use]
+    pub fn cache(mut self, cache: ResultCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the [`ResultCache`] of the evaluation, if one is set.
+    pub fn result_cache(&self) -> Option<&ResultCache> {
+        self.cache.as_ref()
+    }
+
+    /// Sets whether each keyword applied during the evaluation is counted
+    /// toward the [coverage](crate::coverage) of its
+    /// [`Schema`](crate::Schema), as reported by
+    /// [`Interrogator::coverage`](crate::Interrogator::coverage).
+    ///
+    /// Results reused from a [`ResultCache`] or through memoization are not
+    /// counted again. Coverage is not recorded by default.
+    #[must_use]
+    pub fn coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    /// Returns `true` if coverage is recorded.
+    pub fn is_recording_coverage(&self) -> bool {
+        self.coverage
+    }
+
+    /// Sets whether the independent subschemas of the keywords which fan
+    /// out, i.e. the members of `"oneOf"` and the subschemas of
+    /// `"patternProperties"` for each property, are evaluated in parallel on
+    /// the global [rayon](https://docs.rs/rayon) thread pool. Other
+    /// keywords are evaluated sequentially.
+    ///
+    /// The results are merged in the order of the subschemas, so the
+    /// [`Evaluation`](crate::Evaluation) is the same as when evaluated
+    /// sequentially. If a [`budget`](Self::budget) is set, the keyword at
+    /// which it is exceeded may differ between runs.
+    ///
+    /// Requires the `"rayon"` feature; evaluating with `parallel` set
+    /// otherwise returns a
+    /// [`FeatureDisabledError`](crate::error::FeatureDisabledError).
+    /// Evaluation is sequential by default.
+    #[must_use]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Returns `true` if independent subschemas are evaluated in parallel.
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Sets whether numeric keywords, e.g. `"maximum"`, compare numbers as
+    /// arbitrary-precision decimals whenever either side is not represented
+    /// by an `f64` without loss, e.g. `1e309` or `9007199254740993`, rather
+    /// than silently comparing their nearest `f64`s. See
+    /// [`Numbers::is_lossless`](crate::Numbers::is_lossless).
+    ///
+    /// Numbers are otherwise compared with the
+    /// [`Precision`](crate::Precision) of the
+    /// [`Interrogator`](crate::Interrogator).
+    ///
+    /// Requires the `"big_num"` feature; evaluating with `exact_numbers` set
+    /// otherwise returns a
+    /// [`FeatureDisabledError`](crate::error::FeatureDisabledError).
+    /// Disabled by default.
+    #[must_use]
+    pub fn exact_numbers(mut self, exact_numbers: bool) -> Self {
+        self.exact_numbers = exact_numbers;
+        self
+    }
+
+    /// Returns `true` if numbers which are not represented by an `f64`
+    /// without loss are compared as arbitrary-precision decimals.
+    pub fn compares_numbers_exactly(&self) -> bool {
+        self.exact_numbers
+    }
+}
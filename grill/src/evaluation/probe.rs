@@ -0,0 +1,261 @@
+use serde::{
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+};
+use serde_json::Error as SerdeError;
+
+/// Returns the JSON Schema type of the JSON representation of `value`, e.g.
+/// `"object"` or `"integer"`, without serializing any nested value.
+pub(crate) fn type_of<T: Serialize + ?Sized>(value: &T) -> Result<&'static str, SerdeError> {
+    value.serialize(Probe)
+}
+
+/// A [`Serializer`] which stops at the first event of the stream,
+/// yielding the type of the value about to be serialized.
+struct Probe;
+
+/// Discards the members of a compound value.
+struct Discard(&'static str);
+
+fn float(v: f64) -> &'static str {
+    if !v.is_finite() {
+        // serde_json serializes non-finite floats as null
+        "null"
+    } else if v.fract() == 0.0 {
+        "integer"
+    } else {
+        "number"
+    }
+}
+
+impl Serializer for Probe {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    type SerializeSeq = Discard;
+    type SerializeTuple = Discard;
+    type SerializeTupleStruct = Discard;
+    type SerializeTupleVariant = Discard;
+    type SerializeMap = Discard;
+    type SerializeStruct = Discard;
+    type SerializeStructVariant = Discard;
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok, Self::Error> {
+        Ok("boolean")
+    }
+    fn serialize_i8(self, _: i8) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_i16(self, _: i16) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_i32(self, _: i32) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_i64(self, _: i64) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_i128(self, _: i128) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_u8(self, _: u8) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_u16(self, _: u16) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_u32(self, _: u32) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_u64(self, _: u64) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_u128(self, _: u128) -> Result<Self::Ok, Self::Error> {
+        Ok("integer")
+    }
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(float(f64::from(v)))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(float(v))
+    }
+    fn serialize_char(self, _: char) -> Result<Self::Ok, Self::Error> {
+        Ok("string")
+    }
+    fn serialize_str(self, _: &str) -> Result<Self::Ok, Self::Error> {
+        Ok("string")
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok("array")
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok("null")
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok("null")
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok("null")
+    }
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok("string")
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok("object")
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(Discard("array"))
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(Discard("array"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(Discard("array"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(Discard("object"))
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(Discard("object"))
+    }
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(Discard("object"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(Discard("object"))
+    }
+    fn collect_str<T: std::fmt::Display + ?Sized>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+        Ok("string")
+    }
+}
+
+impl SerializeSeq for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeTuple for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeTupleStruct for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeTupleVariant for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeMap for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeStruct for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+impl SerializeStructVariant for Discard {
+    type Ok = &'static str;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
@@ -0,0 +1,236 @@
+use super::{Evaluation, Options};
+use jsonptr::Pointer;
+use parking_lot::Mutex;
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+use uniresid::Uri;
+
+/// Identifies the application of a [`Schema`](crate::Schema) to a node of an
+/// instance under the [context key](State::context_key) of an evaluation.
+///
+/// The node is identified by its instance location, which, unlike the node's
+/// address, hashes consistently between evaluations.
+pub(crate) type MemoKey = (usize, u64, Pointer);
+
+/// The source of the [context key](State::context_key) of each `State`
+/// created with a context. `0` is the key of every `State` without one.
+static NEXT_CONTEXT_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// State shared by every [`Evaluation`] of a single call to
+/// [`Schema::evaluate`](crate::Schema::evaluate).
+///
+/// Cloning `State` is cheap; clones share the same underlying state.
+#[derive(Clone, Default)]
+pub struct State {
+    inner: Arc<Inner>,
+    /// Held apart from `inner` so that branches of the evaluation which run
+    /// in parallel can each have their own.
+    dynamic_scope: Arc<Mutex<Vec<Uri>>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    options: Options,
+    memo: Mutex<HashMap<MemoKey, Evaluation>>,
+    spent: AtomicU64,
+    deadline: Option<Instant>,
+    /// The instance and keyword locations at which the deadline was found to
+    /// have passed, if it has.
+    timed_out: Mutex<Option<(Pointer, Pointer)>>,
+    context: Option<Arc<dyn Any + Send + Sync>>,
+    context_key: u64,
+}
+
+impl State {
+    /// Creates and returns a new `State` for the given [`Options`].
+    pub fn new(options: Options) -> Self {
+        Self::create(options, None)
+    }
+
+    /// Creates and returns a new `State` for the given [`Options`] which
+    /// carries the user-defined `context`, accessible to
+    /// [`Applicator`](crate::Applicator)s with [`context`](Self::context).
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::evaluation::{Options, State};
+    ///
+    /// struct Tenant(&'static str);
+    ///
+    /// let state = State::with_context(Options::default(), Tenant("acme"));
+    /// assert_eq!(state.context::<Tenant>().unwrap().0, "acme");
+    /// assert!(state.context::<String>().is_none());
+    /// ```
+    pub fn with_context<C: Any + Send + Sync>(options: Options, context: C) -> Self {
+        Self::create(options, Some(Arc::new(context)))
+    }
+
+    fn create(options: Options, context: Option<Arc<dyn Any + Send + Sync>>) -> Self {
+        // a deadline beyond what `Instant` can represent never passes
+        let deadline = options
+            .timeout_limit()
+            .and_then(|timeout| Instant::now().checked_add(timeout));
+        let context_key = match context {
+            Some(_) => NEXT_CONTEXT_KEY.fetch_add(1, Ordering::Relaxed),
+            None => 0,
+        };
+        Self {
+            inner: Arc::new(Inner {
+                options,
+                memo: Mutex::new(HashMap::new()),
+                spent: AtomicU64::new(0),
+                deadline,
+                timed_out: Mutex::new(None),
+                context,
+                context_key,
+            }),
+            dynamic_scope: Arc::default(),
+        }
+    }
+
+    /// Returns the user-defined context of the evaluation if one was
+    /// provided and is of type `C`.
+    pub fn context<C: Any>(&self) -> Option<&C> {
+        self.inner.context.as_deref()?.downcast_ref()
+    }
+
+    /// Returns the key which distinguishes results computed under the
+    /// context of this evaluation from those computed under another: `0` if
+    /// the evaluation has no context, otherwise a key which no other context
+    /// is given.
+    pub(crate) fn context_key(&self) -> u64 {
+        self.inner.context_key
+    }
+
+    /// Returns the [`Options`] of the evaluation.
+    pub fn options(&self) -> &Options {
+        &self.inner.options
+    }
+
+    /// Returns the total [cost](crate::cost) of the keywords applied so far.
+    pub fn spent(&self) -> u64 {
+        self.inner.spent.load(Ordering::Relaxed)
+    }
+
+    /// Returns the instant by which the evaluation must complete, if a
+    /// [`timeout`](Options::timeout) is set.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.inner.deadline
+    }
+
+    /// Records that the deadline passed before the keyword at
+    /// `keyword_location` was applied to the node at `instance_location`,
+    /// unless it was already recorded.
+    pub(crate) fn time_out(&self, instance_location: &Pointer, keyword_location: &Pointer) {
+        let mut timed_out = self.inner.timed_out.lock();
+        if timed_out.is_none() {
+            *timed_out = Some((instance_location.clone(), keyword_location.clone()));
+        }
+    }
+
+    /// Returns `true` if the deadline of the evaluation has passed.
+    pub(crate) fn is_timed_out(&self) -> bool {
+        self.inner.timed_out.lock().is_some()
+    }
+
+    /// Returns the instance and keyword locations at which the deadline was
+    /// found to have passed, if it has.
+    pub(crate) fn timed_out(&self) -> Option<(Pointer, Pointer)> {
+        self.inner.timed_out.lock().clone()
+    }
+
+    /// Charges `cost` to the evaluation, returning the total spent.
+    pub(crate) fn charge(&self, cost: u64) -> u64 {
+        // saturates rather than wrapping, so that an exhausted budget stays
+        // exhausted
+        let charge = |spent: u64| Some(spent.saturating_add(cost));
+        let spent = &self.inner.spent;
+        match spent.fetch_update(Ordering::Relaxed, Ordering::Relaxed, charge) {
+            Ok(spent) | Err(spent) => spent.saturating_add(cost),
+        }
+    }
+
+    /// Returns the dynamic scope of the evaluation: the id, without fragment,
+    /// of each schema resource entered and not yet left, outermost first.
+    ///
+    /// `"$dynamicRef"` walks the dynamic scope to find the outermost resource
+    /// which declares the `"$dynamicAnchor"` it refers to.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::evaluation::{Options, State};
+    ///
+    /// let state = State::new(Options::default());
+    /// assert!(state.dynamic_scope().is_empty());
+    /// ```
+    pub fn dynamic_scope(&self) -> Vec<Uri> {
+        self.dynamic_scope.lock().clone()
+    }
+
+    /// Returns the first value returned by `f` for the resources of the
+    /// dynamic scope, outermost first, without copying the scope.
+    pub(crate) fn find_in_scope<T>(&self, f: impl FnMut(&Uri) -> Option<T>) -> Option<T> {
+        self.dynamic_scope.lock().iter().find_map(f)
+    }
+
+    /// Returns the innermost resource of the dynamic scope, if any.
+    pub(crate) fn innermost_scope(&self) -> Option<Uri> {
+        self.dynamic_scope.lock().last().cloned()
+    }
+
+    /// Enters the schema resource `resource`, unless it is already the
+    /// innermost resource of the dynamic scope. Returns `true` if it was
+    /// entered, in which case [`exit_scope`](Self::exit_scope) must be
+    /// called once the resource has been applied.
+    pub(crate) fn enter_scope(&self, resource: Uri) -> bool {
+        let mut scope = self.dynamic_scope.lock();
+        if scope.last() == Some(&resource) {
+            return false;
+        }
+        scope.push(resource);
+        true
+    }
+
+    /// Leaves the innermost schema resource of the dynamic scope.
+    pub(crate) fn exit_scope(&self) {
+        self.dynamic_scope.lock().pop();
+    }
+
+    /// Returns a `State` which shares everything but the dynamic scope with
+    /// this one, and whose dynamic scope starts as a copy of this one's, for
+    /// a branch of the evaluation which runs in parallel.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn fork(&self) -> State {
+        State {
+            inner: self.inner.clone(),
+            dynamic_scope: Arc::new(Mutex::new(self.dynamic_scope())),
+        }
+    }
+
+    pub(crate) fn memoized(&self, key: &MemoKey) -> Option<Evaluation> {
+        self.inner.memo.lock().get(key).cloned()
+    }
+
+    pub(crate) fn memoize(&self, key: MemoKey, evaluation: Evaluation) {
+        self.inner.memo.lock().insert(key, evaluation);
+    }
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("options", self.options())
+            .field("spent", &self.spent())
+            .field("deadline", &self.deadline())
+            .field("dynamic_scope", &self.dynamic_scope())
+            .finish_non_exhaustive()
+    }
+}
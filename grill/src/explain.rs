@@ -0,0 +1,297 @@
+//! Explanations of why an instance is valid.
+//!
+//! An [`Explanation`] is the inverse of a [`Report`](crate::report::Report):
+//! rather than the errors of an invalid instance, it lists, for each
+//! location within the instance, the constraints which were checked there
+//! and the branches which were taken.
+//!
+//! Only keywords which were compiled into the schema are treated as
+//! branches; an [`Explanation`] is built from an [`Evaluation`] and the
+//! [`CompiledKeyword`]s of the schema which produced it, e.g. those returned
+//! by [`Schema::keywords`](crate::Schema::keywords). Of those, `"anyOf"` and
+//! `"oneOf"` are explained by the members which matched, and `"if"` by
+//! whether its condition held. The evaluations of every other keyword are
+//! listed as checks.
+//!
+//! Subschemas which did not match, e.g. the failing members of a
+//! `"oneOf"`, are listed as unmatched but are not descended into, so only
+//! the constraints which contributed to the outcome are reported.
+//!
+//! The members of `"anyOf"` and `"oneOf"` are expected to be nested beneath
+//! the evaluation of their keyword at the keyword location of their index,
+//! e.g. `/oneOf/1`. The condition of `"if"` held if `"then"` was applied, or
+//! failed if `"else"` was; otherwise it is the validity of the evaluation of
+//! `"if"`.
+//!
+//! ## Example
+//! ```
+//! use grill::{explain::{Decision, Explanation}, schema::CompiledKeyword, Evaluation, OutputFmt};
+//! use jsonptr::Pointer;
+//!
+//! let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+//! let mut one_of = eval.for_keyword("oneOf");
+//! let mut string = one_of.for_keyword("0");
+//! let mut kind = string.for_keyword("type");
+//! kind.set_error("expected \"string\", found \"number\"");
+//! string.push(kind);
+//! let mut number = one_of.for_keyword("1");
+//! let minimum = number.for_keyword("minimum");
+//! number.push(minimum);
+//! one_of.append([string, number]);
+//! eval.push(one_of);
+//!
+//! let keywords = [CompiledKeyword {
+//!     name: Some("oneOf"),
+//!     location: Pointer::try_from("/oneOf").unwrap(),
+//!     is_applicator: true,
+//! }];
+//! let explanation = Explanation::new(&eval, &keywords);
+//! let location = &explanation.locations()[0];
+//! assert_eq!(location.branches[0].keyword, "oneOf");
+//! assert_eq!(
+//!     location.branches[0].decision,
+//!     Decision::Members { matched: vec![1], unmatched: vec![0] }
+//! );
+//! assert_eq!(location.checked[0].keyword_location.as_str(), "/oneOf/1/minimum");
+//! ```
+
+use crate::{schema::CompiledKeyword, Evaluation};
+use jsonptr::Pointer;
+use std::{collections::HashSet, fmt};
+use uniresid::AbsoluteUri;
+
+/// A constraint which was checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// The relative location of the keyword.
+    pub keyword_location: Pointer,
+    /// The absolute location of the keyword, if known.
+    pub absolute_keyword_location: Option<AbsoluteUri>,
+}
+
+/// The branches taken by an applicator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// The indexes of the members of `"anyOf"` or `"oneOf"` which matched
+    /// and which did not.
+    Members {
+        /// The indexes of the members which matched.
+        matched: Vec<usize>,
+        /// The indexes of the members which did not match.
+        unmatched: Vec<usize>,
+    },
+    /// Whether the condition of `"if"` held, i.e. whether `"then"` rather
+    /// than `"else"` applied.
+    Condition(bool),
+}
+
+/// A compiled branching keyword, `"anyOf"`, `"oneOf"`, or `"if"`, and the
+/// [`Decision`] made by it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    /// The keyword.
+    pub keyword: String,
+    /// The relative location of the keyword.
+    pub keyword_location: Pointer,
+    /// The absolute location of the keyword, if known.
+    pub absolute_keyword_location: Option<AbsoluteUri>,
+    /// The branches taken.
+    pub decision: Decision,
+}
+
+/// The constraints checked and branches taken at a single instance
+/// location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// The location within the instance.
+    pub instance_location: Pointer,
+    /// The constraints checked, in the order they were evaluated.
+    pub checked: Vec<Check>,
+    /// The branches, in the order they were evaluated.
+    pub branches: Vec<Branch>,
+}
+
+/// The constraints checked and branches taken while evaluating an
+/// instance, grouped by instance location. See the
+/// [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    valid: bool,
+    locations: Vec<Location>,
+}
+
+impl Explanation {
+    /// Creates the `Explanation` of `eval`, treating as branches only the
+    /// branching keywords among `keywords`, the keywords compiled into the
+    /// schema which produced `eval`.
+    pub fn new(eval: &Evaluation, keywords: &[CompiledKeyword]) -> Self {
+        let branching: HashSet<&str> = keywords
+            .iter()
+            .filter_map(|k| k.name)
+            .filter(|name| BRANCHING_KEYWORDS.contains(name))
+            .collect();
+        let mut explanation = Self {
+            valid: eval.is_valid(),
+            locations: Vec::new(),
+        };
+        explanation.visit(eval, &branching);
+        explanation
+    }
+
+    /// Returns `true` if the instance is valid. The explanation of an
+    /// invalid instance omits each subschema which failed; use a
+    /// [`Report`](crate::report::Report) to find out why.
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Returns the instance locations, ordered by the first constraint or
+    /// branch evaluated at each.
+    pub fn locations(&self) -> &[Location] {
+        &self.locations
+    }
+
+    /// Returns the [`Location`] at `instance_location`, if anything was
+    /// evaluated there.
+    pub fn location(&self, instance_location: &Pointer) -> Option<&Location> {
+        self.locations
+            .iter()
+            .find(|l| l.instance_location == *instance_location)
+    }
+
+    fn entry(&mut self, instance_location: &Pointer) -> &mut Location {
+        let index = match self
+            .locations
+            .iter()
+            .position(|l| l.instance_location == *instance_location)
+        {
+            Some(index) => index,
+            None => {
+                self.locations.push(Location {
+                    instance_location: instance_location.clone(),
+                    checked: Vec::new(),
+                    branches: Vec::new(),
+                });
+                self.locations.len() - 1
+            }
+        };
+        &mut self.locations[index]
+    }
+
+    fn visit(&mut self, eval: &Evaluation, branching: &HashSet<&str>) {
+        let nested = eval.nested();
+        let condition = condition(nested);
+        for node in nested {
+            let keyword = keyword(node);
+            let decision = match keyword {
+                _ if !branching.contains(keyword) => None,
+                "anyOf" | "oneOf" => Some(members(node)),
+                "if" => condition.map(Decision::Condition),
+                _ => None,
+            };
+            if let Some(decision) = decision {
+                let branch = Branch {
+                    keyword: keyword.to_string(),
+                    keyword_location: node.keyword_location().clone(),
+                    absolute_keyword_location: node.absolute_keyword_location().cloned(),
+                    decision,
+                };
+                self.entry(node.instance_location()).branches.push(branch);
+                if keyword == "if" {
+                    if node.is_valid() {
+                        self.visit(node, branching);
+                    }
+                } else {
+                    for member in node.nested().iter().filter(|m| m.is_valid()) {
+                        self.visit(member, branching);
+                    }
+                }
+                continue;
+            }
+            if !node.is_valid() {
+                continue;
+            }
+            if node.nested().is_empty() {
+                let check = Check {
+                    keyword_location: node.keyword_location().clone(),
+                    absolute_keyword_location: node.absolute_keyword_location().cloned(),
+                };
+                self.entry(node.instance_location()).checked.push(check);
+            } else {
+                self.visit(node, branching);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Explanation {
+    /// Renders the `Explanation` as indented text, one instance location at
+    /// a time.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", if self.valid { "valid" } else { "invalid" })?;
+        for location in &self.locations {
+            let instance_location = match location.instance_location.as_str() {
+                "" => "(root)",
+                instance_location => instance_location,
+            };
+            writeln!(f, "\n{}", instance_location)?;
+            for branch in &location.branches {
+                write!(
+                    f,
+                    "  {} at {}: ",
+                    branch.keyword,
+                    branch.keyword_location.as_str()
+                )?;
+                match &branch.decision {
+                    Decision::Members { matched, unmatched } => {
+                        writeln!(f, "matched {:?}, unmatched {:?}", matched, unmatched)?
+                    }
+                    Decision::Condition(true) => writeln!(f, "condition held, \"then\" applied")?,
+                    Decision::Condition(false) => {
+                        writeln!(f, "condition failed, \"else\" applied")?
+                    }
+                }
+            }
+            for check in &location.checked {
+                writeln!(f, "  checked {}", check.keyword_location.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The keywords which are explained as branches if compiled.
+const BRANCHING_KEYWORDS: &[&str] = &["anyOf", "oneOf", "if"];
+
+/// Returns the last token of the keyword location of `eval`.
+fn keyword(eval: &Evaluation) -> &str {
+    let location = eval.keyword_location().as_str();
+    location.rsplit('/').next().unwrap_or_default()
+}
+
+/// Returns whether the condition of an `"if"` among `siblings` held.
+fn condition(siblings: &[Evaluation]) -> Option<bool> {
+    let condition = siblings.iter().find(|s| keyword(s) == "if")?;
+    if siblings.iter().any(|s| keyword(s) == "then") {
+        return Some(true);
+    }
+    if siblings.iter().any(|s| keyword(s) == "else") {
+        return Some(false);
+    }
+    Some(condition.is_valid())
+}
+
+fn members(eval: &Evaluation) -> Decision {
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    for member in eval.nested() {
+        if let Ok(index) = keyword(member).parse::<usize>() {
+            if member.is_valid() {
+                matched.push(index);
+            } else {
+                unmatched.push(index);
+            }
+        }
+    }
+    Decision::Members { matched, unmatched }
+}
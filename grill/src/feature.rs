@@ -0,0 +1,195 @@
+use std::fmt;
+
+/// Optional capabilities of grill which are gated behind cargo features.
+///
+/// No features are enabled by default, other than those of the drafts, so
+/// that the default build avoids heavier dependencies. Requesting a
+/// capability whose feature is not enabled results in a
+/// [`FeatureDisabledError`](crate::error::FeatureDisabledError) rather than
+/// silently degrading.
+///
+/// The `features` example builds under any combination of features,
+/// including `--no-default-features`; each other example lists the features
+/// it requires in `Cargo.toml`.
+///
+/// | Feature   | Capability                                                                  | Dependencies |
+/// |-----------|-----------------------------------------------------------------------------|--------------|
+/// | `big_num` | Exact comparison of numbers beyond `f64` via [`Precision::Arbitrary`](crate::Precision::Arbitrary) | `bigdecimal`, `serde_json/arbitrary_precision` |
+/// | `async`   | Reading source documents from an async reader via [`Interrogator::source_async_reader`](crate::Interrogator::source_async_reader) | `tokio` |
+/// | `nfc`     | Unicode normalization of identifiers via [`Interrogator::set_normalize_identifiers`](crate::Interrogator::set_normalize_identifiers) | `unicode-normalization` |
+/// | `tar`     | Exporting source documents as a tar archive via `Sources::export_tar` | `tar` |
+/// | `simd`    | Parsing documents with simd-json via `backend::SimdJson` and [`Interrogator::source_str`](crate::Interrogator::source_str) | `simd-json` |
+/// | `mmap`    | Lazily parsed, memory-mapped source files via `Sources::insert_mmap` | `memmap2` |
+/// | `time`    | Validation of the `"date-time"`, `"date"`, and `"time"` formats with `time` (see [`format`](crate::format)) | `time` |
+/// | `uuid`    | Validation of the `"uuid"` format with `uuid` | `uuid` |
+/// | `idna`    | Validation of the `"idn-hostname"` and `"idn-email"` formats and of A-labels in `"hostname"` | `idna` |
+/// | `fancy_regex` | Validation of the `"regex"` format with `fancy-regex`, which supports lookaround and backreferences | `fancy-regex` |
+/// | `state`   | Sealing and restoring snapshots of source documents and settings via `Interrogator::seal` (see `state`) | `sha2` |
+/// | `test-util` | Golden-file snapshot testing of evaluations (see `testing`) | |
+/// | `vendor` | Vendoring dependencies to disk with a lockfile via `Interrogator::vendor` (see `vendor`) | `sha2` |
+/// | `cli` | Rendering of validation reports for terminals via `Report::render_terminal` (see `report`) |  |
+/// | `http` | Fetching remote documents with caching and retries via `HttpResolver` | `reqwest`, `tokio` |
+/// | `yaml` | Sourcing YAML documents via `Deserializers` | `serde_yaml` |
+/// | `toml` | Sourcing TOML documents via `Deserializers`, preserving key order | `toml`, `serde_json/preserve_order` |
+/// | `rayon` | Parallel evaluation of the members of `"oneOf"` and `"patternProperties"` via [`Options::parallel`](crate::evaluation::Options::parallel) | `rayon` |
+/// | `regress` | ECMA-262 regular expressions for `"pattern"` and `"patternProperties"` via [`RegexEngine::Ecma`](crate::pattern::RegexEngine::Ecma) | `regress` |
+/// | `draft-04` | The meta schemas of JSON Schema draft 04 (see [`draft`](crate::draft)); enabled by default |  |
+/// | `draft-07` | The meta schemas of JSON Schema draft 07; enabled by default |  |
+/// | `draft-2019-09` | The meta schemas of JSON Schema 2019-09; enabled by default |  |
+/// | `draft-2020-12` | The meta schemas of JSON Schema 2020-12; enabled by default |  |
+///
+/// ## Example
+/// ```
+/// use grill::{Feature, Interrogator, Precision};
+///
+/// let interrogator = Interrogator::new();
+/// let result = interrogator.set_precision(Precision::Arbitrary);
+/// assert_eq!(result.is_ok(), Feature::BigNum.is_enabled());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// The `"big_num"` feature.
+    BigNum,
+    /// The `"async"` feature.
+    Async,
+    /// The `"nfc"` feature.
+    Nfc,
+    /// The `"tar"` feature.
+    Tar,
+    /// The `"simd"` feature.
+    Simd,
+    /// The `"mmap"` feature.
+    Mmap,
+    /// The `"time"` feature.
+    Time,
+    /// The `"uuid"` feature.
+    Uuid,
+    /// The `"idna"` feature.
+    Idna,
+    /// The `"fancy_regex"` feature.
+    FancyRegex,
+    /// The `"state"` feature.
+    State,
+    /// The `"test-util"` feature.
+    TestUtil,
+    /// The `"vendor"` feature.
+    Vendor,
+    /// The `"cli"` feature.
+    Cli,
+    /// The `"http"` feature.
+    Http,
+    /// The `"yaml"` feature.
+    Yaml,
+    /// The `"toml"` feature.
+    Toml,
+    /// The `"rayon"` feature.
+    Rayon,
+    /// The `"regress"` feature.
+    Regress,
+    /// The `"draft-04"` feature.
+    Draft04,
+    /// The `"draft-07"` feature.
+    Draft07,
+    /// The `"draft-2019-09"` feature.
+    Draft2019_09,
+    /// The `"draft-2020-12"` feature.
+    Draft2020_12,
+}
+
+impl Feature {
+    /// Every optional feature of grill.
+    pub const ALL: [Feature; 23] = [
+        Feature::BigNum,
+        Feature::Async,
+        Feature::Nfc,
+        Feature::Tar,
+        Feature::Simd,
+        Feature::Mmap,
+        Feature::Time,
+        Feature::Uuid,
+        Feature::Idna,
+        Feature::FancyRegex,
+        Feature::State,
+        Feature::TestUtil,
+        Feature::Vendor,
+        Feature::Cli,
+        Feature::Http,
+        Feature::Yaml,
+        Feature::Toml,
+        Feature::Rayon,
+        Feature::Regress,
+        Feature::Draft04,
+        Feature::Draft07,
+        Feature::Draft2019_09,
+        Feature::Draft2020_12,
+    ];
+
+    /// Returns the name of the cargo feature.
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::BigNum => "big_num",
+            Feature::Async => "async",
+            Feature::Nfc => "nfc",
+            Feature::Tar => "tar",
+            Feature::Simd => "simd",
+            Feature::Mmap => "mmap",
+            Feature::Time => "time",
+            Feature::Uuid => "uuid",
+            Feature::Idna => "idna",
+            Feature::FancyRegex => "fancy_regex",
+            Feature::State => "state",
+            Feature::TestUtil => "test-util",
+            Feature::Vendor => "vendor",
+            Feature::Cli => "cli",
+            Feature::Http => "http",
+            Feature::Yaml => "yaml",
+            Feature::Toml => "toml",
+            Feature::Rayon => "rayon",
+            Feature::Regress => "regress",
+            Feature::Draft04 => "draft-04",
+            Feature::Draft07 => "draft-07",
+            Feature::Draft2019_09 => "draft-2019-09",
+            Feature::Draft2020_12 => "draft-2020-12",
+        }
+    }
+
+    /// Returns `true` if the feature was enabled at compile time.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Feature::BigNum => cfg!(feature = "big_num"),
+            Feature::Async => cfg!(feature = "async"),
+            Feature::Nfc => cfg!(feature = "nfc"),
+            Feature::Tar => cfg!(feature = "tar"),
+            Feature::Simd => cfg!(feature = "simd"),
+            Feature::Mmap => cfg!(feature = "mmap"),
+            Feature::Time => cfg!(feature = "time"),
+            Feature::Uuid => cfg!(feature = "uuid"),
+            Feature::Idna => cfg!(feature = "idna"),
+            Feature::FancyRegex => cfg!(feature = "fancy_regex"),
+            Feature::State => cfg!(feature = "state"),
+            Feature::TestUtil => cfg!(feature = "test-util"),
+            Feature::Vendor => cfg!(feature = "vendor"),
+            Feature::Cli => cfg!(feature = "cli"),
+            Feature::Http => cfg!(feature = "http"),
+            Feature::Yaml => cfg!(feature = "yaml"),
+            Feature::Toml => cfg!(feature = "toml"),
+            Feature::Rayon => cfg!(feature = "rayon"),
+            Feature::Regress => cfg!(feature = "regress"),
+            Feature::Draft04 => cfg!(feature = "draft-04"),
+            Feature::Draft07 => cfg!(feature = "draft-07"),
+            Feature::Draft2019_09 => cfg!(feature = "draft-2019-09"),
+            Feature::Draft2020_12 => cfg!(feature = "draft-2020-12"),
+        }
+    }
+
+    /// Returns an iterator of the features enabled at compile time.
+    pub fn enabled() -> impl Iterator<Item = Feature> {
+        Feature::ALL.into_iter().filter(|f| f.is_enabled())
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
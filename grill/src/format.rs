@@ -0,0 +1,636 @@
+//! Validators of the `"format"` keyword.
+//!
+//! [`Formats`] is the registry of validators an
+//! [`Interrogator`](crate::Interrogator) consults when applying `"format"`.
+//! Formats which depend on heavier crates are gated behind cargo features;
+//! where feasible, a pure-Rust implementation is used when the feature is not
+//! enabled:
+//!
+//! | Format                            | Feature       | Without the feature        |
+//! |-----------------------------------|---------------|----------------------------|
+//! | `date-time`, `date`, `time`       | `time`        | RFC 3339 grammar           |
+//! | `uuid`                            | `uuid`        | RFC 4122 hyphenated form   |
+//! | `hostname`                        | `idna`        | A-labels are not decoded   |
+//! | `idn-hostname`, `idn-email`       | `idna`        | not available              |
+//! | `regex`                           | `fancy_regex` | `regex` syntax             |
+//!
+//! [`Formats::active`] reports which implementation backs each registered
+//! format and [`Formats::unavailable`] lists the formats which require a
+//! feature which is not enabled.
+//!
+//! ## Example
+//! ```
+//! use grill::format::{Formats, Implementation};
+//! use grill::Feature;
+//!
+//! let formats = Formats::standard();
+//! assert_eq!(formats.validate("date", "2023-02-29"), Some(false));
+//! assert_eq!(formats.validate("ipv4", "127.0.0.1"), Some(true));
+//! assert_eq!(formats.validate("unknown", "value"), None);
+//!
+//! let uuid = formats.get("uuid").unwrap();
+//! if Feature::Uuid.is_enabled() {
+//!     assert_eq!(uuid.implementation(), Implementation::Feature(Feature::Uuid));
+//! } else {
+//!     assert_eq!(uuid.implementation(), Implementation::Builtin);
+//! }
+//!
+//! formats.insert("even", |s: &str| s.len() % 2 == 0);
+//! assert_eq!(formats.validate("even", "ab"), Some(true));
+//! ```
+
+use crate::Feature;
+use jsonptr::Pointer;
+use parking_lot::RwLock;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
+use uniresid::{AbsoluteUri, Uri};
+
+/// A function which returns `true` if a string conforms to a format.
+pub type FormatFn = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Formats which can only be validated with the dependency of a [`Feature`].
+const GATED: &[(&str, Feature)] = &[
+    ("idn-hostname", Feature::Idna),
+    ("idn-email", Feature::Idna),
+];
+
+/// Describes what backs the validator of a [`Format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Implementation {
+    /// Implemented by grill without optional dependencies.
+    Builtin,
+    /// Implemented with the dependency of a cargo [`Feature`].
+    Feature(Feature),
+    /// Registered with [`Formats::insert`].
+    Custom,
+}
+
+impl fmt::Display for Implementation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Implementation::Builtin => f.write_str("builtin"),
+            Implementation::Feature(feature) => write!(f, "feature \"{}\"", feature),
+            Implementation::Custom => f.write_str("custom"),
+        }
+    }
+}
+
+/// A validator of a named format.
+#[derive(Clone)]
+pub struct Format {
+    validate: Arc<FormatFn>,
+    implementation: Implementation,
+}
+
+impl Format {
+    /// Creates a new, [`Custom`](Implementation::Custom) `Format`.
+    pub fn new(validate: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            validate: Arc::new(validate),
+            implementation: Implementation::Custom,
+        }
+    }
+
+    fn builtin(validate: fn(&str) -> bool, implementation: Implementation) -> Self {
+        Self {
+            validate: Arc::new(validate),
+            implementation,
+        }
+    }
+
+    /// Returns `true` if `value` conforms to the format.
+    pub fn validate(&self, value: &str) -> bool {
+        (self.validate)(value)
+    }
+
+    /// Returns the [`Implementation`] of the format.
+    pub fn implementation(&self) -> Implementation {
+        self.implementation
+    }
+}
+
+impl fmt::Debug for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Format")
+            .field("implementation", &self.implementation)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A registry of [`Format`]s, keyed by name.
+///
+/// Cloning `Formats` is cheap; clones share the same registry.
+#[derive(Clone, Default)]
+pub struct Formats {
+    formats: Arc<RwLock<Arc<HashMap<String, Format>>>>,
+}
+
+impl Formats {
+    /// Creates and returns a new, empty `Formats` registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates and returns a `Formats` registry containing each format of
+    /// the JSON Schema specification which is available with the enabled
+    /// features.
+    pub fn standard() -> Self {
+        let formats = Self::new();
+        let builtin = Implementation::Builtin;
+        let time = feature_or_builtin(Feature::Time);
+        let uuid = feature_or_builtin(Feature::Uuid);
+        let idna = feature_or_builtin(Feature::Idna);
+        let regex = feature_or_builtin(Feature::FancyRegex);
+        for (name, validate, implementation) in [
+            ("date-time", is_date_time as fn(&str) -> bool, time),
+            ("date", is_date, time),
+            ("time", is_time, time),
+            ("duration", is_duration, builtin),
+            ("email", is_email, builtin),
+            ("hostname", is_hostname, idna),
+            ("ipv4", is_ipv4, builtin),
+            ("ipv6", is_ipv6, builtin),
+            ("uri", is_uri, builtin),
+            ("uri-reference", is_uri_reference, builtin),
+            ("iri", is_uri, builtin),
+            ("iri-reference", is_uri_reference, builtin),
+            ("json-pointer", is_json_pointer, builtin),
+            ("uuid", is_uuid, uuid),
+            ("regex", is_regex, regex),
+        ] {
+            formats.insert_format(name, Format::builtin(validate, implementation));
+        }
+        #[cfg(feature = "idna")]
+        {
+            let idna = Implementation::Feature(Feature::Idna);
+            formats.insert_format("idn-hostname", Format::builtin(is_idn_hostname, idna));
+            formats.insert_format("idn-email", Format::builtin(is_idn_email, idna));
+        }
+        formats
+    }
+
+    /// Returns an independent copy of this `Formats` registry.
+    ///
+    /// Forking is O(1): the formats are shared until either registry is next
+    /// modified, at which point that registry copies its index of formats.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            formats: Arc::new(RwLock::new(self.read())),
+        }
+    }
+
+    fn read(&self) -> Arc<HashMap<String, Format>> {
+        self.formats.read().clone()
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut HashMap<String, Format>) -> T) -> T {
+        let mut formats = self.formats.write();
+        f(Arc::make_mut(&mut formats))
+    }
+
+    /// Returns the [`Format`] named `name`, if registered.
+    pub fn get(&self, name: &str) -> Option<Format> {
+        self.formats.read().get(name).cloned()
+    }
+
+    /// Registers `validate` as the [`Custom`](Implementation::Custom) format
+    /// `name`, returning the [`Format`] it replaced, if any.
+    pub fn insert(
+        &self,
+        name: impl Into<String>,
+        validate: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Option<Format> {
+        self.insert_format(name, Format::new(validate))
+    }
+
+    /// Registers `format` as `name`, returning the [`Format`] it replaced, if
+    /// any.
+    pub fn insert_format(&self, name: impl Into<String>, format: Format) -> Option<Format> {
+        self.write(|formats| formats.insert(name.into(), format))
+    }
+
+    /// Removes and returns the [`Format`] named `name`, if registered.
+    pub fn remove(&self, name: &str) -> Option<Format> {
+        self.write(|formats| formats.remove(name))
+    }
+
+    /// Returns `Some(true)` if `value` conforms to the format `name`,
+    /// `Some(false)` if it does not, and `None` if `name` is not registered.
+    pub fn validate(&self, name: &str, value: &str) -> Option<bool> {
+        self.get(name).map(|f| f.validate(value))
+    }
+
+    /// Returns the name and [`Implementation`] of each registered format,
+    /// sorted by name.
+    pub fn active(&self) -> Vec<(String, Implementation)> {
+        let mut active: Vec<_> = self
+            .read()
+            .iter()
+            .map(|(name, f)| (name.clone(), f.implementation))
+            .collect();
+        active.sort_by(|a, b| a.0.cmp(&b.0));
+        active
+    }
+
+    /// Returns each format of the JSON Schema specification which is not
+    /// available because the [`Feature`] it requires is not enabled.
+    pub fn unavailable() -> Vec<(&'static str, Feature)> {
+        GATED
+            .iter()
+            .filter(|(_, feature)| !feature.is_enabled())
+            .copied()
+            .collect()
+    }
+
+    /// Returns the number of registered formats.
+    pub fn len(&self) -> usize {
+        self.formats.read().len()
+    }
+
+    /// Returns `true` if no formats are registered.
+    pub fn is_empty(&self) -> bool {
+        self.formats.read().is_empty()
+    }
+}
+
+impl fmt::Debug for Formats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.active()).finish()
+    }
+}
+
+fn feature_or_builtin(feature: Feature) -> Implementation {
+    if feature.is_enabled() {
+        Implementation::Feature(feature)
+    } else {
+        Implementation::Builtin
+    }
+}
+
+/// Parses `digits` as a base 10 integer, failing on any other character.
+fn digits(digits: &[u8]) -> Option<u32> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u32, |acc, c| {
+        if c.is_ascii_digit() {
+            acc.checked_mul(10)?.checked_add(u32::from(c - b'0'))
+        } else {
+            None
+        }
+    })
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parses a `full-date` of RFC 3339 into its year, month, and day.
+fn parse_full_date(s: &str) -> Option<(u32, u32, u32)> {
+    let b = s.as_bytes();
+    if b.len() != 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    let (year, month, day) = (digits(&b[0..4])?, digits(&b[5..7])?, digits(&b[8..10])?);
+    if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+        Some((year, month, day))
+    } else {
+        None
+    }
+}
+
+fn is_full_date(s: &str) -> bool {
+    parse_full_date(s).is_some()
+}
+
+/// Parses a `full-time` of RFC 3339 into seconds from midnight UTC, which may
+/// fall outside of the day once the offset is applied, and nanoseconds.
+///
+/// A leap second is only permitted at 23:59:60 UTC.
+fn parse_full_time(s: &str) -> Option<(i64, u32)> {
+    let b = s.as_bytes();
+    if b.len() < 9 || b[2] != b':' || b[5] != b':' {
+        return None;
+    }
+    let (hour, minute, second) = (digits(&b[0..2])?, digits(&b[3..5])?, digits(&b[6..8])?);
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let mut rest = &b[8..];
+    let mut nanos = 0;
+    if rest.first() == Some(&b'.') {
+        let n = rest[1..].iter().take_while(|c| c.is_ascii_digit()).count();
+        if n == 0 {
+            return None;
+        }
+        // digits beyond nanosecond precision are truncated
+        nanos = rest[1..=n.min(9)]
+            .iter()
+            .chain(std::iter::repeat(&b'0'))
+            .take(9)
+            .fold(0, |acc, c| acc * 10 + u32::from(c - b'0'));
+        rest = &rest[1 + n..];
+    }
+    // offset in minutes east of UTC
+    let offset = match rest {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let (h, m) = (digits(&[*h1, *h2])?, digits(&[*m1, *m2])?);
+            if h > 23 || m > 59 {
+                return None;
+            }
+            let offset = i64::from(h * 60 + m);
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+    let utc = i64::from(hour * 60 + minute) - offset;
+    if second == 60 && utc.rem_euclid(24 * 60) != 23 * 60 + 59 {
+        return None;
+    }
+    Some((utc * 60 + i64::from(second), nanos))
+}
+
+fn is_full_time(s: &str) -> bool {
+    parse_full_time(s).is_some()
+}
+
+/// Parses a `date-time` of RFC 3339 into seconds since the Unix epoch, in
+/// UTC, and nanoseconds.
+fn parse_date_time(s: &str) -> Option<(i64, u32)> {
+    let b = s.as_bytes();
+    if b.len() <= 11 || !matches!(b[10], b'T' | b't') {
+        return None;
+    }
+    let (year, month, day) = parse_full_date(&s[..10])?;
+    let (seconds, nanos) = parse_full_time(&s[11..])?;
+    Some((days_from_civil(year, month, day) * 86_400 + seconds, nanos))
+}
+
+/// Returns the number of days from 1970-01-01 to the given date of the
+/// proleptic Gregorian calendar.
+fn days_from_civil(year: u32, month: u32, day: u32) -> i64 {
+    let (year, month, day) = (i64::from(year), i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn is_date_time(s: &str) -> bool {
+    let grammar = parse_date_time(s).is_some();
+    #[cfg(feature = "time")]
+    {
+        // time also accepts a space in place of "T"
+        use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+        grammar && OffsetDateTime::parse(s, &Rfc3339).is_ok()
+    }
+    #[cfg(not(feature = "time"))]
+    {
+        grammar
+    }
+}
+
+fn is_date(s: &str) -> bool {
+    #[cfg(feature = "time")]
+    {
+        is_full_date(s) && is_date_time(&format!("{}T00:00:00Z", s))
+    }
+    #[cfg(not(feature = "time"))]
+    {
+        is_full_date(s)
+    }
+}
+
+fn is_time(s: &str) -> bool {
+    #[cfg(feature = "time")]
+    {
+        // leap seconds are checked against UTC by parse_full_time; time only
+        // accepts them on dates which had one
+        is_full_time(s) && (s.get(6..8) == Some("60") || is_date_time(&format!("1970-01-01T{}", s)))
+    }
+    #[cfg(not(feature = "time"))]
+    {
+        is_full_time(s)
+    }
+}
+
+/// Parses a `duration` of RFC 3339, Appendix A, into its nominal length in
+/// seconds, counting a year as 365 days and a month as 30 days.
+fn parse_duration(s: &str) -> Option<u128> {
+    const DAY: u128 = 86_400;
+    let rest = s.strip_prefix('P').filter(|rest| !rest.is_empty())?;
+    if let Some(weeks) = rest.strip_suffix('W') {
+        return u128::from(digits(weeks.as_bytes())?).checked_mul(7 * DAY);
+    }
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, time),
+        Some(_) => return None,
+        None => (rest, ""),
+    };
+    let date = duration_components(date, &[(b'Y', 365 * DAY), (b'M', 30 * DAY), (b'D', DAY)])?;
+    let time = duration_components(time, &[(b'H', 3600), (b'M', 60), (b'S', 1)])?;
+    date.checked_add(time)
+}
+
+/// Sums a sequence of `<digits><unit>` pairs whose units are consecutive in
+/// `units`, each multiplied by the unit's length in seconds.
+fn duration_components(s: &str, units: &[(u8, u128)]) -> Option<u128> {
+    let mut next = None;
+    let mut total: u128 = 0;
+    let mut rest = s.as_bytes();
+    while !rest.is_empty() {
+        let n = rest.iter().take_while(|c| c.is_ascii_digit()).count();
+        if n == 0 || n == rest.len() {
+            return None;
+        }
+        let unit = units.iter().position(|(u, _)| *u == rest[n])?;
+        if next.map_or(false, |next| unit != next) {
+            return None;
+        }
+        let count = u128::from(digits(&rest[..n])?);
+        total = total.checked_add(count.checked_mul(units[unit].1)?)?;
+        next = Some(unit + 1);
+        rest = &rest[n + 1..];
+    }
+    Some(total)
+}
+
+fn is_duration(s: &str) -> bool {
+    parse_duration(s).is_some()
+}
+
+/// Compares `a` and `b` as values of `format`, which must be one of
+/// `"date-time"`, `"date"`, `"time"`, or `"duration"`.
+///
+/// Date-times and times are compared in UTC. Durations are compared by their
+/// nominal length, counting a year as 365 days and a month as 30 days.
+///
+/// Returns `None` if `format` is not comparable or if either value does not
+/// conform to it.
+///
+/// ## Example
+/// ```
+/// use grill::format::compare;
+/// use std::cmp::Ordering;
+///
+/// let ord = compare("date-time", "2023-01-01T00:00:00+01:00", "2022-12-31T23:30:00Z");
+/// assert_eq!(ord, Some(Ordering::Less));
+/// assert_eq!(compare("duration", "P1D", "PT24H"), Some(Ordering::Equal));
+/// assert_eq!(compare("email", "a@example.com", "b@example.com"), None);
+/// ```
+pub fn compare(format: &str, a: &str, b: &str) -> Option<Ordering> {
+    match format {
+        "date-time" => Some(parse_date_time(a)?.cmp(&parse_date_time(b)?)),
+        "date" => Some(parse_full_date(a)?.cmp(&parse_full_date(b)?)),
+        "time" => Some(parse_full_time(a)?.cmp(&parse_full_time(b)?)),
+        "duration" => Some(parse_duration(a)?.cmp(&parse_duration(b)?)),
+        _ => None,
+    }
+}
+
+/// Returns `true` if values of `format` can be ordered with [`compare`].
+pub fn is_comparable(format: &str) -> bool {
+    matches!(format, "date-time" | "date" | "time" | "duration")
+}
+
+fn is_email(s: &str) -> bool {
+    let (local, domain) = match s.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let local_ok = !local.is_empty()
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !"()<>[]:;@\\,\"".contains(c));
+    local_ok && is_email_domain(domain)
+}
+
+fn is_email_domain(domain: &str) -> bool {
+    match domain.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+        Some(literal) => match literal.strip_prefix("IPv6:") {
+            Some(v6) => is_ipv6(v6),
+            None => is_ipv4(literal),
+        },
+        None => is_ascii_hostname(domain),
+    }
+}
+
+/// A hostname of RFC 1123, composed of letters, digits, and hyphens.
+fn is_ascii_hostname(s: &str) -> bool {
+    let s = s.strip_suffix('.').unwrap_or(s);
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|c| c.is_ascii_alphanumeric() || c == b'-')
+        })
+}
+
+fn is_hostname(s: &str) -> bool {
+    #[cfg(feature = "idna")]
+    {
+        // A-labels ("xn--") must decode to valid U-labels
+        is_ascii_hostname(s)
+            && (!s.to_ascii_lowercase().contains("xn--") || idna::domain_to_unicode(s).1.is_ok())
+    }
+    #[cfg(not(feature = "idna"))]
+    {
+        is_ascii_hostname(s)
+    }
+}
+
+#[cfg(feature = "idna")]
+fn is_idn_hostname(s: &str) -> bool {
+    match idna::domain_to_ascii_strict(s) {
+        Ok(ascii) => is_ascii_hostname(&ascii),
+        Err(_) => false,
+    }
+}
+
+#[cfg(feature = "idna")]
+fn is_idn_email(s: &str) -> bool {
+    match s.rsplit_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !local.chars().any(char::is_whitespace)
+                && (is_email_domain(domain) || is_idn_hostname(domain))
+        }
+        None => false,
+    }
+}
+
+fn is_ipv4(s: &str) -> bool {
+    s.parse::<Ipv4Addr>().is_ok()
+}
+
+fn is_ipv6(s: &str) -> bool {
+    s.parse::<Ipv6Addr>().is_ok()
+}
+
+fn is_uri(s: &str) -> bool {
+    AbsoluteUri::parse(s).is_ok()
+}
+
+fn is_uri_reference(s: &str) -> bool {
+    Uri::parse(s).is_ok()
+}
+
+fn is_json_pointer(s: &str) -> bool {
+    Pointer::try_from(s).is_ok()
+}
+
+fn is_uuid(s: &str) -> bool {
+    // only the hyphenated form of RFC 4122 is permitted
+    let b = s.as_bytes();
+    let hyphenated = b.len() == 36
+        && b.iter().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => *c == b'-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    #[cfg(feature = "uuid")]
+    {
+        hyphenated && uuid::Uuid::try_parse(s).is_ok()
+    }
+    #[cfg(not(feature = "uuid"))]
+    {
+        hyphenated
+    }
+}
+
+fn is_regex(s: &str) -> bool {
+    #[cfg(feature = "fancy_regex")]
+    {
+        fancy_regex::Regex::new(s).is_ok()
+    }
+    #[cfg(not(feature = "fancy_regex"))]
+    {
+        regex::Regex::new(s).is_ok()
+    }
+}
@@ -0,0 +1,496 @@
+//! Resolution of URI fragments within source documents.
+//!
+//! A fragment is either empty, a JSON Pointer (e.g. `"#/$defs/node"`), or a
+//! plain-name anchor (e.g. `"#node"`) declared with `"$anchor"`,
+//! `"$dynamicAnchor"`, or, prior to 2019-09, an `"$id"` of the form
+//! `"#node"`.
+//!
+//! The specification does not permit an anchor to be followed by a JSON
+//! Pointer, e.g. `"#node/properties/name"`, yet such references are common
+//! in the wild. By default, [`locate`] fails with an
+//! [`AnchorPointerFragmentError`] which names the anchor, the pointer, and,
+//! where possible, the equivalent JSON Pointer fragment. When resolving
+//! leniently, the anchor is resolved first and the pointer is then applied
+//! to the anchored schema; a [`Diagnostic`] with the lint
+//! [`Lint::AnchorPointerFragment`] is returned so that the reference can be
+//! corrected. See
+//! [`Interrogator::set_lenient_fragments`](crate::Interrogator::set_lenient_fragments).
+//!
+//! Anchor names must match the grammar of their draft, which differs between
+//! 2019-09 and 2020-12; see [`AnchorGrammar`]. An anchor declared more than
+//! once within a resource is handled according to the
+//! [`DuplicateAnchorPolicy`]. Both are checked by [`check_anchors`] when a
+//! [`Schema`](crate::Schema) is added to an
+//! [`Interrogator`](crate::Interrogator).
+//!
+//! ## Example
+//! ```
+//! use grill::fragment::locate;
+//! use serde_json::json;
+//!
+//! let document = json!({
+//!     "$defs": {
+//!         "node": {
+//!             "$anchor": "node",
+//!             "properties": { "name": { "type": "string" } }
+//!         }
+//!     }
+//! });
+//!
+//! let node = locate(&document, "node", false).unwrap().unwrap();
+//! assert_eq!(node.location.as_str(), "/$defs/node");
+//!
+//! let err = locate(&document, "node/properties/name", false).unwrap_err();
+//! assert!(err.is_anchor_pointer_fragment());
+//!
+//! let name = locate(&document, "node/properties/name", true).unwrap().unwrap();
+//! assert_eq!(name.location.as_str(), "/$defs/node/properties/name");
+//! assert!(name.diagnostic.is_some());
+//! ```
+
+use crate::{
+    draft::{HYPER_SCHEMA_2019_09_URI, SCHEMA_2019_09_URI},
+    error::AnchorPointerFragmentError,
+    lint::{Diagnostic, Lint},
+    urn, Error,
+};
+use jsonptr::{Pointer, Token};
+use serde_json::Value;
+use std::{borrow::Cow, collections::HashMap};
+use uniresid::Uri;
+
+/// Keywords whose value is an instance rather than a schema, and is
+/// therefore not searched for anchors.
+const INSTANCE_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// The parsed form of a URI fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fragment<'a> {
+    /// An empty fragment, referring to the root of the document.
+    Empty,
+    /// A JSON Pointer, e.g. `"/$defs/node"`.
+    Pointer(&'a str),
+    /// A plain-name anchor, e.g. `"node"`.
+    Anchor(&'a str),
+    /// An anchor followed by a JSON Pointer, e.g. `"node/properties/name"`.
+    /// Not permitted by the specification.
+    AnchorPointer {
+        /// The anchor, e.g. `"node"`.
+        anchor: &'a str,
+        /// The JSON Pointer, e.g. `"/properties/name"`.
+        pointer: &'a str,
+    },
+}
+
+impl<'a> Fragment<'a> {
+    /// Parses `fragment`, which should be percent-decoded and may include
+    /// the leading `'#'`.
+    pub fn parse(fragment: &'a str) -> Self {
+        let fragment = fragment.strip_prefix('#').unwrap_or(fragment);
+        if fragment.is_empty() {
+            return Fragment::Empty;
+        }
+        if fragment.starts_with('/') {
+            return Fragment::Pointer(fragment);
+        }
+        match fragment.find('/') {
+            Some(i) => Fragment::AnchorPointer {
+                anchor: &fragment[..i],
+                pointer: &fragment[i..],
+            },
+            None => Fragment::Anchor(fragment),
+        }
+    }
+}
+
+/// The location of a fragment within a document, as returned by [`locate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located {
+    /// The JSON Pointer to the value referred to by the fragment.
+    pub location: Pointer,
+    /// Reported if the fragment was resolved leniently. The
+    /// `keyword_location` of the `Diagnostic` is `location`.
+    pub diagnostic: Option<Diagnostic>,
+}
+
+/// Returns the location of each anchor declared within the resource
+/// `document`, keyed by name.
+///
+/// Anchors of embedded resources, i.e. subschemas with their own `"$id"`,
+/// are not included. If an anchor is declared more than once, the first
+/// declaration encountered wins; see [`check_anchors`].
+pub fn anchors(document: &Value) -> HashMap<String, Pointer> {
+    let mut anchors = HashMap::new();
+    collect(document, &Pointer::default(), true, false, &mut anchors);
+    anchors
+}
+
+/// Returns the location of each `"$dynamicAnchor"` declared within the
+/// resource `document`, keyed by name.
+///
+/// As with [`anchors`], anchors of embedded resources are not included and
+/// the first declaration of a name wins.
+pub fn dynamic_anchors(document: &Value) -> HashMap<String, Pointer> {
+    let mut anchors = HashMap::new();
+    collect(document, &Pointer::default(), true, true, &mut anchors);
+    anchors
+}
+
+fn collect(
+    value: &Value,
+    location: &Pointer,
+    root: bool,
+    dynamic: bool,
+    anchors: &mut HashMap<String, Pointer>,
+) {
+    match value {
+        Value::Object(obj) => {
+            let id = obj
+                .get("$id")
+                .or_else(|| obj.get("id"))
+                .and_then(Value::as_str);
+            if let Some(id) = id {
+                match id.strip_prefix('#') {
+                    Some(anchor) if !anchor.is_empty() => {
+                        if !dynamic {
+                            anchors
+                                .entry(anchor.to_string())
+                                .or_insert_with(|| location.clone());
+                        }
+                    }
+                    _ if !root => return,
+                    _ => {}
+                }
+            }
+            let keywords: &[&str] = if dynamic {
+                &["$dynamicAnchor"]
+            } else {
+                &["$anchor", "$dynamicAnchor"]
+            };
+            for keyword in keywords {
+                if let Some(anchor) = obj.get(*keyword).and_then(Value::as_str) {
+                    anchors
+                        .entry(anchor.to_string())
+                        .or_insert_with(|| location.clone());
+                }
+            }
+            for (k, v) in obj {
+                if INSTANCE_KEYWORDS.contains(&k.as_str()) {
+                    continue;
+                }
+                let mut location = location.clone();
+                location.push_back(k.as_str().into());
+                collect(v, &location, false, dynamic, anchors);
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                let mut location = location.clone();
+                location.push_back(Token::from(i));
+                collect(v, &location, false, dynamic, anchors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the absolute id, without fragment, and the location of each
+/// resource identified by `"$id"` within `document`, including `document`
+/// itself, where `base` is the URI of `document`.
+///
+/// Ids are resolved against the id of the enclosing resource with
+/// [`urn::resolve`]; those which can not be resolved are skipped.
+pub fn resources(document: &Value, base: &Uri) -> Vec<(String, Pointer)> {
+    let mut resources = Vec::new();
+    collect_resources(document, &Pointer::default(), base, &mut resources);
+    resources
+}
+
+fn collect_resources(
+    value: &Value,
+    location: &Pointer,
+    base: &Uri,
+    resources: &mut Vec<(String, Pointer)>,
+) {
+    match value {
+        Value::Object(obj) => {
+            let mut base = Cow::Borrowed(base);
+            let id = obj.get("$id").and_then(Value::as_str);
+            if let Some(id) = id.filter(|id| !id.starts_with('#')) {
+                if let Some(id) = Uri::parse(id).ok().and_then(|id| urn::resolve(&base, &id)) {
+                    let document = id
+                        .to_string()
+                        .split('#')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                    resources.push((document, location.clone()));
+                    base = Cow::Owned(id);
+                }
+            }
+            for (k, v) in obj {
+                if INSTANCE_KEYWORDS.contains(&k.as_str()) {
+                    continue;
+                }
+                let mut location = location.clone();
+                location.push_back(k.as_str().into());
+                collect_resources(v, &location, &base, resources);
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                let mut location = location.clone();
+                location.push_back(Token::from(i));
+                collect_resources(v, &location, base, resources);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The grammar of plain-name anchors, which differs between drafts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnchorGrammar {
+    /// Draft 2019-09: `"$anchor"` must match `^[A-Za-z][-A-Za-z0-9.:_]*$`.
+    /// `"$dynamicAnchor"` is not a keyword of this draft.
+    Draft2019_09,
+    /// Draft 2020-12: `"$anchor"` and `"$dynamicAnchor"` must match
+    /// `^[A-Za-z_][-A-Za-z0-9._]*$`.
+    Draft2020_12,
+}
+
+impl AnchorGrammar {
+    /// Returns the grammar of the draft identified by `meta_schema_id`.
+    /// Drafts other than 2019-09 use the grammar of 2020-12.
+    pub fn for_meta_schema(meta_schema_id: &Uri) -> Self {
+        if meta_schema_id == &*SCHEMA_2019_09_URI || meta_schema_id == &*HYPER_SCHEMA_2019_09_URI {
+            AnchorGrammar::Draft2019_09
+        } else {
+            AnchorGrammar::Draft2020_12
+        }
+    }
+
+    /// Returns the keywords which declare an anchor.
+    pub fn keywords(self) -> &'static [&'static str] {
+        match self {
+            AnchorGrammar::Draft2019_09 => &["$anchor"],
+            AnchorGrammar::Draft2020_12 => &["$anchor", "$dynamicAnchor"],
+        }
+    }
+
+    /// Returns `true` if `name` is a valid anchor.
+    pub fn is_valid(self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let first = match chars.next() {
+            Some(c) => c,
+            None => return false,
+        };
+        match self {
+            AnchorGrammar::Draft2019_09 => {
+                first.is_ascii_alphabetic()
+                    && chars
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':' | '_'))
+            }
+            AnchorGrammar::Draft2020_12 => {
+                (first.is_ascii_alphabetic() || first == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+            }
+        }
+    }
+}
+
+/// Determines how an anchor declared more than once within a resource is
+/// handled when a [`Schema`](crate::Schema) is added to an
+/// [`Interrogator`](crate::Interrogator).
+///
+/// See [`Interrogator::set_duplicate_anchor_policy`](crate::Interrogator::set_duplicate_anchor_policy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DuplicateAnchorPolicy {
+    /// The `Schema` is rejected with [`Error::InvalidAnchor`].
+    #[default]
+    Error,
+    /// The first declaration is used and each duplicate is reported as a
+    /// [`Diagnostic`].
+    FirstWins,
+}
+
+/// Checks the anchors of `document` and each of its embedded resources
+/// against `grammar`, returning a [`Diagnostic`] with the lint
+/// [`Lint::InvalidAnchor`] for each malformed name and with
+/// [`Lint::DuplicateAnchor`] for each name declared more than once within
+/// the same resource.
+///
+/// ## Example
+/// ```
+/// use grill::{fragment::{check_anchors, AnchorGrammar}, lint::Lint};
+/// use serde_json::json;
+///
+/// let document = json!({
+///     "$defs": {
+///         "a": { "$anchor": "node" },
+///         "b": { "$anchor": "node" },
+///         "c": { "$anchor": "_private" }
+///     }
+/// });
+/// let diagnostics = check_anchors(&document, AnchorGrammar::Draft2020_12);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].lint, Lint::DuplicateAnchor);
+/// assert_eq!(diagnostics[0].keyword_location.as_str(), "/$defs/b");
+///
+/// let diagnostics = check_anchors(&document, AnchorGrammar::Draft2019_09);
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!(diagnostics[1].lint, Lint::InvalidAnchor);
+/// ```
+pub fn check_anchors(document: &Value, grammar: AnchorGrammar) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_resource(document, &Pointer::default(), grammar, &mut diagnostics);
+    diagnostics
+}
+
+/// The anchors declared within a resource and the locations of the
+/// resources embedded within it.
+struct Resource {
+    grammar: AnchorGrammar,
+    declared: HashMap<String, Pointer>,
+    embedded: Vec<Pointer>,
+}
+
+fn check_resource(
+    value: &Value,
+    location: &Pointer,
+    grammar: AnchorGrammar,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut resource = Resource {
+        grammar,
+        declared: HashMap::new(),
+        embedded: Vec::new(),
+    };
+    check_value(value, location, true, &mut resource, diagnostics);
+    for embedded in resource.embedded {
+        // `embedded` is absolute; strip the location of this resource
+        let relative = &embedded.as_str()[location.as_str().len()..];
+        if let Some(value) = value.pointer(relative) {
+            check_resource(value, &embedded, grammar, diagnostics);
+        }
+    }
+}
+
+fn check_value(
+    value: &Value,
+    location: &Pointer,
+    root: bool,
+    resource: &mut Resource,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value {
+        Value::Object(obj) => {
+            let id = obj.get("$id").and_then(Value::as_str);
+            if !root && matches!(id, Some(id) if !id.starts_with('#')) {
+                resource.embedded.push(location.clone());
+                return;
+            }
+            for keyword in resource.grammar.keywords() {
+                let name = match obj.get(*keyword).and_then(Value::as_str) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let mut report = |lint: Lint, message: String| {
+                    diagnostics.push(Diagnostic {
+                        lint,
+                        schema_id: None,
+                        keyword_location: location.clone(),
+                        message,
+                    });
+                };
+                if !resource.grammar.is_valid(name) {
+                    report(
+                        Lint::InvalidAnchor,
+                        format!("\"{}\" of \"{}\" is not a valid anchor", keyword, name),
+                    );
+                    continue;
+                }
+                match resource.declared.get(name) {
+                    Some(first) if first != location => report(
+                        Lint::DuplicateAnchor,
+                        format!("anchor \"{}\" is already declared at \"{}\"", name, first),
+                    ),
+                    Some(_) => {}
+                    None => {
+                        resource.declared.insert(name.to_string(), location.clone());
+                    }
+                }
+            }
+            for (k, v) in obj {
+                if INSTANCE_KEYWORDS.contains(&k.as_str()) {
+                    continue;
+                }
+                let mut location = location.clone();
+                location.push_back(k.as_str().into());
+                check_value(v, &location, false, resource, diagnostics);
+            }
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                let mut location = location.clone();
+                location.push_back(Token::from(i));
+                check_value(v, &location, false, resource, diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the location of `fragment` within `document`, or `None` if the
+/// anchor or the value the pointer refers to does not exist.
+///
+/// If `fragment` combines an anchor with a JSON Pointer, an
+/// [`AnchorPointerFragmentError`] is returned unless `lenient` is `true`.
+///
+/// # Errors
+/// Returns [`Error::InvalidPointer`] if the JSON Pointer of `fragment` is
+/// malformed and [`Error::AnchorPointerFragment`] as described above.
+pub fn locate(document: &Value, fragment: &str, lenient: bool) -> Result<Option<Located>, Error> {
+    let located = |location: Pointer| Located {
+        location,
+        diagnostic: None,
+    };
+    match Fragment::parse(fragment) {
+        Fragment::Empty => Ok(Some(located(Pointer::default()))),
+        Fragment::Pointer(pointer) => {
+            let pointer = Pointer::try_from(pointer)?;
+            Ok(document.pointer(pointer.as_str()).map(|_| located(pointer)))
+        }
+        Fragment::Anchor(anchor) => Ok(anchors(document).remove(anchor).map(located)),
+        Fragment::AnchorPointer { anchor, pointer } => {
+            let suffix = Pointer::try_from(pointer)?;
+            let location = anchors(document).remove(anchor).map(|mut location| {
+                location.append(&suffix);
+                location
+            });
+            let location = location.filter(|l| document.pointer(l.as_str()).is_some());
+            if !lenient {
+                return Err(AnchorPointerFragmentError {
+                    fragment: fragment.trim_start_matches('#').to_string(),
+                    anchor: anchor.to_string(),
+                    pointer: pointer.to_string(),
+                    equivalent: location,
+                }
+                .into());
+            }
+            Ok(location.map(|location| Located {
+                diagnostic: Some(Diagnostic {
+                    lint: Lint::AnchorPointerFragment,
+                    schema_id: None,
+                    keyword_location: location.clone(),
+                    message: format!(
+                        "fragment \"#{}\" combines an anchor with a JSON Pointer; resolved as \"#{}\"",
+                        fragment.trim_start_matches('#'),
+                        location
+                    ),
+                }),
+                location,
+            }))
+        }
+    }
+}
@@ -1,15 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::{error::UnidentifiedSchemaError, Schema};
+use crate::{
+    error::UnidentifiedSchemaError,
+    pointer::{self, Pointer},
+    Schema,
+};
 use petgraph::algo::has_path_connecting;
 use petgraph::graph::NodeIndex;
+use petgraph::Direction;
 use petgraph::Graph as PetGraph;
 use uniresid::Uri;
 
 /// Builds a graph of schemas whose edges correspond to references.
 /// The goal is to determine compilation sequences and to recognize when
 /// schemas are recursively referenced.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Graph {
     index: HashMap<Uri, NodeIndex>,
     graph: PetGraph<Uri, ()>,
@@ -82,7 +87,7 @@ impl Graph {
             Some(outer) => *outer,
             None => return false,
         };
-        
+
         let inner = match self.index.get(&src_id) {
             Some(inner) => *inner,
             None => return false,
@@ -91,6 +96,136 @@ impl Graph {
         has_path_connecting(&self.graph, outer, inner, None)
     }
 
+    /// Returns a chain of documents referenced from the document of `from`,
+    /// e.g. `[A, B, C]` for A→B→C, which is longer than `max` references, if
+    /// one exists.
+    ///
+    /// References within a document and references back to a document
+    /// already in the chain are not followed.
+    pub fn chain_exceeding(&self, from: &Schema, max: usize) -> Option<Vec<Uri>> {
+        let id = from.id()?;
+        let mut edges: HashMap<String, Vec<&Uri>> = HashMap::new();
+        for schema in self.nodes.values() {
+            if let Some(id) = schema.id() {
+                let refs = edges.entry(document(&id)).or_default();
+                for node in self.graph.neighbors(self.index[id.as_ref()]) {
+                    refs.push(&self.graph[node]);
+                }
+            }
+        }
+        let mut longest = HashMap::new();
+        let mut visiting = HashSet::new();
+        if deepest(&edges, &document(&id), &mut longest, &mut visiting) <= max {
+            return None;
+        }
+        let mut chain = vec![id.as_ref().clone()];
+        let mut seen = HashSet::from([document(&id)]);
+        let mut current = document(&id);
+        while let Some((_, Some(next))) = longest.get(&current) {
+            current = document(next);
+            if !seen.insert(current.clone()) {
+                break;
+            }
+            chain.push((*next).clone());
+        }
+        Some(chain)
+    }
+
+    /// Returns the URIs referenced directly by the schema `id`, in the order
+    /// they were added.
+    pub fn direct_references(&self, id: &Uri) -> Vec<Uri> {
+        let index = match self.index.get(id) {
+            Some(index) => *index,
+            None => return Vec::new(),
+        };
+        let mut references: Vec<Uri> = self
+            .graph
+            .neighbors_directed(index, Direction::Outgoing)
+            .map(|n| self.graph[n].clone())
+            .collect();
+        // petgraph yields the most recently added edge first
+        references.reverse();
+        let mut seen = HashSet::new();
+        references.retain(|r| seen.insert(r.clone()));
+        references
+    }
+
+    /// Returns the ids of the schemas which reference the schema `id`,
+    /// directly or through other schemas, nearest first.
+    pub fn transitive_dependents(&self, id: &Uri) -> Vec<Uri> {
+        let start = match self.index.get(id) {
+            Some(index) => *index,
+            None => return Vec::new(),
+        };
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        let mut dependents = Vec::new();
+        while let Some(index) = queue.pop_front() {
+            for n in self.graph.neighbors_directed(index, Direction::Incoming) {
+                if !seen.insert(n) {
+                    continue;
+                }
+                queue.push_back(n);
+                if self.nodes.contains_key(&n) {
+                    dependents.push(self.graph[n].clone());
+                }
+            }
+        }
+        dependents
+    }
+
+    /// Returns the ids of the schemas which contain the schema `id` within
+    /// their document, nearest first.
+    ///
+    /// Containment is determined by location: a schema identified by
+    /// `"https://example.com/a.json#/$defs/b"` is contained by those
+    /// identified by `"https://example.com/a.json#/$defs"` and
+    /// `"https://example.com/a.json"`. `resource` returns the source document
+    /// and location of the schema resource with the given id, so that
+    /// resources embedded with their own `"$id"` are placed within their
+    /// document. Schemas identified by an anchor are neither ancestors nor
+    /// descendants of others.
+    pub fn ancestors(&self, id: &Uri, resource: &Resource) -> Vec<Uri> {
+        let (document, pointer) = match location(id, resource) {
+            Some(location) => location,
+            None => return Vec::new(),
+        };
+        let mut ancestors: Vec<(usize, Uri)> = self
+            .compiled()
+            .filter_map(|other| {
+                let (doc, ptr) = location(other, resource)?;
+                (doc == document && ptr.len() < pointer.len() && pointer.starts_with(&ptr))
+                    .then(|| (ptr.len(), other.clone()))
+            })
+            .collect();
+        ancestors.sort_by(|a, b| b.0.cmp(&a.0));
+        ancestors.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Returns the ids of the schemas contained within the schema `id`,
+    /// ordered by their location. See [`ancestors`](Self::ancestors).
+    pub fn descendants(&self, id: &Uri, resource: &Resource) -> Vec<Uri> {
+        let (document, pointer) = match location(id, resource) {
+            Some(location) => location,
+            None => return Vec::new(),
+        };
+        let mut descendants: Vec<(Vec<String>, Uri)> = self
+            .compiled()
+            .filter_map(|other| {
+                let (doc, ptr) = location(other, resource)?;
+                (doc == document && ptr.len() > pointer.len() && ptr.starts_with(&pointer))
+                    .then(|| (ptr, other.clone()))
+            })
+            .collect();
+        descendants.sort();
+        descendants.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Returns the ids of the compiled schemas.
+    fn compiled(&self) -> impl Iterator<Item = &Uri> {
+        self.nodes.keys().map(|index| &self.graph[*index])
+    }
+
     pub fn nodes(&self) -> Nodes {
         Nodes::new(
             petgraph::algo::kosaraju_scc(&self.graph),
@@ -99,6 +234,66 @@ impl Graph {
     }
 }
 
+/// Returns the number of references in the longest chain from the document
+/// `doc`, recording it, along with the reference which begins it, in
+/// `longest`.
+///
+/// Each document is explored once. References back to a document still being
+/// explored are not followed.
+fn deepest<'a>(
+    edges: &HashMap<String, Vec<&'a Uri>>,
+    doc: &str,
+    longest: &mut HashMap<String, (usize, Option<&'a Uri>)>,
+    visiting: &mut HashSet<String>,
+) -> usize {
+    if let Some((depth, _)) = longest.get(doc) {
+        return *depth;
+    }
+    visiting.insert(doc.to_string());
+    let mut deepest_ref = (0, None);
+    for r in edges.get(doc).into_iter().flatten() {
+        let target = document(r);
+        if visiting.contains(&target) {
+            continue;
+        }
+        let depth = deepest(edges, &target, longest, visiting) + 1;
+        if depth > deepest_ref.0 {
+            deepest_ref = (depth, Some(*r));
+        }
+    }
+    visiting.remove(doc);
+    longest.insert(doc.to_string(), deepest_ref);
+    deepest_ref.0
+}
+
+/// Returns the source document and location of the schema resource with
+/// the given id, which has no fragment, if it is known.
+pub type Resource = dyn Fn(&str) -> Option<(String, Pointer)>;
+
+/// Returns the source document of `uri` and the tokens of its location
+/// within it, or `None` if the fragment of `uri` is an anchor.
+fn location(uri: &Uri, resource: &Resource) -> Option<(String, Vec<String>)> {
+    let s = uri.to_string();
+    let (id, fragment) = s.split_once('#').unwrap_or((&s, ""));
+    let fragment = pointer::from_fragment(fragment).ok()?;
+    let (document, base) = resource(id).unwrap_or_else(|| (id.to_string(), Pointer::default()));
+    let tokens = base
+        .tokens()
+        .chain(fragment.tokens())
+        .map(|t| t.as_str().to_string())
+        .collect();
+    Some((document, tokens))
+}
+
+/// Returns `uri` without its fragment.
+fn document(uri: &Uri) -> String {
+    let s = uri.to_string();
+    match s.split_once('#') {
+        Some((doc, _)) => doc.to_string(),
+        None => s,
+    }
+}
+
 pub struct Nodes {
     indexes: Vec<Vec<NodeIndex>>,
     nodes: HashMap<NodeIndex, Schema>,
@@ -0,0 +1,258 @@
+//! Inference of a JSON Schema from sample instances.
+//!
+//! [`infer`] derives a draft 2020-12 schema which each of the samples is
+//! valid against:
+//!
+//! - `"type"` lists each type seen at a location; integers are subsumed by
+//!   `"number"` when both are seen.
+//! - `"properties"` describes each property seen, and `"required"` lists
+//!   those present in every object seen at the location.
+//! - `"items"` describes every element of the arrays seen at the location.
+//! - `"enum"` lists the strings seen at a location when there are few of
+//!   them and at least one is repeated; see [`InferOptions::max_enum`].
+//! - `"minimum"`/`"maximum"`, `"minLength"`/`"maxLength"`, and
+//!   `"minItems"`/`"maxItems"` bound the values seen, unless disabled with
+//!   [`InferOptions::bounds`].
+//!
+//! The result is a [`Value`] which can be sourced and compiled like any
+//! other schema.
+//!
+//! ## Example
+//! ```
+//! use grill::infer::infer;
+//! use serde_json::json;
+//!
+//! let schema = infer(&[
+//!     json!({ "name": "a", "kind": "user", "age": 30 }),
+//!     json!({ "name": "b", "kind": "user" }),
+//! ]);
+//! assert_eq!(schema["type"], "object");
+//! assert_eq!(schema["required"], json!(["kind", "name"]));
+//! assert_eq!(schema["properties"]["kind"]["enum"], json!(["user"]));
+//! assert_eq!(schema["properties"]["age"]["minimum"], 30);
+//! ```
+
+use crate::draft::SCHEMA_2020_12_URI;
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The default of [`InferOptions::max_enum`].
+pub const DEFAULT_MAX_ENUM: usize = 8;
+
+/// Options for [`infer_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct InferOptions {
+    max_enum: usize,
+    bounds: bool,
+}
+
+impl Default for InferOptions {
+    fn default() -> Self {
+        Self {
+            max_enum: DEFAULT_MAX_ENUM,
+            bounds: true,
+        }
+    }
+}
+
+impl InferOptions {
+    /// Creates and returns a new `InferOptions` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of distinct strings at a location for which
+    /// an `"enum"` is inferred. Setting `0` disables `"enum"` inference.
+    ///
+    /// Defaults to [`DEFAULT_MAX_ENUM`].
+    #[must_use]
+    pub fn max_enum(mut self, max_enum: usize) -> Self {
+        self.max_enum = max_enum;
+        self
+    }
+
+    /// Sets whether the bounds of numbers, strings, and arrays are inferred.
+    ///
+    /// Enabled by default.
+    #[must_use]
+    pub fn bounds(mut self, bounds: bool) -> Self {
+        self.bounds = bounds;
+        self
+    }
+}
+
+/// Returns a schema inferred from `samples` with the default
+/// [`InferOptions`].
+pub fn infer(samples: &[Value]) -> Value {
+    infer_with(samples, InferOptions::default())
+}
+
+/// Returns a schema inferred from `samples`.
+///
+/// If `samples` is empty, the schema accepts any instance.
+pub fn infer_with(samples: &[Value], options: InferOptions) -> Value {
+    let mut shape = Shape::default();
+    for sample in samples {
+        shape.observe(sample, options);
+    }
+    let mut schema = Map::new();
+    schema.insert("$schema".into(), SCHEMA_2020_12_URI.to_string().into());
+    shape.describe(&mut schema, options);
+    Value::Object(schema)
+}
+
+/// The accumulated observations of the values at a single location.
+#[derive(Default)]
+struct Shape {
+    types: BTreeSet<&'static str>,
+    strings: BTreeSet<String>,
+    string_count: usize,
+    // the distinct strings exceeded `max_enum`; `strings` is no longer kept
+    strings_overflowed: bool,
+    length: Option<(usize, usize)>,
+    number: Option<((f64, Number), (f64, Number))>,
+    items_len: Option<(usize, usize)>,
+    items: Option<Box<Shape>>,
+    object_count: usize,
+    properties: BTreeMap<String, (usize, Shape)>,
+}
+
+impl Shape {
+    fn observe(&mut self, value: &Value, options: InferOptions) {
+        match value {
+            Value::Null => {
+                self.types.insert("null");
+            }
+            Value::Bool(_) => {
+                self.types.insert("boolean");
+            }
+            Value::Number(n) => self.observe_number(n),
+            Value::String(s) => self.observe_string(s, options),
+            Value::Array(items) => {
+                self.types.insert("array");
+                self.items_len = Some(widen(self.items_len, items.len()));
+                let shape = self.items.get_or_insert_with(Box::default);
+                for item in items {
+                    shape.observe(item, options);
+                }
+            }
+            Value::Object(obj) => {
+                self.types.insert("object");
+                self.object_count += 1;
+                for (key, value) in obj {
+                    let (seen, shape) = self.properties.entry(key.clone()).or_default();
+                    *seen += 1;
+                    shape.observe(value, options);
+                }
+            }
+        }
+    }
+
+    fn observe_number(&mut self, n: &Number) {
+        let is_integer = n.is_i64() || n.is_u64();
+        self.types
+            .insert(if is_integer { "integer" } else { "number" });
+        let f = match n.as_f64() {
+            Some(f) => f,
+            None => return,
+        };
+        let observed = (f, n.clone());
+        self.number = Some(match self.number.take() {
+            None => (observed.clone(), observed),
+            Some((min, max)) => (
+                if f < min.0 { observed.clone() } else { min },
+                if f > max.0 { observed } else { max },
+            ),
+        });
+    }
+
+    fn observe_string(&mut self, s: &str, options: InferOptions) {
+        self.types.insert("string");
+        self.string_count += 1;
+        self.length = Some(widen(self.length, s.chars().count()));
+        if self.strings_overflowed {
+            return;
+        }
+        self.strings.insert(s.to_string());
+        if self.strings.len() > options.max_enum {
+            self.strings_overflowed = true;
+            self.strings.clear();
+        }
+    }
+
+    fn is_enum(&self, options: InferOptions) -> bool {
+        self.types.len() == 1
+            && self.types.contains("string")
+            && !self.strings_overflowed
+            && !self.strings.is_empty()
+            && self.strings.len() <= options.max_enum
+            && self.strings.len() < self.string_count
+    }
+
+    fn describe(&self, schema: &mut Map<String, Value>, options: InferOptions) {
+        let mut types: Vec<&str> = self.types.iter().copied().collect();
+        if self.types.contains("number") {
+            types.retain(|t| *t != "integer");
+        }
+        match types.as_slice() {
+            [] => return,
+            [t] => {
+                schema.insert("type".into(), (*t).into());
+            }
+            _ => {
+                schema.insert("type".into(), types.into());
+            }
+        }
+        if self.is_enum(options) {
+            let values = self.strings.iter().cloned().map(Value::String).collect();
+            schema.insert("enum".into(), Value::Array(values));
+        } else if options.bounds {
+            if let Some((min, max)) = self.length {
+                schema.insert("minLength".into(), min.into());
+                schema.insert("maxLength".into(), max.into());
+            }
+        }
+        if options.bounds {
+            if let Some(((_, min), (_, max))) = &self.number {
+                schema.insert("minimum".into(), Value::Number(min.clone()));
+                schema.insert("maximum".into(), Value::Number(max.clone()));
+            }
+            if let Some((min, max)) = self.items_len {
+                schema.insert("minItems".into(), min.into());
+                schema.insert("maxItems".into(), max.into());
+            }
+        }
+        if let Some(items) = &self.items {
+            let mut sub = Map::new();
+            items.describe(&mut sub, options);
+            if !sub.is_empty() {
+                schema.insert("items".into(), Value::Object(sub));
+            }
+        }
+        if self.object_count > 0 {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for (key, (seen, shape)) in &self.properties {
+                let mut sub = Map::new();
+                shape.describe(&mut sub, options);
+                properties.insert(key.clone(), Value::Object(sub));
+                if *seen == self.object_count {
+                    required.push(Value::String(key.clone()));
+                }
+            }
+            if !properties.is_empty() {
+                schema.insert("properties".into(), Value::Object(properties));
+            }
+            if !required.is_empty() {
+                schema.insert("required".into(), Value::Array(required));
+            }
+        }
+    }
+}
+
+fn widen(range: Option<(usize, usize)>, n: usize) -> (usize, usize) {
+    match range {
+        None => (n, n),
+        Some((min, max)) => (min.min(n), max.max(n)),
+    }
+}
@@ -1,27 +1,132 @@
+mod interrogator_builder;
+pub use interrogator_builder::InterrogatorBuilder;
+
+#[cfg(feature = "async")]
+use crate::AsyncResolver;
 use crate::{
+    backend::ValueBackend,
+    cancel::CancellationToken,
+    content::Contents,
+    coverage::{Recorder, SchemaCoverage},
+    deserializer::{Deserializers, JSON_MEDIA_TYPE},
+    dialect::KeywordOrder,
     draft::HYPER_SCHEMA_2020_12_URI,
-    error::{UnidentifiedSchemaError, UnknownMetaSchema},
-    Error, Graph, MetaSchema, Schema, Vocabulary,
+    error::{
+        CompileAllError, CompileFailure, FeatureDisabledError, ImportConflictError,
+        InvalidAnchorError, ReferenceDepthError, SchemaInUseError, UnidentifiedSchemaError,
+        UnknownMetaSchema, UnknownSchemaError, UnknownVocabularyError, UnsupportedDialectError,
+    },
+    explain::Explanation,
+    format::Formats,
+    fragment::{self, AnchorGrammar, DuplicateAnchorPolicy},
+    lint::{self, Diagnostic, Lint},
+    mirror::Mirrors,
+    namespace::{self, Namespace},
+    number,
+    pattern::{Patterns, RegexEngine},
+    plugin::Registrar,
+    redact::{self, RedactOptions},
+    report::Report,
+    rewrite::{self, RewriteReport},
+    schema::CompiledKeyword,
+    source::{self, Provenance, SourceEntry},
+    urn, Dialect, Error, Evaluation, Feature, Graph, MetaSchema, Numbers, OutputFmt, Precision,
+    ResolveError, Resolver, Schema, Sources, Vocabulary,
 };
 use dashmap::DashMap;
-use parking_lot::{Mutex, RwLock};
+use jsonptr::Pointer;
+use parking_lot::{MappedRwLockWriteGuard, Mutex, RwLock, RwLockWriteGuard};
+use serde::Serialize;
+use serde_json::Value;
 use std::{
+    borrow::Cow,
     collections::{HashMap, HashSet},
     fmt::Debug,
+    io::Read,
     sync::Arc,
 };
 use uniresid::{AbsoluteUri, Uri};
 
+/// The default maximum length of a chain of references between documents;
+/// see [`Interrogator::set_max_reference_depth`].
+pub const DEFAULT_MAX_REFERENCE_DEPTH: usize = 32;
+
+/// The source documents and cached numbers dropped by
+/// [`Interrogator::gc`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// The URIs of the source documents dropped, in lexical order.
+    pub sources: Vec<AbsoluteUri>,
+    /// The number of cached arbitrary-precision numbers dropped.
+    pub numbers: usize,
+    /// The URIs of the lazily inserted source documents which could not be
+    /// parsed and were therefore retained, in lexical order.
+    pub unparsed: Vec<AbsoluteUri>,
+}
+
 /// Centeral hub to manage [`Schema`] and [`Applicator`] instances.
 #[derive(Clone)]
 pub struct Interrogator {
-    schemas: Arc<RwLock<Schemas>>,
-    meta_schemas: Arc<RwLock<MetaSchemas>>,
-    graph: Arc<RwLock<Graph>>,
+    schemas: Arc<RwLock<Arc<Schemas>>>,
+    meta_schemas: Arc<RwLock<Arc<MetaSchemas>>>,
+    graph: Arc<RwLock<Arc<Graph>>>,
     base_uri: Arc<RwLock<Option<Arc<AbsoluteUri>>>>,
     vocabularies: Arc<DashMap<String, Vocabulary>>,
     lock: Arc<Mutex<()>>,
     default_meta_schema_uri: Arc<RwLock<Uri>>,
+    numbers: Numbers,
+    patterns: Patterns,
+    regex_engine: Arc<RwLock<RegexEngine>>,
+    precision: Arc<RwLock<Precision>>,
+    sources: Sources,
+    diagnostics: Arc<RwLock<Vec<Diagnostic>>>,
+    dialects: Arc<DashMap<Uri, Arc<Dialect>>>,
+    normalize_identifiers: Arc<RwLock<bool>>,
+    lenient_fragments: Arc<RwLock<bool>>,
+    optimize: Arc<RwLock<bool>>,
+    validate_examples: Arc<RwLock<bool>>,
+    duplicate_anchor_policy: Arc<RwLock<DuplicateAnchorPolicy>>,
+    max_reference_depth: Arc<RwLock<Option<usize>>>,
+    mirrors: Arc<RwLock<Arc<Mirrors>>>,
+    coverage: Recorder,
+    namespaces: Arc<RwLock<Arc<namespace::Registry>>>,
+    formats: Formats,
+    contents: Contents,
+    deserializers: Deserializers,
+    resolvers: Arc<RwLock<Arc<Vec<Arc<dyn Resolver>>>>>,
+    #[cfg(feature = "async")]
+    async_resolvers: Arc<RwLock<Arc<Vec<Arc<dyn AsyncResolver>>>>>,
+    #[cfg(feature = "vendor")]
+    locked_resolver: Arc<RwLock<Option<Arc<crate::vendor::LockedResolver>>>>,
+}
+
+/// Returns the document, without fragment, of the id of `schema` and of each
+/// schema resource embedded within its source.
+fn resource_documents(schema: &Schema) -> Vec<String> {
+    let id = match schema.id() {
+        Some(id) => id,
+        None => return Vec::new(),
+    };
+    let mut documents = vec![namespace::document_of(&id)];
+    documents.extend(
+        fragment::resources(&schema.source(), &id)
+            .into_iter()
+            .map(|(document, _)| document),
+    );
+    documents
+}
+
+/// Returns the id of the meta schema `document` retrieved from `uri`: its
+/// `"$id"`, resolved against `uri`, or `uri` if it does not declare one.
+fn meta_schema_id(uri: &AbsoluteUri, document: &Value) -> Uri {
+    let declared = document
+        .get("$id")
+        .and_then(Value::as_str)
+        .and_then(|id| Uri::parse(id).ok())
+        .and_then(|id| AbsoluteUri::parse(&uri.uri().resolve(&id).to_string()).ok());
+    source::without_fragment(declared.as_ref().unwrap_or(uri))
+        .uri()
+        .clone()
 }
 
 impl Debug for Interrogator {
@@ -35,17 +140,397 @@ impl Debug for Interrogator {
 }
 
 impl Interrogator {
-    /// Creates
+    /// Creates a new `Interrogator` with the [`Dialect`] of each draft
+    /// enabled by its cargo feature registered. See [`draft`](crate::draft).
     pub fn new() -> Self {
-        Self {
-            schemas: Arc::new(RwLock::new(Schemas::new())),
-            meta_schemas: Arc::new(RwLock::new(MetaSchemas::new())),
-            graph: Arc::new(RwLock::new(Graph::new(&[]).unwrap())),
+        let interrogator = Self {
+            schemas: Arc::new(RwLock::new(Arc::new(Schemas::new()))),
+            meta_schemas: Arc::new(RwLock::new(Arc::new(MetaSchemas::new()))),
+            graph: Arc::new(RwLock::new(Arc::new(Graph::new(&[]).unwrap()))),
             base_uri: Arc::new(RwLock::new(None)),
             lock: Arc::new(Mutex::new(())),
             vocabularies: Arc::new(DashMap::new()),
             default_meta_schema_uri: Arc::new(RwLock::new(HYPER_SCHEMA_2020_12_URI.clone())),
+            numbers: Numbers::new(),
+            patterns: Patterns::new(),
+            regex_engine: Arc::new(RwLock::new(RegexEngine::default())),
+            precision: Arc::new(RwLock::new(Precision::default())),
+            sources: Sources::new(),
+            diagnostics: Arc::new(RwLock::new(Vec::new())),
+            dialects: Arc::new(DashMap::new()),
+            normalize_identifiers: Arc::new(RwLock::new(false)),
+            lenient_fragments: Arc::new(RwLock::new(false)),
+            optimize: Arc::new(RwLock::new(false)),
+            validate_examples: Arc::new(RwLock::new(false)),
+            duplicate_anchor_policy: Arc::new(RwLock::new(DuplicateAnchorPolicy::default())),
+            max_reference_depth: Arc::new(RwLock::new(Some(DEFAULT_MAX_REFERENCE_DEPTH))),
+            mirrors: Arc::new(RwLock::new(Arc::new(Mirrors::new()))),
+            coverage: Recorder::default(),
+            namespaces: Arc::new(RwLock::new(Arc::new(namespace::Registry::default()))),
+            formats: Formats::standard(),
+            contents: Contents::standard(),
+            deserializers: Deserializers::standard(),
+            resolvers: Arc::new(RwLock::new(Arc::new(Vec::new()))),
+            #[cfg(feature = "async")]
+            async_resolvers: Arc::new(RwLock::new(Arc::new(Vec::new()))),
+            #[cfg(feature = "vendor")]
+            locked_resolver: Arc::new(RwLock::new(None)),
+        };
+        interrogator.register_drafts();
+        interrogator
+    }
+
+    /// Registers the [`Dialect`] of each draft enabled by its cargo feature
+    /// for the URIs of its schema and hyper schema.
+    fn register_drafts(&self) {
+        let drafts: Vec<([&Uri; 2], Dialect)> = vec![
+            #[cfg(feature = "draft-2020-12")]
+            (
+                [
+                    &*crate::draft::SCHEMA_2020_12_URI,
+                    &*HYPER_SCHEMA_2020_12_URI,
+                ],
+                crate::draft::dialect_2020_12(),
+            ),
+            #[cfg(feature = "draft-2019-09")]
+            (
+                [
+                    &*crate::draft::SCHEMA_2019_09_URI,
+                    &*crate::draft::HYPER_SCHEMA_2019_09_URI,
+                ],
+                crate::draft::dialect_2019_09(),
+            ),
+            #[cfg(feature = "draft-07")]
+            (
+                [
+                    &*crate::draft::SCHEMA_07_URI,
+                    &*crate::draft::HYPER_SCHEMA_07_URI,
+                ],
+                crate::draft::dialect_07(),
+            ),
+            #[cfg(feature = "draft-04")]
+            (
+                [
+                    &*crate::draft::SCHEMA_04_URI,
+                    &*crate::draft::HYPER_SCHEMA_04_URI,
+                ],
+                crate::draft::dialect_04(),
+            ),
+        ];
+        for (ids, dialect) in drafts {
+            let dialect = Arc::new(dialect);
+            for id in ids {
+                self.dialects.insert(id.clone(), dialect.clone());
+            }
+        }
+    }
+
+    /// Returns an independent copy of this `Interrogator`.
+    ///
+    /// Cloning an `Interrogator` yields a handle to the same instance;
+    /// `fork` instead yields a new instance which begins with the same
+    /// schemas, sources, and settings. Forking is O(1): the compiled state,
+    /// the sources, the namespaces, and the registries of formats, content
+    /// decoders, deserializers, resolvers, and mirrors are shared until either
+    /// instance next modifies one, at which point that instance copies the
+    /// index it is modifying. Neither schemas nor documents are copied. This
+    /// allows a background task to compile new schemas into a fork while the
+    /// original continues to serve requests, swapping the fork in once it is
+    /// ready.
+    ///
+    /// The [`Schema`]s present at the time of the fork are shared by both
+    /// instances. Inserting a schema into the fork which is referenced by, or
+    /// references, a shared `Schema` sets up the shared `Schema` again.
+    ///
+    /// The [`Numbers`] cache, the recorded [coverage](crate::coverage), and
+    /// the [`Vocabulary`]s and [`Dialect`]s registered by plugins are shared.
+    /// The fork begins with no [`Diagnostic`]s.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let fork = interrogator.fork();
+    /// let uri = AbsoluteUri::parse("https://example.com/schema.json").unwrap();
+    /// fork.source_value(uri.clone(), json!({ "type": "string" }));
+    /// assert!(fork.source(&uri).unwrap().is_some());
+    /// assert!(interrogator.source(&uri).unwrap().is_none());
+    /// ```
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            schemas: Arc::new(RwLock::new(self.schemas.read().clone())),
+            meta_schemas: Arc::new(RwLock::new(self.meta_schemas.read().clone())),
+            graph: Arc::new(RwLock::new(self.graph.read().clone())),
+            base_uri: Arc::new(RwLock::new(self.base_uri())),
+            vocabularies: self.vocabularies.clone(),
+            lock: Arc::new(Mutex::new(())),
+            default_meta_schema_uri: Arc::new(RwLock::new(
+                self.default_meta_schema_uri.read().clone(),
+            )),
+            numbers: self.numbers.clone(),
+            patterns: self.patterns.clone(),
+            regex_engine: Arc::new(RwLock::new(self.regex_engine())),
+            precision: Arc::new(RwLock::new(self.precision())),
+            sources: self.sources.fork(),
+            diagnostics: Arc::new(RwLock::new(Vec::new())),
+            dialects: self.dialects.clone(),
+            normalize_identifiers: Arc::new(RwLock::new(self.normalizes_identifiers())),
+            lenient_fragments: Arc::new(RwLock::new(self.lenient_fragments())),
+            optimize: Arc::new(RwLock::new(self.optimizes())),
+            validate_examples: Arc::new(RwLock::new(self.validates_examples())),
+            duplicate_anchor_policy: Arc::new(RwLock::new(self.duplicate_anchor_policy())),
+            max_reference_depth: Arc::new(RwLock::new(self.max_reference_depth())),
+            mirrors: Arc::new(RwLock::new(self.mirrors.read().clone())),
+            coverage: self.coverage.clone(),
+            namespaces: Arc::new(RwLock::new(self.namespace_registry())),
+            formats: self.formats.fork(),
+            contents: self.contents.fork(),
+            deserializers: self.deserializers.fork(),
+            resolvers: Arc::new(RwLock::new(self.resolvers.read().clone())),
+            #[cfg(feature = "async")]
+            async_resolvers: Arc::new(RwLock::new(self.async_resolvers.read().clone())),
+            #[cfg(feature = "vendor")]
+            locked_resolver: Arc::new(RwLock::new(self.locked_resolver())),
+        }
+    }
+
+    fn schemas_mut(&self) -> MappedRwLockWriteGuard<'_, Schemas> {
+        RwLockWriteGuard::map(self.schemas.write(), Arc::make_mut)
+    }
+
+    fn graph_mut(&self) -> MappedRwLockWriteGuard<'_, Graph> {
+        RwLockWriteGuard::map(self.graph.write(), Arc::make_mut)
+    }
+
+    fn mirrors_mut(&self) -> MappedRwLockWriteGuard<'_, Mirrors> {
+        RwLockWriteGuard::map(self.mirrors.write(), Arc::make_mut)
+    }
+
+    /// Returns an [`InterrogatorBuilder`] with which to configure an
+    /// `Interrogator` fluently: its plugins, dialect, sources, resolvers,
+    /// and the schemas to compile once it is built.
+    pub fn builder() -> InterrogatorBuilder {
+        InterrogatorBuilder::new()
+    }
+
+    /// Returns a handle to the [`Namespace`] named `name`, which isolates the
+    /// schemas and sources added through it from those of other namespaces.
+    /// See [`namespace`](crate::namespace).
+    pub fn namespace(&self, name: impl Into<String>) -> Namespace {
+        let name: String = name.into();
+        Namespace::new(Arc::from(name), self.clone())
+    }
+
+    pub(crate) fn namespace_registry(&self) -> Arc<namespace::Registry> {
+        self.namespaces.read().clone()
+    }
+
+    pub(crate) fn namespace_registry_mut(&self) -> MappedRwLockWriteGuard<'_, namespace::Registry> {
+        RwLockWriteGuard::map(self.namespaces.write(), Arc::make_mut)
+    }
+
+    /// Removes each top-level [`Schema`] and source document whose URI,
+    /// without its fragment, is among `documents`.
+    pub(crate) fn remove_documents(&self, documents: &[String]) {
+        #[allow(unused_variables)]
+        let g = self.lock.lock();
+        let values = {
+            let mut schemas = self.schemas_mut();
+            schemas.remove_where(|id| documents.contains(&namespace::document_of(id)));
+            schemas.values()
+        };
+        for document in documents {
+            if let Ok(uri) = AbsoluteUri::parse(document) {
+                self.sources.remove(&uri);
+            }
+        }
+        let mut graph = self.graph_mut();
+        graph.rebuild(&values).expect("Rebuilding the graph failed which is a bug. Please report this to https://github.com/chanced/grill/issues");
+    }
+
+    /// Returns the [`Dialect`] registered for the meta schema `id`, if one
+    /// exists.
+    ///
+    /// URIs with and without an empty fragment, e.g.
+    /// `"http://json-schema.org/draft-07/schema#"` and
+    /// `"http://json-schema.org/draft-07/schema"`, identify the same meta
+    /// schema.
+    pub fn dialect(&self, id: &Uri) -> Option<Arc<Dialect>> {
+        if let Some(dialect) = self.dialects.get(id) {
+            return Some(dialect.clone());
+        }
+        let id = id.to_string();
+        let equivalent = match id.strip_suffix('#') {
+            Some(id) => id.to_string(),
+            None if !id.contains('#') => format!("{id}#"),
+            None => return None,
+        };
+        let equivalent = Uri::parse(&equivalent).ok()?;
+        self.dialects.get(&equivalent).map(|d| d.clone())
+    }
+
+    /// Returns `true` if a [`MetaSchema`] or a [`Dialect`] is registered for
+    /// the meta schema `id`, in which case it is not resolved as a
+    /// dependency of the schemas which declare it.
+    fn is_known_meta_schema(&self, id: &Uri) -> bool {
+        self.meta_schema(id).is_some() || self.dialect(id).is_some()
+    }
+
+    /// Returns `true` if the resource `uri`, which has no fragment, is not a
+    /// source document but is embedded within one, e.g. a subschema
+    /// identified by a URN, in which case it is not resolved as a dependency.
+    fn is_embedded(&self, uri: &AbsoluteUri) -> bool {
+        !self.sources.contains(uri)
+            && matches!(self.sources.resource(&uri.to_string()), Ok(Some(_)))
+    }
+
+    /// Constructs, registers, and returns a [`Dialect`] for the meta schema
+    /// at `id` from the vocabularies its source declares with
+    /// `"$vocabulary"`, so that a meta schema can be defined in JSON alone.
+    /// The `Dialect` evaluates keywords in the
+    /// [`standard`](KeywordOrder::standard) order.
+    ///
+    /// Each declared vocabulary must be registered, either with
+    /// [`Registrar::vocabulary`](crate::plugin::Registrar::vocabulary) or as
+    /// part of a registered `Dialect`; unknown vocabularies declared as
+    /// optional, i.e. `false`, are ignored. Meta schemas among the documents
+    /// compiled by [`compile_all`](Self::compile_all) are constructed this
+    /// way when first encountered, and removed again if the batch fails.
+    ///
+    /// The `Dialect` is registered under the `"$id"` the meta schema
+    /// declares, resolved against `id`, rather than the URI it was
+    /// retrieved from, as that is the URI schemas name in `"$schema"`.
+    ///
+    /// Returns the `Dialect` already registered for `id`, if any, and `None`
+    /// if the source at `id` does not exist or does not declare
+    /// `"$vocabulary"`.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{keyword::format_range_vocabulary, uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::builder()
+    ///     .plugin(|registrar| {
+    ///         registrar.vocabulary(format_range_vocabulary());
+    ///         Ok(())
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// let id = AbsoluteUri::parse("https://example.com/meta/ranges").unwrap();
+    /// interrogator.source_value(id.clone(), json!({
+    ///     "$id": "https://example.com/meta/ranges",
+    ///     "$vocabulary": {
+    ///         "https://github.com/chanced/grill/vocab/format-range": true,
+    ///         "https://example.com/vocab/unknown": false
+    ///     }
+    /// }));
+    /// let dialect = interrogator.vocabulary_dialect(&id).unwrap().unwrap();
+    /// assert_eq!(dialect.vocabularies.len(), 1);
+    /// assert!(interrogator.dialect(id.uri()).is_some());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`UnknownVocabularyError`] if a vocabulary declared as
+    /// required is not registered and [`Error::InvalidUri`] if a declared
+    /// vocabulary is not a URI.
+    pub fn vocabulary_dialect(&self, id: &AbsoluteUri) -> Result<Option<Arc<Dialect>>, Error> {
+        if let Some(dialect) = self.dialect(id.uri()) {
+            return Ok(Some(dialect));
+        }
+        match self.sources.get(id)? {
+            Some(document) => self.construct_dialect(id, &document, &mut Vec::new()),
+            None => Ok(None),
+        }
+    }
+
+    /// Constructs and registers the [`Dialect`] of the meta schema `document`
+    /// retrieved from `uri` from its `"$vocabulary"`, unless one is
+    /// registered, keyed by the `"$id"` of `document`. The id of a `Dialect`
+    /// which is registered is pushed to `added`.
+    fn construct_dialect(
+        &self,
+        uri: &AbsoluteUri,
+        document: &Value,
+        added: &mut Vec<Uri>,
+    ) -> Result<Option<Arc<Dialect>>, Error> {
+        let declared = match document.get("$vocabulary").and_then(Value::as_object) {
+            Some(declared) => declared,
+            None => return Ok(None),
+        };
+        let id = meta_schema_id(uri, document);
+        if let Some(dialect) = self.dialect(&id) {
+            return Ok(Some(dialect));
+        }
+        let mut vocabularies = HashMap::with_capacity(declared.len());
+        for (vocabulary, required) in declared {
+            let required = !matches!(required, Value::Bool(false));
+            match self.vocabulary(&Uri::parse(vocabulary)?) {
+                Some(vocabulary) => {
+                    vocabularies.insert(vocabulary, required);
+                }
+                None if required => {
+                    return Err(UnknownVocabularyError {
+                        meta_schema: id.clone(),
+                        vocabulary: vocabulary.clone(),
+                    }
+                    .into())
+                }
+                None => {}
+            }
+        }
+        let dialect = Arc::new(Dialect::new(vocabularies).with_order(KeywordOrder::standard()));
+        self.dialects.insert(id.clone(), dialect.clone());
+        added.push(id);
+        Ok(Some(dialect))
+    }
+
+    /// Returns the [`Vocabulary`] identified by `id`, whether registered on
+    /// its own or as part of a [`Dialect`].
+    fn vocabulary(&self, id: &Uri) -> Option<Vocabulary> {
+        if let Some(vocabulary) = self.vocabularies.get(&id.to_string()) {
+            return Some(vocabulary.clone());
         }
+        self.dialects.iter().find_map(|dialect| {
+            dialect
+                .vocabularies
+                .keys()
+                .find(|vocabulary| vocabulary.id == *id)
+                .cloned()
+        })
+    }
+
+    fn register(&self, registrar: Registrar) -> Result<(), Error> {
+        for (uri, source) in registrar.sources {
+            self.sources.insert_from(uri, source, Provenance::Plugin);
+        }
+        for vocabulary in registrar.vocabularies {
+            self.vocabularies
+                .insert(vocabulary.id.to_string(), vocabulary);
+        }
+        for (id, dialect) in registrar.dialects {
+            self.dialects.insert(id, Arc::new(dialect));
+        }
+        for (base, id, vocabulary) in registrar.extensions {
+            let mut dialect = match self.dialect(&base) {
+                Some(dialect) => Dialect::clone(&dialect),
+                None => {
+                    return Err(UnsupportedDialectError {
+                        requested: base.to_string(),
+                        supported: self.dialects.iter().map(|d| d.key().clone()).collect(),
+                    }
+                    .into())
+                }
+            };
+            self.vocabularies
+                .insert(vocabulary.id.to_string(), vocabulary.clone());
+            dialect.vocabularies.insert(vocabulary, true);
+            self.dialects.insert(id, Arc::new(dialect));
+        }
+        Ok(())
     }
 
     /// Returns the `Schema` with the given `id` if it exists.
@@ -54,6 +539,459 @@ impl Interrogator {
         r.get(id)
     }
 
+    /// Returns the URIs referenced directly by the `Schema` identified by
+    /// `key`, e.g. by `"$ref"`.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if no `Schema` is identified by
+    /// `key`.
+    pub fn direct_references(&self, key: &Uri) -> Result<impl Iterator<Item = Uri>, Error> {
+        self.traverse(key, Graph::direct_references)
+    }
+
+    /// Returns the ids of the top-level `Schema`s which reference the
+    /// `Schema` identified by `key`, directly or transitively, nearest
+    /// first. These are the schemas affected when its source changes.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if no `Schema` is identified by
+    /// `key`.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Interrogator, Uri};
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let key = Uri::parse("https://example.com/unknown.json").unwrap();
+    /// let result = interrogator.transitive_dependents(&key);
+    /// assert!(matches!(result, Err(err) if err.is_unknown_schema()));
+    /// ```
+    pub fn transitive_dependents(&self, key: &Uri) -> Result<impl Iterator<Item = Uri>, Error> {
+        self.traverse(key, Graph::transitive_dependents)
+    }
+
+    /// Returns the ids of the top-level `Schema`s which contain the `Schema`
+    /// identified by `key` within their document, nearest first.
+    ///
+    /// Containment is determined by location within the source document:
+    /// the `Schema` `"https://example.com/a.json#/$defs/b"` is contained by
+    /// `"https://example.com/a.json"`, as is a resource embedded at
+    /// `"/$defs/c"` with its own `"$id"`. Schemas identified by an anchor are
+    /// neither ancestors nor descendants of others.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if no `Schema` is identified by
+    /// `key`.
+    pub fn ancestors(&self, key: &Uri) -> Result<impl Iterator<Item = Uri>, Error> {
+        self.traverse(key, |graph, id| {
+            graph.ancestors(id, &|id| self.resource_location(id))
+        })
+    }
+
+    /// Returns the ids of the top-level `Schema`s contained within the
+    /// document of the `Schema` identified by `key`, ordered by location.
+    /// See [`ancestors`](Self::ancestors).
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if no `Schema` is identified by
+    /// `key`.
+    pub fn descendants(&self, key: &Uri) -> Result<impl Iterator<Item = Uri>, Error> {
+        self.traverse(key, |graph, id| {
+            graph.descendants(id, &|id| self.resource_location(id))
+        })
+    }
+
+    fn traverse(
+        &self,
+        key: &Uri,
+        f: impl FnOnce(&Graph, &Uri) -> Vec<Uri>,
+    ) -> Result<std::vec::IntoIter<Uri>, Error> {
+        let schema = self
+            .schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+        let id = schema
+            .id()
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(|| key.clone());
+        let graph = self.graph.read();
+        Ok(f(&graph, &id).into_iter())
+    }
+
+    /// Returns the source document containing the schema resource `id`, which
+    /// has no fragment, and the location of the resource within it.
+    fn resource_location(&self, id: &str) -> Option<(String, Pointer)> {
+        let (uri, location) = self.sources.locate(id).ok()??;
+        Some((uri.to_string(), location))
+    }
+
+    /// Returns the keywords compiled into the `Schema` identified by `key`,
+    /// and its subschemas, in the order they are applied. See
+    /// [`Schema::keywords`].
+    ///
+    /// Returns `None` if no `Schema` is identified by `key`.
+    pub fn keywords_of(&self, key: &Uri) -> Option<Vec<CompiledKeyword>> {
+        self.schema(key).map(|schema| schema.keywords())
+    }
+
+    /// Eagerly computes the artifacts which are otherwise built lazily on the
+    /// first evaluation of each `Schema` identified by `keys`, and of the
+    /// schemas they or their subschemas reference, directly or transitively.
+    ///
+    /// `warm` determines whether each `Schema` and subschema is pure, i.e.
+    /// whether its results can be cached, and, if the [`Precision`] is
+    /// [`Arbitrary`](Precision::Arbitrary), parses the numbers of their
+    /// sources, other than those of instance data such as `"examples"`, into
+    /// the [`Numbers`] cache.
+    ///
+    /// Everything else is built when a `Schema` is setup rather than by
+    /// `warm`: the order of its keywords, the dispatch tables of its
+    /// applicators, e.g. that of `"oneOf"`, and its regular expressions, which
+    /// are compiled into the [`Patterns`] cache. Caches which are keyed by
+    /// instance, such as the [`ResultCache`](crate::evaluation::ResultCache),
+    /// are not warmed.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if any of `keys` does not identify a
+    /// `Schema`.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Interrogator, Uri};
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let key = Uri::parse("https://example.com/unknown.json").unwrap();
+    /// let result = interrogator.warm([&key]);
+    /// assert!(matches!(result, Err(err) if err.is_unknown_schema()));
+    /// ```
+    pub fn warm<'a>(&self, keys: impl IntoIterator<Item = &'a Uri>) -> Result<(), Error> {
+        let mut queue = Vec::new();
+        for key in keys {
+            let schema = self
+                .schema(key)
+                .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+            queue.push(schema);
+        }
+        let mut warmed = HashSet::new();
+        while let Some(schema) = queue.pop() {
+            if !warmed.insert(schema.key()) {
+                continue;
+            }
+            schema.warm();
+            #[cfg(feature = "big_num")]
+            if self.precision() == Precision::Arbitrary {
+                self.numbers.parse_all(&schema.source());
+            }
+            for reference in schema.references_within() {
+                if let Some(referenced) = self.schema(&reference) {
+                    queue.push(referenced);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the [`SchemaCoverage`] of each top-level [`Schema`]: the
+    /// number of times each of its compiled keywords has been applied by
+    /// evaluations with [`Options::coverage`](crate::evaluation::Options::coverage)
+    /// enabled since the last call to [`reset_coverage`](Self::reset_coverage).
+    pub fn coverage(&self) -> Vec<SchemaCoverage> {
+        self.schemas()
+            .iter()
+            .map(|schema| SchemaCoverage::of(schema, &self.coverage))
+            .collect()
+    }
+
+    /// Clears the recorded coverage of each [`Schema`].
+    pub fn reset_coverage(&self) {
+        self.coverage.reset();
+    }
+
+    pub(crate) fn coverage_recorder(&self) -> &Recorder {
+        &self.coverage
+    }
+
+    /// Evaluates a serializable Rust `value` against the `Schema` identified
+    /// by `key`. See [`Schema::evaluate_serializable`].
+    pub fn evaluate_serializable<T: Serialize + ?Sized>(
+        &self,
+        key: &Uri,
+        value: &T,
+        output: OutputFmt,
+    ) -> Result<Evaluation, Error> {
+        self.schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?
+            .evaluate_serializable(value, output)
+    }
+
+    /// Evaluates `value` against the `Schema` identified by `key`, returning
+    /// a [`Report`] which owns the fragments of `value` it refers to. See
+    /// [`Schema::evaluate_owned`].
+    pub fn evaluate_owned(&self, key: &Uri, value: Cow<'_, Value>) -> Result<Report, Error> {
+        self.schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?
+            .evaluate_owned(value)
+    }
+
+    /// Evaluates `value` against the `Schema` identified by `key`, returning
+    /// verbose output only for the instance subtree at `instance_pointer`.
+    /// See [`Schema::evaluate_with_focus`].
+    pub fn evaluate_with_focus(
+        &self,
+        key: &Uri,
+        value: &Value,
+        instance_pointer: &Pointer,
+    ) -> Result<Evaluation, Error> {
+        self.schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?
+            .evaluate_with_focus(value, instance_pointer)
+    }
+
+    /// Evaluates `value` against the `Schema` identified by `key` and
+    /// returns an [`Explanation`] of why it passed or which constraints
+    /// were checked. See [`explain`](crate::explain).
+    ///
+    /// The keywords compiled into the `Schema` and into the schemas it
+    /// references, directly or transitively, are explained as branches.
+    pub fn explain(&self, key: &Uri, value: &Value) -> Result<Explanation, Error> {
+        let schema = self
+            .schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+        let eval = schema.evaluate(value, OutputFmt::Verbose)?;
+        let mut keywords = Vec::new();
+        let mut queue = vec![schema];
+        let mut visited = HashSet::new();
+        while let Some(schema) = queue.pop() {
+            if !visited.insert(schema.key()) {
+                continue;
+            }
+            keywords.extend(schema.keywords());
+            queue.extend(
+                schema
+                    .references_within()
+                    .iter()
+                    .filter_map(|reference| self.schema(reference)),
+            );
+        }
+        Ok(Explanation::new(&eval, &keywords))
+    }
+
+    /// Returns a sanitized copy of `instance` as described by the `Schema`
+    /// identified by `key`. See [`redact`](crate::redact).
+    ///
+    /// # Errors
+    /// Returns [`UnknownSchemaError`] if no `Schema` is identified by `key`,
+    /// or an [`Error`] if a referenced source could not be located.
+    pub fn redact(
+        &self,
+        key: &Uri,
+        instance: &Value,
+        options: &RedactOptions,
+    ) -> Result<Value, Error> {
+        let schema = self
+            .schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+        redact::redact(self, &schema, instance, options)
+    }
+
+    /// Returns each top-level [`Schema`] of this `Interrogator`.
+    pub fn schemas(&self) -> Vec<Schema> {
+        self.schemas.read().current.values().cloned().collect()
+    }
+
+    /// Removes the top-level [`Schema`] identified by `key`, returning each
+    /// `Schema` removed.
+    ///
+    /// Each other top-level `Schema` identified within the document of a
+    /// removed `Schema`, e.g. by a fragment or an embedded `"$id"`, is removed
+    /// along with it. If other schemas reference those removed, directly or
+    /// transitively, they are removed as well when `cascade` is `true`;
+    /// otherwise nothing is removed.
+    ///
+    /// The coverage recorded for the removed schemas is dropped, as is the
+    /// claim of a [`Namespace`] on their documents unless the documents
+    /// remain as sources. Results cached for them, by a
+    /// [`ResultCache`](crate::evaluation::ResultCache) or by memoization,
+    /// are never reused, as each `Schema` is cached under a key which no other
+    /// `Schema` is given. Source documents are retained; use
+    /// [`gc`](Self::gc) to drop those which are no longer reachable.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnknownSchema`] if no `Schema` is identified by
+    /// `key`, or [`Error::SchemaInUse`] if it is referenced and `cascade` is
+    /// `false`.
+    pub fn remove_schema(&self, key: &Uri, cascade: bool) -> Result<Vec<Schema>, Error> {
+        #[allow(unused_variables)]
+        let g = self.lock.lock();
+        let schema = self
+            .schema(key)
+            .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+        let id = schema
+            .id()
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(|| key.clone());
+        let all = self.schemas.read().values();
+        let mut ids: HashSet<Uri> = HashSet::from([id.clone()]);
+        let mut documents: HashSet<String> = HashSet::new();
+        loop {
+            for schema in all
+                .iter()
+                .filter(|s| s.id().map_or(false, |i| ids.contains(i.as_ref())))
+            {
+                documents.extend(resource_documents(schema));
+            }
+            let siblings = all
+                .iter()
+                .filter_map(|s| s.id())
+                .filter(|i| documents.contains(&namespace::document_of(i)));
+            ids.extend(siblings.map(|i| i.as_ref().clone()));
+            let graph = self.graph.read();
+            let mut found: Vec<Uri> = ids
+                .iter()
+                .flat_map(|id| graph.transitive_dependents(id))
+                .filter(|dependent| !ids.contains(dependent))
+                .collect();
+            drop(graph);
+            if found.is_empty() {
+                break;
+            }
+            if !cascade {
+                found.sort_by_key(ToString::to_string);
+                found.dedup();
+                return Err(SchemaInUseError {
+                    id,
+                    dependents: found,
+                }
+                .into());
+            }
+            ids.extend(found);
+        }
+        let (removed, values) = {
+            let mut schemas = self.schemas_mut();
+            let removed: Vec<Schema> = ids.iter().filter_map(|id| schemas.get(id)).collect();
+            schemas.remove_where(|id| ids.contains(id));
+            (removed, schemas.values())
+        };
+        let mut graph = self.graph_mut();
+        graph.rebuild(&values).expect("Rebuilding the graph failed which is a bug. Please report this to https://github.com/chanced/grill/issues");
+        drop(graph);
+
+        let mut removed_ids = HashSet::new();
+        for schema in &removed {
+            schema.visit_keywords(&mut |owner, _| {
+                if let Some(id) = owner.id() {
+                    removed_ids.insert((*id).clone());
+                }
+            });
+        }
+        self.coverage.forget(&removed_ids);
+        let mut registry = self.namespace_registry_mut();
+        for document in &documents {
+            let retained =
+                AbsoluteUri::parse(document).map_or(false, |uri| self.sources.contains(&uri));
+            if !retained {
+                registry.release(document);
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Drops each source document, and each cached arbitrary-precision
+    /// number, which is no longer reachable from a compiled [`Schema`] or
+    /// [`MetaSchema`].
+    ///
+    /// A document is reachable if it contains a compiled schema or meta
+    /// schema, including as an embedded resource, or if it is referenced
+    /// by one or by another reachable document. A number is reachable if it
+    /// appears within a reachable document.
+    ///
+    /// A lazily inserted source which fails to parse can not be walked, so it
+    /// is retained, along with the numbers it may contain, and reported in
+    /// [`GcReport::unparsed`] rather than failing the collection.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let uri = AbsoluteUri::parse("https://example.com/unused.json").unwrap();
+    /// interrogator.source_value(uri.clone(), json!({ "maximum": 10 }));
+    ///
+    /// let report = interrogator.gc().unwrap();
+    /// assert_eq!(report.sources, vec![uri.clone()]);
+    /// assert!(interrogator.source(&uri).unwrap().is_none());
+    /// ```
+    pub fn gc(&self) -> Result<GcReport, Error> {
+        #[allow(unused_variables)]
+        let g = self.lock.lock();
+        let document_of =
+            |uri: &str| urn::normalize(uri.split('#').next().unwrap_or_default()).into_owned();
+        let mut queue: Vec<String> = Vec::new();
+        for schema in self.schemas.read().values() {
+            if let Some(id) = schema.id() {
+                queue.push(document_of(&id.to_string()));
+            }
+            if let Some(meta_schema_id) = schema.meta_schema_id() {
+                queue.push(document_of(&meta_schema_id.to_string()));
+            }
+            queue.extend(
+                schema
+                    .references()
+                    .iter()
+                    .map(|r| document_of(&r.to_string())),
+            );
+        }
+        queue.extend(
+            self.meta_schemas
+                .read()
+                .current
+                .keys()
+                .map(|id| document_of(&id.to_string())),
+        );
+
+        let (documents, unparsed) = self.sources.documents_lenient();
+        // the document containing each resource, including embedded ones
+        let mut containing: HashMap<String, usize> = HashMap::new();
+        for (i, (uri, document)) in documents.iter().enumerate() {
+            containing.insert(document_of(&uri.to_string()), i);
+            for (id, _) in fragment::resources(document, uri.uri()) {
+                containing.entry(document_of(&id)).or_insert(i);
+            }
+        }
+        let mut reached: HashSet<usize> = HashSet::new();
+        while let Some(next) = queue.pop() {
+            let i = match containing.get(&next) {
+                Some(i) => *i,
+                None => continue,
+            };
+            if !reached.insert(i) {
+                continue;
+            }
+            let (uri, document) = &documents[i];
+            for dependency in source::dependencies(uri, document) {
+                queue.push(document_of(&dependency.to_string()));
+            }
+        }
+
+        let mut report = GcReport::default();
+        let mut numbers = HashSet::new();
+        for (i, (uri, document)) in documents.iter().enumerate() {
+            if reached.contains(&i) {
+                number::collect(document, &mut numbers);
+            } else {
+                self.sources.remove(uri);
+                report.sources.push(uri.clone());
+            }
+        }
+        report.sources.sort_by_key(ToString::to_string);
+        report.unparsed = unparsed;
+        report.unparsed.sort_by_key(ToString::to_string);
+        report.numbers = self.numbers.retain(|n| numbers.contains(n));
+        Ok(report)
+    }
+
     pub fn meta_schema(&self, id: &Uri) -> Option<MetaSchema> {
         let r = self.meta_schemas.read();
         r.get(id)
@@ -72,8 +1010,12 @@ impl Interrogator {
     }
     /// Sets the default meta schema to use when no meta schema is specified,
     /// returning the previous default.
+    ///
+    /// # Errors
+    /// Returns [`UnknownMetaSchema`] if neither a [`MetaSchema`] nor a
+    /// [`Dialect`] is registered for `uri`.
     pub fn set_default_meta_schema(&self, uri: Uri) -> Result<Uri, UnknownMetaSchema> {
-        if self.meta_schema(&uri).is_none() {
+        if self.meta_schema(&uri).is_none() && self.dialect(&uri).is_none() {
             return Err(UnknownMetaSchema { uri });
         }
         let mut guard = self.default_meta_schema_uri.write();
@@ -81,13 +1023,958 @@ impl Interrogator {
         *guard = uri;
         Ok(old)
     }
-    /// Returns the default meta schema to use when no meta schema is specified.
+    /// Returns the URI of the meta schema of schemas which do not declare
+    /// one with `"$schema"`.
+    pub fn default_meta_schema_uri(&self) -> Uri {
+        self.default_meta_schema_uri.read().clone()
+    }
+
+    /// Returns the default meta schema to use when no meta schema is specified,
+    /// if a [`MetaSchema`] is registered for it.
     ///
     /// If not previously set, Draft 2020-12 will be the default.
-    pub fn default_meta_schema(&self) -> MetaSchema {
+    pub fn default_meta_schema(&self) -> Option<MetaSchema> {
         let r = self.default_meta_schema_uri.read();
+        self.meta_schema(&r)
+    }
+
+    /// Returns the [`Numbers`] cache shared by all [`Schema`]s of this
+    /// `Interrogator`.
+    pub fn numbers(&self) -> Numbers {
+        self.numbers.clone()
+    }
+
+    /// Returns the [`Patterns`] cache of the regular expressions of the
+    /// `"pattern"` and `"patternProperties"` keywords, shared by all
+    /// [`Schema`]s of this `Interrogator`.
+    pub fn patterns(&self) -> Patterns {
+        self.patterns.clone()
+    }
+
+    /// Returns the [`Formats`] registry consulted by the `"format"` keyword.
+    ///
+    /// The registry is shared by all clones of this `Interrogator`; formats
+    /// inserted into it are available to [`Schema`]s setup afterward.
+    pub fn formats(&self) -> Formats {
+        self.formats.clone()
+    }
+
+    /// Returns the [`Contents`] registry of decoders and parsers consulted
+    /// by the [`content`](crate::keyword::content) keyword.
+    ///
+    /// The registry is shared by all clones of this `Interrogator`; decoders
+    /// and parsers inserted into it are available to [`Schema`]s setup
+    /// afterward.
+    pub fn contents(&self) -> Contents {
+        self.contents.clone()
+    }
+
+    /// Returns the [`Deserializers`] registry with which source documents
+    /// are deserialized. See [`deserializer`](crate::deserializer).
+    ///
+    /// The registry is shared by all clones of this `Interrogator`;
+    /// deserializers inserted into it are used by sources added afterward.
+    pub fn deserializers(&self) -> Deserializers {
+        self.deserializers.clone()
+    }
+
+    /// Returns the [`Precision`] numeric keywords use to compare numbers.
+    ///
+    /// If not previously set, [`Precision::Float`] will be the default.
+    pub fn precision(&self) -> Precision {
+        *self.precision.read()
+    }
+
+    /// Sets the [`Precision`] numeric keywords use to compare numbers,
+    /// returning the previous value.
+    ///
+    /// [`Schema`]s which have already been setup are not affected until they
+    /// are setup again.
+    ///
+    /// If `precision` is [`Precision::Arbitrary`] and the `"big_num"` feature
+    /// is not enabled, a [`FeatureDisabledError`] is returned and the
+    /// precision is left unchanged.
+    pub fn set_precision(&self, precision: Precision) -> Result<Precision, FeatureDisabledError> {
+        if precision == Precision::Arbitrary && !Feature::BigNum.is_enabled() {
+            return Err(FeatureDisabledError {
+                feature: Feature::BigNum,
+                capability: "arbitrary-precision number comparison".to_string(),
+            });
+        }
+        let mut guard = self.precision.write();
+        Ok(std::mem::replace(&mut *guard, precision))
+    }
+
+    /// Returns the [`RegexEngine`] which compiles the regular expressions of
+    /// the `"pattern"` and `"patternProperties"` keywords.
+    ///
+    /// If not previously set, [`RegexEngine::Rust`] will be the default.
+    pub fn regex_engine(&self) -> RegexEngine {
+        *self.regex_engine.read()
+    }
+
+    /// Sets the [`RegexEngine`] which compiles the regular expressions of the
+    /// `"pattern"` and `"patternProperties"` keywords, returning the previous
+    /// value.
+    ///
+    /// [`Schema`]s which have already been setup are not affected until they
+    /// are setup again.
+    ///
+    /// If `engine` is [`RegexEngine::Ecma`] and the `"regress"` feature is
+    /// not enabled, a [`FeatureDisabledError`] is returned and the engine is
+    /// left unchanged.
+    pub fn set_regex_engine(
+        &self,
+        engine: RegexEngine,
+    ) -> Result<RegexEngine, FeatureDisabledError> {
+        if engine == RegexEngine::Ecma && !Feature::Regress.is_enabled() {
+            return Err(FeatureDisabledError {
+                feature: Feature::Regress,
+                capability: "ECMA-262 regular expressions".to_string(),
+            });
+        }
+        Ok(std::mem::replace(&mut *self.regex_engine.write(), engine))
+    }
+
+    /// Returns `true` if identifiers are converted to Unicode Normalization
+    /// Form C before [`Schema`]s are identified.
+    ///
+    /// See [`normalize`](crate::normalize) for more information.
+    pub fn normalizes_identifiers(&self) -> bool {
+        *self.normalize_identifiers.read()
+    }
+
+    /// Sets whether identifiers are converted to Unicode Normalization Form C
+    /// before [`Schema`]s are identified, returning the previous value.
+    ///
+    /// If `normalize` is `true` and the `"nfc"` feature is not enabled, a
+    /// [`FeatureDisabledError`] is returned and the setting is left
+    /// unchanged.
+    pub fn set_normalize_identifiers(&self, normalize: bool) -> Result<bool, FeatureDisabledError> {
+        if normalize && !Feature::Nfc.is_enabled() {
+            return Err(FeatureDisabledError {
+                feature: Feature::Nfc,
+                capability: "unicode normalization of identifiers".to_string(),
+            });
+        }
+        let mut guard = self.normalize_identifiers.write();
+        Ok(std::mem::replace(&mut *guard, normalize))
+    }
+
+    /// Returns `true` if fragments which combine an anchor with a JSON
+    /// Pointer, e.g. `"#node/properties/name"`, are resolved leniently.
+    ///
+    /// See [`fragment`](crate::fragment) for more information.
+    pub fn lenient_fragments(&self) -> bool {
+        *self.lenient_fragments.read()
+    }
+
+    /// Sets whether fragments which combine an anchor with a JSON Pointer are
+    /// resolved leniently, returning the previous value.
+    ///
+    /// When `false`, the default, such fragments fail with
+    /// [`Error::AnchorPointerFragment`]. When `true`, the anchor is resolved
+    /// and the pointer is applied to the anchored schema; each such fragment
+    /// is reported as a [`Diagnostic`].
+    pub fn set_lenient_fragments(&self, lenient: bool) -> bool {
+        std::mem::replace(&mut *self.lenient_fragments.write(), lenient)
+    }
+
+    /// Returns `true` if the keywords of each [`Schema`] are optimized when
+    /// it is setup.
+    ///
+    /// See [`optimize`](crate::optimize) for more information.
+    pub fn optimizes(&self) -> bool {
+        *self.optimize.read()
+    }
+
+    /// Sets whether the keywords of each [`Schema`] are optimized when it is
+    /// setup, returning the previous value. Optimization is disabled by
+    /// default. See [`optimize`](crate::optimize).
+    ///
+    /// [`Schema`]s which have already been setup are not affected until they
+    /// are setup again.
+    pub fn set_optimize(&self, optimize: bool) -> bool {
+        std::mem::replace(&mut *self.optimize.write(), optimize)
+    }
+
+    /// Returns `true` if the `"examples"` and `"default"` of each [`Schema`]
+    /// are validated against it when it is added.
+    ///
+    /// See [`lint::check_examples`] for more information.
+    pub fn validates_examples(&self) -> bool {
+        *self.validate_examples.read()
+    }
+
+    /// Sets whether the `"examples"` and `"default"` of each [`Schema`] are
+    /// validated against it when it is added, returning the previous value.
+    /// Each which is not valid is reported as a [`Diagnostic`]. Disabled by
+    /// default, as each is evaluated.
+    pub fn set_validate_examples(&self, validate: bool) -> bool {
+        std::mem::replace(&mut *self.validate_examples.write(), validate)
+    }
+
+    /// Returns how an anchor declared more than once within a resource is
+    /// handled when a [`Schema`] is inserted.
+    ///
+    /// If not previously set, [`DuplicateAnchorPolicy::Error`] is the
+    /// default.
+    pub fn duplicate_anchor_policy(&self) -> DuplicateAnchorPolicy {
+        *self.duplicate_anchor_policy.read()
+    }
+
+    /// Sets how an anchor declared more than once within a resource is
+    /// handled when a [`Schema`] is inserted, returning the previous policy.
+    ///
+    /// Under [`DuplicateAnchorPolicy::Error`], inserting such a [`Schema`]
+    /// fails with [`Error::InvalidAnchor`]. Under
+    /// [`DuplicateAnchorPolicy::FirstWins`], the first declaration is used
+    /// and each duplicate is reported as a [`Diagnostic`]. Regardless of the
+    /// policy, a [`Schema`] with an anchor which does not match the grammar
+    /// of its draft fails with [`Error::InvalidAnchor`]. See
+    /// [`check_anchors`](crate::fragment::check_anchors).
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{fragment::DuplicateAnchorPolicy, Interrogator};
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let previous = interrogator.set_duplicate_anchor_policy(DuplicateAnchorPolicy::FirstWins);
+    /// assert_eq!(previous, DuplicateAnchorPolicy::Error);
+    /// assert_eq!(interrogator.duplicate_anchor_policy(), DuplicateAnchorPolicy::FirstWins);
+    /// ```
+    pub fn set_duplicate_anchor_policy(
+        &self,
+        policy: DuplicateAnchorPolicy,
+    ) -> DuplicateAnchorPolicy {
+        std::mem::replace(&mut *self.duplicate_anchor_policy.write(), policy)
+    }
+
+    /// Returns the maximum length of a chain of references between documents
+    /// (A→B→C→…) permitted when a [`Schema`] is inserted, if any.
+    ///
+    /// If not previously set, [`DEFAULT_MAX_REFERENCE_DEPTH`] is the default.
+    pub fn max_reference_depth(&self) -> Option<usize> {
+        *self.max_reference_depth.read()
+    }
+
+    /// Sets the maximum length of a chain of references between documents
+    /// permitted when a [`Schema`] is inserted, returning the previous value.
+    /// `None` permits chains of any length.
+    ///
+    /// Inserting a [`Schema`] from which a longer chain is reachable fails
+    /// with [`Error::ReferenceDepthExceeded`], which lists the chain. This
+    /// guards against maliciously deep registries and cycles routed through
+    /// a [`Resolver`]. References within a document are not counted.
+    pub fn set_max_reference_depth(&self, max: Option<usize>) -> Option<usize> {
+        std::mem::replace(&mut *self.max_reference_depth.write(), max)
+    }
+
+    /// Returns the value at `uri` within its source document, resolving the
+    /// fragment of `uri` as an anchor or JSON Pointer.
+    ///
+    /// If no source document was added at `uri`, the resource identified by
+    /// `uri` with `"$id"` within another source document, such as a URN, is
+    /// used instead. See [`urn`](crate::urn).
+    ///
+    /// `None` is returned if the source document, the anchor, or the value
+    /// the pointer refers to does not exist.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let base = AbsoluteUri::parse("https://example.com/tree.json").unwrap();
+    /// interrogator.source_value(base, json!({
+    ///     "$defs": { "node": { "$anchor": "node", "properties": { "name": true } } }
+    /// }));
+    ///
+    /// let uri = AbsoluteUri::parse("https://example.com/tree.json#node/properties/name").unwrap();
+    /// assert!(interrogator.locate(&uri).unwrap_err().is_anchor_pointer_fragment());
+    ///
+    /// interrogator.set_lenient_fragments(true);
+    /// assert_eq!(interrogator.locate(&uri).unwrap(), Some(json!(true)));
+    /// assert_eq!(interrogator.diagnostics().len(), 1);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if the source document could not be parsed or the
+    /// fragment is malformed; see [`fragment::locate`].
+    pub fn locate(&self, uri: &AbsoluteUri) -> Result<Option<Value>, Error> {
+        let fragment = uri
+            .fragment()
+            .map(|f| String::from_utf8_lossy(f).into_owned())
+            .unwrap_or_default();
+        let mut base = uri.clone();
+        base.set_fragment(None);
+        let document = match self.sources.get(&base)? {
+            Some(document) => document,
+            None => match self.embedded_resource(&base)? {
+                Some(document) => document,
+                None => return Ok(None),
+            },
+        };
+        let located = match fragment::locate(&document, &fragment, self.lenient_fragments())? {
+            Some(located) => located,
+            None => return Ok(None),
+        };
+        if let Some(mut diagnostic) = located.diagnostic {
+            diagnostic.schema_id = Some(Arc::new(base.uri().clone()));
+            self.report(vec![diagnostic]);
+        }
+        Ok(document.pointer(located.location.as_str()).cloned())
+    }
+
+    /// Returns the resource identified by `uri`, which has no fragment,
+    /// embedded within a source document under another URI. URNs are
+    /// compared in the form returned by [`urn::normalize`].
+    fn embedded_resource(&self, uri: &AbsoluteUri) -> Result<Option<Arc<Value>>, Error> {
+        self.sources.resource(&uri.to_string())
+    }
+
+    /// Returns the id, without fragment, of each schema resource within the
+    /// source documents which declares the `"$dynamicAnchor"` `name`.
+    pub(crate) fn dynamic_anchor_resources(&self, name: &str) -> Result<Vec<Uri>, Error> {
+        self.sources.dynamic_anchor_resources(name)
+    }
+
+    /// Returns a [`Snapshot`](crate::state::Snapshot) of the source documents
+    /// and settings of this `Interrogator`, sealed with its digest and, if
+    /// `signer` is provided, a signature. See [`state`](crate::state).
+    ///
+    /// Compiled [`Schema`]s are not included; they are compiled again from
+    /// the restored sources.
+    #[cfg(feature = "state")]
+    pub fn seal(
+        &self,
+        signer: Option<&dyn crate::state::Signer>,
+    ) -> Result<crate::state::Sealed, Error> {
+        let sources = self
+            .sources
+            .documents()?
+            .into_iter()
+            .map(|(uri, doc)| (uri.to_string(), doc.as_ref().clone()))
+            .collect();
+        let snapshot = crate::state::Snapshot {
+            version: crate::state::SNAPSHOT_VERSION,
+            sources,
+            default_meta_schema: self.default_meta_schema_uri.read().to_string(),
+            base_uri: self.base_uri().map(|uri| uri.to_string()),
+            normalize_identifiers: self.normalizes_identifiers(),
+            lenient_fragments: self.lenient_fragments(),
+        };
+        Ok(crate::state::Sealed::new(snapshot, signer)?)
+    }
+
+    /// Verifies `sealed` and applies its source documents and settings to
+    /// this `Interrogator`.
+    ///
+    /// If `verifier` is provided, `sealed` must be signed by a key it
+    /// accepts. Nothing is applied unless verification succeeds.
+    ///
+    /// # Errors
+    /// Returns [`Error::StateVerification`] if the digest or signature of
+    /// `sealed` does not match its contents, [`Error::InvalidUri`] if a URI
+    /// within it can not be parsed, and [`Error::FeatureDisabled`] if it
+    /// normalizes identifiers and the `"nfc"` feature is not enabled.
+    #[cfg(feature = "state")]
+    pub fn restore(
+        &self,
+        sealed: &crate::state::Sealed,
+        verifier: Option<&dyn crate::state::Verifier>,
+    ) -> Result<(), Error> {
+        sealed.verify(verifier)?;
+        let snapshot = &sealed.snapshot;
+        let mut sources = Vec::with_capacity(snapshot.sources.len());
+        for (uri, doc) in &snapshot.sources {
+            sources.push((AbsoluteUri::parse(uri)?, doc.clone()));
+        }
+        let default_meta_schema = Uri::parse(&snapshot.default_meta_schema)?;
+        let base_uri = snapshot
+            .base_uri
+            .as_deref()
+            .map(AbsoluteUri::parse)
+            .transpose()?;
+        self.set_normalize_identifiers(snapshot.normalize_identifiers)?;
+        for (uri, doc) in sources {
+            self.sources.insert_from(uri, doc, Provenance::Restored);
+        }
+        *self.default_meta_schema_uri.write() = default_meta_schema;
+        if let Some(uri) = base_uri {
+            self.set_base_uri(uri);
+        }
+        self.set_lenient_fragments(snapshot.lenient_fragments);
+        Ok(())
+    }
+
+    /// Resolves each document referenced, directly or transitively, by the
+    /// documents at `roots`, writes the roots and their dependencies to `dir`,
+    /// and writes a [`Lockfile`](crate::vendor::Lockfile) to
+    /// [`LOCKFILE_NAME`](crate::vendor::LOCKFILE_NAME) within `dir`. See
+    /// [`vendor`](crate::vendor).
+    ///
+    /// Documents which are not already sources of this `Interrogator` are
+    /// resolved with [`resolve`](Self::resolve) and added as sources. Meta
+    /// schemas known to this `Interrogator` are not vendored.
+    ///
+    /// # Errors
+    /// Returns [`Error::Vendor`] if a document could not be resolved or a
+    /// file could not be written.
+    #[cfg(feature = "vendor")]
+    pub fn vendor(
+        &self,
+        roots: &[AbsoluteUri],
+        dir: impl AsRef<std::path::Path>,
+    ) -> Result<crate::vendor::Lockfile, Error> {
+        use crate::{error::VendorError, vendor};
+        let dir = dir.as_ref();
+        let io_error = |path: &std::path::Path, err: std::io::Error| VendorError::Io {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        };
+        let mut lockfile = vendor::Lockfile::new();
+        let mut queue: Vec<AbsoluteUri> = roots.iter().map(vendor::without_fragment).collect();
+        while let Some(uri) = queue.pop() {
+            let key = uri.to_string();
+            if lockfile.entries.contains_key(&key) {
+                continue;
+            }
+            let document = match self.sources.get(&uri)? {
+                Some(document) => document,
+                None => {
+                    let document = self.resolve(&key).map_err(|err| VendorError::Resolve {
+                        uri: key.clone(),
+                        message: err.to_string(),
+                    })?;
+                    self.sources
+                        .insert_from(uri.clone(), document, Provenance::Resolved);
+                    self.sources.get(&uri)?.expect("document was just inserted")
+                }
+            };
+            for dependency in vendor::dependencies(&uri, &document) {
+                if !self.is_known_meta_schema(dependency.uri()) && !self.is_embedded(&dependency) {
+                    queue.push(dependency);
+                }
+            }
+            let relative = vendor::vendor_path(&uri)
+                .ok_or_else(|| VendorError::UnsupportedUri { uri: key.clone() })?;
+            let path = dir.join(&relative);
+            let mut bytes = serde_json::to_vec_pretty(document.as_ref())?;
+            bytes.push(b'\n');
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|err| io_error(parent, err))?;
+            }
+            std::fs::write(&path, &bytes).map_err(|err| io_error(&path, err))?;
+            lockfile.entries.insert(
+                key,
+                vendor::LockEntry {
+                    path: vendor::slash_path(&relative),
+                    sha256: vendor::digest(&bytes),
+                },
+            );
+        }
+        let path = dir.join(vendor::LOCKFILE_NAME);
+        lockfile.write(&path).map_err(|err| io_error(&path, err))?;
+        Ok(lockfile)
+    }
+
+    /// Returns the [`LockedResolver`](crate::vendor::LockedResolver) which
+    /// resolves documents in place of each other resolver, if one is set.
+    #[cfg(feature = "vendor")]
+    pub fn locked_resolver(&self) -> Option<Arc<crate::vendor::LockedResolver>> {
+        self.locked_resolver.read().clone()
+    }
+
+    /// Sets the [`LockedResolver`](crate::vendor::LockedResolver) which
+    /// resolves documents in place of each other resolver, returning the
+    /// previous value. While set, documents which are not listed in its
+    /// lockfile, or whose digest differs, are not resolved; mirrors are not
+    /// applied. See [`vendor`](crate::vendor).
+    #[cfg(feature = "vendor")]
+    pub fn set_locked_resolver(
+        &self,
+        resolver: Option<crate::vendor::LockedResolver>,
+    ) -> Option<Arc<crate::vendor::LockedResolver>> {
+        std::mem::replace(&mut *self.locked_resolver.write(), resolver.map(Arc::new))
+    }
+
+    /// Returns the source document at `uri` if it exists.
+    ///
+    /// # Errors
+    /// Returns [`Error::Serde`] if the document was added lazily, e.g. by
+    /// memory-mapping it, and is not valid JSON.
+    pub fn source(&self, uri: &AbsoluteUri) -> Result<Option<Arc<Value>>, Error> {
+        self.sources.get(uri)
+    }
+
+    /// Returns a [`SourceEntry`] for each source document, ordered by URI.
+    /// Documents added lazily are not parsed.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{source::Provenance, uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let uri = AbsoluteUri::parse("https://example.com/schemas/a.json").unwrap();
+    /// interrogator.source_value(uri.clone(), json!({ "type": "string" }));
+    ///
+    /// let entry = interrogator.sources().find(|e| e.uri == uri).unwrap();
+    /// assert_eq!(entry.size, br#"{"type":"string"}"#.len());
+    /// assert_eq!(entry.provenance, Provenance::Added);
+    /// assert_eq!(entry.dialect, None);
+    /// ```
+    pub fn sources(&self) -> impl Iterator<Item = SourceEntry> {
+        self.sources_with_prefix("")
+    }
+
+    /// Returns a [`SourceEntry`] for each source document whose URI starts
+    /// with `prefix`, ordered by URI. Documents added lazily are not parsed.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// for uri in ["https://example.com/a.json", "https://example.org/b.json"] {
+    ///     interrogator.source_value(AbsoluteUri::parse(uri).unwrap(), json!(true));
+    /// }
+    /// let uris: Vec<String> = interrogator
+    ///     .sources_with_prefix("https://example.com/")
+    ///     .map(|e| e.uri.to_string())
+    ///     .collect();
+    /// assert_eq!(uris, ["https://example.com/a.json"]);
+    /// ```
+    pub fn sources_with_prefix(&self, prefix: &str) -> impl Iterator<Item = SourceEntry> {
+        let mut entries = self.sources.entries(prefix);
+        for entry in &mut entries {
+            entry.dialect = entry.dialect.take().filter(|id| self.dialect(id).is_some());
+        }
+        entries.into_iter()
+    }
+
+    /// Memory-maps the file at `path` and adds it as the source at `uri`.
+    /// The document is not parsed until it is first used, so large bundles
+    /// of which only a fraction is referenced do not inflate memory usage.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while it is mapped. See
+    /// [`Sources::insert_mmap`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn source_mmap(
+        &self,
+        uri: AbsoluteUri,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<()> {
+        self.sources.insert_mmap(uri, path)
+    }
+
+    /// Adds `document` as the source at `uri`, returning the previous source
+    /// if one existed.
+    pub fn source_value(&self, uri: AbsoluteUri, document: Value) -> Option<Arc<Value>> {
+        self.sources.insert(uri, document)
+    }
+
+    /// Rewrites the references and identifiers of each source document
+    /// according to the URI prefix `mapping`, moving each document whose URI
+    /// matches a prefix. See [`rewrite`](crate::rewrite).
+    ///
+    /// A moved document replaces any source already at its new URI; each
+    /// replaced source is listed in [`RewriteReport::replaced`].
+    ///
+    /// # Errors
+    /// Returns an [`Error`] if a lazily inserted source fails to parse or if
+    /// a rewritten document URI is not absolute.
+    pub fn rewrite_refs(&self, mapping: &[(&str, &str)]) -> Result<RewriteReport, Error> {
+        self.rewrite_refs_with(mapping, true)
+    }
+
+    /// Returns the [`RewriteReport`] of [`rewrite_refs`](Self::rewrite_refs)
+    /// without modifying any source.
+    pub fn rewrite_refs_dry_run(&self, mapping: &[(&str, &str)]) -> Result<RewriteReport, Error> {
+        self.rewrite_refs_with(mapping, false)
+    }
+
+    fn rewrite_refs_with(
+        &self,
+        mapping: &[(&str, &str)],
+        apply: bool,
+    ) -> Result<RewriteReport, Error> {
+        let mut documents = self.sources.documents()?;
+        documents.sort_by_key(|(uri, _)| uri.to_string());
+        let mut report = RewriteReport::default();
+        let mut rewritten = Vec::new();
+        for (uri, document) in &documents {
+            let mut document = (**document).clone();
+            let changes = rewrite::rewrite_document(uri, &mut document, mapping);
+            let target = match rewrite::map_uri(&uri.to_string(), mapping) {
+                Some(target) => Some(AbsoluteUri::parse(&target)?),
+                None => None,
+            };
+            if let Some(target) = &target {
+                report.moved.push((uri.clone(), target.clone()));
+            }
+            if !changes.is_empty() || target.is_some() {
+                rewritten.push((uri.clone(), target, document));
+            }
+            report.changes.extend(changes);
+        }
+        // a moved document replaces the source at its target unless that
+        // source is moved as well, and replaces any moved there before it
+        let moved: HashSet<&AbsoluteUri> = report.moved.iter().map(|(from, _)| from).collect();
+        let mut remaining: HashSet<&AbsoluteUri> = documents
+            .iter()
+            .map(|(uri, _)| uri)
+            .filter(|uri| !moved.contains(uri))
+            .collect();
+        let mut replaced = Vec::new();
+        for (_, to) in &report.moved {
+            if !remaining.insert(to) {
+                replaced.push(to.clone());
+            }
+        }
+        report.replaced = replaced;
+        if apply {
+            let provenance: Vec<_> = rewritten
+                .iter()
+                .map(|(uri, _, _)| self.sources.provenance(uri).unwrap_or_default())
+                .collect();
+            // every moved document is removed before any is inserted so that
+            // a document moved to the previous URI of another is not removed
+            for (uri, target, _) in &rewritten {
+                if target.is_some() {
+                    self.sources.remove(uri);
+                }
+            }
+            for ((uri, target, document), provenance) in rewritten.into_iter().zip(provenance) {
+                self.sources
+                    .insert_from(target.unwrap_or(uri), document, provenance);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Adds `document`, represented by the [`ValueBackend`] `B`, as the
+    /// source at `uri`, returning the previous source if one existed.
+    ///
+    /// # Errors
+    /// Returns the error of [`ValueBackend::to_json`] if `document` can not
+    /// be represented as a [`Value`].
+    pub fn source_with_backend<B: ValueBackend>(
+        &self,
+        uri: AbsoluteUri,
+        document: &B::Value,
+    ) -> Result<Option<Arc<Value>>, Error> {
+        Ok(self.source_value(uri, B::to_json(document)?.into_owned()))
+    }
+
+    /// Deserializes `document` with the deserializer of `media_type` and adds
+    /// it as the source at `uri`, returning the previous source if one
+    /// existed.
+    ///
+    /// If `media_type` is `None`, the media type is implied by the extension
+    /// of `uri` (e.g. `"application/yaml"` for `.yaml` with the `"yaml"`
+    /// feature enabled), falling back to JSON. See
+    /// [`deserializer`](crate::deserializer).
+    ///
+    /// # Errors
+    /// Returns [`Error::Deserialize`], with the line and column of the
+    /// failure within `document`, if `document` is malformed or no
+    /// deserializer is registered for `media_type`.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator};
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let uri = AbsoluteUri::parse("https://example.com/string.json").unwrap();
+    /// interrogator
+    ///     .source_bytes(uri.clone(), br#"{ "type": "string" }"#, None)
+    ///     .unwrap();
+    /// assert_eq!(interrogator.source(&uri).unwrap().unwrap()["type"], "string");
+    ///
+    /// let err = interrogator
+    ///     .source_bytes(uri, b"type: string", Some("text/x-unknown"))
+    ///     .unwrap_err();
+    /// assert!(err.is_deserialize());
+    /// ```
+    pub fn source_bytes(
+        &self,
+        uri: AbsoluteUri,
+        document: &[u8],
+        media_type: Option<&str>,
+    ) -> Result<Option<Arc<Value>>, Error> {
+        let document = self.deserializers.deserialize(&uri, media_type, document)?;
+        Ok(self.source_value(uri, document))
+    }
+
+    /// Returns the media type implied by the extension of `uri` if it is
+    /// registered with a deserializer other than JSON's.
+    fn non_json_media_type(&self, uri: &AbsoluteUri) -> Option<String> {
+        self.deserializers
+            .media_type_of(uri)
+            .filter(|media_type| media_type != JSON_MEDIA_TYPE)
+    }
+
+    /// Deserializes `document` and adds it as the source at `uri`, returning
+    /// the previous source if one existed.
+    ///
+    /// `document` is deserialized as JSON unless the extension of `uri`
+    /// implies another media type registered in
+    /// [`deserializers`](Self::deserializers), e.g. `.yaml` or `.yml` with
+    /// the `"yaml"` feature enabled. Use
+    /// [`source_bytes`](Self::source_bytes) to provide the media type.
+    ///
+    /// With the `"simd"` feature enabled, JSON is parsed with simd-json.
+    pub fn source_str(
+        &self,
+        uri: AbsoluteUri,
+        document: &str,
+    ) -> Result<Option<Arc<Value>>, Error> {
+        if let Some(media_type) = self.non_json_media_type(&uri) {
+            return self.source_bytes(uri, document.as_bytes(), Some(&media_type));
+        }
+        #[cfg(feature = "simd")]
+        let document = simd_json::serde::from_slice(&mut document.as_bytes().to_vec())
+            .map_err(crate::backend::simd_error)?;
+        #[cfg(not(feature = "simd"))]
+        let document = serde_json::from_str(document)?;
+        Ok(self.source_value(uri, document))
+    }
+
+    /// Deserializes the document read from `reader` and adds it as the source
+    /// at `uri`, returning the previous source if one existed.
+    ///
+    /// The document is deserialized as it is read, so callers need not buffer
+    /// it first. `reader` is not buffered internally; wrap it in a
+    /// [`BufReader`](std::io::BufReader) if it performs a system call per
+    /// read (e.g. a [`File`](std::fs::File)).
+    ///
+    /// If the extension of `uri` implies a media type other than JSON, as
+    /// with [`source_str`](Self::source_str), the document is read in full
+    /// before it is deserialized. IO errors are reported as
+    /// [`Error::Serde`].
+    pub fn source_reader(
+        &self,
+        uri: AbsoluteUri,
+        reader: impl Read,
+    ) -> Result<Option<Arc<Value>>, Error> {
+        if let Some(media_type) = self.non_json_media_type(&uri) {
+            let mut reader = reader;
+            let mut buf = Vec::new();
+            reader
+                .read_to_end(&mut buf)
+                .map_err(serde_json::Error::io)?;
+            return self.source_bytes(uri, &buf, Some(&media_type));
+        }
+        let document = serde_json::from_reader(reader)?;
+        Ok(self.source_value(uri, document))
+    }
+
+    /// Reads the document from the async `reader`, deserializes it, and adds
+    /// it as the source at `uri`, returning the previous source if one
+    /// existed.
+    ///
+    /// `serde_json` is unable to deserialize incrementally from an async
+    /// reader, so the raw bytes are collected before deserializing. Only
+    /// the bytes are buffered; callers need not decode the document into a
+    /// `String`. IO errors are reported as [`Error::Serde`]. Media types are
+    /// implied as with [`source_str`](Self::source_str).
+    #[cfg(feature = "async")]
+    pub async fn source_async_reader<R>(
+        &self,
+        uri: AbsoluteUri,
+        reader: R,
+    ) -> Result<Option<Arc<Value>>, Error>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        use tokio::io::AsyncReadExt;
+        let mut reader = reader;
+        let mut buf = Vec::new();
+        reader
+            .read_to_end(&mut buf)
+            .await
+            .map_err(serde_json::Error::io)?;
+        if let Some(media_type) = self.non_json_media_type(&uri) {
+            return self.source_bytes(uri, &buf, Some(&media_type));
+        }
+        let document = serde_json::from_slice(&buf)?;
+        Ok(self.source_value(uri, document))
+    }
+
+    /// Registers a [`Resolver`], which is consulted after those previously
+    /// registered.
+    pub fn add_resolver(&self, resolver: impl Resolver + 'static) {
+        Arc::make_mut(&mut self.resolvers.write()).push(Arc::new(resolver));
+    }
+
+    /// Returns the [`Mirrors`] URIs are rewritten with before they are
+    /// resolved. See [`mirror`](crate::mirror).
+    pub fn mirrors(&self) -> Mirrors {
+        Mirrors::clone(&self.mirrors.read())
+    }
+
+    /// Maps URIs beginning with `prefix` to `mirror` before they are
+    /// resolved, returning the previous mirror of `prefix` if one existed.
+    pub fn add_mirror(
+        &self,
+        prefix: impl Into<String>,
+        mirror: impl Into<String>,
+    ) -> Option<String> {
+        self.mirrors_mut().insert(prefix, mirror)
+    }
+
+    /// Resolves the source document identified by `id` with the registered
+    /// [`Resolver`]s, in the order they were added.
+    ///
+    /// `id` is first rewritten with the [`Mirrors`] of this `Interrogator`;
+    /// resolvers receive the rewritten URI.
+    ///
+    /// A resolver which reports [`ResolveError::NotFound`] defers to the
+    /// next; any other error is returned immediately. If no resolver finds the
+    /// document, [`ResolveError::NotFound`] is returned with the original
+    /// `id`.
+    ///
+    /// If a locked resolver is set with `set_locked_resolver`, it alone
+    /// resolves `id`.
+    pub fn resolve(&self, id: &str) -> Result<Value, ResolveError> {
+        #[cfg(feature = "vendor")]
+        if let Some(locked) = self.locked_resolver() {
+            return locked.resolve(id.to_string());
+        }
+        let mirrored = self.mirrors.read().rewrite(id).into_owned();
+        // released before resolving so resolvers may register others
+        let resolvers = self.resolvers.read().clone();
+        for resolver in resolvers.iter() {
+            match resolver.resolve(mirrored.clone()) {
+                Err(err) if err.is_not_found() => {}
+                result => return result,
+            }
+        }
+        Err(ResolveError::NotFound(id.to_string()))
+    }
+
+    /// Registers an [`AsyncResolver`], which is consulted after those
+    /// previously registered.
+    #[cfg(feature = "async")]
+    pub fn add_async_resolver(&self, resolver: impl AsyncResolver + 'static) {
+        Arc::make_mut(&mut self.async_resolvers.write()).push(Arc::new(resolver));
+    }
+
+    /// Resolves the source document identified by `id` with the registered
+    /// [`AsyncResolver`]s, in the order they were added, followed by the
+    /// registered [`Resolver`]s. See [`resolve`](Self::resolve).
+    ///
+    /// The returned future is `Send`.
+    #[cfg(feature = "async")]
+    pub async fn resolve_async(&self, id: &str) -> Result<Value, ResolveError> {
+        #[cfg(feature = "vendor")]
+        if let Some(locked) = self.locked_resolver() {
+            return locked.resolve(id.to_string());
+        }
+        let mirrored = self.mirrors.read().rewrite(id).into_owned();
+        let resolvers = self.async_resolvers.read().clone();
+        for resolver in resolvers.iter() {
+            match resolver.resolve(mirrored.clone()).await {
+                Err(err) if err.is_not_found() => {}
+                result => return result,
+            }
+        }
+        self.resolve(id)
+    }
+
+    /// Returns the [`Diagnostic`]s reported for each [`Schema`] added to this
+    /// `Interrogator`.
+    ///
+    /// See [`lint`](crate::lint) for more information.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.read().clone()
+    }
 
-        self.meta_schema(&*r).unwrap()
+    /// Removes and returns the [`Diagnostic`]s reported thus far.
+    pub fn take_diagnostics(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.diagnostics.write())
+    }
+
+    pub(crate) fn report(&self, mut diagnostics: Vec<Diagnostic>) {
+        self.diagnostics.write().append(&mut diagnostics);
+    }
+
+    /// Checks the anchors of `schema` against the grammar of its draft and
+    /// the [`DuplicateAnchorPolicy`], returning the diagnostics to report
+    /// once `schema` has been inserted.
+    fn check_anchors(&self, schema: &Schema) -> Result<Vec<Diagnostic>, Error> {
+        let declared = schema
+            .source()
+            .get("$schema")
+            .and_then(Value::as_str)
+            .and_then(|id| Uri::parse(id).ok());
+        let meta_schema_id = match declared {
+            Some(id) => id,
+            None => match schema.meta_schema_id() {
+                Some(id) => id.as_ref().clone(),
+                None => self.default_meta_schema_uri(),
+            },
+        };
+        let grammar = AnchorGrammar::for_meta_schema(&self.draft_of(&meta_schema_id));
+        let schema_id = schema.id();
+        let mut diagnostics = fragment::check_anchors(&schema.source(), grammar);
+        for diagnostic in &mut diagnostics {
+            diagnostic.schema_id = schema_id.clone();
+        }
+        let permitted = |d: &Diagnostic| {
+            d.lint == Lint::DuplicateAnchor
+                && self.duplicate_anchor_policy() == DuplicateAnchorPolicy::FirstWins
+        };
+        if diagnostics.iter().all(permitted) {
+            return Ok(diagnostics);
+        }
+        Err(InvalidAnchorError {
+            schema_id,
+            diagnostics: diagnostics.into_iter().filter(|d| !permitted(d)).collect(),
+        }
+        .into())
+    }
+
+    /// Returns the draft of the meta schema `id`: `id` itself if it is a
+    /// draft, otherwise the draft of the `"$schema"` its source declares,
+    /// followed until a draft, or a meta schema without a source, is reached.
+    fn draft_of(&self, id: &Uri) -> Uri {
+        let mut id = id.clone();
+        let mut visited = HashSet::new();
+        while !crate::draft::is_draft(&id) && visited.insert(id.clone()) {
+            let declared = AbsoluteUri::parse(&id.to_string())
+                .ok()
+                // a source which can not be parsed declares no draft
+                .and_then(|uri| self.sources.get(&source::without_fragment(&uri)).ok()?)
+                .and_then(|document| {
+                    let declared = document.get("$schema")?.as_str()?;
+                    Uri::parse(declared).ok()
+                });
+            match declared {
+                Some(declared) => id = declared,
+                None => break,
+            }
+        }
+        id
+    }
+
+    fn report_diagnostics(&self, schema: &Schema) {
+        let id = schema.id();
+        let mut found = lint::check(&schema.source());
+        for diagnostic in &mut found {
+            diagnostic.schema_id = id.clone();
+        }
+        if self.validates_examples() {
+            let exact = self.precision() == Precision::Arbitrary;
+            let options = crate::evaluation::Options::default().exact_numbers(exact);
+            found.extend(lint::check_examples(schema, &options));
+        }
+        self.diagnostics.write().append(&mut found);
     }
 
     /// Adds a top-level `Schema` to the `Interrogator`, associated by its `id`.
@@ -97,24 +1984,71 @@ impl Interrogator {
     /// If the `id` of the `Schema` is not set, an `Error::UnidentifiedSchema`
     /// is returned and the `Schema` is not inserted.
     pub fn insert_schema(&self, schema: Schema) -> Result<Option<Schema>, Error> {
+        self.insert_schema_cancellable(schema, &CancellationToken::new())
+    }
+
+    /// Adds a top-level `Schema` to the `Interrogator` as with
+    /// [`insert_schema`](Self::insert_schema), checking `cancel` before each
+    /// affected `Schema` is setup.
+    ///
+    /// If `cancel` is cancelled or its deadline passes, the schemas and graph
+    /// of this `Interrogator` are restored to their state prior to the call
+    /// and [`Error::Cancelled`] is returned. See [`cancel`](crate::cancel).
+    /// The same holds for any other error.
+    ///
+    /// Each existing `Schema` which was set up again against `schema` before
+    /// the insert failed is set up once more against the restored state, so
+    /// that none refer to `schema`. Errors in doing so are ignored in favor
+    /// of the error of the insert.
+    pub fn insert_schema_cancellable(
+        &self,
+        schema: Schema,
+        cancel: &CancellationToken,
+    ) -> Result<Option<Schema>, Error> {
         // this mutex lock ensures that only one process can modify the schemas at a time.
         // this is necessary because the RwLock guarding schemas cannot be held for
         // the duration of the `insert_schema` call as it would cause a deadlock.
         #[allow(unused_variables)]
         let g = self.lock.lock();
 
-        match {
-            let mut schemas = self.schemas.write();
-            match schemas.insert(schema.clone()) {
-                Ok(old) => match schema.setup(self) {
-                    Ok(_) => Ok(old),
-                    Err(e) => Err(e),
-                },
-                Err(e) => Err(e.into()),
+        // copy-on-write snapshots, restored if the insert fails
+        let schemas = self.schemas.read().clone();
+        let graph = self.graph.read().clone();
+        let mut touched = HashSet::new();
+        match self.try_insert_schema(schema, cancel, &mut touched) {
+            Err(err) => {
+                *self.schemas.write() = schemas;
+                *self.graph.write() = graph;
+                // best-effort: the error of the insert is returned regardless
+                for s in &touched {
+                    let _ = s.setup(self);
+                }
+                Err(err)
             }
-        } {
+            result => result,
+        }
+    }
+
+    /// Inserts `schema`, adding each existing `Schema` which is set up again
+    /// to `touched`.
+    fn try_insert_schema(
+        &self,
+        schema: Schema,
+        cancel: &CancellationToken,
+        touched: &mut HashSet<Schema>,
+    ) -> Result<Option<Schema>, Error> {
+        cancel.check()?;
+        let anchor_diagnostics = self.check_anchors(&schema)?;
+        // the write guard is released before `schema` is setup, as setting up
+        // a "$ref" reads the schemas
+        let inserted = self.schemas_mut().insert(schema.clone());
+        let inserted = match inserted {
+            Ok(old) => schema.setup(self).map(|_| old),
+            Err(e) => Err(e.into()),
+        };
+        match inserted {
             Err(err) => {
-                let mut schemas = self.schemas.write();
+                let mut schemas = self.schemas_mut();
                 schemas.rollback();
                 Err(err)
             }
@@ -128,32 +2062,41 @@ impl Interrogator {
                     match Graph::new(&values) {
                         Ok(g) => g,
                         Err(err) => {
-                            let mut schemas = self.schemas.write();
+                            let mut schemas = self.schemas_mut();
                             schemas.rollback();
                             return Err(err.into());
                         }
                     }
                 };
+                if let Some(max) = self.max_reference_depth() {
+                    if let Some(chain) = new_graph.chain_exceeding(&schema, max) {
+                        let mut schemas = self.schemas_mut();
+                        schemas.rollback();
+                        return Err(ReferenceDepthError { max, chain }.into());
+                    }
+                }
                 let mut setup = HashSet::new();
                 for s in values.iter().cloned() {
                     if s == schema {
                         continue;
                     }
+                    cancel.check()?;
                     if !setup.contains(&schema)
                         && !setup.contains(&s)
                         && new_graph.is_referenced(&s, &schema)
                     {
                         if !setup.contains(&schema) {
                             if let Err(err) = schema.setup(self) {
-                                let mut schemas = self.schemas.write();
+                                let mut schemas = self.schemas_mut();
                                 schemas.rollback();
                                 return Err(err);
                             }
                             setup.insert(schema.clone());
                         }
                         if !setup.contains(&s) {
+                            touched.insert(s.clone());
                             if let Err(err) = s.setup(self) {
-                                let mut schemas = self.schemas.write();
+                                let mut schemas = self.schemas_mut();
                                 schemas.rollback();
                                 return Err(err);
                             }
@@ -167,24 +2110,28 @@ impl Interrogator {
                     {
                         if !setup.contains(&schema) {
                             if let Err(err) = schema.setup(self) {
-                                let mut schemas = self.schemas.write();
+                                let mut schemas = self.schemas_mut();
                                 schemas.rollback();
                                 return Err(err);
                             }
                         }
                         if !setup.contains(&s) {
+                            touched.insert(s.clone());
                             if let Err(err) = s.setup(self) {
-                                let mut schemas = self.schemas.write();
+                                let mut schemas = self.schemas_mut();
                                 schemas.rollback();
                                 return Err(err);
                             }
                         }
                     }
                 }
-                let mut schemas = self.schemas.write();
+                cancel.check()?;
+                let mut schemas = self.schemas_mut();
                 schemas.commit();
-                let mut graph = self.graph.write();
+                let mut graph = self.graph_mut();
                 graph.rebuild(&values).expect("Rebuilding the graph failed which is a bug. Please report this to https://github.com/chanced/grill/issues");
+                self.report_diagnostics(&schema);
+                self.report(anchor_diagnostics);
                 Ok(old)
             }
         }
@@ -201,8 +2148,20 @@ impl Interrogator {
         // the duration of the `insert_schema` call as it would cause a deadlock.
         #[allow(unused_variables)]
         let g = self.lock.lock();
+        self.insert_schemas_locked(schemas_to_add)
+    }
 
-        let mut schemas = self.schemas.write();
+    /// Adds `schemas_to_add` as with [`insert_schemas`](Self::insert_schemas)
+    /// while `self.lock` is held by the caller.
+    fn insert_schemas_locked(
+        &self,
+        schemas_to_add: &[Schema],
+    ) -> Result<Option<Vec<Schema>>, Error> {
+        let mut anchor_diagnostics = Vec::new();
+        for s in schemas_to_add {
+            anchor_diagnostics.append(&mut self.check_anchors(s)?);
+        }
+        let mut schemas = self.schemas_mut();
         let mut existing = Vec::new();
         for s in schemas_to_add {
             match schemas.insert(s.clone()) {
@@ -237,13 +2196,18 @@ impl Interrogator {
         }
         for s in schemas_to_update {
             if let Err(err) = s.setup(self) {
-                let mut schemas = self.schemas.write();
+                let mut schemas = self.schemas_mut();
                 schemas.rollback();
                 return Err(err);
             }
         }
-        let mut schemas = self.schemas.write();
+        let mut schemas = self.schemas_mut();
         schemas.commit();
+        drop(schemas);
+        for s in schemas_to_add {
+            self.report_diagnostics(s);
+        }
+        self.report(anchor_diagnostics);
         if existing.is_empty() {
             Ok(None)
         } else {
@@ -251,6 +2215,369 @@ impl Interrogator {
         }
     }
 
+    /// Resolves and compiles the [`Schema`] at each of `uris` as a single
+    /// batch, returning the id of each in the order the URIs were provided.
+    ///
+    /// Each source document, and each document referenced by one directly or
+    /// transitively, is taken from the sources of this `Interrogator` or
+    /// otherwise resolved once with [`resolve`](Self::resolve) and added as
+    /// a source, so that documents shared between schemas of the batch are
+    /// not resolved repeatedly. URIs which occur more than once are compiled
+    /// once. A document without an `"$id"` is identified by its URI.
+    ///
+    /// The batch is transactional: if any URI fails, no [`Schema`] is added
+    /// and the sources resolved for the batch are removed.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Error, Interrogator};
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let err = interrogator
+    ///     .compile_all(["https://example.com/missing.json", "not a uri"])
+    ///     .unwrap_err();
+    /// let failures = match err {
+    ///     Error::CompileAll(err) => err.failures,
+    ///     err => panic!("unexpected error: {}", err),
+    /// };
+    /// assert_eq!(failures.len(), 2);
+    /// assert_eq!(failures[0].uri, "https://example.com/missing.json");
+    /// assert_eq!(failures[1].uri, "not a uri");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`Error::CompileAll`], listing each URI which could not be
+    /// parsed, resolved, or compiled, or the error of
+    /// [`insert_schemas`](Self::insert_schemas) if the batch could not be
+    /// added.
+    pub fn compile_all<I>(&self, uris: I) -> Result<Vec<Uri>, Error>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let uris: Vec<String> = uris.into_iter().map(|u| u.as_ref().to_string()).collect();
+        self.compile_batch(&uris, |batch| self.insert_schemas(batch))
+    }
+
+    /// Compiles the schemas at `uris` as with [`compile_all`](Self::compile_all),
+    /// adding the batch with `insert`.
+    fn compile_batch(
+        &self,
+        uris: &[String],
+        insert: impl FnOnce(&[Schema]) -> Result<Option<Vec<Schema>>, Error>,
+    ) -> Result<Vec<Uri>, Error> {
+        let mut failures = Vec::new();
+        let fail = |uri: &str, error: Error| CompileFailure {
+            uri: uri.to_string(),
+            error,
+        };
+        // sources and dialects added by this batch, removed should it fail
+        let mut added: Vec<AbsoluteUri> = Vec::new();
+        let mut dialects: Vec<Uri> = Vec::new();
+        let mut visited: HashSet<AbsoluteUri> = HashSet::new();
+        let mut parsed: Vec<Option<AbsoluteUri>> = Vec::with_capacity(uris.len());
+        for uri in uris {
+            let absolute = match AbsoluteUri::parse(uri) {
+                Ok(absolute) => absolute,
+                Err(err) => {
+                    failures.push(fail(uri, err.into()));
+                    parsed.push(None);
+                    continue;
+                }
+            };
+            let mut queue = vec![source::without_fragment(&absolute)];
+            while let Some(document_uri) = queue.pop() {
+                if !visited.insert(document_uri.clone()) {
+                    continue;
+                }
+                let document = match self.sources.get(&document_uri) {
+                    Ok(Some(document)) => document,
+                    Ok(None) => match self.resolve(&document_uri.to_string()) {
+                        Ok(document) => {
+                            self.sources.insert_from(
+                                document_uri.clone(),
+                                document,
+                                Provenance::Resolved,
+                            );
+                            added.push(document_uri.clone());
+                            match self.sources.get(&document_uri) {
+                                Ok(document) => document.expect("document was just inserted"),
+                                Err(err) => {
+                                    failures.push(fail(uri, err));
+                                    continue;
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            failures.push(fail(uri, Error::from_resolve(err)));
+                            continue;
+                        }
+                    },
+                    Err(err) => {
+                        failures.push(fail(uri, err));
+                        continue;
+                    }
+                };
+                if let Err(err) = self.construct_dialect(&document_uri, &document, &mut dialects) {
+                    failures.push(fail(uri, err));
+                    continue;
+                }
+                for dependency in source::dependencies(&document_uri, &document) {
+                    if !self.is_known_meta_schema(dependency.uri())
+                        && !self.is_embedded(&dependency)
+                    {
+                        queue.push(dependency);
+                    }
+                }
+            }
+            parsed.push(Some(absolute));
+        }
+
+        let mut compiled: HashMap<AbsoluteUri, Schema> = HashMap::new();
+        let mut batch = Vec::new();
+        if failures.is_empty() {
+            for (uri, absolute) in uris.iter().zip(&parsed) {
+                let absolute = absolute.as_ref().expect("each uri was parsed");
+                if compiled.contains_key(absolute) {
+                    continue;
+                }
+                let schema = match self.locate(absolute) {
+                    Ok(Some(source)) => Schema::initialized(source, None, self),
+                    Ok(None) => Err(Error::from_resolve(ResolveError::NotFound(uri.clone()))),
+                    Err(err) => Err(err),
+                };
+                match schema {
+                    Ok(schema) => {
+                        if schema.id().is_none() {
+                            schema.set_id(absolute.uri().clone());
+                        }
+                        compiled.insert(absolute.clone(), schema.clone());
+                        batch.push(schema);
+                    }
+                    Err(err) => failures.push(fail(uri, err)),
+                }
+            }
+        }
+        let result = if failures.is_empty() {
+            insert(&batch)
+        } else {
+            Err(CompileAllError { failures }.into())
+        };
+        if let Err(err) = result {
+            for uri in &added {
+                self.sources.remove(uri);
+            }
+            for id in &dialects {
+                self.dialects.remove(id);
+            }
+            return Err(err);
+        }
+        Ok(parsed
+            .iter()
+            .flatten()
+            .map(|absolute| {
+                let id = compiled[absolute].id().expect("schema was identified");
+                id.as_ref().clone()
+            })
+            .collect())
+    }
+
+    /// Imports the [`Schema`] identified by each of `keys` from `other`,
+    /// along with the source documents it requires, returning the key of
+    /// each in `other` paired with its id in this `Interrogator`.
+    ///
+    /// The source document of each `Schema` and each document it references,
+    /// directly or transitively, is copied from `other`, with its references
+    /// and identifiers rewritten according to the URI prefix `mapping` as by
+    /// [`rewrite_refs`](Self::rewrite_refs); the sources of `other` are not
+    /// modified. Documents which `other` does not have, and meta schemas
+    /// known to this `Interrogator`, are not copied. The [`Dialect`] of each
+    /// meta schema which only `other` knows is registered with this
+    /// `Interrogator`. The schemas are then compiled as a single batch as by
+    /// [`compile_all`](Self::compile_all), so that they are set up against
+    /// the settings of this `Interrogator`.
+    ///
+    /// The import is transactional: each document is rewritten and checked
+    /// against the sources of this `Interrogator` before any is added, and
+    /// if compilation fails, the copied sources and dialects are removed.
+    /// Other schemas and sources can not be added while the import is in
+    /// progress.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Interrogator, Uri};
+    ///
+    /// let base = Interrogator::new();
+    /// let service = Interrogator::new();
+    /// let key = Uri::parse("https://example.com/missing.json").unwrap();
+    /// let result = service.import(&base, &[key], &[]);
+    /// assert!(matches!(result, Err(err) if err.is_unknown_schema()));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`UnknownSchemaError`] if a key does not identify a `Schema`
+    /// of `other`, [`Error::InvalidUri`] if a mapped URI is not absolute,
+    /// [`Error::ImportConflict`] if this `Interrogator` has a different
+    /// source at the URI a document is copied to, or the error of
+    /// [`compile_all`](Self::compile_all).
+    pub fn import(
+        &self,
+        other: &Interrogator,
+        keys: &[Uri],
+        mapping: &[(&str, &str)],
+    ) -> Result<Vec<(Uri, Uri)>, Error> {
+        #[allow(unused_variables)]
+        let g = self.lock.lock();
+        let map = |uri: &str| rewrite::map_uri(uri, mapping).unwrap_or_else(|| uri.to_string());
+        let mut ids = Vec::with_capacity(keys.len());
+        let mut queue = Vec::with_capacity(keys.len());
+        for key in keys {
+            let schema = other
+                .schema(key)
+                .ok_or_else(|| UnknownSchemaError { id: key.clone() })?;
+            let id = schema
+                .id()
+                .as_deref()
+                .cloned()
+                .unwrap_or_else(|| key.clone());
+            let absolute = AbsoluteUri::parse(&id.to_string())?;
+            queue.push(source::without_fragment(&absolute));
+            ids.push(map(&id.to_string()));
+        }
+        let mut documents = Vec::new();
+        let mut dialects: HashMap<Uri, Arc<Dialect>> = HashMap::new();
+        let mut visited = HashSet::new();
+        while let Some(uri) = queue.pop() {
+            if !visited.insert(uri.clone()) {
+                continue;
+            }
+            let document = match other.sources.get(&uri)? {
+                Some(document) => document,
+                None => continue,
+            };
+            for dependency in source::dependencies(&uri, &document) {
+                if self.is_known_meta_schema(dependency.uri()) {
+                    continue;
+                }
+                match other.dialect(dependency.uri()) {
+                    Some(dialect) => {
+                        dialects.insert(dependency.uri().clone(), dialect);
+                    }
+                    None => queue.push(dependency),
+                }
+            }
+            documents.push((uri, document));
+        }
+
+        // each document is rewritten and checked before any is added
+        let mut rewritten: Vec<(AbsoluteUri, Value)> = Vec::with_capacity(documents.len());
+        for (uri, document) in documents {
+            let mut document = document.as_ref().clone();
+            rewrite::rewrite_document(&uri, &mut document, mapping);
+            let target = AbsoluteUri::parse(&map(&uri.to_string()))?;
+            let existing = match self.sources.get(&target)? {
+                Some(existing) => Some(existing.as_ref().clone()),
+                None => rewritten
+                    .iter()
+                    .find(|(uri, _)| *uri == target)
+                    .map(|(_, document)| document.clone()),
+            };
+            match existing {
+                Some(existing) if existing == document => {}
+                Some(_) => return Err(ImportConflictError { uri: target }.into()),
+                None => rewritten.push((target, document)),
+            }
+        }
+
+        for (id, dialect) in &dialects {
+            self.dialects.insert(id.clone(), dialect.clone());
+        }
+        let mut added = Vec::with_capacity(rewritten.len());
+        for (uri, document) in rewritten {
+            self.sources
+                .insert_from(uri.clone(), document, Provenance::Imported);
+            added.push(uri);
+        }
+        match self.compile_batch(&ids, |batch| self.insert_schemas_locked(batch)) {
+            Ok(compiled) => Ok(keys.iter().cloned().zip(compiled).collect()),
+            Err(err) => {
+                for uri in &added {
+                    self.sources.remove(uri);
+                }
+                for id in dialects.keys() {
+                    self.dialects.remove(id);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Resolves each source document required by the schemas at `uris`
+    /// which is not yet a source with [`resolve_async`](Self::resolve_async),
+    /// adding it as a source, so that [`compile_all`](Self::compile_all)
+    /// finds them without resolving synchronously.
+    ///
+    /// # Errors
+    /// Returns [`Error::CompileAll`], listing each URI which could not be
+    /// parsed or resolved, as [`compile_all`](Self::compile_all) would.
+    #[cfg(feature = "async")]
+    pub(crate) async fn resolve_all_async(&self, uris: &[String]) -> Result<(), Error> {
+        let mut failures = Vec::new();
+        let mut visited = HashSet::new();
+        for uri in uris {
+            let absolute = match AbsoluteUri::parse(uri) {
+                Ok(absolute) => absolute,
+                Err(err) => {
+                    failures.push(CompileFailure {
+                        uri: uri.clone(),
+                        error: err.into(),
+                    });
+                    continue;
+                }
+            };
+            let mut queue = vec![source::without_fragment(&absolute)];
+            while let Some(document_uri) = queue.pop() {
+                if !visited.insert(document_uri.clone()) {
+                    continue;
+                }
+                let document = match self.sources.get(&document_uri)? {
+                    Some(document) => document,
+                    None => match self.resolve_async(&document_uri.to_string()).await {
+                        Ok(document) => {
+                            self.sources.insert_from(
+                                document_uri.clone(),
+                                document,
+                                Provenance::Resolved,
+                            );
+                            self.sources
+                                .get(&document_uri)?
+                                .expect("document was just inserted")
+                        }
+                        Err(err) => {
+                            failures.push(CompileFailure {
+                                uri: uri.clone(),
+                                error: Error::from_resolve(err),
+                            });
+                            continue;
+                        }
+                    },
+                };
+                for dependency in source::dependencies(&document_uri, &document) {
+                    if !self.is_known_meta_schema(dependency.uri())
+                        && !self.is_embedded(&dependency)
+                    {
+                        queue.push(dependency);
+                    }
+                }
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(CompileAllError { failures }.into())
+        }
+    }
+
     //     pub fn add_applicator(&self, applicator: impl Applicator + 'static) -> Result<(), Error> {
     //         #[allow(unused_variables)]
     //         let lock = self.lock.lock();
@@ -299,7 +2626,7 @@ impl Default for Interrogator {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Schemas {
     current: HashMap<Uri, Schema>,
     pending: HashMap<Uri, Schema>,
@@ -328,6 +2655,10 @@ impl Schemas {
             Err(UnidentifiedSchemaError { schema })
         }
     }
+    fn remove_where(&mut self, f: impl Fn(&Uri) -> bool) {
+        self.current.retain(|id, _| !f(id));
+        self.pending.retain(|id, _| !f(id));
+    }
     fn values(&self) -> Vec<Schema> {
         let mut set = HashSet::new();
         for s in self.pending.values() {
@@ -357,6 +2688,7 @@ impl Schemas {
     }
 }
 
+#[derive(Clone)]
 struct MetaSchemas {
     current: HashMap<Uri, MetaSchema>,
     pending: HashMap<Uri, MetaSchema>,
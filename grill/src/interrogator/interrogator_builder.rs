@@ -0,0 +1,245 @@
+use crate::{
+    mirror::Mirrors,
+    plugin::{PluginFn, Registrar},
+    BoxedResolver, Error, Interrogator, Resolver,
+};
+use serde_json::Value;
+use uniresid::{AbsoluteUri, Uri};
+
+/// A fluent builder of an [`Interrogator`], returned by
+/// [`Interrogator::builder`].
+///
+/// Plugins, the default dialect, source documents, resolvers, and the
+/// schemas to compile are collected as the builder is configured; nothing is
+/// registered or compiled until [`finish`](Self::finish) (or its synchronous
+/// counterpart, [`build`](Self::build)) is called. Plugins are run first,
+/// then the settings, sources, and resolvers are applied, and finally the
+/// schemas are compiled as a single batch with
+/// [`Interrogator::compile_all`].
+///
+/// ## Example
+/// ```
+/// use grill::{uri::AbsoluteUri, Error, Interrogator};
+/// use serde_json::json;
+///
+/// async fn interrogator() -> Result<Interrogator, Error> {
+///     let uri = AbsoluteUri::parse("https://example.com/string.json")?;
+///     Interrogator::builder()
+///         .json_schema_2020_12()
+///         .source(uri, json!({ "type": "string" }))
+///         .compile("https://example.com/string.json")
+///         .finish()
+///         .await
+/// }
+/// ```
+#[derive(Default)]
+pub struct InterrogatorBuilder {
+    plugins: Vec<Box<PluginFn>>,
+    max_reference_depth: Option<Option<usize>>,
+    mirrors: Mirrors,
+    default_meta_schema: Option<Uri>,
+    base_uri: Option<AbsoluteUri>,
+    sources: Vec<(AbsoluteUri, Value)>,
+    resolvers: Vec<BoxedResolver>,
+    #[cfg(feature = "async")]
+    async_resolvers: Vec<crate::BoxedAsyncResolver>,
+    compile: Vec<String>,
+    #[cfg(feature = "vendor")]
+    locked_resolver: Option<crate::vendor::LockedResolver>,
+}
+
+impl InterrogatorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a plugin which registers drafts, dialects, or vocabularies with
+    /// the [`Interrogator`] upon [`build`](InterrogatorBuilder::build).
+    ///
+    /// Plugins are run in the order they are added.
+    #[must_use]
+    pub fn plugin(
+        mut self,
+        plugin: impl FnOnce(&mut Registrar) -> Result<(), Error> + 'static,
+    ) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    /// Sets the maximum length of a chain of references between documents.
+    /// See [`Interrogator::set_max_reference_depth`].
+    #[must_use]
+    pub fn max_reference_depth(mut self, max: Option<usize>) -> Self {
+        self.max_reference_depth = Some(max);
+        self
+    }
+
+    /// Maps URIs beginning with `prefix` to `mirror` before they are
+    /// resolved. See [`mirror`](crate::mirror).
+    #[must_use]
+    pub fn mirror(mut self, prefix: impl Into<String>, mirror: impl Into<String>) -> Self {
+        self.mirrors.insert(prefix, mirror);
+        self
+    }
+
+    /// Sets the meta schema of the dialect of schemas which do not declare
+    /// one with `"$schema"`. See [`Interrogator::set_default_meta_schema`].
+    #[must_use]
+    pub fn default_meta_schema(mut self, uri: Uri) -> Self {
+        self.default_meta_schema = Some(uri);
+        self
+    }
+
+    /// Uses JSON Schema 2020-12 as the default dialect.
+    #[cfg(feature = "draft-2020-12")]
+    #[must_use]
+    pub fn json_schema_2020_12(self) -> Self {
+        self.default_meta_schema(crate::draft::SCHEMA_2020_12_URI.clone())
+    }
+
+    /// Uses JSON Schema 2019-09 as the default dialect.
+    #[cfg(feature = "draft-2019-09")]
+    #[must_use]
+    pub fn json_schema_2019_09(self) -> Self {
+        self.default_meta_schema(crate::draft::SCHEMA_2019_09_URI.clone())
+    }
+
+    /// Uses JSON Schema draft 07 as the default dialect.
+    #[cfg(feature = "draft-07")]
+    #[must_use]
+    pub fn json_schema_07(self) -> Self {
+        self.default_meta_schema(crate::draft::SCHEMA_07_URI.clone())
+    }
+
+    /// Uses JSON Schema draft 04 as the default dialect.
+    #[cfg(feature = "draft-04")]
+    #[must_use]
+    pub fn json_schema_04(self) -> Self {
+        self.default_meta_schema(crate::draft::SCHEMA_04_URI.clone())
+    }
+
+    /// Sets the base URI of relative URIs. See
+    /// [`Interrogator::set_base_uri`].
+    #[must_use]
+    pub fn base_uri(mut self, uri: AbsoluteUri) -> Self {
+        self.base_uri = Some(uri);
+        self
+    }
+
+    /// Adds `document` as the source at `uri`. See
+    /// [`Interrogator::source_value`].
+    #[must_use]
+    pub fn source(mut self, uri: AbsoluteUri, document: Value) -> Self {
+        self.sources.push((uri, document));
+        self
+    }
+
+    /// Registers a [`Resolver`], consulted after those previously added.
+    /// See [`Interrogator::add_resolver`].
+    #[must_use]
+    pub fn resolver(mut self, resolver: impl Resolver + 'static) -> Self {
+        self.resolvers.push(Box::new(resolver));
+        self
+    }
+
+    /// Registers an [`AsyncResolver`](crate::AsyncResolver), consulted by
+    /// [`finish`](Self::finish) before the [`Resolver`]s. See
+    /// [`Interrogator::add_async_resolver`].
+    #[cfg(feature = "async")]
+    #[must_use]
+    pub fn async_resolver(mut self, resolver: impl crate::AsyncResolver + 'static) -> Self {
+        self.async_resolvers.push(Box::new(resolver));
+        self
+    }
+
+    /// Compiles the schema at `uri` once the [`Interrogator`] is built.
+    #[must_use]
+    pub fn compile(mut self, uri: impl Into<String>) -> Self {
+        self.compile.push(uri.into());
+        self
+    }
+
+    /// Resolves documents solely with `resolver`. See
+    /// [`Interrogator::set_locked_resolver`].
+    #[cfg(feature = "vendor")]
+    #[must_use]
+    pub fn locked_resolver(mut self, resolver: crate::vendor::LockedResolver) -> Self {
+        self.locked_resolver = Some(resolver);
+        self
+    }
+
+    /// Builds the [`Interrogator`] and compiles each schema added with
+    /// [`compile`](Self::compile), resolving the documents they require
+    /// with the registered [`AsyncResolver`](crate::AsyncResolver)s, if the
+    /// `"async"` feature is enabled, and [`Resolver`]s.
+    ///
+    /// # Errors
+    /// Returns the error of a plugin which fails, [`Error::MetaSchema`] if
+    /// the default meta schema is not known, or the error of
+    /// [`Interrogator::compile_all`]. A URI which can not be parsed or
+    /// resolved is reported in an [`Error::CompileAll`] just as `compile_all`
+    /// reports it, whether or not the `"async"` feature is enabled.
+    pub async fn finish(self) -> Result<Interrogator, Error> {
+        let compile = self.compile.clone();
+        let interrogator = self.configure()?;
+        if compile.is_empty() {
+            return Ok(interrogator);
+        }
+        #[cfg(feature = "async")]
+        interrogator.resolve_all_async(&compile).await?;
+        interrogator.compile_all(&compile)?;
+        Ok(interrogator)
+    }
+
+    /// Builds the [`Interrogator`], running each plugin, and compiles each
+    /// schema added with [`compile`](Self::compile), resolving the documents
+    /// they require with the registered [`Resolver`]s. See
+    /// [`finish`](Self::finish).
+    ///
+    /// # Errors
+    /// See [`finish`](Self::finish).
+    pub fn build(self) -> Result<Interrogator, Error> {
+        let compile = self.compile.clone();
+        let interrogator = self.configure()?;
+        if !compile.is_empty() {
+            interrogator.compile_all(&compile)?;
+        }
+        Ok(interrogator)
+    }
+
+    fn configure(self) -> Result<Interrogator, Error> {
+        let interrogator = Interrogator::new();
+        let mut registrar = Registrar::default();
+        for plugin in self.plugins {
+            plugin(&mut registrar)?;
+        }
+        interrogator.register(registrar)?;
+        for (prefix, mirror) in self.mirrors.iter() {
+            interrogator.add_mirror(prefix, mirror);
+        }
+        if let Some(max) = self.max_reference_depth {
+            interrogator.set_max_reference_depth(max);
+        }
+        if let Some(uri) = self.default_meta_schema {
+            interrogator.set_default_meta_schema(uri)?;
+        }
+        if let Some(uri) = self.base_uri {
+            interrogator.set_base_uri(uri);
+        }
+        for (uri, document) in self.sources {
+            interrogator.source_value(uri, document);
+        }
+        for resolver in self.resolvers {
+            interrogator.add_resolver(resolver);
+        }
+        #[cfg(feature = "async")]
+        for resolver in self.async_resolvers {
+            interrogator.add_async_resolver(resolver);
+        }
+        #[cfg(feature = "vendor")]
+        if let Some(resolver) = self.locked_resolver {
+            interrogator.set_locked_resolver(Some(resolver));
+        }
+        Ok(interrogator)
+    }
+}
@@ -0,0 +1,139 @@
+//! [`Applicator`](crate::Applicator) implementations of JSON Schema keywords.
+//!
+//! Each keyword is exposed as a function which can be used directly as an
+//! [`Applicator`](crate::Applicator). Keywords which constrain an instance
+//! also provide a [`Describer`](crate::describe::Describer), named
+//! `describe_<keyword>`.
+
+use crate::{applicator::SetupFn, Applicator, Error, Interrogator, Schema};
+
+mod range;
+pub use range::{describe_range, exclusive_maximum, exclusive_minimum, maximum, minimum};
+
+mod bson_type;
+pub use bson_type::{bson_type, describe_bson_type, BSON_TYPES};
+
+mod enumeration;
+pub(crate) use enumeration::equals;
+pub use enumeration::{const_, enum_, suggestions};
+
+mod format;
+pub use format::format;
+
+mod reference;
+pub use reference::ref_;
+
+#[cfg(feature = "draft-2020-12")]
+mod dynamic_reference;
+#[cfg(feature = "draft-2020-12")]
+pub use dynamic_reference::dynamic_ref;
+
+mod one_of;
+pub use one_of::one_of;
+
+mod pattern;
+pub use pattern::{pattern, pattern_properties};
+
+mod unique_items;
+pub use unique_items::unique_items;
+
+mod custom;
+pub use custom::{custom, Custom};
+
+mod format_range;
+pub use format_range::{
+    describe_format_range, format_exclusive_maximum, format_exclusive_minimum, format_maximum,
+    format_minimum, format_range, format_range_vocabulary, FORMAT_RANGE_KEYWORDS,
+    FORMAT_RANGE_VOCABULARY_URI,
+};
+
+mod content;
+pub(crate) use content::push_content_annotations;
+pub use content::{
+    content, content_annotation, content_assertion, content_assertion_vocabulary,
+    content_vocabulary, CONTENT_ASSERTION_VOCABULARY_URI, CONTENT_VOCABULARY_URI,
+};
+
+mod meta_data;
+pub(crate) use meta_data::push_meta_data_keywords;
+pub use meta_data::{
+    meta_data, meta_data_vocabulary, META_DATA_KEYWORDS, META_DATA_VOCABULARY_URI,
+};
+
+mod array_extension;
+pub use array_extension::{
+    array_extension, array_extension_vocabulary, describe_array_extension, sort_order, unique_keys,
+    ARRAY_EXTENSION_KEYWORDS, ARRAY_EXTENSION_VOCABULARY_URI,
+};
+
+/// Keywords which apply subschemas to the instance or its children, as
+/// opposed to asserting or annotating the instance directly.
+pub const APPLICATOR_KEYWORDS: &[&str] = &[
+    "$ref",
+    "$dynamicRef",
+    "$recursiveRef",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "if",
+    "then",
+    "else",
+    "dependentSchemas",
+    "dependencies",
+    "prefixItems",
+    "items",
+    "additionalItems",
+    "contains",
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Associates an [`Applicator`] with the keyword it implements so that it can
+/// be ordered by a [`KeywordOrder`](crate::dialect::KeywordOrder).
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{maximum, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("maximum", maximum);
+/// assert_eq!(applicator.keyword(), Some("maximum"));
+/// ```
+#[derive(Clone)]
+pub struct Keyword<A> {
+    name: &'static str,
+    applicator: A,
+}
+
+impl<A> Keyword<A> {
+    /// Creates a new `Keyword` named `name`.
+    pub fn new(name: &'static str, applicator: A) -> Self {
+        Self { name, applicator }
+    }
+
+    /// Returns the name of the keyword.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<A> Applicator for Keyword<A>
+where
+    A: Applicator + Clone + 'static,
+{
+    fn init(
+        &self,
+        interrogator: Interrogator,
+        schema: Schema,
+    ) -> Result<Option<Box<SetupFn>>, Error> {
+        self.applicator.init(interrogator, schema)
+    }
+
+    fn keyword(&self) -> Option<&'static str> {
+        Some(self.name)
+    }
+}
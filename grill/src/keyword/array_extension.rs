@@ -0,0 +1,295 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    keyword::{
+        unique_items::{equals, find_duplicate},
+        Keyword,
+    },
+    plugin::Registrar,
+    Error, Evaluation, Interrogator, Next, Numbers, Precision, Schema, Vocabulary,
+};
+use jsonptr::Pointer;
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use uniresid::Uri;
+
+/// The [Uri] of the vocabulary implementing `"uniqueKeys"` and
+/// `"sortOrder"`.
+///
+/// The vocabulary is not part of the JSON Schema specification; this
+/// identifier is specific to grill.
+pub static ARRAY_EXTENSION_VOCABULARY_URI: Lazy<Uri> =
+    Lazy::new(|| Uri::parse("https://github.com/chanced/grill/vocab/array-extension").unwrap());
+
+/// Keywords of the array extension vocabulary.
+pub const ARRAY_EXTENSION_KEYWORDS: &[&str] = &["uniqueKeys", "sortOrder"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Direction {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ascending" => Some(Direction::Ascending),
+            "descending" => Some(Direction::Descending),
+            _ => None,
+        }
+    }
+    fn is_satisfied_by(self, ord: Ordering) -> bool {
+        match self {
+            Direction::Ascending => ord != Ordering::Greater,
+            Direction::Descending => ord != Ordering::Less,
+        }
+    }
+    fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ascending => "ascending",
+            Direction::Descending => "descending",
+        }
+    }
+}
+
+/// Plugin which registers the array extension vocabulary.
+///
+/// The vocabulary provides two assertions on array instances:
+///
+/// - `"uniqueKeys"`, an array of JSON Pointers, requires that no two items
+///   have equal values at every pointer. Items lacking a value at any of the
+///   pointers are not compared.
+/// - `"sortOrder"`, either `"ascending"` or `"descending"`, or an object with
+///   a `"direction"` and an optional `"key"` pointer, requires that the items,
+///   or the values at `"key"` within them, are ordered. Numbers are compared
+///   with numbers and strings with strings; other pairs are not compared.
+///
+/// Items are indexed by their keys, so `"uniqueKeys"` is linear in the
+/// length of the array. Numbers are compared exactly, with the
+/// [`Precision`] of the [`Interrogator`] through its [`Numbers`] cache.
+///
+/// Add [`array_extension_vocabulary`] to a [`Dialect`](crate::Dialect) to
+/// enable the keywords for its schemas.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::array_extension, Interrogator};
+///
+/// let interrogator = Interrogator::builder().plugin(array_extension).build().unwrap();
+/// ```
+pub fn array_extension(registrar: &mut Registrar) -> Result<(), Error> {
+    registrar.vocabulary(array_extension_vocabulary());
+    Ok(())
+}
+
+/// Returns the [`Vocabulary`] of `"uniqueKeys"` and `"sortOrder"`.
+pub fn array_extension_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::new(ARRAY_EXTENSION_VOCABULARY_URI.clone());
+    vocabulary.push(Keyword::new("uniqueKeys", unique_keys));
+    vocabulary.push(Keyword::new("sortOrder", sort_order));
+    vocabulary
+}
+
+/// [`Applicator`](crate::Applicator) for the `"uniqueKeys"` keyword.
+pub fn unique_keys(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    let value = match source.get("uniqueKeys") {
+        None => return Ok(None),
+        Some(value) => value,
+    };
+    let keys = match value.as_array().and_then(|keys| pointers(keys)) {
+        Some(keys) if !keys.is_empty() => keys,
+        _ => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "uniqueKeys".to_string(),
+                expected: "a non-empty array of JSON Pointers".to_string(),
+                value: value.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, _: &Schema| {
+            let (numbers, precision) = (interrogator.numbers(), interrogator.precision());
+            Ok(unique_keys_executor(keys.clone(), numbers, precision))
+        },
+    )))
+}
+
+/// [`Applicator`](crate::Applicator) for the `"sortOrder"` keyword.
+pub fn sort_order(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    let value = match source.get("sortOrder") {
+        None => return Ok(None),
+        Some(value) => value,
+    };
+    let (direction, key) = match parse_sort_order(value) {
+        Some(order) => order,
+        None => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "sortOrder".to_string(),
+                expected: "\"ascending\", \"descending\", or an object with a \"direction\" \
+                           and an optional \"key\" JSON Pointer"
+                    .to_string(),
+                value: value.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, _: &Schema| {
+            let (numbers, precision) = (interrogator.numbers(), interrogator.precision());
+            Ok(sort_order_executor(
+                direction,
+                key.clone(),
+                numbers,
+                precision,
+            ))
+        },
+    )))
+}
+
+/// [`Describer`](crate::describe::Describer) for the array extension
+/// keywords, e.g. `"unique by /id, sorted ascending by /date"`.
+pub fn describe_array_extension(object: &Map<String, Value>) -> Option<String> {
+    let mut parts = Vec::new();
+    if let Some(keys) = object
+        .get("uniqueKeys")
+        .and_then(Value::as_array)
+        .and_then(|keys| pointers(keys))
+    {
+        parts.push(format!("unique by {}", keys.join(" and ")));
+    }
+    if let Some((direction, key)) = object.get("sortOrder").and_then(parse_sort_order) {
+        match key {
+            Some(key) => parts.push(format!("sorted {} by {}", direction.as_str(), key)),
+            None => parts.push(format!("sorted {}", direction.as_str())),
+        }
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
+fn pointers(values: &[Value]) -> Option<Vec<String>> {
+    values
+        .iter()
+        .map(|value| {
+            let s = value.as_str()?;
+            Pointer::try_from(s).ok()?;
+            Some(s.to_string())
+        })
+        .collect()
+}
+
+fn parse_sort_order(value: &Value) -> Option<(Direction, Option<String>)> {
+    match value {
+        Value::String(direction) => Some((Direction::parse(direction)?, None)),
+        Value::Object(obj) => {
+            let direction = Direction::parse(obj.get("direction")?.as_str()?)?;
+            let key = match obj.get("key") {
+                None => None,
+                Some(key) => Some(pointers(std::slice::from_ref(key))?.remove(0)),
+            };
+            Some((direction, key))
+        }
+        _ => None,
+    }
+}
+
+fn unique_keys_executor(
+    keys: Vec<String>,
+    numbers: Numbers,
+    precision: Precision,
+) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Some(items) = value.as_array() {
+                // items lacking a value at any of the keys are not compared
+                let keyed = items.iter().enumerate().filter_map(|(i, item)| {
+                    let values: Option<Vec<&Value>> =
+                        keys.iter().map(|key| item.pointer(key)).collect();
+                    Some((i, values?))
+                });
+                let duplicate = find_duplicate(keyed, |a, b| equals(a, b, &numbers, precision));
+                if let Some((j, i)) = duplicate {
+                    let mut kw = eval.for_keyword("uniqueKeys");
+                    kw.set_error_kind("duplicate");
+                    kw.set_error_param("first", j);
+                    kw.set_error_param("second", i);
+                    kw.set_error_param("keys", keys.clone());
+                    kw.set_error(format!(
+                        "items {} and {} have the same value at {}",
+                        j,
+                        i,
+                        keys.join(" and ")
+                    ));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+fn sort_order_executor(
+    direction: Direction,
+    key: Option<String>,
+    numbers: Numbers,
+    precision: Precision,
+) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Some(items) = value.as_array() {
+                let sort_keys: Vec<Option<&Value>> = items
+                    .iter()
+                    .map(|item| match &key {
+                        Some(key) => item.pointer(key),
+                        None => Some(item),
+                    })
+                    .collect();
+                for (i, pair) in sort_keys.windows(2).enumerate() {
+                    let ord = match (pair[0], pair[1]) {
+                        (Some(a), Some(b)) => compare(a, b, &numbers, precision),
+                        _ => None,
+                    };
+                    if matches!(ord, Some(ord) if !direction.is_satisfied_by(ord)) {
+                        let mut kw = eval.for_keyword("sortOrder");
+                        kw.set_error_kind("unordered");
+                        kw.set_error_param("first", i);
+                        kw.set_error_param("second", i + 1);
+                        kw.set_error_param("direction", direction.as_str());
+                        kw.set_error(format!(
+                            "items {} and {} are not in {} order",
+                            i,
+                            i + 1,
+                            direction.as_str()
+                        ));
+                        eval.push(kw);
+                        break;
+                    }
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+fn compare(a: &Value, b: &Value, numbers: &Numbers, precision: Precision) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => numbers.compare(a, b, precision),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
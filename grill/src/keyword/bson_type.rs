@@ -0,0 +1,166 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+/// BSON type aliases accepted by the `"bsonType"` keyword.
+pub const BSON_TYPES: &[&str] = &[
+    "double",
+    "string",
+    "object",
+    "array",
+    "binData",
+    "objectId",
+    "bool",
+    "date",
+    "null",
+    "regex",
+    "javascript",
+    "int",
+    "timestamp",
+    "long",
+    "decimal",
+    "minKey",
+    "maxKey",
+    "number",
+];
+
+/// [`Applicator`](crate::Applicator) for MongoDB's `"bsonType"` keyword.
+///
+/// Instances are expected to be in MongoDB Extended JSON. Numeric aliases
+/// accept both JSON numbers and their Extended JSON wrappers (e.g.
+/// `{"$numberLong": "1"}`); the remaining BSON-specific types are only
+/// matched by their Extended JSON form (e.g. `{"$oid": "..."}`). An `"int"`
+/// or `"long"` must be within the bounds of an `i32` or `i64` respectively.
+pub fn bson_type(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let invalid = |value: &Value| -> Error {
+        InvalidKeywordError {
+            schema: schema.clone(),
+            keyword: "bsonType".to_string(),
+            expected: "a BSON type alias or an array of BSON type aliases".to_string(),
+            value: value.clone(),
+        }
+        .into()
+    };
+    let aliases: Vec<String> = match schema.source().get("bsonType") {
+        None => return Ok(None),
+        Some(Value::String(alias)) => vec![alias.clone()],
+        Some(Value::Array(aliases)) => aliases
+            .iter()
+            .map(|a| a.as_str().map(String::from).ok_or_else(|| invalid(a)))
+            .collect::<Result<_, _>>()?,
+        Some(v) => return Err(invalid(v)),
+    };
+    if let Some(unknown) = aliases.iter().find(|a| !BSON_TYPES.contains(&a.as_str())) {
+        return Err(invalid(&Value::String(unknown.clone())));
+    }
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(executor(aliases.clone()))
+    })))
+}
+
+/// [`Describer`](crate::describe::Describer) for the `"bsonType"` keyword,
+/// e.g. `"BSON objectId or null"`.
+pub fn describe_bson_type(object: &Map<String, Value>) -> Option<String> {
+    match object.get("bsonType")? {
+        Value::String(alias) => Some(format!("BSON {}", alias)),
+        Value::Array(aliases) => {
+            let aliases: Vec<&str> = aliases.iter().filter_map(Value::as_str).collect();
+            Some(format!("BSON {}", aliases.join(" or ")))
+        }
+        _ => None,
+    }
+}
+
+fn executor(aliases: Vec<String>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if !aliases.iter().any(|alias| is_bson_type(value, alias)) {
+                let mut kw = eval.for_keyword("bsonType");
+                kw.set_error_kind("mismatch");
+                kw.set_error_param("expected", aliases.clone());
+                kw.set_error(format!(
+                    "{} is not of bsonType {}",
+                    value,
+                    aliases.join(" or ")
+                ));
+                eval.push(kw);
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+/// Returns `true` if `value`, in MongoDB Extended JSON, is of the BSON type
+/// `alias`.
+fn is_bson_type(value: &Value, alias: &str) -> bool {
+    let wrapper =
+        |key: &str| matches!(value, Value::Object(obj) if obj.len() == 1 && obj.contains_key(key));
+    match alias {
+        "double" => value.is_number() || wrapper("$numberDouble"),
+        "int" => fits::<i32>(value, "$numberInt"),
+        "long" => fits::<i64>(value, "$numberLong"),
+        "decimal" => value.is_number() || wrapper("$numberDecimal"),
+        "number" => {
+            value.is_number()
+                || [
+                    "$numberDouble",
+                    "$numberInt",
+                    "$numberLong",
+                    "$numberDecimal",
+                ]
+                .iter()
+                .any(|k| wrapper(k))
+        }
+        "string" => value.is_string(),
+        "bool" => value.is_boolean(),
+        "null" => value.is_null(),
+        "array" => value.is_array(),
+        "object" => value.is_object() && !EXTENDED_JSON_KEYS.iter().any(|k| wrapper(k)),
+        "objectId" => wrapper("$oid"),
+        "date" => wrapper("$date"),
+        "binData" => wrapper("$binary"),
+        "regex" => wrapper("$regularExpression"),
+        "timestamp" => wrapper("$timestamp"),
+        "javascript" => wrapper("$code"),
+        "minKey" => wrapper("$minKey"),
+        "maxKey" => wrapper("$maxKey"),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `value` is an integer within the bounds of `T`, either
+/// as a JSON number or as the string of the Extended JSON wrapper `key`.
+fn fits<T: TryFrom<i64> + FromStr>(value: &Value, key: &str) -> bool {
+    match value {
+        Value::Number(n) => n.as_i64().map_or(false, |n| T::try_from(n).is_ok()),
+        Value::Object(obj) if obj.len() == 1 => obj
+            .get(key)
+            .and_then(Value::as_str)
+            .map_or(false, |s| s.parse::<T>().is_ok()),
+        _ => false,
+    }
+}
+
+/// Keys of single-field objects which denote a BSON type in MongoDB Extended
+/// JSON rather than an embedded document.
+const EXTENDED_JSON_KEYS: &[&str] = &[
+    "$oid",
+    "$date",
+    "$binary",
+    "$regularExpression",
+    "$timestamp",
+    "$code",
+    "$minKey",
+    "$maxKey",
+    "$numberDouble",
+    "$numberInt",
+    "$numberLong",
+    "$numberDecimal",
+];
@@ -0,0 +1,294 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    content::Contents,
+    error::InvalidKeywordError,
+    keyword::Keyword,
+    plugin::Registrar,
+    schema::SubSchema,
+    Error, Evaluation, Interrogator, Next, Schema, Vocabulary,
+};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::borrow::Cow;
+use uniresid::Uri;
+
+/// The [Uri] of the vocabulary which asserts `"contentEncoding"`,
+/// `"contentMediaType"`, and `"contentSchema"`.
+///
+/// The specification defines the content keywords as annotations; this
+/// identifier, specific to grill, opts into asserting them.
+pub static CONTENT_ASSERTION_VOCABULARY_URI: Lazy<Uri> =
+    Lazy::new(|| Uri::parse("https://github.com/chanced/grill/vocab/content-assertion").unwrap());
+
+/// The [Uri] of the content vocabulary of JSON Schema 2020-12, which
+/// annotates `"contentEncoding"`, `"contentMediaType"`, and
+/// `"contentSchema"` without asserting them.
+pub static CONTENT_VOCABULARY_URI: Lazy<Uri> =
+    Lazy::new(|| Uri::parse("https://json-schema.org/draft/2020-12/vocab/content").unwrap());
+
+/// Plugin which registers the content assertion vocabulary.
+///
+/// The dialects of Draft 2019-09 and 2020-12 annotate the content keywords.
+/// Add [`content_assertion_vocabulary`] to a [`Dialect`](crate::Dialect), in
+/// place of its content vocabulary, to assert them for its schemas. Decoders and parsers are
+/// registered with [`Interrogator::contents`].
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::content_assertion, Interrogator};
+///
+/// let interrogator = Interrogator::builder().plugin(content_assertion).build().unwrap();
+/// interrogator.contents().insert_parser("application/yaml", |_: &[u8]| {
+///     Err("yaml is not supported".to_string())
+/// });
+/// ```
+pub fn content_assertion(registrar: &mut Registrar) -> Result<(), Error> {
+    registrar.vocabulary(content_assertion_vocabulary());
+    Ok(())
+}
+
+/// Returns the [`Vocabulary`] which asserts the content keywords.
+pub fn content_assertion_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::new(CONTENT_ASSERTION_VOCABULARY_URI.clone());
+    for &keyword in CONTENT_KEYWORDS {
+        let applicator = move |interrogator: Interrogator, schema: Schema| {
+            // the keywords are asserted together, by the first present
+            let source = schema.source();
+            let first = CONTENT_KEYWORDS.iter().find(|k| source.get(**k).is_some());
+            if first != Some(&keyword) {
+                return Ok(None);
+            }
+            content(interrogator, schema)
+        };
+        vocabulary.push(Keyword::new(keyword, applicator));
+    }
+    vocabulary
+}
+
+/// Returns the [`Vocabulary`] which annotates the content keywords, as the
+/// specification prescribes. A [`Dialect`](crate::Dialect) should include
+/// either this vocabulary or [`content_assertion_vocabulary`], not both.
+///
+/// ## Example
+/// ```
+/// use grill::keyword::{content_vocabulary, CONTENT_VOCABULARY_URI};
+///
+/// assert_eq!(content_vocabulary().id, *CONTENT_VOCABULARY_URI);
+/// ```
+pub fn content_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::new(CONTENT_VOCABULARY_URI.clone());
+    push_content_annotations(&mut vocabulary);
+    vocabulary
+}
+
+/// Pushes each content keyword onto `vocabulary` as an annotation, so that
+/// the content vocabulary of each draft can be composed.
+pub(crate) fn push_content_annotations(vocabulary: &mut Vocabulary) {
+    for &keyword in CONTENT_KEYWORDS {
+        let applicator = move |_: Interrogator, schema: Schema| annotation(&schema, keyword);
+        vocabulary.push(Keyword::new(keyword, applicator));
+    }
+}
+
+/// [`Applicator`](crate::Applicator) for the `"contentEncoding"`,
+/// `"contentMediaType"`, and `"contentSchema"` keywords as annotations.
+///
+/// The evaluation of each keyword present is annotated with its value, as
+/// `"annotation"`, for string instances. Content is neither decoded nor
+/// parsed, so no instance is invalid; use [`content`] to assert it.
+///
+/// [`content_vocabulary`] instead registers each keyword on its own.
+pub fn content_annotation(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    string(&schema, "contentEncoding")?;
+    string(&schema, "contentMediaType")?;
+    let annotations: Vec<(&'static str, Value)> = CONTENT_KEYWORDS
+        .iter()
+        .filter_map(|keyword| Some((*keyword, source.get(*keyword)?.clone())))
+        .collect();
+    if annotations.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(annotator(annotations.clone()))
+    })))
+}
+
+/// Annotates string instances with the value of the content keyword
+/// `keyword` of `schema`, if present.
+fn annotation(schema: &Schema, keyword: &'static str) -> Result<Option<Box<SetupFn>>, Error> {
+    if keyword != "contentSchema" {
+        string(schema, keyword)?;
+    }
+    let annotation = match schema.source().get(keyword) {
+        Some(annotation) => annotation.clone(),
+        None => return Ok(None),
+    };
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(annotator(vec![(keyword, annotation.clone())]))
+    })))
+}
+
+/// The content keywords, in the order they are evaluated.
+const CONTENT_KEYWORDS: &[&str] = &["contentEncoding", "contentMediaType", "contentSchema"];
+
+fn annotator(annotations: Vec<(&'static str, Value)>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if !value.is_string() {
+                return next.call(value, eval);
+            }
+            for (keyword, annotation) in &annotations {
+                let mut kw = eval.for_keyword(keyword);
+                kw.insert("annotation".to_string(), annotation)?;
+                eval.push(kw);
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+/// [`Applicator`](crate::Applicator) for the `"contentEncoding"`,
+/// `"contentMediaType"`, and `"contentSchema"` keywords as assertions.
+///
+/// String instances are decoded with the decoder of their
+/// `"contentEncoding"` and parsed with the parser of their
+/// `"contentMediaType"`, as registered in [`Interrogator::contents`], and the
+/// parsed value is evaluated against `"contentSchema"`. Instances which fail
+/// to decode or parse are invalid. Encodings and media types which are not
+/// registered are not asserted, nor is `"contentSchema"` if the media type
+/// can not be parsed.
+///
+/// Decoders and parsers are looked up as each instance is evaluated, so
+/// those registered after the `Schema` is compiled apply to it.
+pub fn content(interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    let encoding = string(&schema, "contentEncoding")?;
+    let media_type = string(&schema, "contentMediaType")?;
+    if encoding.is_none() && media_type.is_none() {
+        return Ok(None);
+    }
+    let contents = interrogator.contents();
+    let content_schema = match &media_type {
+        Some(_) => source.get("contentSchema").cloned(),
+        None => None,
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| {
+            let content_schema = match content_schema.clone() {
+                Some(Value::Bool(b)) => Some(Target::Bool(b)),
+                Some(source) => {
+                    match schema.add_sub_schema("contentSchema", source, interrogator)? {
+                        SubSchema::Single(compiled) => Some(Target::Schema(compiled)),
+                        SubSchema::Array(_) => None,
+                    }
+                }
+                None => None,
+            };
+            let executor = Executor {
+                contents: contents.clone(),
+                encoding: encoding.clone(),
+                media_type: media_type.clone(),
+                content_schema,
+            };
+            Ok(executor.into_fn())
+        },
+    )))
+}
+
+fn string(schema: &Schema, keyword: &str) -> Result<Option<String>, Error> {
+    match schema.source().get(keyword) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(v) => Err(InvalidKeywordError {
+            schema: schema.clone(),
+            keyword: keyword.to_string(),
+            expected: "a string".to_string(),
+            value: v.clone(),
+        }
+        .into()),
+    }
+}
+
+#[derive(Clone)]
+enum Target {
+    Bool(bool),
+    Schema(Schema),
+}
+
+struct Executor {
+    contents: Contents,
+    encoding: Option<String>,
+    media_type: Option<String>,
+    content_schema: Option<Target>,
+}
+
+impl Executor {
+    fn into_fn(self) -> Box<ExecutorFn> {
+        Box::new(
+            move |value: &Value, eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+                let eval = match value {
+                    Value::String(s) => self.assert(value, s, eval)?,
+                    _ => eval,
+                };
+                next.call(value, eval)
+            },
+        )
+    }
+
+    fn assert(&self, value: &Value, s: &str, mut eval: Evaluation) -> Result<Evaluation, Error> {
+        let bytes = match &self.encoding {
+            // an unregistered encoding leaves the content opaque
+            Some(encoding) => match self.contents.decoder(encoding) {
+                Some(decode) => match decode(s) {
+                    Ok(bytes) => Cow::Owned(bytes),
+                    Err(err) => {
+                        let mut kw = eval.for_keyword("contentEncoding");
+                        kw.set_error_kind("undecodable");
+                        kw.set_error_param("encoding", encoding.as_str());
+                        kw.set_error_param("reason", err.to_string());
+                        kw.set_error(format!("{} is not valid \"{}\": {}", value, encoding, err));
+                        eval.push(kw);
+                        return Ok(eval);
+                    }
+                },
+                None => return Ok(eval),
+            },
+            None => Cow::Borrowed(s.as_bytes()),
+        };
+        let media_type = match &self.media_type {
+            Some(media_type) => media_type,
+            None => return Ok(eval),
+        };
+        let parse = match self.contents.parser(media_type) {
+            Some(parse) => parse,
+            None => return Ok(eval),
+        };
+        match parse(&bytes) {
+            Ok(content) => match &self.content_schema {
+                None | Some(Target::Bool(true)) => {}
+                Some(Target::Bool(false)) => {
+                    let mut kw = eval.for_keyword("contentSchema");
+                    kw.set_false_schema_error();
+                    eval.push(kw);
+                }
+                Some(Target::Schema(schema)) => {
+                    let kw = eval.for_keyword("contentSchema");
+                    eval.push(schema.apply(&content, kw)?);
+                }
+            },
+            Err(err) => {
+                let mut kw = eval.for_keyword("contentMediaType");
+                kw.set_error_kind("unparsable");
+                kw.set_error_param("mediaType", media_type.as_str());
+                kw.set_error_param("reason", err.to_string());
+                kw.set_error(format!("content is not valid \"{}\": {}", media_type, err));
+                eval.push(kw);
+            }
+        }
+        Ok(eval)
+    }
+}
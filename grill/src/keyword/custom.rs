@@ -0,0 +1,117 @@
+use super::Keyword;
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    Applicator, Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::Value;
+use std::marker::PhantomData;
+
+/// Creates a [`Keyword`] named `name` from a pair of hooks, for keywords
+/// which are not part of any draft, e.g. `"x-internal-routing"`.
+///
+/// `compile` is called once per [`Schema`] which has the keyword, with the
+/// keyword's value, when the `Schema` is set up; its output is handed to
+/// `evaluate` for each instance. `evaluate` annotates or sets the error of
+/// the evaluation of the keyword, which is nested beneath that of the
+/// `Schema`.
+///
+/// Add the `Keyword` to a [`Vocabulary`](crate::Vocabulary) and attach the
+/// vocabulary to a dialect with
+/// [`Registrar::extend_dialect`](crate::plugin::Registrar::extend_dialect)
+/// or [`Registrar::derive_dialect`](crate::plugin::Registrar::derive_dialect).
+///
+/// ## Example
+/// ```
+/// use grill::{error::InvalidKeywordError, keyword::custom, Applicator, Schema};
+/// use serde_json::Value;
+///
+/// let routing = custom(
+///     "x-internal-routing",
+///     |_, schema: &Schema, value: &Value| match value.as_str() {
+///         Some(queue) => Ok(queue.to_string()),
+///         None => Err(InvalidKeywordError {
+///             schema: schema.clone(),
+///             keyword: "x-internal-routing".to_string(),
+///             expected: "a string".to_string(),
+///             value: value.clone(),
+///         }
+///         .into()),
+///     },
+///     |queue: &String, _: &Value, eval| {
+///         eval.insert("queue".to_string(), queue)?;
+///         Ok(())
+///     },
+/// );
+/// assert_eq!(routing.keyword(), Some("x-internal-routing"));
+/// ```
+pub fn custom<T, C, E>(name: &'static str, compile: C, evaluate: E) -> Keyword<Custom<T, C, E>>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Fn(&Interrogator, &Schema, &Value) -> Result<T, Error> + Clone + Send + Sync + 'static,
+    E: Fn(&T, &Value, &mut Evaluation) -> Result<(), Error> + Clone + Send + Sync + 'static,
+{
+    Keyword::new(
+        name,
+        Custom {
+            name,
+            compile,
+            evaluate,
+            compiled: PhantomData,
+        },
+    )
+}
+
+/// The [`Applicator`] of a keyword created with [`custom`].
+#[derive(Clone)]
+pub struct Custom<T, C, E> {
+    name: &'static str,
+    compile: C,
+    evaluate: E,
+    compiled: PhantomData<fn() -> T>,
+}
+
+impl<T, C, E> Applicator for Custom<T, C, E>
+where
+    T: Clone + Send + Sync + 'static,
+    C: Fn(&Interrogator, &Schema, &Value) -> Result<T, Error> + Clone + Send + Sync + 'static,
+    E: Fn(&T, &Value, &mut Evaluation) -> Result<(), Error> + Clone + Send + Sync + 'static,
+{
+    fn init(
+        &self,
+        _interrogator: Interrogator,
+        schema: Schema,
+    ) -> Result<Option<Box<SetupFn>>, Error> {
+        let value = match schema.source().get(self.name) {
+            Some(value) => value.clone(),
+            None => return Ok(None),
+        };
+        let name = self.name;
+        let compile = self.compile.clone();
+        let evaluate = self.evaluate.clone();
+        Ok(Some(Box::new(
+            move |interrogator: &Interrogator, schema: &Schema| {
+                let compiled = compile(interrogator, schema, &value)?;
+                Ok(executor(name, compiled, evaluate.clone()))
+            },
+        )))
+    }
+
+    fn keyword(&self) -> Option<&'static str> {
+        Some(self.name)
+    }
+}
+
+fn executor<T, E>(name: &'static str, compiled: T, evaluate: E) -> Box<ExecutorFn>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Fn(&T, &Value, &mut Evaluation) -> Result<(), Error> + Clone + Send + Sync + 'static,
+{
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let mut kw = eval.for_keyword(name);
+            evaluate(&compiled, value, &mut kw)?;
+            eval.push(kw);
+            next.call(value, eval)
+        },
+    )
+}
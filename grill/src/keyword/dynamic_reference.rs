@@ -0,0 +1,104 @@
+use super::reference::{parse, resolve, Resolved, Target};
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    evaluation::State,
+    fragment::Fragment,
+    urn, Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::Value;
+use uniresid::AbsoluteUri;
+
+/// [`Applicator`](crate::Applicator) for the `"$dynamicRef"` keyword.
+///
+/// The reference is resolved and its initial target set up as for
+/// [`"$ref"`](super::ref_). If the fragment of the reference is a plain name
+/// and the initial target declares a `"$dynamicAnchor"` of that name, the
+/// reference is dynamic: each schema resource within the source documents
+/// of the [`Interrogator`] which declares the same `"$dynamicAnchor"` is set
+/// up as a candidate, and at evaluation the target is that of the outermost
+/// resource of the [dynamic scope](crate::evaluation::State::dynamic_scope)
+/// which is a candidate. If no resource in scope is a candidate, or the
+/// reference is not dynamic, the initial target is applied.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{dynamic_ref, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("$dynamicRef", dynamic_ref);
+/// assert_eq!(applicator.keyword(), Some("$dynamicRef"));
+/// ```
+pub fn dynamic_ref(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let (reference, target) = match parse(&schema, "$dynamicRef")? {
+        Some(parsed) => parsed,
+        None => return Ok(None),
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| {
+            setup(interrogator, schema, &reference, &target)
+        },
+    )))
+}
+
+fn setup(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    reference: &str,
+    target: &Resolved,
+) -> Result<Box<ExecutorFn>, Error> {
+    let initial = resolve(interrogator, schema, "$dynamicRef", reference, target)?;
+    let target_str = target.uri().to_string();
+    let (document, fragment) = target_str.split_once('#').unwrap_or((&target_str, ""));
+    let name = match Fragment::parse(fragment) {
+        Fragment::Anchor(name) => name,
+        _ => return Ok(executor(initial, Vec::new())),
+    };
+    let source = initial.source();
+    if source.get("$dynamicAnchor").and_then(Value::as_str) != Some(name) {
+        return Ok(executor(initial, Vec::new()));
+    }
+    let mut candidates = Vec::new();
+    for resource in interrogator.dynamic_anchor_resources(name)? {
+        let resource_str = resource.to_string();
+        if urn::normalize(&resource_str) == urn::normalize(document) {
+            candidates.push((urn::normalize(&resource_str).into_owned(), initial.clone()));
+            continue;
+        }
+        let anchored = AbsoluteUri::parse(&format!("{}#{}", resource_str, name))?;
+        let anchored = Resolved::Absolute(anchored);
+        let candidate = resolve(interrogator, schema, "$dynamicRef", reference, &anchored)?;
+        candidates.push((urn::normalize(&resource_str).into_owned(), candidate));
+    }
+    Ok(executor(initial, candidates))
+}
+
+/// Returns the target of the outermost resource of the dynamic scope of
+/// `state` among `candidates`, keyed by the normalized id of their resource.
+fn outermost<'a>(state: &State, candidates: &'a [(String, Target)]) -> Option<&'a Target> {
+    state.find_in_scope(|resource| {
+        let resource = resource.to_string();
+        let resource = urn::normalize(&resource);
+        candidates
+            .iter()
+            .find(|(id, _)| *id == resource)
+            .map(|(_, target)| target)
+    })
+}
+
+fn executor(initial: Target, candidates: Vec<(String, Target)>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let kw = eval.for_keyword("$dynamicRef");
+            let target = if candidates.is_empty() {
+                &initial
+            } else {
+                outermost(kw.state(), &candidates).unwrap_or(&initial)
+            };
+            let kw = target.apply(value, kw)?;
+            eval.push(kw);
+            next.call(value, eval)
+        },
+    )
+}
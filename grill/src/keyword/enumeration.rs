@@ -0,0 +1,173 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    keyword::unique_items,
+    Error, Evaluation, Interrogator, Next, Numbers, Precision, Schema,
+};
+use serde_json::Value;
+
+/// The maximum number of suggestions attached to an error.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// [`Applicator`](crate::Applicator) for the `"enum"` keyword.
+///
+/// Numbers are compared with the [`Precision`] of the [`Interrogator`]
+/// through its [`Numbers`] cache.
+///
+/// If [`Options::suggest`](crate::evaluation::Options::suggest) is enabled
+/// and a string instance fails, the nearest string members are attached to
+/// the error under `"suggestions"`.
+pub fn enum_(_interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let values = match schema.source().get("enum") {
+        None => return Ok(None),
+        Some(Value::Array(values)) => values.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "enum".to_string(),
+                expected: "an array".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, _: &Schema| {
+            Ok(executor(
+                "enum",
+                values.clone(),
+                interrogator.numbers(),
+                interrogator.precision(),
+            ))
+        },
+    )))
+}
+
+/// [`Applicator`](crate::Applicator) for the `"const"` keyword.
+///
+/// If [`Options::suggest`](crate::evaluation::Options::suggest) is enabled
+/// and a string instance fails against a string constant within a small edit
+/// distance, the constant is attached to the error under `"suggestions"`.
+pub fn const_(_interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    match schema.source().get("const") {
+        None => Ok(None),
+        Some(value) => {
+            let values = vec![value.clone()];
+            Ok(Some(Box::new(
+                move |interrogator: &Interrogator, _: &Schema| {
+                    Ok(executor(
+                        "const",
+                        values.clone(),
+                        interrogator.numbers(),
+                        interrogator.precision(),
+                    ))
+                },
+            )))
+        }
+    }
+}
+
+fn executor(
+    keyword: &'static str,
+    values: Vec<Value>,
+    numbers: Numbers,
+    precision: Precision,
+) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let equals = |v: &Value| unique_items::equals(v, value, &numbers, precision);
+            if !values.iter().any(equals) {
+                let mut kw = eval.for_keyword(keyword);
+                let message = if keyword == "const" {
+                    kw.set_error_kind("unequal");
+                    kw.set_error_param("expected", values[0].clone());
+                    format!("{} is not equal to {}", value, values[0])
+                } else {
+                    kw.set_error_kind("not_enumerated");
+                    kw.set_error_param("count", values.len());
+                    format!("{} is not one of the enumerated values", value)
+                };
+                kw.set_error(message);
+                if let Value::String(s) = value {
+                    if eval.state().options().is_suggesting() {
+                        let candidates = values.iter().filter_map(Value::as_str);
+                        let found = suggestions(s, candidates);
+                        if !found.is_empty() {
+                            kw.insert("suggestions".to_string(), found)?;
+                        }
+                    }
+                }
+                eval.push(kw);
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+/// Returns up to three `candidates` nearest to `input` by edit distance,
+/// nearest first.
+///
+/// Candidates further than a third of the length of `input` (minimum `2`, so
+/// that a transposition is within reach) are excluded.
+///
+/// ## Example
+/// ```
+/// use grill::keyword::suggestions;
+///
+/// let colors = ["red", "blue", "green"];
+/// assert_eq!(suggestions("bleu", colors.into_iter()), vec!["blue"]);
+/// ```
+pub fn suggestions<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let threshold = (input.chars().count() / 3).max(2);
+    let mut found: Vec<(usize, &str)> = candidates
+        .map(|c| (levenshtein(input, c), c))
+        .filter(|(d, _)| *d <= threshold)
+        .collect();
+    found.sort_unstable();
+    found.dedup();
+    found
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.to_string())
+        .collect()
+}
+
+/// Returns the Levenshtein distance between `a` and `b`, by `char`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(cur)
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Compares `a` and `b` for equality as defined by JSON Schema, in which
+/// numbers are equal if they are mathematically equal (e.g. `1` and `1.0`).
+pub(crate) fn equals(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a == b,
+            _ => a == b || a.as_f64() == b.as_f64(),
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equals(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, v)| matches!(b.get(k), Some(bv) if equals(v, bv)))
+        }
+        _ => a == b,
+    }
+}
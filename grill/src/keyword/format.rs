@@ -0,0 +1,53 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    format::Format,
+    Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::Value;
+
+/// [`Applicator`](crate::Applicator) for the `"format"` keyword as an
+/// assertion.
+///
+/// String instances are validated by the [`Format`] of the same name
+/// registered in [`Interrogator::formats`]. Formats which are not registered,
+/// such as those whose cargo feature is not enabled, are not asserted.
+pub fn format(interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let name = match schema.source().get("format") {
+        None => return Ok(None),
+        Some(Value::String(name)) => name.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "format".to_string(),
+                expected: "a string".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    let format = match interrogator.formats().get(&name) {
+        Some(format) => format,
+        None => return Ok(None),
+    };
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(executor(name.clone(), format.clone()))
+    })))
+}
+
+fn executor(name: String, format: Format) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Value::String(s) = value {
+                if !format.validate(s) {
+                    let mut kw = eval.for_keyword("format");
+                    kw.set_error_kind("invalid");
+                    kw.set_error_param("format", name.as_str());
+                    kw.set_error(format!("{} is not a valid \"{}\"", value, name));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
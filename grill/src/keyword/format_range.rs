@@ -0,0 +1,229 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    format,
+    keyword::Keyword,
+    plugin::Registrar,
+    Error, Evaluation, Interrogator, Next, Schema, Vocabulary,
+};
+use once_cell::sync::Lazy;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use uniresid::Uri;
+
+/// The [Uri] of the vocabulary implementing `"formatMinimum"`,
+/// `"formatMaximum"`, `"formatExclusiveMinimum"`, and
+/// `"formatExclusiveMaximum"`.
+///
+/// The vocabulary is not part of the JSON Schema specification; this
+/// identifier is specific to grill.
+pub static FORMAT_RANGE_VOCABULARY_URI: Lazy<Uri> =
+    Lazy::new(|| Uri::parse("https://github.com/chanced/grill/vocab/format-range").unwrap());
+
+/// Keywords of the format range vocabulary.
+pub const FORMAT_RANGE_KEYWORDS: &[&str] = &[
+    "formatMinimum",
+    "formatMaximum",
+    "formatExclusiveMinimum",
+    "formatExclusiveMaximum",
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Maximum,
+    ExclusiveMaximum,
+    Minimum,
+    ExclusiveMinimum,
+}
+
+impl Bound {
+    fn is_satisfied_by(self, ord: Ordering) -> bool {
+        match self {
+            Bound::Maximum => ord != Ordering::Greater,
+            Bound::ExclusiveMaximum => ord == Ordering::Less,
+            Bound::Minimum => ord != Ordering::Less,
+            Bound::ExclusiveMinimum => ord == Ordering::Greater,
+        }
+    }
+    fn describe(self) -> &'static str {
+        match self {
+            Bound::Maximum => "at or before",
+            Bound::ExclusiveMaximum => "before",
+            Bound::Minimum => "at or after",
+            Bound::ExclusiveMinimum => "after",
+        }
+    }
+    /// Returns the kind of the error of a value which does not satisfy the
+    /// bound.
+    fn kind(self) -> &'static str {
+        match self {
+            Bound::Maximum => "maximum",
+            Bound::ExclusiveMaximum => "exclusiveMaximum",
+            Bound::Minimum => "minimum",
+            Bound::ExclusiveMinimum => "exclusiveMinimum",
+        }
+    }
+}
+
+/// Plugin which registers the format range vocabulary, in the style of
+/// ajv-keywords.
+///
+/// The keywords of the vocabulary bound string instances whose sibling
+/// `"format"` is `"date-time"`, `"date"`, `"time"`, or `"duration"`, compared
+/// with [`format::compare`]. Add [`format_range_vocabulary`] to a
+/// [`Dialect`](crate::Dialect) to enable the keywords for its schemas.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::format_range, Interrogator};
+///
+/// let interrogator = Interrogator::builder().plugin(format_range).build().unwrap();
+/// ```
+pub fn format_range(registrar: &mut Registrar) -> Result<(), Error> {
+    registrar.vocabulary(format_range_vocabulary());
+    Ok(())
+}
+
+/// Returns the [`Vocabulary`] of `"formatMinimum"`, `"formatMaximum"`,
+/// `"formatExclusiveMinimum"`, and `"formatExclusiveMaximum"`.
+pub fn format_range_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::new(FORMAT_RANGE_VOCABULARY_URI.clone());
+    vocabulary.push(Keyword::new("formatMinimum", format_minimum));
+    vocabulary.push(Keyword::new("formatMaximum", format_maximum));
+    vocabulary.push(Keyword::new(
+        "formatExclusiveMinimum",
+        format_exclusive_minimum,
+    ));
+    vocabulary.push(Keyword::new(
+        "formatExclusiveMaximum",
+        format_exclusive_maximum,
+    ));
+    vocabulary
+}
+
+/// [`Applicator`](crate::Applicator) for the `"formatMinimum"` keyword.
+pub fn format_minimum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(&interrogator, &schema, "formatMinimum", Bound::Minimum)
+}
+
+/// [`Applicator`](crate::Applicator) for the `"formatMaximum"` keyword.
+pub fn format_maximum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(&interrogator, &schema, "formatMaximum", Bound::Maximum)
+}
+
+/// [`Applicator`](crate::Applicator) for the `"formatExclusiveMinimum"`
+/// keyword.
+pub fn format_exclusive_minimum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(
+        &interrogator,
+        &schema,
+        "formatExclusiveMinimum",
+        Bound::ExclusiveMinimum,
+    )
+}
+
+/// [`Applicator`](crate::Applicator) for the `"formatExclusiveMaximum"`
+/// keyword.
+pub fn format_exclusive_maximum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(
+        &interrogator,
+        &schema,
+        "formatExclusiveMaximum",
+        Bound::ExclusiveMaximum,
+    )
+}
+
+/// [`Describer`](crate::describe::Describer) for the format range keywords,
+/// e.g. `"after 2020-01-01"`.
+pub fn describe_format_range(object: &Map<String, Value>) -> Option<String> {
+    let bounds: Vec<String> = [
+        ("formatMinimum", Bound::Minimum),
+        ("formatExclusiveMinimum", Bound::ExclusiveMinimum),
+        ("formatMaximum", Bound::Maximum),
+        ("formatExclusiveMaximum", Bound::ExclusiveMaximum),
+    ]
+    .iter()
+    .filter_map(|(keyword, bound)| {
+        let limit = object.get(*keyword)?.as_str()?;
+        Some(format!("{} {}", bound.describe(), limit))
+    })
+    .collect();
+    if bounds.is_empty() {
+        None
+    } else {
+        Some(bounds.join(" and "))
+    }
+}
+
+fn init(
+    _interrogator: &Interrogator,
+    schema: &Schema,
+    keyword: &'static str,
+    bound: Bound,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let source = schema.source();
+    let limit = match source.get(keyword) {
+        None => return Ok(None),
+        Some(limit) => limit,
+    };
+    let invalid = |expected: String| -> Error {
+        InvalidKeywordError {
+            schema: schema.clone(),
+            keyword: keyword.to_string(),
+            expected,
+            value: limit.clone(),
+        }
+        .into()
+    };
+    let fmt = match source.get("format").and_then(Value::as_str) {
+        Some(fmt) if format::is_comparable(fmt) => fmt.to_string(),
+        _ => {
+            return Err(invalid(
+                "a sibling \"format\" of \"date-time\", \"date\", \"time\", or \"duration\""
+                    .to_string(),
+            ))
+        }
+    };
+    let limit = match limit.as_str() {
+        Some(limit) if format::compare(&fmt, limit, limit).is_some() => limit.to_string(),
+        _ => return Err(invalid(format!("a string formatted as \"{}\"", fmt))),
+    };
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(executor(keyword, bound, fmt.clone(), limit.clone()))
+    })))
+}
+
+fn executor(keyword: &'static str, bound: Bound, fmt: String, limit: String) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            // values which do not conform to the format are left to "format"
+            if let Some(ord) = value
+                .as_str()
+                .and_then(|s| format::compare(&fmt, s, &limit))
+            {
+                if !bound.is_satisfied_by(ord) {
+                    let mut kw = eval.for_keyword(keyword);
+                    kw.set_error_kind(bound.kind());
+                    kw.set_error_param("format", fmt.as_str());
+                    kw.set_error_param("limit", limit.as_str());
+                    kw.set_error_param("value", value.clone());
+                    kw.set_error(format!("{} is not {} {}", value, bound.describe(), limit));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
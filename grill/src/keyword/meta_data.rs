@@ -0,0 +1,128 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    keyword::Keyword,
+    Error, Evaluation, Interrogator, Next, Schema, Vocabulary,
+};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use uniresid::Uri;
+
+/// The [Uri] of the meta-data vocabulary of JSON Schema 2020-12.
+pub static META_DATA_VOCABULARY_URI: Lazy<Uri> =
+    Lazy::new(|| Uri::parse("https://json-schema.org/draft/2020-12/vocab/meta-data").unwrap());
+
+/// The keywords of the meta-data vocabulary, in the order they are
+/// evaluated.
+pub const META_DATA_KEYWORDS: &[&str] = &[
+    "title",
+    "description",
+    "default",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "examples",
+];
+
+/// Returns the meta-data [`Vocabulary`], which annotates instances with the
+/// keywords of [`META_DATA_KEYWORDS`].
+///
+/// ## Example
+/// ```
+/// use grill::keyword::{meta_data_vocabulary, META_DATA_VOCABULARY_URI};
+///
+/// assert_eq!(meta_data_vocabulary().id, *META_DATA_VOCABULARY_URI);
+/// ```
+pub fn meta_data_vocabulary() -> Vocabulary {
+    let mut vocabulary = Vocabulary::new(META_DATA_VOCABULARY_URI.clone());
+    push_meta_data_keywords(&mut vocabulary, META_DATA_KEYWORDS);
+    vocabulary
+}
+
+/// Pushes each of `keywords`, which are among [`META_DATA_KEYWORDS`], onto
+/// `vocabulary`, so that the meta-data keywords of each draft can be
+/// composed.
+pub(crate) fn push_meta_data_keywords(vocabulary: &mut Vocabulary, keywords: &[&'static str]) {
+    for &keyword in keywords {
+        let applicator = move |_: Interrogator, schema: Schema| annotation(&schema, keyword);
+        vocabulary.push(Keyword::new(keyword, applicator));
+    }
+}
+
+/// [`Applicator`](crate::Applicator) for the keywords of the meta-data
+/// vocabulary.
+///
+/// The evaluation of each keyword present is annotated with its value, as
+/// `"annotation"`. No instance is invalid. Collect the annotations of an
+/// instance location with
+/// [`Report::annotations_at`](crate::report::Report::annotations_at).
+///
+/// [`meta_data_vocabulary`] instead registers each keyword on its own.
+pub fn meta_data(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let mut annotations: Vec<(&'static str, Value)> = Vec::new();
+    for &keyword in META_DATA_KEYWORDS {
+        if let Some(value) = value_of(&schema, keyword)? {
+            annotations.push((keyword, value));
+        }
+    }
+    if annotations.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(annotator(annotations.clone()))
+    })))
+}
+
+/// Annotates instances with the value of the meta-data keyword `keyword` of
+/// `schema`, if present.
+fn annotation(schema: &Schema, keyword: &'static str) -> Result<Option<Box<SetupFn>>, Error> {
+    let annotation = match value_of(schema, keyword)? {
+        Some(annotation) => annotation,
+        None => return Ok(None),
+    };
+    Ok(Some(Box::new(move |_: &Interrogator, _: &Schema| {
+        Ok(annotator(vec![(keyword, annotation.clone())]))
+    })))
+}
+
+/// Returns the value of the meta-data keyword `keyword` of `schema`, if
+/// present.
+fn value_of(schema: &Schema, keyword: &str) -> Result<Option<Value>, Error> {
+    let source = schema.source();
+    let value = match source.get(keyword) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let expected = match keyword {
+        "title" | "description" if !value.is_string() => Some("a string"),
+        "deprecated" | "readOnly" | "writeOnly" if !value.is_boolean() => Some("a boolean"),
+        "examples" if !value.is_array() => Some("an array"),
+        _ => None,
+    };
+    if let Some(expected) = expected {
+        return Err(InvalidKeywordError {
+            schema: schema.clone(),
+            keyword: keyword.to_string(),
+            expected: expected.to_string(),
+            value: value.clone(),
+        }
+        .into());
+    }
+    Ok(Some(value.clone()))
+}
+
+fn annotator(annotations: Vec<(&'static str, Value)>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            for (keyword, annotation) in &annotations {
+                let mut kw = eval.for_keyword(keyword);
+                kw.insert("annotation".to_string(), annotation)?;
+                eval.push(kw);
+            }
+            next.call(value, eval)
+        },
+    )
+}
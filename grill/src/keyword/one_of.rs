@@ -0,0 +1,323 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    keyword::enumeration::equals,
+    schema::SubSchema,
+    Error, Evaluation, Interrogator, Next, Schema,
+};
+use jsonptr::Pointer;
+use serde_json::{Map, Value};
+use std::ops::Range;
+
+/// [`Applicator`](crate::Applicator) for the `"oneOf"` keyword.
+///
+/// An instance is valid if it is valid against exactly one member. When it
+/// is valid against more than one, the error enumerates the index of each
+/// matching member along with its `"$id"` or `"title"`, and the evaluation
+/// of `"oneOf"` records the indexes as `"matched"`. When it is valid against
+/// none, the error names the member which came closest, i.e. the one with
+/// the fewest errors (the first such member on a tie). In either case the
+/// evaluation of each member is nested beneath that of `"oneOf"` so that
+/// its errors are reported.
+///
+/// When each member constrains a property with a `"const"` or an `"enum"`
+/// and no two members permit the same value, an instance with the property
+/// can match no member but the one its value selects. That member is looked
+/// up by the value, compared as by `"const"`, in a table compiled at setup
+/// and is the only one evaluated.
+///
+/// When the schema has an OpenAPI `"discriminator"` naming a property as its
+/// `"propertyName"`, a value of the property selects the member whose
+/// `"$ref"` is the value's entry in `"mapping"` or, otherwise, ends with
+/// `"/"` followed by the value. As a discriminator does not prevent other
+/// members from matching, every member is still evaluated; if none matches,
+/// the selected member is reported as the closest.
+///
+/// Instances which are not objects, lack the property, or whose value
+/// selects no member are evaluated against every member.
+///
+/// With the `"rayon"` feature, the members are evaluated in parallel if
+/// [`Options::parallel`](crate::evaluation::Options::parallel) is set; the
+/// evaluation is the same as when they are evaluated sequentially.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{one_of, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("oneOf", one_of);
+/// assert_eq!(applicator.keyword(), Some("oneOf"));
+/// ```
+pub fn one_of(_interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let members = match schema.source().get("oneOf") {
+        None => return Ok(None),
+        Some(Value::Array(members)) if !members.is_empty() => members.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "oneOf".to_string(),
+                expected: "a non-empty array of schemas".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| setup(interrogator, schema, &members),
+    )))
+}
+
+fn setup(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    members: &[Value],
+) -> Result<Box<ExecutorFn>, Error> {
+    let mut targets = Vec::with_capacity(members.len());
+    for (index, source) in members.iter().enumerate() {
+        let target = match source {
+            Value::Bool(b) => Target::Bool(*b),
+            Value::Object(_) => {
+                let key = format!("oneOf/{}", index);
+                match schema.add_sub_schema(&key, source.clone(), interrogator)? {
+                    SubSchema::Single(compiled) => Target::Schema(compiled),
+                    SubSchema::Array(_) => unreachable!("an object compiles to a single schema"),
+                }
+            }
+            _ => {
+                return Err(InvalidKeywordError {
+                    schema: schema.clone(),
+                    keyword: "oneOf".to_string(),
+                    expected: "a non-empty array of schemas".to_string(),
+                    value: Value::Array(members.to_vec()),
+                }
+                .into())
+            }
+        };
+        targets.push((label(index, source), target));
+    }
+    let dispatch = discriminator(schema.source().as_ref(), members).or_else(|| tag(members));
+    Ok(executor(targets, dispatch))
+}
+
+/// A table of the member selected by each value of a tag property.
+struct Dispatch {
+    property: String,
+    /// Each value of the property and the index of the member it selects.
+    table: Vec<(Value, usize)>,
+    /// Whether no member but the one selected can match, so that the others
+    /// need not be evaluated.
+    exclusive: bool,
+}
+
+impl Dispatch {
+    /// Returns the index of the member selected by `value`, if any.
+    fn select(&self, value: &Value) -> Option<usize> {
+        let tag = value.as_object()?.get(&self.property)?;
+        self.table
+            .iter()
+            .find(|(v, _)| equals(v, tag))
+            .map(|(_, index)| *index)
+    }
+}
+
+/// Inserts `value` into `table`, selecting the member at `index`, unless an
+/// equal value is already present. Returns `true` if it was inserted.
+fn insert(table: &mut Vec<(Value, usize)>, value: Value, index: usize) -> bool {
+    if table.iter().any(|(v, _)| equals(v, &value)) {
+        return false;
+    }
+    table.push((value, index));
+    true
+}
+
+/// Returns the [`Dispatch`] of an OpenAPI `"discriminator"` of `source`, if
+/// it has one which selects at least one of `members`.
+fn discriminator(source: &Value, members: &[Value]) -> Option<Dispatch> {
+    let discriminator = source.get("discriminator")?;
+    let property = discriminator.get("propertyName")?.as_str()?.to_string();
+    let refs: Vec<Option<&str>> = members
+        .iter()
+        .map(|m| m.get("$ref").and_then(Value::as_str))
+        .collect();
+    let mut table = Vec::new();
+    let empty = Map::new();
+    let mapping = discriminator
+        .get("mapping")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    for (tag, target) in mapping {
+        let target = target.as_str();
+        if let Some(index) = refs.iter().position(|r| r.is_some() && *r == target) {
+            insert(&mut table, Value::String(tag.clone()), index);
+        }
+    }
+    for (index, reference) in refs.iter().enumerate() {
+        let name = match reference.and_then(|r| r.rsplit('/').next()) {
+            Some(name) if !name.is_empty() => name,
+            _ => continue,
+        };
+        insert(&mut table, Value::String(name.to_string()), index);
+    }
+    if table.is_empty() {
+        return None;
+    }
+    Some(Dispatch {
+        property,
+        table,
+        exclusive: false,
+    })
+}
+
+/// Returns the [`Dispatch`] of the first property which each of `members`
+/// constrains to distinct values with `"const"` or `"enum"`, if any.
+fn tag(members: &[Value]) -> Option<Dispatch> {
+    let first = members.first()?.get("properties")?.as_object()?;
+    'properties: for property in first.keys() {
+        let mut table = Vec::new();
+        for (index, member) in members.iter().enumerate() {
+            let constraint = match member.get("properties").and_then(|p| p.get(property)) {
+                Some(constraint) => constraint,
+                None => continue 'properties,
+            };
+            let values = match (constraint.get("const"), constraint.get("enum")) {
+                (Some(value), _) => vec![value],
+                (None, Some(Value::Array(values))) => values.iter().collect(),
+                _ => continue 'properties,
+            };
+            for value in values {
+                if !insert(&mut table, value.clone(), index) {
+                    continue 'properties;
+                }
+            }
+        }
+        return Some(Dispatch {
+            property: property.clone(),
+            table,
+            exclusive: true,
+        });
+    }
+    None
+}
+
+#[derive(Clone)]
+enum Target {
+    Bool(bool),
+    Schema(Schema),
+}
+
+/// Returns the index of a member, followed by its `"$id"` or `"title"` if
+/// it has one.
+fn label(index: usize, source: &Value) -> String {
+    let name = source
+        .get("$id")
+        .or_else(|| source.get("title"))
+        .and_then(Value::as_str);
+    match name {
+        Some(name) => format!("{} ({:?})", index, name),
+        None => index.to_string(),
+    }
+}
+
+/// Returns a description of the value at `location` for error messages.
+fn describe(location: &Pointer) -> String {
+    match location.as_str() {
+        "" => "the instance".to_string(),
+        location => format!("the value at {:?}", location),
+    }
+}
+
+/// Returns the number of errors of `eval` and its nested evaluations.
+fn error_count(eval: &Evaluation) -> usize {
+    usize::from(eval.error().is_some()) + eval.nested().iter().map(error_count).sum::<usize>()
+}
+
+/// Evaluates `value` against the member of `targets` at each of `indexes`,
+/// returning the evaluation of each in the order of `indexes`.
+///
+/// With the `"rayon"` feature, the members are evaluated in parallel if the
+/// evaluation [is parallel](crate::evaluation::Options::parallel).
+fn evaluate_members(
+    targets: &[(String, Target)],
+    indexes: Range<usize>,
+    value: &Value,
+    kw: &Evaluation,
+) -> Result<Vec<(usize, Evaluation)>, Error> {
+    let evaluate = |index: usize, mut member: Evaluation| -> Result<(usize, Evaluation), Error> {
+        match &targets[index].1 {
+            Target::Bool(true) => {}
+            Target::Bool(false) => member.set_false_schema_error(),
+            Target::Schema(schema) => member = schema.apply(value, member)?,
+        }
+        Ok((index, member))
+    };
+    #[cfg(feature = "rayon")]
+    if kw.state().options().is_parallel() {
+        use rayon::prelude::*;
+        let results: Vec<_> = indexes
+            .into_par_iter()
+            .map(|index| evaluate(index, kw.for_keyword(&index.to_string()).fork()))
+            .collect();
+        // collected separately so that the error returned, if any, is that of
+        // the first member to fail, as when evaluated sequentially
+        return results.into_iter().collect();
+    }
+    indexes
+        .map(|index| evaluate(index, kw.for_keyword(&index.to_string())))
+        .collect()
+}
+
+fn executor(targets: Vec<(String, Target)>, dispatch: Option<Dispatch>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let mut kw = eval.for_keyword("oneOf");
+            let dispatch = dispatch.as_ref();
+            let selected = dispatch.and_then(|d| d.select(value));
+            let indexes = match selected {
+                Some(index) if dispatch.map_or(false, |d| d.exclusive) => index..index + 1,
+                _ => 0..targets.len(),
+            };
+            let mut results = evaluate_members(&targets, indexes, value, &kw)?;
+            let matched: Vec<usize> = results
+                .iter()
+                .filter(|(_, r)| r.is_valid())
+                .map(|(i, _)| *i)
+                .collect();
+            match matched.len() {
+                1 => {
+                    let position = results.iter().position(|(i, _)| *i == matched[0]);
+                    kw.push(results.swap_remove(position.unwrap_or_default()).1);
+                }
+                0 => {
+                    let closest = selected.unwrap_or_else(|| {
+                        results
+                            .iter()
+                            .min_by_key(|(i, r)| (error_count(r), *i))
+                            .map_or(0, |(i, _)| *i)
+                    });
+                    kw.set_error_kind("none");
+                    kw.set_error_param("closest", targets[closest].0.as_str());
+                    kw.set_error(format!(
+                        "{} is not valid against any subschema of \"oneOf\"; the closest was {}",
+                        describe(kw.instance_location()),
+                        targets[closest].0
+                    ));
+                    kw.append(results.into_iter().map(|(_, r)| r));
+                }
+                _ => {
+                    let labels: Vec<&str> =
+                        matched.iter().map(|&i| targets[i].0.as_str()).collect();
+                    kw.set_error_kind("multiple");
+                    kw.set_error(format!(
+                        "{} is valid against more than one subschema of \"oneOf\": {}",
+                        describe(kw.instance_location()),
+                        labels.join(", ")
+                    ));
+                    kw.set_error_param("matched", labels);
+                    kw.insert("matched".to_string(), &matched)?;
+                    kw.append(results.into_iter().map(|(_, r)| r));
+                }
+            }
+            eval.push(kw);
+            next.call(value, eval)
+        },
+    )
+}
@@ -0,0 +1,255 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    pattern::Pattern,
+    pointer,
+    schema::SubSchema,
+    Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+/// [`Applicator`](crate::Applicator) for the `"pattern"` keyword.
+///
+/// The regular expression is compiled through the
+/// [`Patterns`](crate::pattern::Patterns) cache of the
+/// [`Interrogator`](crate::Interrogator), with its
+/// [`RegexEngine`](crate::pattern::RegexEngine), when the schema is setup;
+/// setup fails with an [`InvalidKeywordError`] if it is not valid.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{pattern, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("pattern", pattern);
+/// assert_eq!(applicator.keyword(), Some("pattern"));
+/// ```
+pub fn pattern(_interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let pattern = match schema.source().get("pattern") {
+        None => return Ok(None),
+        Some(Value::String(pattern)) => pattern.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "pattern".to_string(),
+                expected: "a string".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| {
+            let regex = compile(interrogator, schema, "pattern", &pattern)?;
+            Ok(pattern_executor(regex))
+        },
+    )))
+}
+
+/// [`Applicator`](crate::Applicator) for the `"patternProperties"` keyword.
+///
+/// Each property of an object instance is evaluated against the subschema
+/// of each pattern it matches. The regular expressions are compiled as
+/// for [`pattern`].
+///
+/// With the `"rayon"` feature, the properties are evaluated in parallel if
+/// [`Options::parallel`](crate::evaluation::Options::parallel) is set; the
+/// evaluation is the same as when they are evaluated sequentially.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{pattern_properties, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("patternProperties", pattern_properties);
+/// assert_eq!(applicator.keyword(), Some("patternProperties"));
+/// ```
+pub fn pattern_properties(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let members = match schema.source().get("patternProperties") {
+        None => return Ok(None),
+        Some(Value::Object(members)) => members.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: "patternProperties".to_string(),
+                expected: "an object of schemas".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| {
+            setup_pattern_properties(interrogator, schema, &members)
+        },
+    )))
+}
+
+fn compile(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    keyword: &str,
+    pattern: &str,
+) -> Result<Arc<Pattern>, Error> {
+    let engine = interrogator.regex_engine();
+    interrogator
+        .patterns()
+        .compile(pattern, engine)
+        .map_err(|err| {
+            InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: keyword.to_string(),
+                expected: format!("a valid regular expression ({})", err.reason),
+                value: Value::String(pattern.to_string()),
+            }
+            .into()
+        })
+}
+
+fn pattern_executor(regex: Arc<Pattern>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Value::String(s) = value {
+                if !regex.is_match(s) {
+                    let mut kw = eval.for_keyword("pattern");
+                    kw.set_error_kind("mismatch");
+                    kw.set_error_param("pattern", regex.as_str());
+                    kw.set_error(format!("the string does not match {:?}", regex.as_str()));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+#[derive(Clone)]
+enum Target {
+    Bool(bool),
+    Schema(Schema),
+}
+
+fn setup_pattern_properties(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    members: &Map<String, Value>,
+) -> Result<Box<ExecutorFn>, Error> {
+    let mut targets = Vec::with_capacity(members.len());
+    for (pattern, source) in members {
+        let regex = compile(interrogator, schema, "patternProperties", pattern)?;
+        let target = match source {
+            Value::Bool(b) => Target::Bool(*b),
+            Value::Object(_) => {
+                let key = format!("patternProperties/{}", pointer::escape(pattern));
+                match schema.add_sub_schema(&key, source.clone(), interrogator)? {
+                    SubSchema::Single(compiled) => Target::Schema(compiled),
+                    SubSchema::Array(_) => unreachable!("an object compiles to a single schema"),
+                }
+            }
+            _ => {
+                return Err(InvalidKeywordError {
+                    schema: schema.clone(),
+                    keyword: "patternProperties".to_string(),
+                    expected: "an object of schemas".to_string(),
+                    value: Value::Object(members.clone()),
+                }
+                .into())
+            }
+        };
+        targets.push((regex, target));
+    }
+    Ok(pattern_properties_executor(targets))
+}
+
+fn pattern_properties_executor(targets: Vec<(Arc<Pattern>, Target)>) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let properties = match value {
+                Value::Object(properties) => properties,
+                _ => return next.call(value, eval),
+            };
+            let mut kw = eval.for_keyword("patternProperties");
+            let mut pairs = Vec::new();
+            for (name, property) in properties {
+                for (index, (regex, _)) in targets.iter().enumerate() {
+                    if regex.is_match(name) {
+                        pairs.push((name, property, index));
+                    }
+                }
+            }
+            let members = evaluate_members(&targets, &pairs, &kw)?;
+            let mut matched = Vec::new();
+            let mut invalid = Vec::new();
+            // the pairs of each property are adjacent, so a property already
+            // listed is the last one listed
+            for ((name, _, _), member) in pairs.iter().zip(members) {
+                if !member.is_valid() && invalid.last() != Some(name) {
+                    invalid.push(*name);
+                }
+                kw.push(member);
+                if matched.last() != Some(name) {
+                    matched.push(*name);
+                }
+            }
+            if !invalid.is_empty() {
+                kw.set_error_kind("invalid");
+                let names = invalid.iter().map(|name| Value::from(name.as_str()));
+                kw.set_error_param("properties", Value::Array(names.collect()));
+                kw.set_error(format!(
+                    "{:?} are not valid against the subschemas of the patterns they match",
+                    invalid
+                ));
+            }
+            kw.insert("matched".to_string(), &matched)?;
+            eval.push(kw);
+            next.call(value, eval)
+        },
+    )
+}
+
+/// The name and value of a property, and the index of the target of a
+/// pattern it matches.
+type Pair<'v> = (&'v String, &'v Value, usize);
+
+/// Evaluates each property of `pairs` against the subschema of the pattern of
+/// `targets` it matches, returning the evaluation of each in the order of
+/// `pairs`.
+///
+/// With the `"rayon"` feature, the properties are evaluated in parallel if
+/// the evaluation [is parallel](crate::evaluation::Options::parallel).
+fn evaluate_members(
+    targets: &[(Arc<Pattern>, Target)],
+    pairs: &[Pair],
+    kw: &Evaluation,
+) -> Result<Vec<Evaluation>, Error> {
+    let evaluate = |&(name, property, index): &Pair, mut member: Evaluation| -> Result<_, Error> {
+        let mut location = member.instance_location().clone();
+        location.push_back(name.as_str().into());
+        member.set_instance_location(location);
+        match &targets[index].1 {
+            Target::Bool(true) => {}
+            Target::Bool(false) => member.set_false_schema_error(),
+            Target::Schema(schema) => member = schema.apply(property, member)?,
+        }
+        Ok(member)
+    };
+    // the pattern is escaped as a token of the keyword location
+    let member = |&(_, _, index): &Pair| kw.for_keyword(targets[index].0.as_str());
+    #[cfg(feature = "rayon")]
+    if kw.state().options().is_parallel() {
+        use rayon::prelude::*;
+        let results: Vec<_> = pairs
+            .par_iter()
+            .map(|pair| evaluate(pair, member(pair).fork()))
+            .collect();
+        // collected separately so that the error returned, if any, is that of
+        // the first property to fail, as when evaluated sequentially
+        return results.into_iter().collect();
+    }
+    pairs
+        .iter()
+        .map(|pair| evaluate(pair, member(pair)))
+        .collect()
+}
@@ -0,0 +1,198 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    Error, Evaluation, Interrogator, Next, Precision, Schema,
+};
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Maximum,
+    ExclusiveMaximum,
+    Minimum,
+    ExclusiveMinimum,
+}
+
+impl Bound {
+    fn is_satisfied_by(self, ord: Ordering) -> bool {
+        match self {
+            Bound::Maximum => ord != Ordering::Greater,
+            Bound::ExclusiveMaximum => ord == Ordering::Less,
+            Bound::Minimum => ord != Ordering::Less,
+            Bound::ExclusiveMinimum => ord == Ordering::Greater,
+        }
+    }
+    fn describe(self) -> &'static str {
+        match self {
+            Bound::Maximum => "less than or equal to",
+            Bound::ExclusiveMaximum => "less than",
+            Bound::Minimum => "greater than or equal to",
+            Bound::ExclusiveMinimum => "greater than",
+        }
+    }
+    /// Returns the kind of the error of a value which does not satisfy the
+    /// bound.
+    fn kind(self) -> &'static str {
+        match self {
+            Bound::Maximum => "maximum",
+            Bound::ExclusiveMaximum => "exclusiveMaximum",
+            Bound::Minimum => "minimum",
+            Bound::ExclusiveMinimum => "exclusiveMinimum",
+        }
+    }
+}
+
+/// [`Applicator`](crate::Applicator) for the `"maximum"` keyword.
+///
+/// Supports the boolean form of `"exclusiveMaximum"` from Draft 04.
+pub fn maximum(interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let bound = if is_exclusive_flag_set(&schema, "exclusiveMaximum") {
+        Bound::ExclusiveMaximum
+    } else {
+        Bound::Maximum
+    };
+    init(&interrogator, &schema, "maximum", bound)
+}
+
+/// [`Applicator`](crate::Applicator) for the numeric `"exclusiveMaximum"`
+/// keyword.
+pub fn exclusive_maximum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(
+        &interrogator,
+        &schema,
+        "exclusiveMaximum",
+        Bound::ExclusiveMaximum,
+    )
+}
+
+/// [`Applicator`](crate::Applicator) for the `"minimum"` keyword.
+///
+/// Supports the boolean form of `"exclusiveMinimum"` from Draft 04.
+pub fn minimum(interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let bound = if is_exclusive_flag_set(&schema, "exclusiveMinimum") {
+        Bound::ExclusiveMinimum
+    } else {
+        Bound::Minimum
+    };
+    init(&interrogator, &schema, "minimum", bound)
+}
+
+/// [`Applicator`](crate::Applicator) for the numeric `"exclusiveMinimum"`
+/// keyword.
+pub fn exclusive_minimum(
+    interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    init(
+        &interrogator,
+        &schema,
+        "exclusiveMinimum",
+        Bound::ExclusiveMinimum,
+    )
+}
+
+/// [`Describer`](crate::describe::Describer) for the `"maximum"`,
+/// `"exclusiveMaximum"`, `"minimum"` and `"exclusiveMinimum"` keywords, e.g.
+/// `"3–20"` or `"> 0 and <= 100"`.
+pub fn describe_range(object: &Map<String, Value>) -> Option<String> {
+    let bound = |inclusive: &str, exclusive: &str| -> Option<(Number, bool)> {
+        match (object.get(inclusive), object.get(exclusive)) {
+            (_, Some(Value::Number(n))) => Some((n.clone(), true)),
+            (Some(Value::Number(n)), Some(Value::Bool(exclusive))) => Some((n.clone(), *exclusive)),
+            (Some(Value::Number(n)), _) => Some((n.clone(), false)),
+            _ => None,
+        }
+    };
+    let lower = bound("minimum", "exclusiveMinimum");
+    let upper = bound("maximum", "exclusiveMaximum");
+    match (lower, upper) {
+        (Some((min, false)), Some((max, false))) => Some(format!("{}–{}", min, max)),
+        (Some((min, min_ex)), Some((max, max_ex))) => Some(format!(
+            "{} {} and {} {}",
+            if min_ex { ">" } else { ">=" },
+            min,
+            if max_ex { "<" } else { "<=" },
+            max
+        )),
+        (Some((min, true)), None) => Some(format!("> {}", min)),
+        (Some((min, false)), None) => Some(format!(">= {}", min)),
+        (None, Some((max, true))) => Some(format!("< {}", max)),
+        (None, Some((max, false))) => Some(format!("<= {}", max)),
+        (None, None) => None,
+    }
+}
+
+/// Returns `true` if `keyword` is present in `schema` as `true`, as is the
+/// case for `"exclusiveMaximum"` and `"exclusiveMinimum"` in Draft 04.
+fn is_exclusive_flag_set(schema: &Schema, keyword: &str) -> bool {
+    schema.source().get(keyword).and_then(Value::as_bool) == Some(true)
+}
+
+fn init(
+    _interrogator: &Interrogator,
+    schema: &Schema,
+    keyword: &'static str,
+    bound: Bound,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    let limit = match schema.source().get(keyword) {
+        None => return Ok(None),
+        // Draft 04 boolean form; handled by "maximum" / "minimum"
+        Some(Value::Bool(_)) if matches!(keyword, "exclusiveMaximum" | "exclusiveMinimum") => {
+            return Ok(None)
+        }
+        Some(Value::Number(n)) => n.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: keyword.to_string(),
+                expected: "a number".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, _: &Schema| {
+            Ok(executor(interrogator, keyword, bound, &limit))
+        },
+    )))
+}
+
+fn executor(
+    interrogator: &Interrogator,
+    keyword: &'static str,
+    bound: Bound,
+    limit: &Number,
+) -> Box<ExecutorFn> {
+    let numbers = interrogator.numbers();
+    let precision = interrogator.precision();
+    let limit_value = limit.clone();
+    let limit = numbers.intern(limit);
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Value::Number(n) = value {
+                let exact = eval.state().options().compares_numbers_exactly()
+                    && !(limit.is_lossless() && numbers.is_lossless(n));
+                let precision = if exact {
+                    Precision::Arbitrary
+                } else {
+                    precision
+                };
+                let ord = numbers.compare_key(n, &limit, precision);
+                if !matches!(ord, Some(ord) if bound.is_satisfied_by(ord)) {
+                    let mut kw = eval.for_keyword(keyword);
+                    kw.set_error_kind(bound.kind());
+                    kw.set_error_param("limit", limit_value.clone());
+                    kw.set_error_param("value", n.clone());
+                    kw.set_error(format!("{} is not {} {}", n, bound.describe(), limit));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
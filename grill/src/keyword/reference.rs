@@ -0,0 +1,204 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::{InvalidKeywordError, InvalidRefTargetError, UnknownSchemaError},
+    schema::SubSchema,
+    uri::UriStr,
+    urn, Error, Evaluation, Interrogator, Next, Schema,
+};
+use serde_json::Value;
+use uniresid::{AbsoluteUri, Uri};
+
+/// [`Applicator`](crate::Applicator) for the `"$ref"` keyword.
+///
+/// The reference is resolved against the id of the [`Schema`] with
+/// [`urn::resolve`] and recorded with [`Schema::add_reference`]. A relative
+/// path can not be resolved against a URN and fails with
+/// [`Error::InvalidKeyword`](crate::Error::InvalidKeyword). When the [`Schema`] is setup, the target
+/// is the top-level [`Schema`] with the referenced id, if one has been added
+/// to the [`Interrogator`]; otherwise it is located within the source
+/// documents with [`Interrogator::locate`].
+///
+/// Targets which are boolean schemas are applied as such. Targets which are
+/// neither an object nor a boolean, e.g. a reference to `"#/$defs/name/type"`
+/// rather than `"#/$defs/name"`, fail setup with
+/// [`Error::InvalidRefTarget`](crate::Error::InvalidRefTarget).
+pub fn ref_(_interrogator: Interrogator, schema: Schema) -> Result<Option<Box<SetupFn>>, Error> {
+    let (reference, target) = match parse(&schema, "$ref")? {
+        Some(parsed) => parsed,
+        None => return Ok(None),
+    };
+    Ok(Some(Box::new(
+        move |interrogator: &Interrogator, schema: &Schema| {
+            resolve(interrogator, schema, "$ref", &reference, &target).map(executor)
+        },
+    )))
+}
+
+/// Returns the reference of `keyword` and its target, resolved against the
+/// id of `schema`, recording the target with [`Schema::add_reference`].
+pub(super) fn parse(schema: &Schema, keyword: &str) -> Result<Option<(String, Resolved)>, Error> {
+    let reference = match schema.source().get(keyword) {
+        None => return Ok(None),
+        Some(Value::String(reference)) => reference.clone(),
+        Some(v) => {
+            return Err(InvalidKeywordError {
+                schema: schema.clone(),
+                keyword: keyword.to_string(),
+                expected: "a string".to_string(),
+                value: v.clone(),
+            }
+            .into())
+        }
+    };
+    let id = schema.id();
+    // most references are fragments, which replace the fragment of the id
+    // whatever its scheme, so are parsed once as the absolute target rather
+    // than parsed, resolved, and parsed again to be located
+    let fragment = match (&id, UriStr::parse(&reference)) {
+        (Some(id), Ok(uri)) if uri.is_fragment_only() => {
+            let id = id.to_string();
+            let document = id.split('#').next().unwrap_or_default();
+            AbsoluteUri::parse(&format!("{}{}", document, reference)).ok()
+        }
+        _ => None,
+    };
+    let target = match fragment {
+        Some(target) => Resolved::Absolute(target),
+        // uniresid is the parser of record, so a reference `UriStr` rejects
+        // resolves as it would otherwise
+        None => {
+            let uri = Uri::parse(&reference)?;
+            let target = match &id {
+                Some(id) => urn::resolve(id, &uri),
+                None => Some(uri),
+            };
+            match target {
+                Some(target) => Resolved::from(target),
+                None => {
+                    return Err(InvalidKeywordError {
+                        schema: schema.clone(),
+                        keyword: keyword.to_string(),
+                        expected: "an absolute URI or a fragment, as the id of the schema is a URN"
+                            .to_string(),
+                        value: Value::String(reference),
+                    }
+                    .into())
+                }
+            }
+        }
+    };
+    schema.add_reference(target.uri().clone());
+    Ok(Some((reference, target)))
+}
+
+/// The target of a reference, parsed as an [`AbsoluteUri`] when it has a
+/// scheme so that it is located without being parsed again.
+#[derive(Clone)]
+pub(super) enum Resolved {
+    Absolute(AbsoluteUri),
+    Relative(Uri),
+}
+
+impl Resolved {
+    /// Returns the target as a [`Uri`].
+    pub(super) fn uri(&self) -> &Uri {
+        match self {
+            Resolved::Absolute(uri) => uri.uri(),
+            Resolved::Relative(uri) => uri,
+        }
+    }
+}
+
+impl From<Uri> for Resolved {
+    fn from(uri: Uri) -> Self {
+        match AbsoluteUri::parse(&uri.to_string()) {
+            Ok(uri) => Resolved::Absolute(uri),
+            Err(_) => Resolved::Relative(uri),
+        }
+    }
+}
+
+/// Returns the [`Target`] of `reference`, the value of `keyword`, which
+/// resolved to `target`.
+pub(super) fn resolve(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    keyword: &str,
+    reference: &str,
+    target: &Resolved,
+) -> Result<Target, Error> {
+    if let Some(compiled) = interrogator.schema(target.uri()) {
+        return Ok(Target::Schema(compiled));
+    }
+    let located = match target {
+        Resolved::Absolute(uri) => interrogator.locate(uri)?,
+        Resolved::Relative(_) => None,
+    };
+    match located {
+        None => Err(UnknownSchemaError {
+            id: target.uri().clone(),
+        }
+        .into()),
+        Some(Value::Bool(b)) => Ok(Target::Bool(b)),
+        Some(source @ Value::Object(_)) => {
+            match schema.add_sub_schema(keyword, source, interrogator)? {
+                SubSchema::Single(compiled) => Ok(Target::Schema(compiled)),
+                SubSchema::Array(_) => unreachable!("an object compiles to a single schema"),
+            }
+        }
+        Some(value) => Err(InvalidRefTargetError {
+            schema_id: schema.id().as_deref().cloned(),
+            reference: reference.to_string(),
+            target: target.uri().clone(),
+            kind: kind_of(&value),
+        }
+        .into()),
+    }
+}
+
+#[derive(Clone)]
+pub(super) enum Target {
+    Bool(bool),
+    Schema(Schema),
+}
+
+impl Target {
+    /// Returns the source of the target.
+    pub(super) fn source(&self) -> Value {
+        match self {
+            Target::Bool(b) => Value::Bool(*b),
+            Target::Schema(schema) => schema.source().as_ref().clone(),
+        }
+    }
+
+    /// Applies the target to `value`, annotating `kw`.
+    pub(super) fn apply(&self, value: &Value, mut kw: Evaluation) -> Result<Evaluation, Error> {
+        match self {
+            Target::Bool(true) => {}
+            Target::Bool(false) => kw.set_false_schema_error(),
+            Target::Schema(schema) => kw = schema.apply(value, kw)?,
+        }
+        Ok(kw)
+    }
+}
+
+fn executor(target: Target) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            let kw = target.apply(value, eval.for_keyword("$ref"))?;
+            eval.push(kw);
+            next.call(value, eval)
+        },
+    )
+}
+
+fn kind_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
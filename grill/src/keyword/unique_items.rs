@@ -0,0 +1,165 @@
+use crate::{
+    applicator::{ExecutorFn, SetupFn},
+    error::InvalidKeywordError,
+    Error, Evaluation, Interrogator, Next, Numbers, Precision, Schema,
+};
+use serde_json::Value;
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// [`Applicator`](crate::Applicator) for the `"uniqueItems"` keyword.
+///
+/// Items are bucketed by a canonical hash, under which mathematically equal
+/// numbers (e.g. `1` and `1.0`) and objects with the same members in any
+/// order hash alike, so that only items which share a bucket are compared.
+/// Evaluation is therefore linear in the length of the array rather than
+/// quadratic. Numbers are compared with the [`Precision`] of the
+/// [`Interrogator`] through its [`Numbers`] cache.
+///
+/// ## Example
+/// ```
+/// use grill::{keyword::{unique_items, Keyword}, Applicator};
+///
+/// let applicator = Keyword::new("uniqueItems", unique_items);
+/// assert_eq!(applicator.keyword(), Some("uniqueItems"));
+/// ```
+pub fn unique_items(
+    _interrogator: Interrogator,
+    schema: Schema,
+) -> Result<Option<Box<SetupFn>>, Error> {
+    match schema.source().get("uniqueItems") {
+        None | Some(Value::Bool(false)) => Ok(None),
+        Some(Value::Bool(true)) => Ok(Some(Box::new(|interrogator: &Interrogator, _: &Schema| {
+            Ok(executor(interrogator.numbers(), interrogator.precision()))
+        }))),
+        Some(v) => Err(InvalidKeywordError {
+            schema: schema.clone(),
+            keyword: "uniqueItems".to_string(),
+            expected: "a boolean".to_string(),
+            value: v.clone(),
+        }
+        .into()),
+    }
+}
+
+fn executor(numbers: Numbers, precision: Precision) -> Box<ExecutorFn> {
+    Box::new(
+        move |value: &Value, mut eval: Evaluation, next: Next| -> Result<Evaluation, Error> {
+            if let Some(items) = value.as_array() {
+                let items = items.iter().map(std::slice::from_ref).enumerate();
+                let duplicate = find_duplicate(items, |a, b| equals(a, b, &numbers, precision));
+                if let Some((i, j)) = duplicate {
+                    let mut kw = eval.for_keyword("uniqueItems");
+                    kw.set_error_kind("duplicate");
+                    kw.set_error_param("first", i);
+                    kw.set_error_param("second", j);
+                    kw.set_error(format!("items {} and {} are equal", i, j));
+                    eval.push(kw);
+                }
+            }
+            next.call(value, eval)
+        },
+    )
+}
+
+/// Returns the indexes of the first pair of `items`, each an index and a
+/// tuple of values, e.g. a slice of one item, whose values are equal
+/// according to `eq`, if any.
+///
+/// Items are bucketed by their [`canonical_hash`], so `eq` is only called
+/// for items whose hashes collide.
+pub(super) fn find_duplicate<T, V>(
+    items: impl Iterator<Item = (usize, T)>,
+    eq: impl Fn(&Value, &Value) -> bool,
+) -> Option<(usize, usize)>
+where
+    T: AsRef<[V]>,
+    V: Borrow<Value>,
+{
+    let mut buckets: HashMap<u64, Vec<(usize, T)>> = HashMap::new();
+    for (i, values) in items {
+        let mut hasher = DefaultHasher::new();
+        for value in values.as_ref() {
+            canonical_hash(value.borrow(), &mut hasher);
+        }
+        let bucket = buckets.entry(hasher.finish()).or_default();
+        let duplicate = bucket.iter().find(|(_, other)| {
+            let pairs = other.as_ref().iter().zip(values.as_ref());
+            pairs.all(|(a, b)| eq(a.borrow(), b.borrow()))
+        });
+        if let Some((j, _)) = duplicate {
+            return Some((*j, i));
+        }
+        bucket.push((i, values));
+    }
+    None
+}
+
+/// Feeds `value` into `state` such that values which are equal as defined
+/// by JSON Schema produce the same hash.
+///
+/// Numbers are hashed by their value as an `f64`, which is the same for
+/// mathematically equal numbers at any [`Precision`]; numbers which are not
+/// equal but round to the same `f64` are told apart by comparison. The
+/// members of an object are hashed independently of their order.
+fn canonical_hash<H: Hasher>(value: &Value, state: &mut H) {
+    match value {
+        Value::Null => 0u8.hash(state),
+        Value::Bool(b) => {
+            1u8.hash(state);
+            b.hash(state);
+        }
+        Value::Number(n) => {
+            2u8.hash(state);
+            // -0.0 and 0.0 are equal but differ in bits
+            let n = n.as_f64().map(|n| if n == 0.0 { 0.0 } else { n });
+            n.map(f64::to_bits).hash(state);
+        }
+        Value::String(s) => {
+            3u8.hash(state);
+            s.hash(state);
+        }
+        Value::Array(items) => {
+            4u8.hash(state);
+            items.len().hash(state);
+            items.iter().for_each(|item| canonical_hash(item, state));
+        }
+        Value::Object(members) => {
+            5u8.hash(state);
+            members.len().hash(state);
+            let combined = members.iter().fold(0u64, |combined, (key, value)| {
+                let mut member = DefaultHasher::new();
+                key.hash(&mut member);
+                canonical_hash(value, &mut member);
+                combined.wrapping_add(member.finish())
+            });
+            combined.hash(state);
+        }
+    }
+}
+
+/// Compares `a` and `b` for equality as defined by JSON Schema, comparing
+/// numbers with `precision`.
+pub(super) fn equals(a: &Value, b: &Value, numbers: &Numbers, precision: Precision) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            numbers.compare(a, b, precision) == Some(Ordering::Equal)
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| equals(a, b, numbers, precision))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, v)| matches!(b.get(k), Some(bv) if equals(v, bv, numbers, precision)))
+        }
+        _ => a == b,
+    }
+}
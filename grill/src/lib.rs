@@ -40,7 +40,7 @@ pub use next::Next;
 mod graph;
 pub(crate) use graph::Graph;
 
-pub use uniresid as uri;
+pub mod uri;
 
 pub use uri::Uri;
 
@@ -48,13 +48,94 @@ pub use jsonptr;
 pub use jsonptr::Pointer;
 
 pub mod draft;
-pub use draft::{
-    create_hyper_schema_04, create_hyper_schema_07, create_hyper_schema_2019_09,
-    create_hyper_schema_2020_12, create_schema_04, create_schema_07, create_schema_2019_09,
-    create_schema_2020_12,
-};
+#[cfg(feature = "draft-04")]
+pub use draft::{create_hyper_schema_04, create_schema_04};
+#[cfg(feature = "draft-07")]
+pub use draft::{create_hyper_schema_07, create_schema_07};
+#[cfg(feature = "draft-2019-09")]
+pub use draft::{create_hyper_schema_2019_09, create_schema_2019_09};
+#[cfg(feature = "draft-2020-12")]
+pub use draft::{create_hyper_schema_2020_12, create_schema_2020_12};
 mod vocabulary;
 pub use vocabulary::*;
 
 pub mod dialect;
 pub use dialect::Dialect;
+
+pub mod number;
+pub use number::{Numbers, Precision};
+
+pub mod pattern;
+
+pub mod pointer;
+
+mod feature;
+pub use feature::Feature;
+
+pub mod keyword;
+
+pub mod source;
+pub use source::{Provenance, SourceEntry, Sources};
+
+pub mod docs;
+
+pub mod describe;
+
+pub mod lint;
+
+pub mod plugin;
+
+pub mod algebra;
+pub mod backend;
+pub mod cancel;
+pub mod components;
+pub mod content;
+pub mod cost;
+pub mod coverage;
+pub mod deserialize;
+pub mod deserializer;
+pub mod explain;
+pub mod format;
+pub mod fragment;
+pub mod infer;
+pub mod mirror;
+pub mod namespace;
+pub mod normalize;
+pub mod optimize;
+pub mod output;
+pub mod redact;
+pub mod report;
+pub mod rewrite;
+pub mod simplify;
+#[cfg(feature = "state")]
+pub mod state;
+pub mod stream;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod urn;
+#[cfg(feature = "vendor")]
+pub mod vendor;
+
+// public types must be usable across threads, e.g. on a multi-threaded tokio
+// runtime
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Interrogator>();
+    assert_send_sync::<Schema>();
+    assert_send_sync::<Evaluation>();
+    assert_send_sync::<Error>();
+    assert_send_sync::<Sources>();
+    assert_send_sync::<Numbers>();
+    assert_send_sync::<pattern::Patterns>();
+    assert_send_sync::<format::Formats>();
+    assert_send_sync::<BoxedResolver>();
+};
+
+#[cfg(feature = "async")]
+const _: fn() = || {
+    fn assert_send<T: Send>(_: &T) {}
+    let interrogator = Interrogator::new();
+    let uri = uri::AbsoluteUri::parse("urn:example").unwrap();
+    assert_send(&interrogator.source_async_reader(uri, &b""[..]));
+    assert_send(&interrogator.resolve_async("urn:example"));
+};
@@ -0,0 +1,481 @@
+//! Static analysis of schema objects for keyword combinations which are
+//! mutually exclusive or otherwise nonsensical.
+//!
+//! Lints do not prevent a [`Schema`](crate::Schema) from being added to an
+//! [`Interrogator`](crate::Interrogator); instead, each is reported as a
+//! [`Diagnostic`] which can be retrieved with
+//! [`Interrogator::diagnostics`](crate::Interrogator::diagnostics).
+//!
+//! In addition, [`check_examples`] evaluates the `"examples"` and
+//! `"default"` of a compiled `Schema` against it.
+//!
+//! ## Example
+//! ```
+//! use grill::lint::{check, Lint};
+//! use serde_json::json;
+//!
+//! let diagnostics = check(&json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "name": { "minLength": 10, "maxLength": 3 }
+//!     }
+//! }));
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].lint, Lint::EmptyRange);
+//! assert_eq!(diagnostics[0].keyword_location.as_str(), "/properties/name");
+//! ```
+
+use crate::{
+    evaluation::{Options, State},
+    pointer::{self, Pointer},
+    schema::SubSchema,
+    Evaluation, OutputFmt, Schema,
+};
+use regex::Regex;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::{fmt, sync::Arc};
+use uniresid::Uri;
+
+/// Keywords whose value is a single subschema.
+pub(crate) const SCHEMA_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "else",
+    "if",
+    "items",
+    "not",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords whose value is an array of subschemas.
+pub(crate) const ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf", "prefixItems"];
+
+/// Keywords whose value is an object of subschemas.
+pub(crate) const OBJECT_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// Keywords which only apply to instances of a given type.
+const TYPED_KEYWORDS: &[(&str, &[&str])] = &[
+    (
+        "array",
+        &[
+            "items",
+            "prefixItems",
+            "additionalItems",
+            "contains",
+            "maxItems",
+            "minItems",
+            "uniqueItems",
+            "maxContains",
+            "minContains",
+        ],
+    ),
+    (
+        "object",
+        &[
+            "properties",
+            "patternProperties",
+            "additionalProperties",
+            "required",
+            "maxProperties",
+            "minProperties",
+            "propertyNames",
+            "dependentRequired",
+        ],
+    ),
+    ("string", &["maxLength", "minLength", "pattern"]),
+    (
+        "number",
+        &[
+            "maximum",
+            "minimum",
+            "exclusiveMaximum",
+            "exclusiveMinimum",
+            "multipleOf",
+        ],
+    ),
+];
+
+/// Pairs of keywords which, when the lower bound exceeds the upper bound,
+/// can not be satisfied.
+const RANGES: &[(&str, &str)] = &[
+    ("minimum", "maximum"),
+    ("minLength", "maxLength"),
+    ("minItems", "maxItems"),
+    ("minProperties", "maxProperties"),
+    ("minContains", "maxContains"),
+];
+
+/// The kind of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// A keyword which only applies to instances of a type excluded by
+    /// `"type"`, e.g. `"items"` alongside `"type": "object"`.
+    IneffectiveKeyword,
+    /// A lower bound exceeds its upper bound, e.g. `"minLength"` greater than
+    /// `"maxLength"`.
+    EmptyRange,
+    /// A property listed in `"required"` can never be present as it is not
+    /// permitted by `"properties"` or `"patternProperties"` while
+    /// `"additionalProperties"` is `false`.
+    UnsatisfiableRequired,
+    /// An identifier was converted to Unicode Normalization Form C. See
+    /// [`normalize`](crate::normalize).
+    NormalizedIdentifier,
+    /// A fragment which combines an anchor with a JSON Pointer, e.g.
+    /// `"#node/properties/name"`, was resolved leniently. See
+    /// [`fragment`](crate::fragment).
+    AnchorPointerFragment,
+    /// An `"$anchor"` or `"$dynamicAnchor"` does not match the grammar of
+    /// the draft. See [`check_anchors`](crate::fragment::check_anchors).
+    InvalidAnchor,
+    /// An anchor is declared more than once within a resource. See
+    /// [`DuplicateAnchorPolicy`](crate::fragment::DuplicateAnchorPolicy).
+    DuplicateAnchor,
+    /// A member of `"examples"` or the `"default"` of a schema is not valid
+    /// against it. See [`check_examples`].
+    InvalidExample,
+}
+
+impl Lint {
+    /// Returns the stable, machine-readable code of the lint, prefixed with
+    /// `GRILL_L`. A code is never changed or reused once assigned.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lint::IneffectiveKeyword => "GRILL_L0001",
+            Lint::EmptyRange => "GRILL_L0002",
+            Lint::UnsatisfiableRequired => "GRILL_L0003",
+            Lint::NormalizedIdentifier => "GRILL_L0004",
+            Lint::AnchorPointerFragment => "GRILL_L0005",
+            Lint::InvalidAnchor => "GRILL_L0006",
+            Lint::DuplicateAnchor => "GRILL_L0007",
+            Lint::InvalidExample => "GRILL_L0008",
+        }
+    }
+}
+
+/// A [`Lint`] found within a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The kind of lint.
+    pub lint: Lint,
+    /// The id of the [`Schema`](crate::Schema), if known.
+    pub schema_id: Option<Arc<Uri>>,
+    /// The location of the offending schema object, relative to the root of
+    /// the schema.
+    pub keyword_location: Pointer,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Returns the stable, machine-readable code of the [`Lint`] of the
+    /// diagnostic.
+    pub fn code(&self) -> &'static str {
+        self.lint.code()
+    }
+}
+
+/// Serializes as an object with the [`code`](Diagnostic::code), the id of
+/// the schema, if known, the keyword location, and the message of the
+/// diagnostic.
+///
+/// ## Example
+/// ```
+/// use grill::lint::check;
+/// use serde_json::json;
+///
+/// let diagnostics = check(&json!({ "minLength": 10, "maxLength": 3 }));
+/// assert_eq!(
+///     serde_json::to_value(&diagnostics[0]).unwrap(),
+///     json!({
+///         "code": "GRILL_L0002",
+///         "keywordLocation": "",
+///         "message": diagnostics[0].message,
+///     })
+/// );
+/// ```
+impl Serialize for Diagnostic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("code", self.code())?;
+        if let Some(id) = &self.schema_id {
+            map.serialize_entry("schemaId", &id.to_string())?;
+        }
+        map.serialize_entry("keywordLocation", self.keyword_location.as_str())?;
+        map.serialize_entry("message", &self.message)?;
+        map.end()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at \"{}\"", self.message, self.keyword_location)?;
+        if let Some(id) = &self.schema_id {
+            write!(f, " [{}]", id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `source` and each of its subschemas, returning a [`Diagnostic`] per
+/// [`Lint`] found.
+pub fn check(source: &Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    walk(source, &Pointer::default(), &mut diagnostics);
+    diagnostics
+}
+
+/// Evaluates each member of `"examples"` and the `"default"` of `schema`
+/// and of each of its compiled [`SubSchema`]s against the schema which
+/// contains it, returning a [`Diagnostic`] of [`Lint::InvalidExample`] for
+/// each which is not valid or can not be evaluated.
+///
+/// Examples of a schema object which is not compiled, e.g. one nested
+/// beneath a keyword which no [`Applicator`](crate::Applicator) of the
+/// dialect implements, are not checked.
+///
+/// Each is evaluated with `options`, within the schema resources which
+/// contain its schema, and is abbreviated in the message of its
+/// `Diagnostic` if its JSON is longer than 40 characters.
+///
+/// Run for each `Schema` added to an [`Interrogator`](crate::Interrogator)
+/// set to [validate examples](crate::Interrogator::set_validate_examples),
+/// with numbers compared exactly if its [`Precision`](crate::Precision) is
+/// arbitrary.
+///
+/// ## Example
+/// ```
+/// use grill::{evaluation::Options, lint::{check_examples, Lint}, Interrogator, Schema};
+/// use serde_json::json;
+///
+/// let interrogator = Interrogator::new();
+/// let source = json!({ "maximum": 10, "default": 5, "examples": [1, 20] });
+/// let schema = Schema::new(source, &interrogator).unwrap();
+/// let diagnostics = check_examples(&schema, &Options::default());
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].lint, Lint::InvalidExample);
+/// assert!(diagnostics[0].message.starts_with("\"examples\"[1]"));
+/// ```
+pub fn check_examples(schema: &Schema, options: &Options) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    // tracks the dynamic scope of the walk, rather than of an evaluation
+    let scope = State::new(Options::default());
+    walk_examples(
+        schema,
+        &Pointer::default(),
+        options,
+        &scope,
+        &mut diagnostics,
+    );
+    for diagnostic in &mut diagnostics {
+        diagnostic.schema_id = schema.id();
+    }
+    diagnostics
+}
+
+/// The maximum length of the JSON of an example within a [`Diagnostic`].
+const EXAMPLE_WIDTH: usize = 40;
+
+fn walk_examples(
+    schema: &Schema,
+    location: &Pointer,
+    options: &Options,
+    scope: &State,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let entered = match schema.resource(scope) {
+        Some(resource) => scope.enter_scope(resource),
+        None => false,
+    };
+    let source = schema.source();
+    let examples = source.get("examples").and_then(Value::as_array);
+    let instances = examples
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, example)| (format!("\"examples\"[{}]", i), example))
+        .chain(
+            source
+                .get("default")
+                .map(|d| ("\"default\"".to_string(), d)),
+        );
+    for (name, instance) in instances {
+        let eval = schema.evaluate_in_scope(
+            instance,
+            OutputFmt::Basic,
+            options.clone(),
+            &scope.dynamic_scope(),
+        );
+        let problem = match eval {
+            Ok(eval) if eval.is_valid() => continue,
+            Ok(eval) => format!(
+                "is not valid against its schema: {}",
+                first_error(&eval).unwrap_or("unknown error")
+            ),
+            Err(err) => format!("could not be evaluated: {}", err),
+        };
+        diagnostics.push(Diagnostic {
+            lint: Lint::InvalidExample,
+            schema_id: None,
+            keyword_location: location.clone(),
+            message: format!("{} ({}) {}", name, abbreviate(instance), problem),
+        });
+    }
+    let mut subs: Vec<_> = schema.sub_schemas().into_iter().collect();
+    subs.sort_by(|a, b| a.0.cmp(&b.0));
+    for (key, sub) in subs {
+        // keys are the keyword followed by the member, e.g. "oneOf/0"
+        let tokens: Vec<&str> = key.splitn(2, '/').collect();
+        let location = pointer::join(location, &tokens);
+        match sub {
+            SubSchema::Single(sub) => walk_examples(&sub, &location, options, scope, diagnostics),
+            SubSchema::Array(subs) => {
+                for (i, sub) in subs.iter().enumerate() {
+                    let location = pointer::join(&location, &[&i.to_string()]);
+                    walk_examples(sub, &location, options, scope, diagnostics);
+                }
+            }
+        }
+    }
+    if entered {
+        scope.exit_scope();
+    }
+}
+
+/// Returns the JSON of `value`, cut short with an ellipsis if it is longer
+/// than [`EXAMPLE_WIDTH`] characters.
+fn abbreviate(value: &Value) -> String {
+    let json = value.to_string();
+    if json.chars().count() <= EXAMPLE_WIDTH {
+        return json;
+    }
+    let mut abbreviated: String = json.chars().take(EXAMPLE_WIDTH - 1).collect();
+    abbreviated.push('…');
+    abbreviated
+}
+
+/// Returns the first error of `eval` or its nested evaluations, if any.
+fn first_error(eval: &Evaluation) -> Option<&str> {
+    eval.error()
+        .or_else(|| eval.nested().iter().find_map(first_error))
+}
+
+fn walk(value: &Value, location: &Pointer, diagnostics: &mut Vec<Diagnostic>) {
+    let obj = match value.as_object() {
+        Some(obj) => obj,
+        None => return,
+    };
+    check_object(obj, location, diagnostics);
+    for keyword in SCHEMA_KEYWORDS {
+        if let Some(sub) = obj.get(*keyword) {
+            walk(sub, &pointer::join(location, &[keyword]), diagnostics);
+        }
+    }
+    for keyword in ARRAY_KEYWORDS {
+        if let Some(subs) = obj.get(*keyword).and_then(Value::as_array) {
+            for (i, sub) in subs.iter().enumerate() {
+                walk(
+                    sub,
+                    &pointer::join(location, &[keyword, &i.to_string()]),
+                    diagnostics,
+                );
+            }
+        }
+    }
+    for keyword in OBJECT_KEYWORDS {
+        if let Some(subs) = obj.get(*keyword).and_then(Value::as_object) {
+            for (name, sub) in subs {
+                walk(sub, &pointer::join(location, &[keyword, name]), diagnostics);
+            }
+        }
+    }
+}
+
+fn check_object(obj: &Map<String, Value>, location: &Pointer, diagnostics: &mut Vec<Diagnostic>) {
+    let mut report = |lint: Lint, message: String| {
+        diagnostics.push(Diagnostic {
+            lint,
+            schema_id: None,
+            keyword_location: location.clone(),
+            message,
+        });
+    };
+
+    if let Some(types) = types(obj) {
+        for (ty, keywords) in TYPED_KEYWORDS {
+            let permitted = types
+                .iter()
+                .any(|t| t == ty || (*ty == "number" && *t == "integer"));
+            if permitted {
+                continue;
+            }
+            for keyword in keywords.iter().filter(|k| obj.contains_key(**k)) {
+                report(
+                    Lint::IneffectiveKeyword,
+                    format!(
+                        "\"{}\" has no effect as \"type\" does not permit {}",
+                        keyword, ty
+                    ),
+                );
+            }
+        }
+    }
+
+    for (min, max) in RANGES {
+        let lower = obj.get(*min).and_then(Value::as_f64);
+        let upper = obj.get(*max).and_then(Value::as_f64);
+        if let (Some(lower), Some(upper)) = (lower, upper) {
+            if lower > upper {
+                report(
+                    Lint::EmptyRange,
+                    format!("\"{}\" ({}) exceeds \"{}\" ({})", min, lower, max, upper),
+                );
+            }
+        }
+    }
+
+    // a required property may match a pattern which fails to compile, so
+    // nothing can be said of them unless every pattern compiles
+    let patterns: Result<Vec<Regex>, _> = obj
+        .get("patternProperties")
+        .and_then(Value::as_object)
+        .map_or(Ok(Vec::new()), |p| {
+            p.keys().map(|k| Regex::new(k)).collect()
+        });
+    if let (Some(Value::Bool(false)), Ok(patterns)) = (obj.get("additionalProperties"), patterns) {
+        let properties = obj.get("properties").and_then(Value::as_object);
+        let required = obj.get("required").and_then(Value::as_array);
+        for name in required.into_iter().flatten().filter_map(Value::as_str) {
+            let declared = matches!(properties, Some(p) if p.contains_key(name));
+            if !declared && !patterns.iter().any(|p| p.is_match(name)) {
+                report(
+                    Lint::UnsatisfiableRequired,
+                    format!(
+                        "required property \"{}\" is not permitted as \"additionalProperties\" is false",
+                        name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the types permitted by `"type"`, if present.
+fn types(obj: &Map<String, Value>) -> Option<Vec<&str>> {
+    match obj.get("type")? {
+        Value::String(t) => Some(vec![t.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
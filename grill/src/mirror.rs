@@ -0,0 +1,98 @@
+//! Rewriting of URIs to mirrors before they are resolved.
+//!
+//! In locked-down environments, schemas published at public URIs (e.g.
+//! `https://json-schema.org/`) are typically served from an internal
+//! artifact server. [`Mirrors`] maps URI prefixes to the prefixes they are
+//! mirrored at. [`Interrogator::resolve`](crate::Interrogator::resolve)
+//! passes the rewritten URI to each [`Resolver`](crate::Resolver), while the
+//! original URI remains the canonical identity of the document: it is the
+//! URI schemas are identified and referenced by, and the URI reported if the
+//! document is not found.
+//!
+//! ## Example
+//! ```
+//! use grill::{mirror::Mirrors, Interrogator};
+//!
+//! let mut mirrors = Mirrors::new();
+//! mirrors.insert("https://json-schema.org/", "https://artifacts.internal/json-schema/");
+//! assert_eq!(
+//!     mirrors.rewrite("https://json-schema.org/draft/2020-12/schema"),
+//!     "https://artifacts.internal/json-schema/draft/2020-12/schema"
+//! );
+//! assert_eq!(mirrors.rewrite("https://example.com/a.json"), "https://example.com/a.json");
+//!
+//! let interrogator = Interrogator::builder()
+//!     .mirror("https://json-schema.org/", "https://artifacts.internal/json-schema/")
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(interrogator.mirrors().len(), 1);
+//! ```
+
+use std::borrow::Cow;
+
+/// An ordered set of URI prefix rewrites.
+///
+/// When more than one prefix matches a URI, the longest is applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mirrors {
+    rules: Vec<(String, String)>,
+}
+
+impl Mirrors {
+    /// Creates and returns a new, empty `Mirrors`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps URIs beginning with `prefix` to `mirror`, returning the previous
+    /// mirror of `prefix` if one existed.
+    pub fn insert(
+        &mut self,
+        prefix: impl Into<String>,
+        mirror: impl Into<String>,
+    ) -> Option<String> {
+        let prefix = prefix.into();
+        let mirror = mirror.into();
+        if let Some(rule) = self.rules.iter_mut().find(|(p, _)| *p == prefix) {
+            return Some(std::mem::replace(&mut rule.1, mirror));
+        }
+        self.rules.push((prefix, mirror));
+        None
+    }
+
+    /// Removes the mirror of `prefix`, returning it if one existed.
+    pub fn remove(&mut self, prefix: &str) -> Option<String> {
+        let i = self.rules.iter().position(|(p, _)| p == prefix)?;
+        Some(self.rules.remove(i).1)
+    }
+
+    /// Returns the number of rewrites.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns `true` if there are no rewrites.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns an iterator over each `(prefix, mirror)` pair, in the order
+    /// they were inserted.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.rules.iter().map(|(p, m)| (p.as_str(), m.as_str()))
+    }
+
+    /// Returns `uri` with its longest matching prefix replaced by that
+    /// prefix's mirror, or `uri` unchanged if no prefix matches.
+    pub fn rewrite<'a>(&self, uri: &'a str) -> Cow<'a, str> {
+        let rule = self
+            .rules
+            .iter()
+            .filter(|(prefix, _)| uri.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len());
+        match rule {
+            Some((prefix, mirror)) => Cow::Owned(format!("{}{}", mirror, &uri[prefix.len()..])),
+            None => Cow::Borrowed(uri),
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! Isolation of the schemas and sources of multiple tenants within a single
+//! [`Interrogator`].
+//!
+//! A [`Namespace`], obtained with [`Interrogator::namespace`], claims each
+//! source document and [`Schema`] added through it. A `Schema` inserted
+//! through a `Namespace` may only reference documents claimed by the same
+//! `Namespace`, documents which their owning `Namespace` has
+//! [shared](Namespace::share), and documents which belong to no namespace,
+//! i.e. those added directly to the [`Interrogator`], such as meta schemas.
+//! Otherwise insertion fails with [`Error::Namespace`].
+//!
+//! [`Namespace::remove`] removes each document and `Schema` claimed by a
+//! `Namespace`, allowing a tenant to be unloaded without disturbing others.
+//!
+//! ## Example
+//! ```
+//! use grill::{uri::AbsoluteUri, Interrogator};
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! let acme = interrogator.namespace("acme");
+//! let globex = interrogator.namespace("globex");
+//!
+//! let uri = AbsoluteUri::parse("https://acme.example/address.json").unwrap();
+//! acme.source_value(uri.clone(), json!({ "type": "object" })).unwrap();
+//! assert!(acme.locate(&uri).unwrap().is_some());
+//! assert!(globex.locate(&uri).unwrap_err().is_namespace());
+//!
+//! acme.share(&uri).unwrap();
+//! assert!(globex.locate(&uri).unwrap().is_some());
+//!
+//! acme.remove();
+//! assert!(interrogator.source(&uri).unwrap().is_none());
+//! ```
+
+use crate::{
+    error::{NamespaceError, UnidentifiedSchemaError},
+    source, Error, Interrogator, Schema,
+};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use uniresid::{AbsoluteUri, Uri};
+
+/// The owning namespace of each claimed document and the documents which
+/// have been shared.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Registry {
+    owners: HashMap<String, Arc<str>>,
+    shared: HashSet<String>,
+}
+
+impl Registry {
+    pub(crate) fn owner(&self, document: &str) -> Option<&Arc<str>> {
+        self.owners.get(document)
+    }
+
+    /// Releases the claim on `document`, if any.
+    pub(crate) fn release(&mut self, document: &str) {
+        self.owners.remove(document);
+        self.shared.remove(document);
+    }
+
+    fn is_accessible(&self, namespace: &str, document: &str) -> bool {
+        match self.owners.get(document) {
+            None => true,
+            Some(owner) => owner.as_ref() == namespace || self.shared.contains(document),
+        }
+    }
+}
+
+/// A handle to the schemas and sources of an [`Interrogator`] which are
+/// claimed by a single tenant. See the [module documentation](self).
+///
+/// Cloning a `Namespace` is cheap; clones refer to the same namespace.
+#[derive(Debug, Clone)]
+pub struct Namespace {
+    name: Arc<str>,
+    interrogator: Interrogator,
+}
+
+impl Namespace {
+    pub(crate) fn new(name: Arc<str>, interrogator: Interrogator) -> Self {
+        Self { name, interrogator }
+    }
+
+    /// Returns the name of the `Namespace`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`Interrogator`] of the `Namespace`.
+    pub fn interrogator(&self) -> &Interrogator {
+        &self.interrogator
+    }
+
+    /// Returns `true` if the document at `uri` is claimed by this
+    /// `Namespace`.
+    pub fn owns(&self, uri: &Uri) -> bool {
+        let registry = self.interrogator.namespace_registry();
+        registry
+            .owner(&document_of(uri))
+            .map_or(false, |owner| *owner == self.name)
+    }
+
+    /// Inserts the source `document` at `uri`, claiming it for this
+    /// `Namespace`.
+    ///
+    /// # Errors
+    /// Returns [`Error::Namespace`] if `uri` is claimed by another
+    /// `Namespace`.
+    pub fn source_value(
+        &self,
+        uri: AbsoluteUri,
+        document: Value,
+    ) -> Result<Option<Arc<Value>>, Error> {
+        self.claim(uri.uri())?;
+        Ok(self.interrogator.source_value(uri, document))
+    }
+
+    /// Inserts `schema` into the [`Interrogator`] as with
+    /// [`Interrogator::insert_schema`], claiming its id for this `Namespace`.
+    ///
+    /// The references of `schema` are checked against its source, and those
+    /// of the documents it references against theirs, before `schema` is
+    /// inserted and setup, so that no document of another `Namespace` is
+    /// resolved on its behalf.
+    ///
+    /// # Errors
+    /// Returns [`Error::Namespace`] if the id of `schema` is claimed by
+    /// another `Namespace`, or if `schema`, or a document it references
+    /// directly or transitively, references a document which is not
+    /// accessible from this `Namespace`. The id of `schema` must be absolute.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{uri::AbsoluteUri, Interrogator, Schema};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let acme = interrogator.namespace("acme");
+    /// let globex = interrogator.namespace("globex");
+    ///
+    /// let uri = AbsoluteUri::parse("https://acme.example/limit.json").unwrap();
+    /// acme.source_value(uri, json!({ "maximum": 10 })).unwrap();
+    ///
+    /// let source = json!({
+    ///     "$id": "https://globex.example/user.json",
+    ///     "$ref": "https://acme.example/limit.json"
+    /// });
+    /// let schema = Schema::new(source, &interrogator).unwrap();
+    /// assert!(globex.insert_schema(schema).unwrap_err().is_namespace());
+    /// ```
+    pub fn insert_schema(&self, schema: Schema) -> Result<Option<Schema>, Error> {
+        let id = match schema.id() {
+            Some(id) => id,
+            None => return Err(UnidentifiedSchemaError { schema }.into()),
+        };
+        self.check_references(&id, &schema.source())?;
+        let claimed = self.claim(&id)?;
+        let result = self.interrogator.insert_schema(schema);
+        if result.is_err() && claimed {
+            let mut registry = self.interrogator.namespace_registry_mut();
+            registry.owners.remove(&document_of(&id));
+        }
+        result
+    }
+
+    /// Returns the [`Schema`] with the given `id` if it exists and is
+    /// accessible from this `Namespace`.
+    pub fn schema(&self, id: &Uri) -> Option<Schema> {
+        self.check(id).ok()?;
+        self.interrogator.schema(id)
+    }
+
+    /// Locates the value at `uri` as with [`Interrogator::locate`].
+    ///
+    /// # Errors
+    /// Returns [`Error::Namespace`] if the document at `uri` is not
+    /// accessible from this `Namespace`.
+    pub fn locate(&self, uri: &AbsoluteUri) -> Result<Option<Value>, Error> {
+        self.check(uri.uri())?;
+        self.interrogator.locate(uri)
+    }
+
+    /// Permits every `Namespace` to reference the document at `uri`, which
+    /// must be claimed by this `Namespace`.
+    ///
+    /// # Errors
+    /// Returns [`Error::Namespace`] if `uri` is claimed by another
+    /// `Namespace`.
+    pub fn share(&self, uri: &AbsoluteUri) -> Result<(), Error> {
+        self.claim(uri.uri())?;
+        let mut registry = self.interrogator.namespace_registry_mut();
+        registry.shared.insert(document_of(uri.uri()));
+        Ok(())
+    }
+
+    /// Removes each source document and [`Schema`] claimed by this
+    /// `Namespace` from the [`Interrogator`], returning the number of
+    /// documents removed.
+    ///
+    /// `Schema`s of other namespaces which reference a shared document of
+    /// this `Namespace` retain their compiled form of it.
+    pub fn remove(&self) -> usize {
+        let documents: Vec<String> = {
+            let mut registry = self.interrogator.namespace_registry_mut();
+            let documents: Vec<String> = registry
+                .owners
+                .iter()
+                .filter(|(_, owner)| **owner == self.name)
+                .map(|(document, _)| document.clone())
+                .collect();
+            for document in &documents {
+                registry.release(document);
+            }
+            documents
+        };
+        self.interrogator.remove_documents(&documents);
+        documents.len()
+    }
+
+    /// Claims the document of `uri` for this `Namespace`, returning `true`
+    /// if it was not already claimed.
+    fn claim(&self, uri: &Uri) -> Result<bool, Error> {
+        let document = document_of(uri);
+        let mut registry = self.interrogator.namespace_registry_mut();
+        match registry.owners.get(&document) {
+            Some(owner) if *owner == self.name => Ok(false),
+            Some(owner) => Err(NamespaceError {
+                namespace: self.name.to_string(),
+                uri: uri.clone(),
+                owner: owner.to_string(),
+            }
+            .into()),
+            None => {
+                registry.owners.insert(document, self.name.clone());
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns an error if a document referenced by `document`, the source
+    /// identified by `id`, or by a document it references, directly or
+    /// transitively, is not accessible from this `Namespace`.
+    ///
+    /// References are read from the sources rather than from compiled
+    /// schemas, which record their references only once they are setup.
+    fn check_references(&self, id: &Uri, document: &Value) -> Result<(), Error> {
+        let base = AbsoluteUri::parse(&id.to_string())?;
+        let mut queue = source::dependencies(&base, document);
+        let mut visited = HashSet::new();
+        while let Some(dependency) = queue.pop() {
+            if !visited.insert(dependency.clone()) {
+                continue;
+            }
+            self.check(dependency.uri())?;
+            if let Some(document) = self.interrogator.source(&dependency)? {
+                queue.extend(source::dependencies(&dependency, &document));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the document of `uri` is not accessible from this
+    /// `Namespace`.
+    fn check(&self, uri: &Uri) -> Result<(), Error> {
+        let document = document_of(uri);
+        let registry = self.interrogator.namespace_registry();
+        if registry.is_accessible(&self.name, &document) {
+            return Ok(());
+        }
+        Err(NamespaceError {
+            namespace: self.name.to_string(),
+            uri: uri.clone(),
+            owner: registry
+                .owner(&document)
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        }
+        .into())
+    }
+}
+
+/// Returns `uri` without its fragment.
+pub(crate) fn document_of(uri: &Uri) -> String {
+    let uri = uri.to_string();
+    match uri.split_once('#') {
+        Some((document, _)) => document.to_string(),
+        None => uri,
+    }
+}
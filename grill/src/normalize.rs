@@ -0,0 +1,126 @@
+//! Unicode normalization of schema identifiers.
+//!
+//! Identifiers which render identically may be composed of different code
+//! points, e.g. `"é"` as a single code point (NFC) or as `"e"` followed by a
+//! combining acute accent (NFD). When enabled with
+//! [`Interrogator::set_normalize_identifiers`](crate::Interrogator::set_normalize_identifiers),
+//! `"$id"`, `"id"`, `"$anchor"`, `"$dynamicAnchor"`, and the fragments of
+//! `"$ref"`, `"$dynamicRef"`, and `"$recursiveRef"` are converted to NFC
+//! before a [`Schema`](crate::Schema) is identified so that each form
+//! resolves to the same [`Schema`](crate::Schema).
+//!
+//! Normalization requires the `"nfc"` feature. Each identifier which is
+//! changed is reported as a [`Diagnostic`] with the lint
+//! [`Lint::NormalizedIdentifier`].
+//!
+//! ## Example
+//! ```
+//! use grill::{normalize, Feature};
+//! use serde_json::json;
+//!
+//! let mut source = json!({ "$anchor": "cafe\u{301}" });
+//! let diagnostics = normalize::identifiers(&mut source);
+//! if Feature::Nfc.is_enabled() {
+//!     assert_eq!(source["$anchor"], "caf\u{e9}");
+//!     assert_eq!(diagnostics.len(), 1);
+//! } else {
+//!     assert!(diagnostics.is_empty());
+//! }
+//! ```
+
+use crate::lint::{Diagnostic, Lint};
+use jsonptr::{Pointer, Token};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Keywords whose value is normalized in its entirety.
+const IDENTIFIER_KEYWORDS: &[&str] = &["$id", "id", "$anchor", "$dynamicAnchor"];
+
+/// Keywords whose fragment is normalized.
+const REFERENCE_KEYWORDS: &[&str] = &["$ref", "$dynamicRef", "$recursiveRef"];
+
+/// Keywords whose value is instance data rather than schema.
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// Returns `s` in Unicode Normalization Form C.
+///
+/// If the `"nfc"` feature is not enabled, `s` is returned unchanged.
+pub fn nfc(s: &str) -> Cow<'_, str> {
+    #[cfg(feature = "nfc")]
+    {
+        use unicode_normalization::{is_nfc, UnicodeNormalization};
+        if !is_nfc(s) {
+            return Cow::Owned(s.nfc().collect());
+        }
+    }
+    Cow::Borrowed(s)
+}
+
+/// Converts the identifiers of `source` and each of its subschemas to NFC,
+/// returning a [`Diagnostic`] for each value which changed.
+pub fn identifiers(source: &mut Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    walk(source, &Pointer::default(), &mut diagnostics);
+    diagnostics
+}
+
+fn walk(value: &mut Value, location: &Pointer, diagnostics: &mut Vec<Diagnostic>) {
+    match value {
+        Value::Object(obj) => {
+            for (keyword, value) in obj.iter_mut() {
+                if DATA_KEYWORDS.contains(&keyword.as_str()) {
+                    continue;
+                }
+                if let Value::String(s) = value {
+                    if let Some(normalized) = normalize(keyword, s) {
+                        diagnostics.push(Diagnostic {
+                            lint: Lint::NormalizedIdentifier,
+                            schema_id: None,
+                            keyword_location: location.clone(),
+                            message: format!(
+                                "\"{}\" normalized from \"{}\" to \"{}\"",
+                                keyword,
+                                s.escape_unicode(),
+                                normalized.escape_unicode()
+                            ),
+                        });
+                        *s = normalized;
+                    }
+                    continue;
+                }
+                let mut location = location.clone();
+                location.push_back(Token::new(keyword));
+                walk(value, &location, diagnostics);
+            }
+        }
+        Value::Array(arr) => {
+            for (i, value) in arr.iter_mut().enumerate() {
+                let mut location = location.clone();
+                location.push_back(Token::new(i.to_string()));
+                walk(value, &location, diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the normalized form of the value of `keyword`, if it differs.
+fn normalize(keyword: &str, value: &str) -> Option<String> {
+    let normalized = if IDENTIFIER_KEYWORDS.contains(&keyword) {
+        nfc(value)
+    } else if REFERENCE_KEYWORDS.contains(&keyword) {
+        match value.split_once('#') {
+            Some((base, fragment)) => match nfc(fragment) {
+                Cow::Owned(fragment) => Cow::Owned(format!("{}#{}", base, fragment)),
+                Cow::Borrowed(_) => Cow::Borrowed(value),
+            },
+            None => Cow::Borrowed(value),
+        }
+    } else {
+        return None;
+    };
+    match normalized {
+        Cow::Owned(normalized) if normalized != value => Some(normalized),
+        _ => None,
+    }
+}
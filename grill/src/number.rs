@@ -0,0 +1,348 @@
+use crate::error::OverflowError;
+#[cfg(feature = "big_num")]
+use bigdecimal::BigDecimal;
+#[cfg(feature = "big_num")]
+use dashmap::DashMap;
+use serde_json::{Number, Value};
+use std::{cmp::Ordering, collections::HashSet};
+#[cfg(feature = "big_num")]
+use std::{str::FromStr, sync::Arc};
+
+/// Converts `len` to a `u32`, returning an [`OverflowError`] describing
+/// `context` if it does not fit.
+///
+/// This is the single conversion used for length guards throughout grill so
+/// that overflows are reported consistently.
+///
+/// ## Example
+/// ```
+/// use grill::number::usize_to_u32;
+///
+/// assert_eq!(usize_to_u32(7, "string").unwrap(), 7);
+/// # #[cfg(target_pointer_width = "64")]
+/// assert_eq!(usize_to_u32(usize::MAX, "string").unwrap_err().context, "string");
+/// ```
+pub fn usize_to_u32(len: usize, context: &str) -> Result<u32, OverflowError> {
+    u32::try_from(len).map_err(|_| overflow(len, u64::from(u32::MAX), context))
+}
+
+/// Converts `len` to a `u64`, returning an [`OverflowError`] describing
+/// `context` if it does not fit.
+pub fn usize_to_u64(len: usize, context: &str) -> Result<u64, OverflowError> {
+    u64::try_from(len).map_err(|_| overflow(len, u64::MAX, context))
+}
+
+fn overflow(len: usize, max: u64, context: &str) -> OverflowError {
+    OverflowError {
+        len,
+        max,
+        context: context.to_string(),
+    }
+}
+
+/// Keywords whose value is instance data rather than schema, the numbers of
+/// which are not cached.
+#[cfg(feature = "big_num")]
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// The greatest magnitude up to which every integer is represented exactly
+/// by an `f64`.
+const MAX_SAFE_INTEGER: u64 = 1 << 53;
+
+/// Determines how numeric keywords (e.g. `"maximum"`, `"minimum"`) compare
+/// numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Precision {
+    /// Integers are compared exactly when both sides fit within an `i64` or
+    /// `u64`. All other numbers are compared as `f64`.
+    #[default]
+    Float,
+    /// Numbers which can not be compared exactly as `i64` or `u64` are parsed
+    /// into arbitrary-precision decimals. Those of schemas are cached by
+    /// [`Numbers`]; those of instances are parsed as they are compared.
+    ///
+    /// Requires the `"big_num"` feature, which enables the
+    /// `"arbitrary_precision"` feature of `serde_json` so that the original
+    /// digits of each number are retained.
+    /// [`Interrogator::set_precision`](crate::Interrogator::set_precision)
+    /// returns an error if the feature is not enabled.
+    Arbitrary,
+}
+
+/// A cache of parsed arbitrary-precision numbers, keyed by their textual
+/// representation.
+///
+/// A single cache is shared by all [`Schema`](crate::Schema)s of an
+/// [`Interrogator`](crate::Interrogator), so that a number which appears in
+/// many schemas is parsed once. See [`NumberKey`]. The numbers of instances
+/// are not cached, as they are unbounded; they are parsed each time they are
+/// compared.
+///
+/// Cloning `Numbers` is cheap; clones share the same cache.
+#[derive(Clone, Default)]
+pub struct Numbers {
+    #[cfg(feature = "big_num")]
+    big: Arc<DashMap<String, Arc<BigDecimal>>>,
+}
+
+impl Numbers {
+    /// Creates and returns a new, empty `Numbers` cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares `a` to `b` according to `precision`. Neither number is
+    /// cached.
+    ///
+    /// Returns `None` if the numbers are not comparable.
+    pub fn compare(&self, a: &Number, b: &Number, precision: Precision) -> Option<Ordering> {
+        if let Some(ord) = compare_integers(a, b) {
+            return Some(ord);
+        }
+        match precision {
+            #[cfg(feature = "big_num")]
+            Precision::Arbitrary => Some(parse(a)?.cmp(&parse(b)?)),
+            #[cfg(not(feature = "big_num"))]
+            Precision::Arbitrary => a.as_f64()?.partial_cmp(&b.as_f64()?),
+            Precision::Float => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        }
+    }
+
+    /// Compares `a` to the interned number `b` according to `precision`,
+    /// without looking `b` up. `a`, typically a number of an instance, is not
+    /// cached.
+    ///
+    /// Returns `None` if the numbers are not comparable.
+    pub fn compare_key(&self, a: &Number, b: &NumberKey, precision: Precision) -> Option<Ordering> {
+        if let Some(ord) = compare_integers(a, &b.number) {
+            return Some(ord);
+        }
+        match precision {
+            #[cfg(feature = "big_num")]
+            Precision::Arbitrary => Some(parse(a)?.cmp(b.big.as_deref()?)),
+            #[cfg(not(feature = "big_num"))]
+            Precision::Arbitrary => a.as_f64()?.partial_cmp(&b.float?),
+            Precision::Float => a.as_f64()?.partial_cmp(&b.float?),
+        }
+    }
+
+    /// Returns `true` if `number` is represented by an `f64` without loss,
+    /// i.e. if it is finite and either an integer no greater in magnitude
+    /// than 2<sup>53</sup> or, with the `"big_num"` feature, the same number
+    /// as the shortest decimal representation of its `f64`.
+    ///
+    /// Without the `"big_num"` feature, numbers which are not integers have
+    /// been parsed into an `f64` by `serde_json` already and are considered
+    /// lossless.
+    ///
+    /// `number` is not cached, as it is typically a number of an instance.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::Numbers;
+    /// use serde_json::Number;
+    ///
+    /// let numbers = Numbers::new();
+    /// assert!(numbers.is_lossless(&Number::from(9_007_199_254_740_992u64)));
+    /// assert!(!numbers.is_lossless(&Number::from(9_007_199_254_740_993u64)));
+    /// ```
+    pub fn is_lossless(&self, number: &Number) -> bool {
+        let float = match number.as_f64() {
+            Some(float) if float.is_finite() => float,
+            _ => return false,
+        };
+        if let Some(n) = number.as_i64() {
+            return n.unsigned_abs() <= MAX_SAFE_INTEGER;
+        }
+        if let Some(n) = number.as_u64() {
+            return n <= MAX_SAFE_INTEGER;
+        }
+        #[cfg(feature = "big_num")]
+        {
+            match (parse(number), BigDecimal::from_str(&float.to_string())) {
+                (Some(exact), Ok(rounded)) => exact == rounded,
+                _ => false,
+            }
+        }
+        #[cfg(not(feature = "big_num"))]
+        {
+            let _ = float;
+            true
+        }
+    }
+
+    /// Interns `number`, returning a [`NumberKey`] which holds its parsed
+    /// representations.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{Numbers, Precision};
+    /// use serde_json::Number;
+    /// use std::cmp::Ordering;
+    ///
+    /// let numbers = Numbers::new();
+    /// let limit = numbers.intern(&Number::from_f64(2.5).unwrap());
+    /// let ord = numbers.compare_key(&Number::from(3), &limit, Precision::Float);
+    /// assert_eq!(ord, Some(Ordering::Greater));
+    /// ```
+    pub fn intern(&self, number: &Number) -> NumberKey {
+        NumberKey {
+            number: number.clone(),
+            float: number.as_f64(),
+            lossless: self.is_lossless(number),
+            #[cfg(feature = "big_num")]
+            big: self.big_decimal(number),
+        }
+    }
+
+    /// Returns the [`BigDecimal`] representation of `number`, parsing and
+    /// caching it if it has not previously been seen.
+    ///
+    /// Returns `None` if `number` could not be parsed.
+    #[cfg(feature = "big_num")]
+    pub fn big_decimal(&self, number: &Number) -> Option<Arc<BigDecimal>> {
+        let key = number.to_string();
+        if let Some(existing) = self.big.get(&key) {
+            return Some(existing.clone());
+        }
+        let parsed = Arc::new(BigDecimal::from_str(&key).ok()?);
+        self.big.insert(key, parsed.clone());
+        Some(parsed)
+    }
+
+    /// Parses each number within the schema `value` into the cache, so that
+    /// comparisons against it do not parse on first use. The values of
+    /// keywords which hold instance data, e.g. `"examples"`, are skipped.
+    #[cfg(feature = "big_num")]
+    pub(crate) fn parse_all(&self, value: &Value) {
+        match value {
+            Value::Number(n) => {
+                self.big_decimal(n);
+            }
+            Value::Array(values) => values.iter().for_each(|v| self.parse_all(v)),
+            Value::Object(map) => map
+                .iter()
+                .filter(|(k, _)| !DATA_KEYWORDS.contains(&k.as_str()))
+                .for_each(|(_, v)| self.parse_all(v)),
+            _ => {}
+        }
+    }
+
+    /// Returns the number of cached arbitrary-precision numbers.
+    pub fn len(&self) -> usize {
+        #[cfg(feature = "big_num")]
+        {
+            self.big.len()
+        }
+        #[cfg(not(feature = "big_num"))]
+        {
+            0
+        }
+    }
+
+    /// Returns `true` if no arbitrary-precision numbers are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes each cached number whose textual representation does not
+    /// satisfy `keep`, returning the number of numbers removed.
+    pub fn retain(&self, keep: impl Fn(&str) -> bool) -> usize {
+        #[cfg(feature = "big_num")]
+        {
+            let before = self.big.len();
+            self.big.retain(|key, _| keep(key));
+            before - self.big.len()
+        }
+        #[cfg(not(feature = "big_num"))]
+        {
+            let _ = keep;
+            0
+        }
+    }
+}
+
+/// A number interned in a [`Numbers`] cache with [`Numbers::intern`].
+///
+/// Keywords intern the numbers of their schema when it is setup and compare
+/// against them with [`Numbers::compare_key`], so that evaluation neither
+/// parses nor looks up the schema's numbers. Numbers interned from any
+/// number of schemas share one parsed representation.
+#[derive(Debug, Clone)]
+pub struct NumberKey {
+    number: Number,
+    float: Option<f64>,
+    lossless: bool,
+    #[cfg(feature = "big_num")]
+    big: Option<Arc<BigDecimal>>,
+}
+
+impl NumberKey {
+    /// Returns the interned number.
+    pub fn number(&self) -> &Number {
+        &self.number
+    }
+
+    /// Returns the number as an `f64`, if it can be represented as one.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.float
+    }
+
+    /// Returns `true` if the number is represented by an `f64` without loss.
+    /// See [`Numbers::is_lossless`].
+    pub fn is_lossless(&self) -> bool {
+        self.lossless
+    }
+
+    /// Returns the [`BigDecimal`] representation of the number, if it could
+    /// be parsed.
+    #[cfg(feature = "big_num")]
+    pub fn big_decimal(&self) -> Option<&Arc<BigDecimal>> {
+        self.big.as_ref()
+    }
+}
+
+impl std::fmt::Display for NumberKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.number, f)
+    }
+}
+
+impl std::fmt::Debug for Numbers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Numbers").field("len", &self.len()).finish()
+    }
+}
+
+/// Parses `number` into a [`BigDecimal`] without caching it.
+#[cfg(feature = "big_num")]
+fn parse(number: &Number) -> Option<BigDecimal> {
+    BigDecimal::from_str(&number.to_string()).ok()
+}
+
+/// Compares `a` and `b` exactly if both are integers representable as either
+/// an `i64` or a `u64`.
+fn compare_integers(a: &Number, b: &Number) -> Option<Ordering> {
+    match (a.as_i64(), b.as_i64(), a.as_u64(), b.as_u64()) {
+        (Some(a), Some(b), _, _) => Some(a.cmp(&b)),
+        (_, _, Some(a), Some(b)) => Some(a.cmp(&b)),
+        // a is negative, b exceeds i64::MAX
+        (Some(_), None, None, Some(_)) => Some(Ordering::Less),
+        // a exceeds i64::MAX, b is negative
+        (None, Some(_), Some(_), None) => Some(Ordering::Greater),
+        _ => None,
+    }
+}
+
+/// Adds the textual representation of each number within `value` to
+/// `numbers`.
+pub(crate) fn collect(value: &Value, numbers: &mut HashSet<String>) {
+    match value {
+        Value::Number(n) => {
+            numbers.insert(n.to_string());
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect(v, numbers)),
+        Value::Object(map) => map.values().for_each(|v| collect(v, numbers)),
+        _ => {}
+    }
+}
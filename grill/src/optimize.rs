@@ -0,0 +1,135 @@
+//! An optional pass over the compiled keywords of a [`Schema`](crate::Schema)
+//! which improves evaluation throughput.
+//!
+//! When enabled with
+//! [`Interrogator::set_optimize`](crate::Interrogator::set_optimize), each
+//! `Schema` set up by the [`Interrogator`](crate::Interrogator):
+//! - omits keywords which are [trivially valid](is_trivially_valid), e.g.
+//!   `"allOf": [true]` or `"required": []`, as their result is known at
+//!   compile time;
+//! - evaluates its assertions, e.g. `"type"`, `"const"`, and `"required"`,
+//!   before its applicators, e.g. `"$ref"` and `"oneOf"`, each ordered by
+//!   [estimated cost](crate::cost::estimate) so that cheap keywords, which
+//!   often decide validity, run before expensive ones, e.g. `"pattern"`.
+//!
+//! The order of the [`Dialect`](crate::Dialect) is otherwise respected: the
+//! gates of its [`ShortCircuit`](crate::dialect::ShortCircuit)s are
+//! evaluated first and keywords which depend on the annotations of their
+//! siblings, e.g. `"unevaluatedProperties"`, last.
+//!
+//! Validity is unaffected, but the output of an evaluation may differ:
+//! omitted keywords do not appear in it and errors are reported in the order
+//! keywords are evaluated.
+//!
+//! ## Example
+//! ```
+//! use grill::{Interrogator, OutputFmt, Schema};
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! interrogator.set_optimize(true);
+//! let schema = Schema::new(json!({ "oneOf": [true, false] }), &interrogator).unwrap();
+//! let eval = schema.evaluate(&json!("anything"), OutputFmt::Verbose).unwrap();
+//! assert!(eval.is_valid());
+//! assert!(schema.keywords().iter().all(|k| k.name != Some("oneOf")));
+//! ```
+
+use crate::{applicator::ExecutorFn, cost, dialect::KeywordOrder, keyword::APPLICATOR_KEYWORDS};
+use serde_json::Value;
+
+/// Keywords which depend on the annotations of their siblings and so are
+/// evaluated after them.
+const DEPENDENT_KEYWORDS: &[&str] = &[
+    "then",
+    "else",
+    "items",
+    "additionalItems",
+    "additionalProperties",
+    "minContains",
+    "maxContains",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Returns `true` if `keyword`, with the given `value`, is valid for every
+/// instance and produces no annotations, in which case it can be omitted.
+///
+/// ## Example
+/// ```
+/// use grill::optimize::is_trivially_valid;
+/// use serde_json::json;
+///
+/// assert!(is_trivially_valid("allOf", &json!([true, {}])));
+/// assert!(is_trivially_valid("oneOf", &json!([false, true])));
+/// assert!(is_trivially_valid("minLength", &json!(0)));
+/// assert!(!is_trivially_valid("oneOf", &json!([true, true])));
+/// assert!(!is_trivially_valid("allOf", &json!([true, { "type": "string" }])));
+/// ```
+pub fn is_trivially_valid(keyword: &str, value: &Value) -> bool {
+    let members = value.as_array().map(Vec::as_slice).unwrap_or_default();
+    match keyword {
+        "allOf" => !members.is_empty() && members.iter().all(is_true),
+        "anyOf" => members.iter().any(is_true) && members.iter().all(|m| is_true(m) || is_false(m)),
+        "oneOf" => {
+            members.iter().filter(|m| is_true(m)).count() == 1
+                && members.iter().all(|m| is_true(m) || is_false(m))
+        }
+        "not" => is_false(value),
+        "required" => value.as_array().map_or(false, Vec::is_empty),
+        "dependentRequired" => value.as_object().map_or(false, |obj| obj.is_empty()),
+        "minLength" | "minItems" | "minProperties" => value.as_f64() == Some(0.0),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `schema` is `true` or `{}`.
+fn is_true(schema: &Value) -> bool {
+    match schema {
+        Value::Bool(b) => *b,
+        Value::Object(obj) => obj.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_false(schema: &Value) -> bool {
+    schema == &Value::Bool(false)
+}
+
+/// Sorts the compiled keywords of the schema `source` for evaluation: the
+/// gates of `order` first, then assertions and applicators, each by
+/// estimated cost, then keywords which depend on their siblings and, lastly,
+/// those without a name. Ties are broken by `order` and then by the
+/// original position.
+pub(crate) fn sort(
+    fns: &mut Vec<(Option<&'static str>, Box<ExecutorFn>)>,
+    source: &Value,
+    order: &KeywordOrder,
+) {
+    let mut keyed: Vec<_> = fns
+        .drain(..)
+        .enumerate()
+        .map(|(index, (keyword, f))| ((sort_key(keyword, source, order), index), keyword, f))
+        .collect();
+    keyed.sort_by_key(|(key, _, _)| *key);
+    fns.extend(keyed.into_iter().map(|(_, keyword, f)| (keyword, f)));
+}
+
+fn sort_key(keyword: Option<&str>, source: &Value, order: &KeywordOrder) -> (u8, u64, usize) {
+    let keyword = match keyword {
+        Some(keyword) => keyword,
+        None => return (4, 0, 0),
+    };
+    let rank = order.sort_key(Some(keyword));
+    let cost = source
+        .get(keyword)
+        .map_or(cost::BASE_COST, |value| cost::estimate(keyword, value));
+    if order.short_circuits().iter().any(|sc| sc.gate == keyword) {
+        (0, 0, rank)
+    } else if DEPENDENT_KEYWORDS.contains(&keyword) {
+        (3, 0, rank)
+    } else if APPLICATOR_KEYWORDS.contains(&keyword) {
+        (2, cost, rank)
+    } else {
+        (1, cost, rank)
+    }
+}
@@ -0,0 +1,452 @@
+//! Structured output of an [`Evaluation`] in the formats of the JSON Schema
+//! specification.
+//!
+//! A [`Verbose`] report is built from an [`Evaluation`] and can be reduced,
+//! without re-evaluating, to a [`Detailed`], [`Basic`], or [`Flag`] report
+//! with the [`From`] conversions `Verbose -> Detailed -> Basic -> Flag`. Each
+//! conversion discards information but preserves the validity of the report.
+//! A [`Report`](crate::report::Report) converts to each format with
+//! [`into_verbose`](crate::report::Report::into_verbose),
+//! [`into_detailed`](crate::report::Report::into_detailed),
+//! [`into_basic`](crate::report::Report::into_basic), and
+//! [`into_flag`](crate::report::Report::into_flag).
+//!
+//! Each format serializes to the structure defined by the specification:
+//! every unit has a `"keywordLocation"` and an `"instanceLocation"`, along
+//! with an `"absoluteKeywordLocation"` if known and an `"error"` and its
+//! stable `"errorCode"`, given by
+//! [`validation_code`](crate::error::validation_code), if it failed;
+//! annotations and other data follow as additional properties. The nodes of
+//! [`Verbose`] and [`Detailed`] output have a `"valid"` property and nest
+//! their children within `"errors"` if invalid or `"annotations"` if
+//! valid. [`Basic`] output has a single `"valid"` property, with its
+//! units, which do not, listed in `"errors"` or `"annotations"`. [`Flag`]
+//! output has a `"valid"` property alone. Empty lists are omitted.
+//!
+//! See [JSON Schema Core Specification 12.4 for more
+//! information](https://json-schema.org/draft/2020-12/json-schema-core.html#name-output-structure).
+//!
+//! ## Example
+//! ```
+//! use grill::{
+//!     output::{Basic, Detailed, Flag, Verbose},
+//!     Evaluation, OutputFmt,
+//! };
+//! use jsonptr::Pointer;
+//!
+//! let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+//! let mut all_of = eval.for_keyword("allOf");
+//! let mut max = all_of.for_keyword("maxLength");
+//! max.set_error("string is too long");
+//! all_of.push(max);
+//! all_of.push(eval.for_keyword("type"));
+//! eval.push(all_of);
+//!
+//! let verbose = Verbose::from(&eval);
+//! assert_eq!(verbose.nested[0].nested.len(), 2);
+//!
+//! let detailed = Detailed::from(verbose);
+//! assert_eq!(detailed.nested.len(), 1);
+//! assert_eq!(detailed.nested[0].unit.keyword_location.as_str(), "/allOf/maxLength");
+//!
+//! let basic = Basic::from(detailed);
+//! assert_eq!(basic.units.len(), 1);
+//! assert_eq!(basic.units[0].error.as_deref(), Some("string is too long"));
+//!
+//! assert!(!Flag::from(basic).valid);
+//! ```
+
+use crate::{
+    evaluation::{ErrorDetail, ErrorPart},
+    lint::{ARRAY_KEYWORDS, OBJECT_KEYWORDS},
+    Evaluation,
+};
+use jsonptr::Pointer;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::{Map, Value};
+use uniresid::AbsoluteUri;
+
+/// The fields of a single output unit: the result of one keyword at one
+/// instance location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    /// Whether the instance is valid at this unit, including any nested
+    /// units.
+    pub valid: bool,
+    /// The relative location of the keyword.
+    pub keyword_location: Pointer,
+    /// The absolute location of the keyword, if known.
+    pub absolute_keyword_location: Option<AbsoluteUri>,
+    /// The location of the instance.
+    pub instance_location: Pointer,
+    /// The error of the unit, if any.
+    pub error: Option<String>,
+    /// The [code](crate::error::validation_code) of the error, if any,
+    /// serialized as `"errorCode"`.
+    pub error_code: Option<&'static str>,
+    /// The kind and parameters of the error and of each error merged into
+    /// it. They are not part of the specification and are not serialized;
+    /// see [`error_parts`](Self::error_parts).
+    pub error_details: Vec<ErrorDetail>,
+    /// The annotations and other custom fields of the unit.
+    pub data: Map<String, Value>,
+}
+
+impl Unit {
+    /// Returns the error, split into each error merged into it along with
+    /// its kind and parameters, or an empty `Vec` if there is no error.
+    pub fn error_parts(&self) -> Vec<ErrorPart<'_>> {
+        match &self.error {
+            Some(error) => ErrorPart::split(error, &self.error_details),
+            None => Vec::new(),
+        }
+    }
+
+    fn of(eval: &Evaluation) -> Self {
+        Self {
+            valid: eval.is_valid(),
+            keyword_location: eval.keyword_location().clone(),
+            absolute_keyword_location: eval.absolute_keyword_location().cloned(),
+            instance_location: eval.instance_location().clone(),
+            error: eval.error().map(ToString::to_string),
+            error_code: eval.error_code(),
+            error_details: eval.error_details().to_vec(),
+            data: eval.data().clone(),
+        }
+    }
+
+    fn serialize_fields<M: SerializeMap>(&self, map: &mut M, valid: bool) -> Result<(), M::Error> {
+        if valid {
+            map.serialize_entry("valid", &self.valid)?;
+        }
+        map.serialize_entry("keywordLocation", self.keyword_location.as_str())?;
+        if let Some(uri) = &self.absolute_keyword_location {
+            map.serialize_entry("absoluteKeywordLocation", &uri.to_string())?;
+        }
+        map.serialize_entry("instanceLocation", self.instance_location.as_str())?;
+        if let Some(error) = &self.error {
+            map.serialize_entry("error", error)?;
+        }
+        if let Some(code) = self.error_code {
+            map.serialize_entry("errorCode", code)?;
+        }
+        for (key, value) in &self.data {
+            // the code of the error takes the place of data of the same name
+            if key == "errorCode" && self.error_code.is_some() {
+                continue;
+            }
+            map.serialize_entry(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Unit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        self.serialize_fields(&mut map, true)?;
+        map.end()
+    }
+}
+
+/// A [`Unit`] of [`Basic`] output, which is serialized without `"valid"`.
+struct BasicUnit<'a>(&'a Unit);
+
+impl Serialize for BasicUnit<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        self.0.serialize_fields(&mut map, false)?;
+        map.end()
+    }
+}
+
+/// Output in an uncondensed hierarchy matching the structure of the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verbose {
+    /// The unit of this node.
+    pub unit: Unit,
+    /// The nested nodes.
+    pub nested: Vec<Verbose>,
+}
+
+impl From<&Evaluation> for Verbose {
+    fn from(eval: &Evaluation) -> Self {
+        Self {
+            unit: Unit::of(eval),
+            nested: eval.nested().iter().map(Verbose::from).collect(),
+        }
+    }
+}
+
+impl From<Evaluation> for Verbose {
+    fn from(eval: Evaluation) -> Self {
+        Verbose::from(&eval)
+    }
+}
+
+impl Serialize for Verbose {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_node(&self.unit, &self.nested, serializer)
+    }
+}
+
+/// Output in a condensed hierarchy: nodes which do not contribute to the
+/// result are removed, and nodes with neither an error nor data and a single
+/// nested node are replaced by that node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detailed {
+    /// The unit of this node.
+    pub unit: Unit,
+    /// The nested nodes.
+    pub nested: Vec<Detailed>,
+}
+
+impl From<Verbose> for Detailed {
+    fn from(verbose: Verbose) -> Self {
+        let Verbose { unit, nested } = verbose;
+        Self {
+            nested: condense(unit.valid, nested),
+            unit,
+        }
+    }
+}
+
+/// Returns the condensed form of the `nested` nodes of a node whose validity
+/// is `valid`.
+fn condense(valid: bool, nested: Vec<Verbose>) -> Vec<Detailed> {
+    nested
+        .into_iter()
+        // an invalid node is explained by its invalid nodes alone
+        .filter(|node| valid || !node.unit.valid)
+        .map(|Verbose { unit, nested }| {
+            let mut nested = condense(unit.valid, nested);
+            if unit.error.is_none() && unit.data.is_empty() && nested.len() == 1 {
+                return nested.remove(0);
+            }
+            Detailed { unit, nested }
+        })
+        .filter(|node| !node.unit.valid || !node.unit.data.is_empty() || !node.nested.is_empty())
+        .collect()
+}
+
+impl Serialize for Detailed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_node(&self.unit, &self.nested, serializer)
+    }
+}
+
+/// Output in a flat list: the units with an error if the instance is
+/// invalid, or otherwise the units with annotations.
+///
+/// The errors of a failing `"anyOf"` or `"oneOf"` are reduced to those of
+/// its best matching member: the member whose errors occurred deepest
+/// within the instance, i.e. which made the most progress, and then the one
+/// with the fewest errors, the first such member on a tie. For
+/// discriminated unions this surfaces the errors of the intended member
+/// rather than those of every member. Use [`Basic::exhaustive`] to retain
+/// the errors of every member; [`Verbose`] and [`Detailed`] always do.
+///
+/// ## Example
+/// ```
+/// use grill::{output::{Basic, Detailed, Verbose}, Evaluation, OutputFmt};
+/// use jsonptr::Pointer;
+///
+/// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+/// let mut any_of = eval.for_keyword("anyOf");
+/// let mut circle = any_of.for_keyword("0");
+/// let mut kind = circle.for_keyword("const");
+/// kind.set_instance_location(Pointer::try_from("/kind").unwrap());
+/// kind.set_error("\"square\" is not \"circle\"");
+/// circle.push(kind);
+/// let mut square = any_of.for_keyword("1");
+/// let mut side = square.for_keyword("minimum");
+/// side.set_instance_location(Pointer::try_from("/sides/side").unwrap());
+/// side.set_error("-1 is less than 0");
+/// square.push(side);
+/// any_of.append([circle, square]);
+/// eval.push(any_of);
+///
+/// let detailed = Detailed::from(Verbose::from(&eval));
+/// let basic = Basic::from(detailed.clone());
+/// assert_eq!(basic.units.len(), 1);
+/// assert_eq!(basic.units[0].keyword_location.as_str(), "/anyOf/1/minimum");
+/// assert_eq!(Basic::exhaustive(detailed).units.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Basic {
+    /// Whether the instance is valid.
+    pub valid: bool,
+    /// The units of the output.
+    pub units: Vec<Unit>,
+}
+
+impl From<Detailed> for Basic {
+    fn from(detailed: Detailed) -> Self {
+        Self::flattened(detailed, true)
+    }
+}
+
+impl Basic {
+    /// Returns the `Basic` output of `detailed` with the errors of each
+    /// member of a failing `"anyOf"` or `"oneOf"` rather than those of the
+    /// best matching member alone.
+    pub fn exhaustive(detailed: Detailed) -> Self {
+        Self::flattened(detailed, false)
+    }
+
+    fn flattened(detailed: Detailed, best_match: bool) -> Self {
+        let valid = detailed.unit.valid;
+        let mut units = Vec::new();
+        flatten(detailed, valid, best_match, &mut units);
+        Self { valid, units }
+    }
+}
+
+fn flatten(node: Detailed, valid: bool, best_match: bool, units: &mut Vec<Unit>) {
+    let Detailed { unit, mut nested } = node;
+    let retained = if valid {
+        !unit.data.is_empty()
+    } else {
+        unit.error.is_some()
+    };
+    if !unit.valid && best_match && is_branching(&unit.keyword_location) {
+        nested = best_member(&unit.keyword_location, nested);
+    }
+    if retained {
+        units.push(unit);
+    }
+    for node in nested {
+        flatten(node, valid, best_match, units);
+    }
+}
+
+/// Returns `true` if `keyword_location` is that of an `"anyOf"` or
+/// `"oneOf"`.
+///
+/// The last token must be in the position of a keyword rather than, e.g.,
+/// the name of a property within `"properties"` or the index of a member of
+/// `"allOf"`.
+fn is_branching(keyword_location: &Pointer) -> bool {
+    let mut tokens = keyword_location.as_str().split('/').skip(1).peekable();
+    let mut keyword = None;
+    while let Some(token) = tokens.next() {
+        keyword = Some(token);
+        let named = OBJECT_KEYWORDS.contains(&token) || token == "dependencies";
+        let indexed = ARRAY_KEYWORDS.contains(&token)
+            && tokens.peek().map_or(false, |t| t.parse::<usize>().is_ok());
+        if named || indexed {
+            // the next token is a name or index, followed by a keyword
+            keyword = None;
+            tokens.next();
+        }
+    }
+    matches!(keyword, Some("anyOf" | "oneOf"))
+}
+
+/// Returns the index of the member of the `"anyOf"` or `"oneOf"` at
+/// `keyword_location` to which `node` belongs, if any.
+fn member_index(keyword_location: &Pointer, node: &Detailed) -> Option<usize> {
+    let rest = node
+        .unit
+        .keyword_location
+        .as_str()
+        .strip_prefix(keyword_location.as_str())?
+        .strip_prefix('/')?;
+    rest.split('/').next()?.parse().ok()
+}
+
+/// Retains, of the members of the `"anyOf"` or `"oneOf"` at
+/// `keyword_location`, the nodes of the best matching member, along with
+/// the nodes which do not belong to a member.
+fn best_member(keyword_location: &Pointer, nested: Vec<Detailed>) -> Vec<Detailed> {
+    // (index, depth of the deepest error, number of errors) of each member
+    let mut scores: Vec<(usize, usize, usize)> = Vec::new();
+    for node in &nested {
+        let index = match member_index(keyword_location, node) {
+            Some(index) => index,
+            None => continue,
+        };
+        let (depth, errors) = progress(node);
+        match scores.iter_mut().find(|(i, _, _)| *i == index) {
+            Some(score) => {
+                score.1 = score.1.max(depth);
+                score.2 += errors;
+            }
+            None => scores.push((index, depth, errors)),
+        }
+    }
+    let best = scores
+        .iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)).then(b.0.cmp(&a.0)))
+        .map(|(index, _, _)| *index);
+    nested
+        .into_iter()
+        .filter(|node| match member_index(keyword_location, node) {
+            Some(index) => Some(index) == best,
+            None => true,
+        })
+        .collect()
+}
+
+/// Returns the depth within the instance of the deepest error of `node` and
+/// the number of its errors.
+fn progress(node: &Detailed) -> (usize, usize) {
+    let mut depth = 0;
+    let mut errors = 0;
+    if node.unit.error.is_some() {
+        depth = node.unit.instance_location.tokens().count();
+        errors = 1;
+    }
+    for nested in &node.nested {
+        let (d, e) = progress(nested);
+        depth = depth.max(d);
+        errors += e;
+    }
+    (depth, errors)
+}
+
+impl Serialize for Basic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("valid", &self.valid)?;
+        if !self.units.is_empty() {
+            let units: Vec<BasicUnit> = self.units.iter().map(BasicUnit).collect();
+            map.serialize_entry(nested_key(self.valid), &units)?;
+        }
+        map.end()
+    }
+}
+
+/// Output consisting solely of whether the instance is valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Flag {
+    /// Whether the instance is valid.
+    pub valid: bool,
+}
+
+impl From<Basic> for Flag {
+    fn from(basic: Basic) -> Self {
+        Self { valid: basic.valid }
+    }
+}
+
+fn nested_key(valid: bool) -> &'static str {
+    if valid {
+        "annotations"
+    } else {
+        "errors"
+    }
+}
+
+fn serialize_node<S: Serializer, N: Serialize>(
+    unit: &Unit,
+    nested: &[N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(None)?;
+    unit.serialize_fields(&mut map, true)?;
+    if !nested.is_empty() {
+        map.serialize_entry(nested_key(unit.valid), nested)?;
+    }
+    map.end()
+}
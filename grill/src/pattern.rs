@@ -0,0 +1,240 @@
+//! A cache of compiled regular expressions shared by the
+//! [`Schema`](crate::Schema)s of an [`Interrogator`](crate::Interrogator).
+//!
+//! The [`pattern`](crate::keyword::pattern) and
+//! [`pattern_properties`](crate::keyword::pattern_properties) keywords
+//! compile their regular expressions through [`Patterns`] when a `Schema` is
+//! setup, so no regular expression is compiled during evaluation and each
+//! distinct pattern is compiled once, however many schemas use it.
+//!
+//! ## Engines
+//! JSON Schema specifies ECMA-262 regular expressions, which the default
+//! [`RegexEngine::Rust`], the [`regex`](https://docs.rs/regex) crate, does
+//! not fully implement: it lacks lookaround and backreferences, and `\d`
+//! and `\w` match Unicode rather than ASCII characters. With the
+//! `"regress"` feature, [`RegexEngine::Ecma`] compiles patterns with the
+//! ECMA-262 compliant [`regress`](https://docs.rs/regress) crate instead.
+//! The engine is selected per `Interrogator` with
+//! [`Interrogator::set_regex_engine`](crate::Interrogator::set_regex_engine).
+//!
+//! ## Example
+//! ```
+//! use grill::{pattern::RegexEngine, Interrogator};
+//!
+//! let interrogator = Interrogator::new();
+//! let patterns = interrogator.patterns();
+//! patterns.compile("^[a-z]+$", RegexEngine::Rust).unwrap();
+//! patterns.compile("^[a-z]+$", RegexEngine::Rust).unwrap();
+//!
+//! let stats = patterns.stats();
+//! assert_eq!(stats.len, 1);
+//! assert_eq!((stats.hits, stats.misses), (1, 1));
+//! assert_eq!(stats.hit_rate(), 0.5);
+//! ```
+
+use crate::error::PatternError;
+use dashmap::DashMap;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// The implementation of regular expressions used to compile the patterns
+/// of a [`Schema`](crate::Schema).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RegexEngine {
+    /// The [`regex`](https://docs.rs/regex) crate, which guarantees matching
+    /// in linear time but is not ECMA-262 compliant.
+    #[default]
+    Rust,
+    /// The ECMA-262 compliant [`regress`](https://docs.rs/regress) crate,
+    /// which compiles patterns in Unicode mode, i.e. with the `u` flag, as
+    /// JSON Schema prescribes.
+    ///
+    /// `regress` backtracks, so a pattern such as `"^(a+)+$"` can take time
+    /// exponential in the length of the instance, and is open to regular
+    /// expression denial of service (ReDoS). Do not use it with schemas of
+    /// untrusted sources: a [`timeout`](crate::evaluation::Options::timeout)
+    /// is checked between keywords and does not interrupt a match.
+    ///
+    /// Requires the `"regress"` feature.
+    /// [`Interrogator::set_regex_engine`](crate::Interrogator::set_regex_engine)
+    /// returns an error if the feature is not enabled.
+    Ecma,
+}
+
+impl fmt::Display for RegexEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegexEngine::Rust => f.write_str("Rust"),
+            RegexEngine::Ecma => f.write_str("ECMA-262"),
+        }
+    }
+}
+
+/// A regular expression compiled by a [`RegexEngine`].
+#[derive(Debug)]
+pub struct Pattern {
+    source: String,
+    compiled: Compiled,
+}
+
+#[derive(Debug)]
+enum Compiled {
+    Rust(regex::Regex),
+    #[cfg(feature = "regress")]
+    Ecma(regress::Regex),
+}
+
+impl Pattern {
+    /// Compiles `pattern` with `engine`.
+    ///
+    /// # Errors
+    /// Returns a [`PatternError`] if `pattern` is not valid for `engine` or
+    /// if `engine` is [`RegexEngine::Ecma`] and the `"regress"` feature is
+    /// not enabled.
+    pub fn new(pattern: &str, engine: RegexEngine) -> Result<Self, PatternError> {
+        let error = |reason: String| PatternError {
+            pattern: pattern.to_string(),
+            engine,
+            reason,
+        };
+        let compiled = match engine {
+            RegexEngine::Rust => {
+                Compiled::Rust(regex::Regex::new(pattern).map_err(|err| error(err.to_string()))?)
+            }
+            #[cfg(feature = "regress")]
+            RegexEngine::Ecma => {
+                let regex = regress::Regex::with_flags(pattern, "u");
+                Compiled::Ecma(regex.map_err(|err| error(err.to_string()))?)
+            }
+            #[cfg(not(feature = "regress"))]
+            RegexEngine::Ecma => {
+                return Err(error(
+                    "the \"regress\" feature of grill is not enabled".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            source: pattern.to_string(),
+            compiled,
+        })
+    }
+
+    /// Returns the pattern this `Pattern` was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the [`RegexEngine`] which compiled this `Pattern`.
+    pub fn engine(&self) -> RegexEngine {
+        match self.compiled {
+            Compiled::Rust(_) => RegexEngine::Rust,
+            #[cfg(feature = "regress")]
+            Compiled::Ecma(_) => RegexEngine::Ecma,
+        }
+    }
+
+    /// Returns `true` if the pattern matches anywhere within `haystack`.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        match &self.compiled {
+            Compiled::Rust(regex) => regex.is_match(haystack),
+            #[cfg(feature = "regress")]
+            Compiled::Ecma(regex) => regex.find(haystack).is_some(),
+        }
+    }
+}
+
+/// A cache of compiled regular expressions, keyed by pattern and
+/// [`RegexEngine`].
+///
+/// Cloning `Patterns` is cheap; clones share the same cache and statistics.
+#[derive(Clone, Default)]
+pub struct Patterns {
+    compiled: Arc<DashMap<(RegexEngine, String), Arc<Pattern>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl Patterns {
+    /// Creates and returns a new, empty `Patterns` cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `pattern` compiled with `engine`, compiling and caching it if
+    /// it has not previously been seen.
+    ///
+    /// # Errors
+    /// Returns a [`PatternError`] if `pattern` can not be compiled; see
+    /// [`Pattern::new`]. Invalid patterns are not cached.
+    pub fn compile(
+        &self,
+        pattern: &str,
+        engine: RegexEngine,
+    ) -> Result<Arc<Pattern>, PatternError> {
+        let key = (engine, pattern.to_string());
+        if let Some(existing) = self.compiled.get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(existing.clone());
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let compiled = Arc::new(Pattern::new(pattern, engine)?);
+        self.compiled.insert(key, compiled.clone());
+        Ok(compiled)
+    }
+
+    /// Returns the [`PatternStats`] of the cache.
+    pub fn stats(&self) -> PatternStats {
+        PatternStats {
+            len: self.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the number of cached regular expressions.
+    pub fn len(&self) -> usize {
+        self.compiled.len()
+    }
+
+    /// Returns `true` if no regular expressions are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Debug for Patterns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Patterns")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// Statistics of a [`Patterns`] cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatternStats {
+    /// The number of cached regular expressions.
+    pub len: usize,
+    /// The number of lookups which found a compiled regular expression.
+    pub hits: u64,
+    /// The number of lookups which compiled a regular expression, including
+    /// those which failed to compile.
+    pub misses: u64,
+}
+
+impl PatternStats {
+    /// Returns the fraction of lookups which were hits, or `0.0` if there
+    /// have been none.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
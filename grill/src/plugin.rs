@@ -0,0 +1,112 @@
+//! Registration of drafts and dialects which live outside of grill.
+//!
+//! A plugin is a function which is handed a [`Registrar`] when an
+//! [`Interrogator`](crate::Interrogator) is built with
+//! [`InterrogatorBuilder::plugin`](crate::interrogator::InterrogatorBuilder::plugin).
+//! Plugins are scoped to the `Interrogator` being built; there is no global
+//! registry.
+//!
+//! ## Example
+//! ```
+//! use grill::{plugin::Registrar, Error, Interrogator};
+//! use grill::uri::AbsoluteUri;
+//! use serde_json::json;
+//!
+//! fn vendor_draft(registrar: &mut Registrar) -> Result<(), Error> {
+//!     let uri = AbsoluteUri::parse("https://example.com/vendor/schema")?;
+//!     registrar.meta_schema_source(uri, json!({ "$id": "https://example.com/vendor/schema" }));
+//!     Ok(())
+//! }
+//!
+//! let interrogator = Interrogator::builder().plugin(vendor_draft).build().unwrap();
+//! let uri = AbsoluteUri::parse("https://example.com/vendor/schema").unwrap();
+//! assert!(interrogator.source(&uri).unwrap().is_some());
+//! ```
+
+use crate::{Dialect, Error, Vocabulary};
+use serde_json::Value;
+use uniresid::{AbsoluteUri, Uri};
+
+/// A function which registers drafts, dialects, or vocabularies with a
+/// [`Registrar`].
+pub type PluginFn = dyn FnOnce(&mut Registrar) -> Result<(), Error>;
+
+/// Collects the meta schema sources, [`Vocabulary`]s, and [`Dialect`]s
+/// registered by plugins.
+///
+/// Registrations made by later plugins replace those of earlier plugins with
+/// the same URI.
+#[derive(Default)]
+pub struct Registrar {
+    pub(crate) sources: Vec<(AbsoluteUri, Value)>,
+    pub(crate) vocabularies: Vec<Vocabulary>,
+    pub(crate) dialects: Vec<(Uri, Dialect)>,
+    /// The base dialect, the id of the extended dialect, and the vocabulary
+    /// it is extended with.
+    pub(crate) extensions: Vec<(Uri, Uri, Vocabulary)>,
+}
+
+impl Registrar {
+    /// Registers the `source` of a meta schema, retrievable from `uri`.
+    pub fn meta_schema_source(&mut self, uri: AbsoluteUri, source: Value) -> &mut Self {
+        self.sources.push((uri, source));
+        self
+    }
+
+    /// Registers a [`Vocabulary`] and the keywords it implements.
+    pub fn vocabulary(&mut self, vocabulary: Vocabulary) -> &mut Self {
+        self.vocabularies.push(vocabulary);
+        self
+    }
+
+    /// Registers the [`Dialect`] of the meta schema identified by `id`.
+    pub fn dialect(&mut self, id: Uri, dialect: Dialect) -> &mut Self {
+        self.dialects.push((id, dialect));
+        self
+    }
+
+    /// Adds `vocabulary`, as required, to the [`Dialect`] of the meta schema
+    /// identified by `id`, which must be registered by this or another
+    /// plugin. Use it to attach keywords created with
+    /// [`keyword::custom`](crate::keyword::custom) to an existing dialect.
+    ///
+    /// Extensions are applied after every plugin has run, in the order they
+    /// were made.
+    pub fn extend_dialect(&mut self, id: Uri, vocabulary: Vocabulary) -> &mut Self {
+        self.extensions.push((id.clone(), id, vocabulary));
+        self
+    }
+
+    /// Registers, as `id`, a copy of the [`Dialect`] of the meta schema
+    /// identified by `base` with `vocabulary` added as required. The source
+    /// of the meta schema `id` must be registered with
+    /// [`meta_schema_source`](Self::meta_schema_source) for schemas to
+    /// declare it with `"$schema"`.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{
+    ///     draft::{mongodb, MONGODB_URI},
+    ///     keyword::custom,
+    ///     plugin::Registrar,
+    ///     Error, Interrogator, Uri, Vocabulary,
+    /// };
+    ///
+    /// fn routing(registrar: &mut Registrar) -> Result<(), Error> {
+    ///     let mut vocabulary = Vocabulary::new(Uri::parse("https://example.com/vocab/routing")?);
+    ///     vocabulary.push(custom("x-internal-routing", |_, _, _| Ok(()), |_, _, _| Ok(())));
+    ///     let id = Uri::parse("https://example.com/mongodb-routing")?;
+    ///     registrar.derive_dialect(MONGODB_URI.clone(), id, vocabulary);
+    ///     Ok(())
+    /// }
+    ///
+    /// let interrogator = Interrogator::builder().plugin(mongodb).plugin(routing).build().unwrap();
+    /// let id = Uri::parse("https://example.com/mongodb-routing").unwrap();
+    /// assert_eq!(interrogator.dialect(&id).unwrap().vocabularies.len(), 2);
+    /// assert_eq!(interrogator.dialect(&MONGODB_URI).unwrap().vocabularies.len(), 1);
+    /// ```
+    pub fn derive_dialect(&mut self, base: Uri, id: Uri, vocabulary: Vocabulary) -> &mut Self {
+        self.extensions.push((base, id, vocabulary));
+        self
+    }
+}
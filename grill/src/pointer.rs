@@ -0,0 +1,288 @@
+//! JSON Pointers ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)),
+//! their representation as URI fragments, and
+//! [Relative JSON Pointers](https://datatracker.ietf.org/doc/html/draft-bhutton-relative-json-pointer-00).
+//!
+//! [`Pointer`] and [`Token`] are re-exported from [`jsonptr`], which parses,
+//! iterates, and resolves pointers against a [`Value`]. This module adds the
+//! pieces keywords otherwise write by hand: escaping of tokens, conversion
+//! to and from URI fragments, which percent-encode the pointer, and
+//! [`RelativePointer`].
+//!
+//! ## Example
+//! ```
+//! use grill::pointer::{self, Pointer};
+//!
+//! let location = Pointer::new(&["$defs", "a/b", "c d"]);
+//! assert_eq!(location.as_str(), "/$defs/a~1b/c d");
+//! assert_eq!(pointer::to_fragment(&location), "#/$defs/a~1b/c%20d");
+//! assert_eq!(pointer::from_fragment("#/$defs/a~1b/c%20d").unwrap(), location);
+//! ```
+
+pub use jsonptr::{Pointer, Token};
+
+use crate::error::PointerSyntaxError;
+use serde_json::Value;
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// Escapes `token` for use within a JSON Pointer, replacing `'~'` with
+/// `"~0"` and `'/'` with `"~1"`.
+pub fn escape(token: &str) -> Cow<'_, str> {
+    if !token.contains(['~', '/']) {
+        return Cow::Borrowed(token);
+    }
+    Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Reverses [`escape`].
+///
+/// # Errors
+/// Returns a [`PointerSyntaxError`] if `token` contains a `'~'` which is not
+/// followed by `'0'` or `'1'`.
+pub fn unescape(token: &str) -> Result<Cow<'_, str>, PointerSyntaxError> {
+    if !token.contains('~') {
+        return Ok(Cow::Borrowed(token));
+    }
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('0') => unescaped.push('~'),
+            Some('1') => unescaped.push('/'),
+            _ => {
+                return Err(PointerSyntaxError {
+                    value: token.to_string(),
+                    reason: "'~' must be followed by '0' or '1'".to_string(),
+                })
+            }
+        }
+    }
+    Ok(Cow::Owned(unescaped))
+}
+
+/// Returns `pointer` extended by each of the unescaped `tokens`.
+pub fn join<T: AsRef<str>>(pointer: &Pointer, tokens: impl IntoIterator<Item = T>) -> Pointer {
+    let mut pointer = pointer.clone();
+    for token in tokens {
+        pointer.push_back(Token::new(token.as_ref()));
+    }
+    pointer
+}
+
+/// Returns `pointer` as a URI fragment, including the leading `'#'`, with
+/// each character which may not appear in a fragment percent-encoded.
+pub fn to_fragment(pointer: &Pointer) -> String {
+    let mut fragment = String::with_capacity(pointer.as_str().len() + 1);
+    fragment.push('#');
+    for byte in pointer.as_str().bytes() {
+        if is_fragment_byte(byte) {
+            fragment.push(char::from(byte));
+        } else {
+            fragment.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    fragment
+}
+
+/// Parses the URI fragment `fragment`, with or without its leading `'#'`,
+/// as a percent-encoded JSON Pointer.
+///
+/// # Errors
+/// Returns a [`PointerSyntaxError`] if `fragment` is not validly
+/// percent-encoded UTF-8 or is not a JSON Pointer, e.g. `"#node"`.
+pub fn from_fragment(fragment: &str) -> Result<Pointer, PointerSyntaxError> {
+    let error = |reason: &str| PointerSyntaxError {
+        value: fragment.to_string(),
+        reason: reason.to_string(),
+    };
+    let encoded = fragment.strip_prefix('#').unwrap_or(fragment).as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex = encoded
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| error("'%' must be followed by two hexadecimal digits"))?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(encoded[i]);
+            i += 1;
+        }
+    }
+    let decoded = String::from_utf8(decoded).map_err(|_| error("not valid UTF-8"))?;
+    Pointer::try_from(decoded.as_str()).map_err(|err| error(&err.to_string()))
+}
+
+/// Returns `true` if `byte` may appear unencoded within a URI fragment, as
+/// defined by RFC 3986.
+fn is_fragment_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/?".contains(&byte)
+}
+
+/// A [Relative JSON Pointer](https://datatracker.ietf.org/doc/html/draft-bhutton-relative-json-pointer-00),
+/// e.g. `"1/name"`, `"0-1"`, or `"2#"`, which refers to a value relative to
+/// a location within a document.
+///
+/// A relative pointer consists of the number of levels to ascend from the
+/// location, an optional offset to apply to the array index reached, and
+/// either a JSON Pointer to descend with or `'#'`, which refers to the key
+/// or index of the value reached rather than the value itself.
+///
+/// ## Example
+/// ```
+/// use grill::pointer::{Pointer, RelativePointer};
+/// use serde_json::json;
+///
+/// let document = json!({ "items": [{ "id": 1 }, { "id": 2 }] });
+/// let location = Pointer::new(&["items", "1", "id"]);
+///
+/// let previous: RelativePointer = "1-1/id".parse().unwrap();
+/// assert_eq!(previous.resolve(&document, &location).unwrap().as_ref(), &json!(1));
+///
+/// let index: RelativePointer = "1#".parse().unwrap();
+/// assert_eq!(index.resolve(&document, &location).unwrap().as_ref(), &json!(1));
+/// assert_eq!(index.to_string(), "1#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativePointer {
+    up: usize,
+    offset: i64,
+    target: RelativeTarget,
+}
+
+/// What a [`RelativePointer`] refers to once it has ascended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RelativeTarget {
+    /// The value at the JSON Pointer, relative to the value reached.
+    Pointer(Pointer),
+    /// The key or index of the value reached, i.e. `'#'`.
+    Name,
+}
+
+impl RelativePointer {
+    /// Parses `s` as a Relative JSON Pointer.
+    ///
+    /// # Errors
+    /// Returns a [`PointerSyntaxError`] if `s` is not a Relative JSON
+    /// Pointer.
+    pub fn parse(s: &str) -> Result<Self, PointerSyntaxError> {
+        let error = |reason: &str| PointerSyntaxError {
+            value: s.to_string(),
+            reason: reason.to_string(),
+        };
+        let (up, rest) = split_integer(s)
+            .ok_or_else(|| error("must begin with a non-negative integer without leading zeros"))?;
+        let (offset, rest) = match rest.chars().next() {
+            Some(sign @ ('+' | '-')) => {
+                let (offset, rest) = split_integer(&rest[1..])
+                    .ok_or_else(|| error("an index offset must be an integer"))?;
+                let offset = i64::try_from(offset).map_err(|_| error("index offset too large"))?;
+                (if sign == '-' { -offset } else { offset }, rest)
+            }
+            _ => (0, rest),
+        };
+        let target = match rest {
+            "#" => RelativeTarget::Name,
+            pointer => RelativeTarget::Pointer(
+                Pointer::try_from(pointer).map_err(|err| error(&err.to_string()))?,
+            ),
+        };
+        Ok(Self { up, offset, target })
+    }
+
+    /// Returns the number of levels the pointer ascends.
+    pub fn up(&self) -> usize {
+        self.up
+    }
+
+    /// Returns the offset applied to the array index reached, or `0` if
+    /// there is none.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Returns what the pointer refers to once it has ascended.
+    pub fn target(&self) -> &RelativeTarget {
+        &self.target
+    }
+
+    /// Resolves the pointer against the value at `location` within
+    /// `document`.
+    ///
+    /// Returns `None` if the pointer ascends beyond the root of `document`,
+    /// offsets an index of something other than an array or beyond its
+    /// bounds, refers to the name of the root, or descends to a value which
+    /// does not exist. A name is returned as a string for a key and as a
+    /// number for an index.
+    pub fn resolve<'v>(&self, document: &'v Value, location: &Pointer) -> Option<Cow<'v, Value>> {
+        let mut tokens: Vec<String> = location.tokens().map(|t| t.as_str().to_string()).collect();
+        let depth = tokens.len().checked_sub(self.up)?;
+        tokens.truncate(depth);
+        let parent = match tokens.split_last() {
+            Some((_, parent)) => Some(Pointer::new(parent).resolve(document).ok()?),
+            None => None,
+        };
+        if self.offset != 0 {
+            let len = parent?.as_array()?.len();
+            let last = tokens.last_mut()?;
+            let index = i64::try_from(last.parse::<usize>().ok()?).ok()? + self.offset;
+            if index < 0 || index >= i64::try_from(len).ok()? {
+                return None;
+            }
+            *last = index.to_string();
+        }
+        match &self.target {
+            RelativeTarget::Name => {
+                let last = tokens.last()?;
+                if parent?.is_array() {
+                    Some(Cow::Owned(Value::from(last.parse::<u64>().ok()?)))
+                } else {
+                    Some(Cow::Owned(Value::String(last.clone())))
+                }
+            }
+            RelativeTarget::Pointer(pointer) => {
+                let mut location = Pointer::new(&tokens);
+                location.append(pointer);
+                location.resolve(document).ok().map(Cow::Borrowed)
+            }
+        }
+    }
+}
+
+/// Splits the non-negative integer without leading zeros which begins `s`
+/// from the remainder of `s`.
+fn split_integer(s: &str) -> Option<(usize, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let digits = &s[..end];
+    if digits.is_empty() || (digits.len() > 1 && digits.starts_with('0')) {
+        return None;
+    }
+    Some((digits.parse().ok()?, &s[end..]))
+}
+
+impl FromStr for RelativePointer {
+    type Err = PointerSyntaxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for RelativePointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.up)?;
+        if self.offset != 0 {
+            write!(f, "{:+}", self.offset)?;
+        }
+        match &self.target {
+            RelativeTarget::Name => f.write_str("#"),
+            RelativeTarget::Pointer(pointer) => f.write_str(pointer.as_str()),
+        }
+    }
+}
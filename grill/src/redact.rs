@@ -0,0 +1,572 @@
+//! Redaction of sensitive values from instances, for logging.
+//!
+//! [`redact`] walks an instance alongside a [`Schema`], collecting the
+//! annotations of each subschema which applies to each value of the
+//! instance, and produces a sanitized copy:
+//!
+//! - Values annotated with `"writeOnly": true` or with the extension keyword
+//!   [`"x-sensitive"`](SENSITIVE_KEYWORD)`: true` are replaced by a
+//!   placeholder, or removed if [`RedactOptions::strip`] is set.
+//! - If the instance is being read ([`Context::Read`]), `"writeOnly"`
+//!   values are removed, as they should not have been returned.
+//! - If the instance is being written ([`Context::Write`]), `"readOnly"`
+//!   values are removed, as they should not have been sent.
+//!
+//! Subschemas reached through `"properties"`, `"patternProperties"`,
+//! `"additionalProperties"`, `"unevaluatedProperties"`, `"prefixItems"`,
+//! `"items"`, `"additionalItems"`, `"contains"`, `"unevaluatedItems"`,
+//! `"$ref"`, `"$dynamicRef"`, `"$recursiveRef"`, and each in-place
+//! applicator are considered. Annotations of the subschemas of `"allOf"`,
+//! `"anyOf"`, `"oneOf"`, `"if"`, `"then"`, `"else"`, `"dependentSchemas"`,
+//! `"dependencies"`, and `"contains"` are collected whether or not those
+//! subschemas are valid, so a value is redacted if any branch which could
+//! apply marks it as sensitive. `"unevaluatedProperties"` and
+//! `"unevaluatedItems"` apply to the values to which no other subschema
+//! applies.
+//!
+//! References are resolved statically, against the schema resource they
+//! appear in; a `"$dynamicRef"` is resolved as a `"$ref"`.
+//!
+//! ## Example
+//! ```
+//! use grill::redact::{redact_value, RedactOptions};
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "properties": {
+//!         "user": { "type": "string" },
+//!         "password": { "writeOnly": true },
+//!         "token": { "$ref": "#/$defs/secret" }
+//!     },
+//!     "$defs": { "secret": { "x-sensitive": true } }
+//! });
+//! let instance = json!({ "user": "ana", "password": "hunter2", "token": "abc" });
+//!
+//! let sanitized = redact_value(&schema, &instance, &RedactOptions::new()).unwrap();
+//! assert_eq!(
+//!     sanitized,
+//!     json!({ "user": "ana", "password": "[REDACTED]", "token": "[REDACTED]" })
+//! );
+//! ```
+
+use crate::{
+    fragment, interrogator::DEFAULT_MAX_REFERENCE_DEPTH, pointer, urn, Error, Interrogator, Schema,
+};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use uniresid::{AbsoluteUri, Uri};
+
+/// The extension keyword which marks a value as sensitive.
+pub const SENSITIVE_KEYWORD: &str = "x-sensitive";
+
+/// The default value which replaces redacted values.
+pub const DEFAULT_PLACEHOLDER: &str = "[REDACTED]";
+
+/// Keywords whose subschemas apply to the same value as the schema.
+const IN_PLACE_KEYWORDS: &[&str] = &["allOf", "anyOf", "oneOf"];
+
+/// Keywords whose subschema applies to the same value as the schema.
+const IN_PLACE_SCHEMA_KEYWORDS: &[&str] = &["if", "then", "else"];
+
+/// Keywords whose value is a reference to a schema which applies to the same
+/// value as the schema.
+const REFERENCE_KEYWORDS: &[&str] = &["$ref", "$dynamicRef", "$recursiveRef"];
+
+/// The direction in which an instance is transferred, which determines
+/// whether `"readOnly"` or `"writeOnly"` values are out of place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// The instance was retrieved from its owning authority, e.g. a response.
+    Read,
+    /// The instance was sent to its owning authority, e.g. a request.
+    Write,
+}
+
+/// Options for [`redact`] and [`redact_value`].
+#[derive(Debug, Clone)]
+pub struct RedactOptions {
+    placeholder: Value,
+    strip: bool,
+    context: Option<Context>,
+}
+
+impl Default for RedactOptions {
+    fn default() -> Self {
+        Self {
+            placeholder: Value::String(DEFAULT_PLACEHOLDER.to_string()),
+            strip: false,
+            context: None,
+        }
+    }
+}
+
+impl RedactOptions {
+    /// Creates and returns a new `RedactOptions` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value which replaces redacted values. Defaults to
+    /// [`DEFAULT_PLACEHOLDER`].
+    #[must_use]
+    pub fn placeholder(mut self, placeholder: impl Into<Value>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets whether redacted values are removed rather than replaced by the
+    /// placeholder. A redacted root value is always replaced.
+    #[must_use]
+    pub fn strip(mut self, strip: bool) -> Self {
+        self.strip = strip;
+        self
+    }
+
+    /// Sets the [`Context`] of the instance. By default, neither
+    /// `"readOnly"` nor `"writeOnly"` values are removed for being out of
+    /// place.
+    #[must_use]
+    pub fn context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+}
+
+/// Returns a sanitized copy of `instance` as described by `schema`.
+/// References are resolved with `interrogator`.
+///
+/// # Errors
+/// Returns an [`Error`] if a referenced source document could not be parsed
+/// or a reference fragment is malformed.
+pub fn redact(
+    interrogator: &Interrogator,
+    schema: &Schema,
+    instance: &Value,
+    options: &RedactOptions,
+) -> Result<Value, Error> {
+    let walker = Walker::new(Some(interrogator), options);
+    let base = schema.id().as_deref().cloned();
+    let frame = Frame::root(Document::new(schema.source(), base.as_ref()), base);
+    walker.redact_root(instance, frame)
+}
+
+/// Returns a sanitized copy of `instance` as described by the schema
+/// `source`. Only references within `source` are resolved.
+///
+/// # Errors
+/// Returns an [`Error`] if a reference fragment is malformed.
+pub fn redact_value(
+    source: &Value,
+    instance: &Value,
+    options: &RedactOptions,
+) -> Result<Value, Error> {
+    let walker = Walker::new(None, options);
+    // resources embedded by "$id" can be found only if the ids can be
+    // resolved, i.e. if the root has an absolute id
+    let base = source
+        .get("$id")
+        .and_then(Value::as_str)
+        .and_then(|id| AbsoluteUri::parse(id).ok())
+        .map(|id| id.uri().clone());
+    let document = Document::new(Arc::new(source.clone()), base.as_ref());
+    let frame = Frame::root(document, base);
+    walker.redact_root(instance, frame)
+}
+
+/// A schema document and the schema resources embedded within it.
+struct Document {
+    value: Arc<Value>,
+    /// The id, without fragment, and location of each schema resource of the
+    /// document, including the document itself. Empty if the id of the
+    /// document is not known.
+    resources: Vec<(String, String)>,
+}
+
+impl Document {
+    fn new(value: Arc<Value>, base: Option<&Uri>) -> Arc<Self> {
+        let resources = match base {
+            Some(base) => {
+                let mut resources = vec![(without_fragment(base), String::new())];
+                resources.extend(
+                    fragment::resources(&value, base)
+                        .into_iter()
+                        .map(|(id, location)| (id, location.to_string())),
+                );
+                resources
+            }
+            None => Vec::new(),
+        };
+        Arc::new(Self { value, resources })
+    }
+}
+
+/// A subschema, identified by its location within a document.
+#[derive(Clone)]
+struct Frame {
+    document: Arc<Document>,
+    location: String,
+    /// The location of the innermost schema resource which contains the
+    /// subschema, against which fragment-only references are resolved.
+    resource: String,
+    base: Option<Uri>,
+}
+
+impl Frame {
+    fn root(document: Arc<Document>, base: Option<Uri>) -> Frame {
+        Frame {
+            document,
+            location: String::new(),
+            resource: String::new(),
+            base,
+        }
+    }
+
+    fn schema(&self) -> Option<&Map<String, Value>> {
+        self.document
+            .value
+            .pointer(&self.location)
+            .and_then(Value::as_object)
+    }
+
+    fn child(&self, tokens: &[&str]) -> Frame {
+        let mut location = self.location.clone();
+        for token in tokens {
+            location.push('/');
+            location.push_str(&pointer::escape(token));
+        }
+        Frame {
+            document: self.document.clone(),
+            location,
+            resource: self.resource.clone(),
+            base: self.base.clone(),
+        }
+    }
+}
+
+/// What happens to a value.
+enum Action {
+    Keep,
+    Mask,
+    Remove,
+}
+
+struct Walker<'a> {
+    interrogator: Option<&'a Interrogator>,
+    options: &'a RedactOptions,
+    /// Each pattern of `"patternProperties"` compiled so far, or `None` if it
+    /// is not a valid regular expression.
+    patterns: RefCell<HashMap<String, Option<Regex>>>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(interrogator: Option<&'a Interrogator>, options: &'a RedactOptions) -> Self {
+        Self {
+            interrogator,
+            options,
+            patterns: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn redact_root(&self, value: &Value, frame: Frame) -> Result<Value, Error> {
+        let frames = self.expand(vec![frame])?;
+        match self.action(&frames) {
+            Action::Keep => self.redact(value, &frames),
+            // the root can not be removed
+            Action::Mask | Action::Remove => Ok(self.options.placeholder.clone()),
+        }
+    }
+
+    /// Returns a sanitized copy of `value`, whose own action is to be kept,
+    /// to which the schemas of `frames` apply.
+    fn redact(&self, value: &Value, frames: &[Frame]) -> Result<Value, Error> {
+        match value {
+            Value::Object(obj) => {
+                let mut redacted = Map::new();
+                for (key, value) in obj {
+                    let mut children: Vec<Frame> =
+                        frames.iter().flat_map(|f| self.property(f, key)).collect();
+                    if children.is_empty() {
+                        children = unevaluated(frames, "unevaluatedProperties");
+                    }
+                    if let Some(value) = self.redact_child(value, children)? {
+                        redacted.insert(key.clone(), value);
+                    }
+                }
+                Ok(Value::Object(redacted))
+            }
+            Value::Array(items) => {
+                let mut redacted = Vec::with_capacity(items.len());
+                for (i, item) in items.iter().enumerate() {
+                    let mut children: Vec<Frame> =
+                        frames.iter().flat_map(|f| item_of(f, i)).collect();
+                    if children.is_empty() {
+                        children = unevaluated(frames, "unevaluatedItems");
+                    }
+                    // any item may be among those which match "contains"
+                    children.extend(unevaluated(frames, "contains"));
+                    if let Some(item) = self.redact_child(item, children)? {
+                        redacted.push(item);
+                    }
+                }
+                Ok(Value::Array(redacted))
+            }
+            value => Ok(value.clone()),
+        }
+    }
+
+    /// Returns the sanitized copy of the child `value`, or `None` if it is to
+    /// be removed.
+    fn redact_child(&self, value: &Value, frames: Vec<Frame>) -> Result<Option<Value>, Error> {
+        let frames = self.expand(frames)?;
+        match self.action(&frames) {
+            Action::Keep => self.redact(value, &frames).map(Some),
+            Action::Mask if !self.options.strip => Ok(Some(self.options.placeholder.clone())),
+            Action::Mask | Action::Remove => Ok(None),
+        }
+    }
+
+    /// Returns the action for a value to which the schemas of `frames`
+    /// apply.
+    fn action(&self, frames: &[Frame]) -> Action {
+        let annotated = |keyword: &str| {
+            frames.iter().any(|f| {
+                f.schema()
+                    .and_then(|s| s.get(keyword))
+                    .map_or(false, |v| v == &Value::Bool(true))
+            })
+        };
+        let write_only = annotated("writeOnly");
+        match self.options.context {
+            Some(Context::Read) if write_only => return Action::Remove,
+            Some(Context::Write) if annotated("readOnly") => return Action::Remove,
+            _ => {}
+        }
+        if write_only || annotated(SENSITIVE_KEYWORD) {
+            Action::Mask
+        } else {
+            Action::Keep
+        }
+    }
+
+    /// Adds the subschemas which apply in place to each of `frames`.
+    fn expand(&self, frames: Vec<Frame>) -> Result<Vec<Frame>, Error> {
+        let mut expanded = Vec::with_capacity(frames.len());
+        let mut pending: Vec<(Frame, usize)> = frames.into_iter().map(|f| (f, 0)).collect();
+        while let Some((frame, depth)) = pending.pop() {
+            let document = frame.document.clone();
+            let schema = match document
+                .value
+                .pointer(&frame.location)
+                .and_then(Value::as_object)
+            {
+                Some(schema) => schema,
+                None => continue,
+            };
+            let mut frame = frame;
+            if let Some(Value::String(id)) = schema.get("$id") {
+                let id = Uri::parse(id).ok().filter(|_| !id.starts_with('#'));
+                if let Some(id) = id {
+                    frame.base = match &frame.base {
+                        Some(base) => urn::resolve(base, &id),
+                        None => Some(id),
+                    };
+                    frame.resource = frame.location.clone();
+                }
+            }
+            for keyword in IN_PLACE_KEYWORDS {
+                if let Some(Value::Array(subs)) = schema.get(*keyword) {
+                    for i in 0..subs.len() {
+                        pending.push((frame.child(&[keyword, &i.to_string()]), depth));
+                    }
+                }
+            }
+            for keyword in IN_PLACE_SCHEMA_KEYWORDS {
+                if schema.contains_key(*keyword) {
+                    pending.push((frame.child(&[keyword]), depth));
+                }
+            }
+            if let Some(Value::Object(deps)) = schema.get("dependentSchemas") {
+                for name in deps.keys() {
+                    pending.push((frame.child(&["dependentSchemas", name]), depth));
+                }
+            }
+            // the schema form of the draft 7 and earlier "dependencies"
+            if let Some(Value::Object(deps)) = schema.get("dependencies") {
+                for (name, dep) in deps {
+                    if dep.is_object() {
+                        pending.push((frame.child(&["dependencies", name]), depth));
+                    }
+                }
+            }
+            for keyword in REFERENCE_KEYWORDS {
+                if let Some(Value::String(reference)) = schema.get(*keyword) {
+                    if depth < DEFAULT_MAX_REFERENCE_DEPTH {
+                        if let Some(target) = self.resolve(&frame, reference)? {
+                            pending.push((target, depth + 1));
+                        }
+                    }
+                }
+            }
+            expanded.push(frame);
+        }
+        Ok(expanded)
+    }
+
+    /// Returns the target of `reference` from the schema of `frame`, if it
+    /// can be found.
+    ///
+    /// A fragment-only reference is resolved against the schema resource
+    /// which contains the schema. Otherwise the reference is resolved against
+    /// the base URI of that resource and looked up among the resources
+    /// embedded within the document of `frame`, then with the
+    /// [`Interrogator`], if any.
+    fn resolve(&self, frame: &Frame, reference: &str) -> Result<Option<Frame>, Error> {
+        if let Some(fragment) = reference.strip_prefix('#') {
+            return locate(
+                &frame.document,
+                &frame.resource,
+                fragment,
+                frame.base.clone(),
+            );
+        }
+        let uri = match Uri::parse(reference) {
+            Ok(uri) => uri,
+            Err(_) => return Ok(None),
+        };
+        let target = match &frame.base {
+            Some(base) => match urn::resolve(base, &uri) {
+                Some(target) => target,
+                None => return Ok(None),
+            },
+            None => uri,
+        };
+        let document_id = without_fragment(&target);
+        let target = target.to_string();
+        let fragment = target.split_once('#').map_or("", |(_, fragment)| fragment);
+        let base = Uri::parse(&document_id).ok();
+        let embedded = frame
+            .document
+            .resources
+            .iter()
+            .find(|(id, _)| *id == document_id);
+        if let Some((_, resource)) = embedded {
+            return locate(&frame.document, resource, fragment, base);
+        }
+        let interrogator = match self.interrogator {
+            Some(interrogator) => interrogator,
+            None => return Ok(None),
+        };
+        let absolute = match AbsoluteUri::parse(&document_id) {
+            Ok(absolute) => absolute,
+            Err(_) => return Ok(None),
+        };
+        let value = match interrogator.locate(&absolute)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let document = Document::new(Arc::new(value), base.as_ref());
+        locate(&document, "", fragment, base)
+    }
+
+    /// Returns the subschemas of `frame` which apply to the property `key`.
+    fn property(&self, frame: &Frame, key: &str) -> Vec<Frame> {
+        let schema = match frame.schema() {
+            Some(schema) => schema,
+            None => return Vec::new(),
+        };
+        let mut children = Vec::new();
+        if schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map_or(false, |p| p.contains_key(key))
+        {
+            children.push(frame.child(&["properties", key]));
+        }
+        if let Some(Value::Object(patterns)) = schema.get("patternProperties") {
+            for pattern in patterns.keys() {
+                if self.is_match(pattern, key) {
+                    children.push(frame.child(&["patternProperties", pattern]));
+                }
+            }
+        }
+        if children.is_empty() && schema.contains_key("additionalProperties") {
+            children.push(frame.child(&["additionalProperties"]));
+        }
+        children
+    }
+
+    /// Returns `true` if `pattern` is a valid regular expression which
+    /// matches `key`. Each pattern is compiled once.
+    fn is_match(&self, pattern: &str, key: &str) -> bool {
+        let mut patterns = self.patterns.borrow_mut();
+        let regex = patterns
+            .entry(pattern.to_string())
+            .or_insert_with(|| Regex::new(pattern).ok());
+        regex.as_ref().map_or(false, |re| re.is_match(key))
+    }
+}
+
+/// Returns the frame of the value at `fragment` within the resource at
+/// `resource` of `document`, if it exists.
+fn locate(
+    document: &Arc<Document>,
+    resource: &str,
+    fragment: &str,
+    base: Option<Uri>,
+) -> Result<Option<Frame>, Error> {
+    let value = match document.value.pointer(resource) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    Ok(
+        fragment::locate(value, fragment, false)?.map(|located| Frame {
+            document: document.clone(),
+            location: format!("{}{}", resource, located.location.as_str()),
+            resource: resource.to_string(),
+            base,
+        }),
+    )
+}
+
+/// Returns `uri` without its fragment.
+fn without_fragment(uri: &Uri) -> String {
+    let uri = uri.to_string();
+    match uri.split_once('#') {
+        Some((document, _)) => document.to_string(),
+        None => uri,
+    }
+}
+
+/// Returns the subschema of `keyword` of each of `frames` which has one.
+fn unevaluated(frames: &[Frame], keyword: &str) -> Vec<Frame> {
+    frames
+        .iter()
+        .filter(|f| f.schema().map_or(false, |s| s.contains_key(keyword)))
+        .map(|f| f.child(&[keyword]))
+        .collect()
+}
+
+/// Returns the subschemas of `frame` which apply to the item at `index`.
+fn item_of(frame: &Frame, index: usize) -> Vec<Frame> {
+    let schema = match frame.schema() {
+        Some(schema) => schema,
+        None => return Vec::new(),
+    };
+    let prefix = match (schema.get("prefixItems"), schema.get("items")) {
+        (Some(Value::Array(prefix)), _) => Some(("prefixItems", prefix.len())),
+        (_, Some(Value::Array(prefix))) => Some(("items", prefix.len())),
+        _ => None,
+    };
+    match prefix {
+        Some((keyword, len)) if index < len => {
+            vec![frame.child(&[keyword, &index.to_string()])]
+        }
+        Some(("items", _)) if schema.contains_key("additionalItems") => {
+            vec![frame.child(&["additionalItems"])]
+        }
+        Some(("items", _)) => Vec::new(),
+        _ if schema.contains_key("items") => vec![frame.child(&["items"])],
+        _ => Vec::new(),
+    }
+}
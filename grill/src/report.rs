@@ -0,0 +1,1239 @@
+//! Human-readable reports of an [`Evaluation`].
+//!
+//! A [`Report`] groups the errors of an [`Evaluation`] by the location of
+//! the instance at which they occurred, for presentation to users. With the
+//! `"cli"` feature, [`Report::render_terminal`] renders it for a terminal,
+//! optionally colorized and with a snippet of the schema for each error.
+//! [`Report::to_markdown`] and [`Report::to_html`] export it as a standalone
+//! document, e.g. for inclusion in CI artifacts.
+//!
+//! The locations of a `Report` are interned in two tables of [`Locations`],
+//! one of keyword locations and one of instance locations: each is a
+//! [`Location`], which is cheap to copy and compare, and is rendered as a
+//! JSON Pointer with [`Report::keyword_location`] or
+//! [`Report::instance_location`] when needed. The tables hold the locations
+//! of the errors of the evaluation, or every location of the evaluation once
+//! [`Report::with_all_locations`] is called, and each serializes as an array
+//! of JSON Pointers indexed by [`Location::index`], so that external
+//! systems, e.g. tracing, can refer to locations by index rather than
+//! repeating them.
+//!
+//! Errors can be localized with a [`Translator`], a callback keyed by the
+//! keyword and [kind](crate::Evaluation::set_error_kind) of each error and
+//! given its [parameters](crate::Evaluation::set_error_param), before the
+//! `Report` is rendered or converted to an output format; see
+//! [`Report::translated`].
+//!
+//! ## Example
+//! ```
+//! use grill::{report::Report, Evaluation, OutputFmt};
+//! use jsonptr::Pointer;
+//!
+//! let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+//! let mut properties = eval.for_keyword("properties");
+//! properties.set_instance_location(Pointer::try_from("/zip").unwrap());
+//! let mut pattern = properties.for_keyword("pattern");
+//! pattern.set_error("\"1234\" does not match \"^[0-9]{5}$\"");
+//! properties.push(pattern);
+//! eval.push(properties);
+//!
+//! let report = Report::from(&eval);
+//! assert!(!report.is_valid());
+//! assert_eq!(report.groups().len(), 1);
+//! assert_eq!(
+//!     report.instance_location(report.groups()[0].instance_location),
+//!     "/zip"
+//! );
+//! assert_eq!(report.error_count(), 1);
+//! ```
+
+use crate::{
+    evaluation::ErrorDetail,
+    keyword::META_DATA_KEYWORDS,
+    output::{Basic, Detailed, Flag, Verbose},
+    pointer, Evaluation,
+};
+use jsonptr::Pointer;
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
+use uniresid::AbsoluteUri;
+
+/// An error of an evaluation, handed to a [`Translator`] to be rendered.
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a> {
+    /// The keyword which produced the error, i.e. the last reference token
+    /// of its keyword location.
+    pub keyword: &'a str,
+    /// The kind of the error, if the keyword set one with
+    /// [`Evaluation::set_error_kind`].
+    pub kind: Option<&'static str>,
+    /// The parameters of the error, e.g. the limit of `"maximum"`, set with
+    /// [`Evaluation::set_error_param`].
+    pub params: &'a Map<String, Value>,
+    /// The error as produced by the keyword. Errors merged with
+    /// [`Evaluation::merge`] are translated one at a time.
+    pub error: &'a str,
+    /// The relative location of the keyword.
+    pub keyword_location: &'a Pointer,
+    /// The location within the instance.
+    pub instance_location: &'a Pointer,
+    /// The annotations and other data of the evaluation of the keyword,
+    /// e.g. the indexes of the members matched by `"oneOf"`.
+    pub data: &'a Map<String, Value>,
+}
+
+/// Renders the errors of a [`Report`], e.g. in the language of a user.
+///
+/// Implemented for closures of the form
+/// `Fn(&Message) -> Option<String>`.
+pub trait Translator {
+    /// Returns the translation of `message`, or `None` to keep its error as
+    /// produced by the keyword.
+    fn translate(&self, message: &Message) -> Option<String>;
+}
+
+impl<F> Translator for F
+where
+    F: Fn(&Message) -> Option<String>,
+{
+    fn translate(&self, message: &Message) -> Option<String> {
+        self(message)
+    }
+}
+
+/// A location within an instance or schema, interned by the [`Locations`]
+/// of a [`Report`].
+///
+/// A `Location` is an index into the table of keyword locations or of
+/// instance locations of the `Report` it belongs to; two `Location`s of the
+/// same table are equal if their JSON Pointers are. Render it with
+/// [`Report::keyword_location`] or [`Report::instance_location`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location(u32);
+
+impl Location {
+    /// Returns the index of the `Location` within its [`Locations`] table,
+    /// which is its index within the serialized table.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A table of interned [`Location`]s.
+///
+/// Each location is stored as the indexes of its reference tokens, which
+/// are interned as well, so that locations sharing a prefix share its
+/// tokens. Each token and location is allocated once, and shared by the
+/// table and its index. Locations are rendered as JSON Pointers on demand.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Locations {
+    tokens: Vec<Arc<str>>,
+    token_indexes: HashMap<Arc<str>, u32>,
+    locations: Vec<Arc<[u32]>>,
+    location_indexes: HashMap<Arc<[u32]>, Location>,
+}
+
+impl Locations {
+    /// Creates and returns a new, empty table of `Locations`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns `pointer`, returning its [`Location`].
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::report::Locations;
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut locations = Locations::new();
+    /// let a = locations.intern(&Pointer::try_from("/items/0").unwrap());
+    /// let b = locations.intern(&Pointer::try_from("/items/0").unwrap());
+    /// assert_eq!(a, b);
+    /// assert_eq!(locations.render(a), "/items/0");
+    /// assert_eq!(locations.len(), 1);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` locations or reference tokens are
+    /// interned.
+    pub fn intern(&mut self, pointer: &Pointer) -> Location {
+        let path: Vec<u32> = pointer
+            .as_str()
+            .split('/')
+            .skip(1)
+            .map(|token| self.intern_token(token))
+            .collect();
+        if let Some(location) = self.location_indexes.get(path.as_slice()) {
+            return *location;
+        }
+        let path: Arc<[u32]> = path.into();
+        let location = Location(to_index(self.locations.len()));
+        self.locations.push(path.clone());
+        self.location_indexes.insert(path, location);
+        location
+    }
+
+    fn intern_token(&mut self, token: &str) -> u32 {
+        if let Some(index) = self.token_indexes.get(token) {
+            return *index;
+        }
+        let index = to_index(self.tokens.len());
+        let token: Arc<str> = token.into();
+        self.tokens.push(token.clone());
+        self.token_indexes.insert(token, index);
+        index
+    }
+
+    /// Renders `location` as a JSON Pointer.
+    ///
+    /// # Panics
+    /// Panics if `location` was not interned by this table.
+    pub fn render(&self, location: Location) -> String {
+        let mut rendered = String::new();
+        for index in self.locations[location.0 as usize].iter() {
+            rendered.push('/');
+            rendered.push_str(&self.tokens[*index as usize]);
+        }
+        rendered
+    }
+
+    /// Returns the [`Location`] of `pointer`, if it has been interned.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut properties = eval.for_keyword("properties");
+    /// properties.set_instance_location(Pointer::try_from("/name").unwrap());
+    /// properties.push(properties.for_keyword("type"));
+    /// eval.push(properties);
+    ///
+    /// let report = Report::from(&eval).with_all_locations();
+    /// let instance_locations = report.instance_locations();
+    /// let name = instance_locations.get(&Pointer::try_from("/name").unwrap()).unwrap();
+    /// assert_eq!(
+    ///     serde_json::to_value(instance_locations).unwrap()[name.index()],
+    ///     "/name"
+    /// );
+    /// let keyword_locations = report.keyword_locations();
+    /// assert!(keyword_locations.get(&Pointer::try_from("/properties/type").unwrap()).is_some());
+    /// assert!(instance_locations.get(&Pointer::try_from("/properties/type").unwrap()).is_none());
+    /// ```
+    pub fn get(&self, pointer: &Pointer) -> Option<Location> {
+        let path = pointer
+            .as_str()
+            .split('/')
+            .skip(1)
+            .map(|token| self.token_indexes.get(token).copied())
+            .collect::<Option<Vec<u32>>>()?;
+        self.location_indexes.get(path.as_slice()).copied()
+    }
+
+    /// Returns the rest of the JSON Pointer `pointer` after `location`, if
+    /// `pointer` is `location` or is nested within it, without rendering
+    /// `location`.
+    fn strip_prefix<'p>(&self, location: Location, pointer: &'p str) -> Option<&'p str> {
+        let mut rest = pointer;
+        for index in self.locations[location.index()].iter() {
+            rest = rest.strip_prefix('/')?;
+            rest = rest.strip_prefix(&*self.tokens[*index as usize])?;
+        }
+        if rest.is_empty() || rest.starts_with('/') {
+            Some(rest)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over each interned [`Location`] along with its
+    /// JSON Pointer, in the order they were interned.
+    pub fn iter(&self) -> impl Iterator<Item = (Location, String)> + '_ {
+        (0..self.locations.len()).map(|index| {
+            let location = Location(to_index(index));
+            (location, self.render(location))
+        })
+    }
+
+    /// Returns the number of interned locations.
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns `true` if no location has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+/// Converts the length of a table of [`Locations`] to the index of its
+/// next entry.
+///
+/// # Panics
+/// Panics if `len` exceeds `u32::MAX`, rather than wrapping around to an
+/// entry already in the table.
+fn to_index(len: usize) -> u32 {
+    match crate::number::usize_to_u32(len, "table of locations") {
+        Ok(index) => index,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+impl Serialize for Locations {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|(_, pointer)| pointer))
+    }
+}
+
+/// The annotations of an instance location, collected by
+/// [`Report::annotations_at`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Annotations {
+    /// The first `"title"`.
+    pub title: Option<String>,
+    /// The first `"description"`.
+    pub description: Option<String>,
+    /// The first `"default"`.
+    pub default: Option<Value>,
+    /// Whether any schema marks the location `"deprecated"`.
+    pub deprecated: bool,
+    /// Whether any schema marks the location `"readOnly"`.
+    pub read_only: bool,
+    /// Whether any schema marks the location `"writeOnly"`.
+    pub write_only: bool,
+    /// The `"examples"` of each schema, in order.
+    pub examples: Vec<Value>,
+    /// The annotations of other keywords, e.g. custom keywords, keyed by
+    /// keyword: the `"annotation"` of each evaluation of a keyword which has
+    /// one, in the order its schema was evaluated. Other data of an
+    /// evaluation is not an annotation and is not collected.
+    pub other: Map<String, Value>,
+}
+
+impl Annotations {
+    fn add(&mut self, keyword: &str, data: &Map<String, Value>) {
+        let annotation = data.get("annotation");
+        match (keyword, annotation) {
+            ("title", Some(Value::String(title))) => {
+                self.title.get_or_insert_with(|| title.clone());
+            }
+            ("description", Some(Value::String(description))) => {
+                self.description.get_or_insert_with(|| description.clone());
+            }
+            ("default", Some(default)) => {
+                self.default.get_or_insert_with(|| default.clone());
+            }
+            ("deprecated", Some(Value::Bool(b))) => self.deprecated |= b,
+            ("readOnly", Some(Value::Bool(b))) => self.read_only |= b,
+            ("writeOnly", Some(Value::Bool(b))) => self.write_only |= b,
+            ("examples", Some(Value::Array(examples))) => {
+                self.examples.extend(examples.iter().cloned())
+            }
+            (keyword, Some(annotation)) => {
+                let value = annotation.clone();
+                match self.other.get_mut(keyword) {
+                    Some(Value::Array(values)) => values.push(value),
+                    _ => {
+                        self.other
+                            .insert(keyword.to_string(), Value::Array(vec![value]));
+                    }
+                }
+            }
+            // the data of keywords which do not annotate, e.g. the matched
+            // indexes of "oneOf", is not an annotation
+            (_, None) => {}
+        }
+    }
+}
+
+/// A single error of a [`Report`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The relative location of the keyword which produced the error.
+    pub keyword_location: Location,
+    /// The absolute location of the keyword, if known.
+    pub absolute_keyword_location: Option<AbsoluteUri>,
+    /// The error.
+    pub error: String,
+    /// The [code](crate::error::validation_code) of the error.
+    pub code: &'static str,
+}
+
+/// The errors of a [`Report`] which occurred at a single instance location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// The location within the instance.
+    pub instance_location: Location,
+    /// The errors, in the order they were produced.
+    pub errors: Vec<Entry>,
+}
+
+/// The errors of an [`Evaluation`], grouped by instance location. See the
+/// [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    tree: Verbose,
+    groups: Vec<Group>,
+    keyword_locations: Locations,
+    instance_locations: Locations,
+    /// Fragments of the instance, none of which is nested within another,
+    /// retained by [`Report::with_instance`].
+    fragments: Vec<(Location, Value)>,
+}
+
+impl Report {
+    /// Returns `true` if the instance is valid.
+    pub fn is_valid(&self) -> bool {
+        self.tree.unit.valid
+    }
+
+    /// Returns the errors, grouped by instance location and ordered by the
+    /// first error of each location.
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// Returns the JSON Pointer of the keyword location `location`.
+    ///
+    /// # Panics
+    /// Panics if `location` is not a keyword location of this `Report`.
+    pub fn keyword_location(&self, location: Location) -> String {
+        self.keyword_locations.render(location)
+    }
+
+    /// Returns the JSON Pointer of the instance location `location`.
+    ///
+    /// # Panics
+    /// Panics if `location` is not an instance location of this `Report`.
+    pub fn instance_location(&self, location: Location) -> String {
+        self.instance_locations.render(location)
+    }
+
+    /// Returns the table of the keyword [`Location`]s of this `Report`: the
+    /// keyword location of each error, or of each node of the evaluation
+    /// once [`with_all_locations`](Self::with_all_locations) is called.
+    pub fn keyword_locations(&self) -> &Locations {
+        &self.keyword_locations
+    }
+
+    /// Returns the table of the instance [`Location`]s of this `Report`: the
+    /// instance location of each error and of each fragment of the instance
+    /// retained by [`with_instance`](Self::with_instance), or of each node of
+    /// the evaluation once [`with_all_locations`](Self::with_all_locations)
+    /// is called.
+    pub fn instance_locations(&self) -> &Locations {
+        &self.instance_locations
+    }
+
+    /// Consumes the `Report`, returning it with every keyword and instance
+    /// location of the evaluation interned, in pre-order, for export to
+    /// external systems, e.g. tracing. Locations already interned keep their
+    /// index.
+    pub fn with_all_locations(mut self) -> Report {
+        intern_all(
+            &self.tree,
+            &mut self.keyword_locations,
+            &mut self.instance_locations,
+        );
+        self
+    }
+
+    /// Returns the [`Annotations`] of `instance_location`, collected from the
+    /// evaluation. As the specification prescribes, annotations of schemas
+    /// which failed are dropped.
+    ///
+    /// Meta-data keywords annotate an instance when evaluated by the
+    /// dialect of each draft, or by
+    /// [`meta_data`](crate::keyword::meta_data) and
+    /// [`meta_data_vocabulary`](crate::keyword::meta_data_vocabulary).
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let name = Pointer::try_from("/name").unwrap();
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut properties = eval.for_keyword("properties");
+    /// properties.set_instance_location(name.clone());
+    /// let mut title = properties.for_keyword("title");
+    /// title.insert("annotation".to_string(), "Name").unwrap();
+    /// let mut read_only = properties.for_keyword("readOnly");
+    /// read_only.insert("annotation".to_string(), true).unwrap();
+    /// let mut widget = properties.for_keyword("x-widget");
+    /// widget.insert("annotation".to_string(), "text").unwrap();
+    /// properties.append([title, read_only, widget]);
+    /// eval.push(properties);
+    ///
+    /// let annotations = Report::from(&eval).annotations_at(&name);
+    /// assert_eq!(annotations.title.as_deref(), Some("Name"));
+    /// assert!(annotations.read_only);
+    /// assert_eq!(annotations.other["x-widget"], serde_json::json!(["text"]));
+    /// ```
+    pub fn annotations_at(&self, instance_location: &Pointer) -> Annotations {
+        let mut annotations = Annotations::default();
+        collect_annotations(&self.tree, instance_location, &mut annotations);
+        annotations
+    }
+
+    /// Returns the number of errors.
+    pub fn error_count(&self) -> usize {
+        self.groups.iter().map(|g| g.errors.len()).sum()
+    }
+
+    /// Returns the full [`Verbose`] output the `Report` was created from.
+    pub fn tree(&self) -> &Verbose {
+        &self.tree
+    }
+
+    /// Consumes the `Report`, returning it with each error rendered by
+    /// `translator`. The errors of the output formats the `Report` converts
+    /// to are translated as well.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::{Message, Report}, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut min = eval.for_keyword("minLength");
+    /// min.set_error_kind("limit");
+    /// min.set_error_param("limit", 3);
+    /// min.set_error("\"ab\" is shorter than 3 characters");
+    /// eval.push(min);
+    ///
+    /// let french = |message: &Message| match (message.keyword, message.kind) {
+    ///     ("minLength", Some("limit")) => Some(format!(
+    ///         "la chaîne est plus courte que {} caractères",
+    ///         message.params["limit"]
+    ///     )),
+    ///     _ => None,
+    /// };
+    /// let report = Report::from(&eval).translated(&french);
+    /// let translation = "la chaîne est plus courte que 3 caractères";
+    /// assert_eq!(report.groups()[0].errors[0].error, translation);
+    /// assert_eq!(report.into_basic().units[0].error.as_deref(), Some(translation));
+    /// ```
+    pub fn translated(mut self, translator: &dyn Translator) -> Report {
+        translate(&mut self.tree, translator);
+        // the tree is unchanged but for its errors, so its locations are
+        // already interned
+        self.groups = group(
+            &self.tree,
+            &mut self.keyword_locations,
+            &mut self.instance_locations,
+        );
+        self
+    }
+
+    /// Consumes the `Report`, returning it with the fragments of `instance`
+    /// at each location which has an error, and of each value an annotation
+    /// refers to, e.g. the properties matched by `"patternProperties"`, so
+    /// that they can be retrieved with [`instance_at`](Self::instance_at)
+    /// once `instance` is dropped. Meta-data annotations, e.g. `"title"`, do
+    /// not refer to the instance.
+    ///
+    /// Only the outermost of the locations are retained, as the others are
+    /// nested within them. A borrowed `instance` is cloned fragment by
+    /// fragment; an owned `instance` is moved into the `Report` rather than
+    /// cloned if its root is one of the locations.
+    ///
+    /// See [`Schema::evaluate_owned`](crate::Schema::evaluate_owned).
+    pub fn with_instance(mut self, instance: Cow<'_, Value>) -> Report {
+        let mut referenced = Vec::new();
+        for group in &self.groups {
+            referenced.push(self.instance_locations.render(group.instance_location));
+        }
+        collect_annotated(&self.tree, &mut referenced);
+        referenced.sort();
+        referenced.dedup();
+        let mut outermost: Vec<String> = Vec::new();
+        for location in referenced {
+            if !outermost.iter().any(|o| contains(o, &location)) {
+                outermost.push(location);
+            }
+        }
+        if outermost.iter().any(String::is_empty) {
+            let root = self.instance_locations.intern(&Pointer::default());
+            self.fragments = vec![(root, instance.into_owned())];
+            return self;
+        }
+        self.fragments = outermost
+            .iter()
+            .filter_map(|location| {
+                let fragment = instance.pointer(location)?.clone();
+                let pointer = Pointer::try_from(location.as_str()).ok()?;
+                Some((self.instance_locations.intern(&pointer), fragment))
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the value of the instance at `instance_location`, if it was
+    /// retained by [`with_instance`](Self::with_instance) because the
+    /// location, or one enclosing it, has an error or is referred to by an
+    /// annotation.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    /// use serde_json::json;
+    /// use std::borrow::Cow;
+    ///
+    /// let zip = Pointer::try_from("/address/zip").unwrap();
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut properties = eval.for_keyword("properties");
+    /// properties.set_instance_location(zip.clone());
+    /// let mut pattern = properties.for_keyword("pattern");
+    /// pattern.set_error("\"1234\" does not match \"^[0-9]{5}$\"");
+    /// properties.push(pattern);
+    /// eval.push(properties);
+    ///
+    /// let instance = json!({ "name": "ann", "address": { "zip": "1234" } });
+    /// let report = Report::from(&eval).with_instance(Cow::Borrowed(&instance));
+    /// drop(instance);
+    /// assert_eq!(report.instance_at(&zip), Some(&json!("1234")));
+    /// assert_eq!(report.instance_at(&Pointer::try_from("/name").unwrap()), None);
+    /// ```
+    pub fn instance_at(&self, instance_location: &Pointer) -> Option<&Value> {
+        let target = instance_location.as_str();
+        self.fragments.iter().find_map(|(location, fragment)| {
+            let rest = self.instance_locations.strip_prefix(*location, target)?;
+            fragment.pointer(rest)
+        })
+    }
+
+    /// Consumes the `Report`, returning the [`Verbose`] output it was created
+    /// from.
+    pub fn into_verbose(self) -> Verbose {
+        self.tree
+    }
+
+    /// Consumes the `Report`, returning its [`Detailed`] output.
+    pub fn into_detailed(self) -> Detailed {
+        Detailed::from(self.tree)
+    }
+
+    /// Consumes the `Report`, returning its [`Basic`] output, which
+    /// serializes as defined by the specification.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    /// use serde_json::json;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut min = eval.for_keyword("minItems");
+    /// min.set_error("Expected at least 3 items but found 2");
+    /// eval.push(min);
+    ///
+    /// let basic = Report::from(&eval).into_basic();
+    /// assert_eq!(
+    ///     serde_json::to_value(&basic).unwrap(),
+    ///     json!({
+    ///         "valid": false,
+    ///         "errors": [{
+    ///             "keywordLocation": "/minItems",
+    ///             "instanceLocation": "",
+    ///             "error": "Expected at least 3 items but found 2"
+    ///         }]
+    ///     })
+    /// );
+    /// ```
+    pub fn into_basic(self) -> Basic {
+        Basic::from(self.into_detailed())
+    }
+
+    /// Consumes the `Report`, returning its [`Flag`] output.
+    pub fn into_flag(self) -> Flag {
+        Flag {
+            valid: self.is_valid(),
+        }
+    }
+}
+
+impl From<&Evaluation> for Report {
+    fn from(eval: &Evaluation) -> Self {
+        Report::from(Verbose::from(eval))
+    }
+}
+
+impl From<Verbose> for Report {
+    fn from(tree: Verbose) -> Self {
+        let mut keyword_locations = Locations::new();
+        let mut instance_locations = Locations::new();
+        let groups = group(&tree, &mut keyword_locations, &mut instance_locations);
+        Self {
+            tree,
+            groups,
+            keyword_locations,
+            instance_locations,
+            fragments: Vec::new(),
+        }
+    }
+}
+
+/// Returns the errors of `tree` grouped by instance location, interning
+/// their locations.
+fn group(
+    tree: &Verbose,
+    keyword_locations: &mut Locations,
+    instance_locations: &mut Locations,
+) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    if tree.unit.valid {
+        return groups;
+    }
+    // the index within `groups` of the group of each instance location
+    let mut group_indexes: HashMap<Location, usize> = HashMap::new();
+    let basic = Basic::from(Detailed::from(invalid_nodes(tree)));
+    for unit in basic.units {
+        let instance_location = instance_locations.intern(&unit.instance_location);
+        let entry = Entry {
+            keyword_location: keyword_locations.intern(&unit.keyword_location),
+            absolute_keyword_location: unit.absolute_keyword_location,
+            error: unit.error.unwrap_or_default(),
+            code: unit.error_code.unwrap_or("GRILL_V0001"),
+        };
+        match group_indexes.get(&instance_location) {
+            Some(index) => groups[*index].errors.push(entry),
+            None => {
+                group_indexes.insert(instance_location, groups.len());
+                groups.push(Group {
+                    instance_location,
+                    errors: vec![entry],
+                });
+            }
+        }
+    }
+    groups
+}
+
+impl Report {
+    /// Renders the `Report` as a Markdown document, with a section for each
+    /// instance location and the annotations of the evaluation as a
+    /// collapsible tree.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut min = eval.for_keyword("minimum");
+    /// min.set_error("3 is less than 5");
+    /// eval.push(min);
+    ///
+    /// let markdown = Report::from(&eval).to_markdown();
+    /// assert!(markdown.starts_with("# Invalid\n"));
+    /// assert!(markdown.contains("## `(root)`\n\n- 3 is less than 5 (`/minimum`)\n"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        if self.is_valid() {
+            out.push_str("# Valid\n");
+        } else {
+            out.push_str("# Invalid\n\n");
+            out.push_str(&self.summary());
+            out.push('\n');
+        }
+        for group in &self.groups {
+            let instance_location = self.instance_location(group.instance_location);
+            out.push_str(&format!(
+                "\n## {}\n\n",
+                markdown_code(display_location(&instance_location))
+            ));
+            for entry in &group.errors {
+                let keyword_location = self.keyword_location(entry.keyword_location);
+                let keyword_location = markdown_code(display_location(&keyword_location));
+                match &entry.absolute_keyword_location {
+                    Some(uri) => out.push_str(&format!(
+                        "- {} ([{}]({}))\n",
+                        markdown_escape(&entry.error),
+                        keyword_location,
+                        markdown_url(&uri.to_string())
+                    )),
+                    None => out.push_str(&format!(
+                        "- {} ({})\n",
+                        markdown_escape(&entry.error),
+                        keyword_location
+                    )),
+                }
+            }
+        }
+        if has_annotations(&self.tree) {
+            out.push_str("\n## Annotations\n\n");
+            out.push_str(&annotation_tree(&self.tree));
+        }
+        out
+    }
+
+    /// Renders the `Report` as a standalone HTML document. Each instance
+    /// location is a section which can be linked to by its `id`, each error
+    /// links to the absolute location of its keyword when known, and the
+    /// annotations of the evaluation are rendered as a collapsible tree.
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Evaluation, OutputFmt};
+    /// use jsonptr::Pointer;
+    ///
+    /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    /// let mut min = eval.for_keyword("minimum");
+    /// min.set_error("3 is less than 5");
+    /// eval.push(min);
+    ///
+    /// let html = Report::from(&eval).to_html();
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// assert!(html.contains("<section id=\"instance-\">"));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let title = if self.is_valid() { "Valid" } else { "Invalid" };
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{}</title>\n", title));
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!("<h1>{}</h1>\n", title));
+        if !self.is_valid() {
+            out.push_str(&format!("<p>{}</p>\n", html_escape(&self.summary())));
+        }
+        for group in &self.groups {
+            let instance_location = self.instance_location(group.instance_location);
+            let location = display_location(&instance_location);
+            let id = format!("instance-{}", html_escape(&instance_location));
+            out.push_str(&format!("<section id=\"{}\">\n", id));
+            out.push_str(&format!(
+                "<h2><a href=\"#{}\"><code>{}</code></a></h2>\n<ul>\n",
+                id,
+                html_escape(location)
+            ));
+            for entry in &group.errors {
+                let keyword_location = self.keyword_location(entry.keyword_location);
+                let keyword_location = html_escape(display_location(&keyword_location));
+                let keyword_location = match &entry.absolute_keyword_location {
+                    Some(uri) => format!(
+                        "<a href=\"{}\"><code>{}</code></a>",
+                        html_escape(&uri.to_string()),
+                        keyword_location
+                    ),
+                    None => format!("<code>{}</code>", keyword_location),
+                };
+                out.push_str(&format!(
+                    "<li>{} ({})</li>\n",
+                    html_escape(&entry.error),
+                    keyword_location
+                ));
+            }
+            out.push_str("</ul>\n</section>\n");
+        }
+        if has_annotations(&self.tree) {
+            out.push_str("<h2>Annotations</h2>\n");
+            out.push_str(&annotation_tree(&self.tree));
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn summary(&self) -> String {
+        let count = self.error_count();
+        format!(
+            "{} error{} at {} location{}.",
+            count,
+            if count == 1 { "" } else { "s" },
+            self.groups.len(),
+            if self.groups.len() == 1 { "" } else { "s" },
+        )
+    }
+}
+
+/// Interns the keyword and instance locations of `node` and each of its
+/// nested nodes, in pre-order.
+fn intern_all(
+    node: &Verbose,
+    keyword_locations: &mut Locations,
+    instance_locations: &mut Locations,
+) {
+    keyword_locations.intern(&node.unit.keyword_location);
+    instance_locations.intern(&node.unit.instance_location);
+    for nested in &node.nested {
+        intern_all(nested, keyword_locations, instance_locations);
+    }
+}
+
+/// Returns a copy of `node`, which is invalid, and of its invalid nested
+/// nodes, which are all its [`Detailed`] output retains, so that the valid
+/// nodes of the tree need not be cloned.
+fn invalid_nodes(node: &Verbose) -> Verbose {
+    Verbose {
+        unit: node.unit.clone(),
+        nested: node
+            .nested
+            .iter()
+            .filter(|nested| !nested.unit.valid)
+            .map(invalid_nodes)
+            .collect(),
+    }
+}
+
+/// Adds the annotations at `instance_location` of `node` and its nested
+/// nodes, skipping those which are invalid, to `annotations`.
+fn collect_annotations(node: &Verbose, instance_location: &Pointer, annotations: &mut Annotations) {
+    if !node.unit.valid {
+        return;
+    }
+    if !node.unit.data.is_empty() && node.unit.instance_location == *instance_location {
+        let keyword = node.unit.keyword_location.as_str().rsplit('/').next();
+        annotations.add(keyword.unwrap_or_default(), &node.unit.data);
+    }
+    for nested in &node.nested {
+        collect_annotations(nested, instance_location, annotations);
+    }
+}
+
+/// Pushes the location of each value of the instance which `node` or one of
+/// its nested nodes, if valid, annotates onto `locations`: each property
+/// matched by `"patternProperties"`, and the instance location of other
+/// annotations. Meta-data annotations, e.g. `"title"`, describe the schema
+/// rather than the value, and other data, e.g. the indexes of the members
+/// matched by `"oneOf"`, is not an annotation, so neither is collected.
+fn collect_annotated(node: &Verbose, locations: &mut Vec<String>) {
+    if !node.unit.valid {
+        return;
+    }
+    let unit = &node.unit;
+    let keyword = unit.keyword_location.as_str().rsplit('/').next();
+    match (keyword.unwrap_or_default(), unit.data.get("matched")) {
+        ("patternProperties", Some(Value::Array(matched))) => {
+            for name in matched.iter().filter_map(Value::as_str) {
+                let location = pointer::join(&unit.instance_location, [name]);
+                locations.push(location.as_str().to_string());
+            }
+        }
+        (keyword, _) if META_DATA_KEYWORDS.contains(&keyword) => {}
+        _ if unit.data.contains_key("annotation") => {
+            locations.push(unit.instance_location.as_str().to_string());
+        }
+        _ => {}
+    }
+    for nested in &node.nested {
+        collect_annotated(nested, locations);
+    }
+}
+
+/// Returns `true` if the JSON Pointer `inner` is `outer` or is nested
+/// within it.
+fn contains(outer: &str, inner: &str) -> bool {
+    inner
+        .strip_prefix(outer)
+        .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Replaces each error merged into the error of each node of `node` with
+/// its translation by `translator`, if any.
+fn translate(node: &mut Verbose, translator: &dyn Translator) {
+    let unit = &node.unit;
+    let keyword = unit.keyword_location.as_str().rsplit('/').next();
+    let parts = unit.error_parts();
+    let translations: Vec<Option<String>> = parts
+        .iter()
+        .map(|part| {
+            translator.translate(&Message {
+                keyword: keyword.unwrap_or_default(),
+                kind: part.kind,
+                params: part.params,
+                error: part.error,
+                keyword_location: &unit.keyword_location,
+                instance_location: &unit.instance_location,
+                data: &unit.data,
+            })
+        })
+        .collect();
+    if translations.iter().any(Option::is_some) {
+        let errors = parts
+            .iter()
+            .zip(&translations)
+            .map(|(part, translation)| translation.as_deref().unwrap_or(part.error));
+        let (error, details) = ErrorDetail::join(errors, &unit.error_details);
+        node.unit.error = Some(error);
+        node.unit.error_details = details;
+    }
+    for nested in &mut node.nested {
+        translate(nested, translator);
+    }
+}
+
+fn display_location(location: &str) -> &str {
+    match location {
+        "" => "(root)",
+        location => location,
+    }
+}
+
+fn has_annotations(node: &Verbose) -> bool {
+    !node.unit.data.is_empty() || node.nested.iter().any(has_annotations)
+}
+
+/// Renders the nodes of `node` which have annotations, or which contain
+/// such nodes, as nested HTML `<details>` elements, which Markdown renderers
+/// commonly support as well.
+fn annotation_tree(node: &Verbose) -> String {
+    let mut out = format!(
+        "<details>\n<summary><code>{}</code> at <code>{}</code></summary>\n",
+        html_escape(display_location(node.unit.keyword_location.as_str())),
+        html_escape(display_location(node.unit.instance_location.as_str()))
+    );
+    if !node.unit.data.is_empty() {
+        let data = serde_json::to_string_pretty(&node.unit.data).unwrap_or_default();
+        out.push_str(&format!("<pre>{}</pre>\n", html_escape(&data)));
+    }
+    for nested in node.nested.iter().filter(|n| has_annotations(n)) {
+        out.push_str(&annotation_tree(nested));
+    }
+    out.push_str("</details>\n");
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes the characters of `text` which Markdown would otherwise interpret,
+/// and replaces line breaks with `<br>` so that `text` remains a single block.
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' | '\n' => escaped.push_str("<br>"),
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '&' | '!' | '~' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns `text` as a Markdown code span, delimited by a run of backticks
+/// longer than any within `text`. Line breaks are replaced with spaces.
+fn markdown_code(text: &str) -> String {
+    let text = text.replace(['\r', '\n'], " ");
+    let mut longest = 0;
+    let mut run = 0;
+    for c in text.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest + 1);
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{0} {1} {0}", fence, text)
+    } else {
+        format!("{0}{1}{0}", fence, text)
+    }
+}
+
+/// Percent-encodes the characters of `url` which would end a Markdown link
+/// destination.
+fn markdown_url(url: &str) -> String {
+    let mut encoded = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            ' ' => encoded.push_str("%20"),
+            '(' => encoded.push_str("%28"),
+            ')' => encoded.push_str("%29"),
+            '<' => encoded.push_str("%3C"),
+            '>' => encoded.push_str("%3E"),
+            c if c.is_control() => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    encoded.push_str(&format!("%{:02X}", b));
+                }
+            }
+            c => encoded.push(c),
+        }
+    }
+    encoded
+}
+
+#[cfg(feature = "cli")]
+pub use terminal::{TerminalOptions, DEFAULT_SNIPPET_WIDTH};
+
+#[cfg(feature = "cli")]
+mod terminal {
+    use super::Report;
+    use serde_json::Value;
+    use std::fmt::Write;
+
+    const RED: &str = "\x1b[1;31m";
+    const GREEN: &str = "\x1b[1;32m";
+    const CYAN: &str = "\x1b[36m";
+    const DIM: &str = "\x1b[2m";
+    const RESET: &str = "\x1b[0m";
+
+    /// The default maximum width of a schema snippet.
+    pub const DEFAULT_SNIPPET_WIDTH: usize = 80;
+
+    /// Options of [`Report::render_terminal`].
+    #[derive(Debug, Clone)]
+    pub struct TerminalOptions {
+        color: bool,
+        schema: Option<Value>,
+        snippet_width: usize,
+    }
+
+    impl Default for TerminalOptions {
+        fn default() -> Self {
+            Self {
+                color: false,
+                schema: None,
+                snippet_width: DEFAULT_SNIPPET_WIDTH,
+            }
+        }
+    }
+
+    impl TerminalOptions {
+        /// Creates and returns a new `TerminalOptions` which renders without
+        /// color or schema snippets.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Sets whether ANSI escape codes are used to colorize the output.
+        #[must_use]
+        pub fn color(mut self, color: bool) -> Self {
+            self.color = color;
+            self
+        }
+
+        /// Sets the source of the schema evaluated, from which a snippet of
+        /// the keyword is rendered with each error.
+        #[must_use]
+        pub fn schema(mut self, source: Value) -> Self {
+            self.schema = Some(source);
+            self
+        }
+
+        /// Sets the maximum width of a schema snippet, beyond which it is
+        /// truncated. Defaults to [`DEFAULT_SNIPPET_WIDTH`].
+        #[must_use]
+        pub fn snippet_width(mut self, width: usize) -> Self {
+            self.snippet_width = width;
+            self
+        }
+
+        /// Returns whether the output is colorized.
+        pub fn is_colored(&self) -> bool {
+            self.color
+        }
+
+        fn paint(&self, style: &str, text: &str) -> String {
+            if self.color {
+                format!("{}{}{}", style, text, RESET)
+            } else {
+                text.to_string()
+            }
+        }
+
+        fn snippet(&self, keyword_location: &str) -> Option<String> {
+            let value = self.schema.as_ref()?.pointer(keyword_location)?;
+            let keyword = keyword_location.rsplit('/').next()?;
+            let keyword = crate::pointer::unescape(keyword).ok()?;
+            let mut snippet = format!("\"{}\": {}", keyword, value);
+            if snippet.chars().count() > self.snippet_width {
+                snippet = snippet
+                    .chars()
+                    .take(self.snippet_width.saturating_sub(1))
+                    .collect();
+                snippet.push('…');
+            }
+            Some(snippet)
+        }
+    }
+
+    impl Report {
+        /// Renders the `Report` for a terminal, listing the errors of each
+        /// instance location beneath it.
+        ///
+        /// ## Example
+        /// ```
+        /// use grill::report::{Report, TerminalOptions};
+        /// use grill::{Evaluation, OutputFmt};
+        /// use jsonptr::Pointer;
+        /// use serde_json::json;
+        ///
+        /// let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+        /// let mut max = eval.for_keyword("maxLength");
+        /// max.set_error("\"abcd\" is longer than 3 characters");
+        /// eval.push(max);
+        ///
+        /// let options = TerminalOptions::new().schema(json!({ "maxLength": 3 }));
+        /// let rendered = Report::from(&eval).render_terminal(&options);
+        /// assert_eq!(
+        ///     rendered,
+        ///     "✗ invalid: 1 error at 1 location\n\n\
+        ///      (root)\n  \
+        ///        \"abcd\" is longer than 3 characters\n    \
+        ///          at /maxLength\n    \
+        ///          | \"maxLength\": 3\n"
+        /// );
+        /// ```
+        pub fn render_terminal(&self, options: &TerminalOptions) -> String {
+            let mut out = String::new();
+            if self.is_valid() {
+                let _ = writeln!(out, "{}", options.paint(GREEN, "✓ valid"));
+                return out;
+            }
+            let count = self.error_count();
+            let _ = writeln!(
+                out,
+                "{}: {} error{} at {} location{}",
+                options.paint(RED, "✗ invalid"),
+                count,
+                if count == 1 { "" } else { "s" },
+                self.groups.len(),
+                if self.groups.len() == 1 { "" } else { "s" },
+            );
+            for group in &self.groups {
+                let location = self.instance_location(group.instance_location);
+                let location = match location.as_str() {
+                    "" => "(root)",
+                    location => location,
+                };
+                let _ = writeln!(out, "\n{}", options.paint(CYAN, location));
+                for entry in &group.errors {
+                    let _ = writeln!(out, "  {}", entry.error);
+                    let keyword_location = self.keyword_location(entry.keyword_location);
+                    let keyword_location = keyword_location.as_str();
+                    let at = match &entry.absolute_keyword_location {
+                        Some(uri) => format!("at {} ({})", keyword_location, uri),
+                        None => format!("at {}", keyword_location),
+                    };
+                    let _ = writeln!(out, "    {}", options.paint(DIM, &at));
+                    if let Some(snippet) = options.snippet(keyword_location) {
+                        let _ =
+                            writeln!(out, "    {}", options.paint(DIM, &format!("| {}", snippet)));
+                    }
+                }
+            }
+            out
+        }
+    }
+}
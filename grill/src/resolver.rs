@@ -1,17 +1,109 @@
 use serde_json::Value;
+use std::sync::Arc;
 
 use self::error::Error;
+pub use self::error::Error as ResolveError;
 
-pub trait Resolver {
+mod file;
+pub(crate) use file::relative_path;
+pub use file::FileResolver;
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::{
+    HttpBodyTooLargeError, HttpRedirectError, HttpResolver, HttpStatusError, RetryPolicy,
+    DEFAULT_HTTP_TIMEOUT, DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_REDIRECTS,
+};
+
+/// Resolves the source documents of schemas which have not been added to an
+/// [`Interrogator`](crate::Interrogator).
+///
+/// `Resolver` is object safe and requires `Send + Sync` so that resolvers can
+/// be registered at runtime as a [`BoxedResolver`] and shared across threads.
+pub trait Resolver: Send + Sync {
     fn resolve(&self, id: String) -> Result<Value, Error>;
 }
 
+/// A type-erased [`Resolver`].
+pub type BoxedResolver = Box<dyn Resolver>;
+
+impl<R: Resolver + ?Sized> Resolver for Box<R> {
+    fn resolve(&self, id: String) -> Result<Value, Error> {
+        (**self).resolve(id)
+    }
+}
+
+impl<R: Resolver + ?Sized> Resolver for Arc<R> {
+    fn resolve(&self, id: String) -> Result<Value, Error> {
+        (**self).resolve(id)
+    }
+}
+
+/// A boxed future which is `Send`, as returned by [`AsyncResolver`].
+#[cfg(feature = "async")]
+pub type BoxFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
+
+/// Resolves the source documents of schemas asynchronously.
+///
+/// The returned future is boxed so that `AsyncResolver` is object safe and is
+/// `Send` so that it can be awaited on a multi-threaded runtime.
+#[cfg(feature = "async")]
+pub trait AsyncResolver: Send + Sync {
+    fn resolve(&self, id: String) -> BoxFuture<'_, Result<Value, Error>>;
+}
+
+/// A type-erased [`AsyncResolver`].
+#[cfg(feature = "async")]
+pub type BoxedAsyncResolver = Box<dyn AsyncResolver>;
+
+#[cfg(feature = "async")]
+impl<R: AsyncResolver + ?Sized> AsyncResolver for Box<R> {
+    fn resolve(&self, id: String) -> BoxFuture<'_, Result<Value, Error>> {
+        (**self).resolve(id)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<R: AsyncResolver + ?Sized> AsyncResolver for Arc<R> {
+    fn resolve(&self, id: String) -> BoxFuture<'_, Result<Value, Error>> {
+        (**self).resolve(id)
+    }
+}
+
 pub mod error {
-    use std::error::Error as StdError;
+    use std::{error::Error as StdError, fmt};
+
+    #[derive(Debug)]
     pub enum Error {
         /// The schema was not found
         NotFound(String),
         ///
         Internal(Box<dyn StdError + Send + Sync + 'static>),
     }
+
+    impl Error {
+        /// Returns `true` if the error is [`NotFound`](Error::NotFound).
+        pub fn is_not_found(&self) -> bool {
+            matches!(self, Error::NotFound(_))
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Error::NotFound(id) => write!(f, "schema not found: {}", id),
+                Error::Internal(err) => write!(f, "failed to resolve schema: {}", err),
+            }
+        }
+    }
+
+    impl StdError for Error {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            match self {
+                Error::NotFound(_) => None,
+                Error::Internal(err) => Some(err.as_ref()),
+            }
+        }
+    }
 }
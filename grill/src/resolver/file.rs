@@ -0,0 +1,151 @@
+use super::{Error, Resolver};
+use crate::deserializer::Deserializers;
+use serde_json::Value;
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+use uniresid::AbsoluteUri;
+use url::Url;
+
+/// A [`Resolver`] which reads documents from the filesystem.
+///
+/// `file://` URIs are read from the path they name. URIs beginning with a
+/// prefix added with [`map`](Self::map) are read from the mapped directory,
+/// joined with the remainder of the URI; where more than one prefix
+/// matches, the longest is used. The fragment of a URI is ignored.
+///
+/// URIs which match neither, and files which do not exist, fail with
+/// [`ResolveError::NotFound`](crate::ResolveError::NotFound). Remainders
+/// containing `..` segments are refused so that a mapping can not be used to
+/// read outside of its directory.
+///
+/// Each file is deserialized with the [`Deserializers`] of the
+/// `FileResolver`, chosen by the extension of the URI, so that errors are
+/// reported at their line and column and documents written in YAML or TOML
+/// are resolved as they are when added as sources.
+///
+/// ## Example
+/// ```
+/// use grill::{FileResolver, Resolver};
+/// use serde_json::json;
+///
+/// let dir = std::env::temp_dir().join("grill-file-resolver-example");
+/// std::fs::create_dir_all(dir.join("common")).unwrap();
+/// std::fs::write(dir.join("common/address.json"), r#"{ "type": "object" }"#).unwrap();
+///
+/// let resolver = FileResolver::new().map("https://example.com/schemas/", &dir);
+/// assert_eq!(
+///     resolver.resolve("https://example.com/schemas/common/address.json#/type".into()).unwrap(),
+///     json!({ "type": "object" })
+/// );
+/// assert!(resolver
+///     .resolve("https://example.com/schemas/../secret.json".into())
+///     .unwrap_err()
+///     .is_not_found());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileResolver {
+    file_scheme: bool,
+    mappings: Vec<(String, PathBuf)>,
+    deserializers: Deserializers,
+}
+
+impl Default for FileResolver {
+    fn default() -> Self {
+        Self {
+            file_scheme: true,
+            mappings: Vec::new(),
+            deserializers: Deserializers::standard(),
+        }
+    }
+}
+
+impl FileResolver {
+    /// Creates and returns a new `FileResolver` which resolves `file://`
+    /// URIs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps URIs beginning with `prefix` to files within `dir`.
+    #[must_use]
+    pub fn map(mut self, prefix: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        self.mappings.push((prefix.into(), dir.into()));
+        self
+    }
+
+    /// Sets whether `file://` URIs are resolved. Defaults to `true`.
+    #[must_use]
+    pub fn file_scheme(mut self, enabled: bool) -> Self {
+        self.file_scheme = enabled;
+        self
+    }
+
+    /// Sets the [`Deserializers`] with which files are deserialized, e.g.
+    /// those of an [`Interrogator`](crate::Interrogator), returned by
+    /// [`Interrogator::deserializers`](crate::Interrogator::deserializers).
+    /// Defaults to [`Deserializers::standard`].
+    #[must_use]
+    pub fn deserializers(mut self, deserializers: Deserializers) -> Self {
+        self.deserializers = deserializers;
+        self
+    }
+
+    /// Returns the prefix and directory of each mapping, in the order they
+    /// were added.
+    pub fn mappings(&self) -> &[(String, PathBuf)] {
+        &self.mappings
+    }
+
+    /// Returns the path of the file the document at `uri` is read from, if
+    /// `uri` is resolvable by this `FileResolver`.
+    pub fn path(&self, uri: &str) -> Option<PathBuf> {
+        let uri = uri.split('#').next().unwrap_or_default();
+        if let Some((prefix, dir)) = self
+            .mappings
+            .iter()
+            .filter(|(prefix, _)| uri.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+        {
+            return relative_path(&uri[prefix.len()..]).map(|rest| dir.join(rest));
+        }
+        if self.file_scheme && uri.starts_with("file:") {
+            return Url::parse(uri).ok()?.to_file_path().ok();
+        }
+        None
+    }
+}
+
+/// Returns the path, relative to a mapped directory, of the file the
+/// remainder `rest` of a URI is read from, or `None` if `rest` does not name
+/// a file within the directory, e.g. if it contains `..` or ends with `/`.
+pub(crate) fn relative_path(rest: &str) -> Option<&Path> {
+    let path = Path::new(rest);
+    let within = path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if rest.is_empty() || rest.ends_with('/') || !within {
+        return None;
+    }
+    Some(path)
+}
+
+impl Resolver for FileResolver {
+    fn resolve(&self, id: String) -> Result<Value, Error> {
+        let path = match self.path(&id) {
+            Some(path) => path,
+            None => return Err(Error::NotFound(id)),
+        };
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Err(Error::NotFound(id)),
+            Err(err) => return Err(Error::Internal(Box::new(err))),
+        };
+        let uri = AbsoluteUri::parse(id.split('#').next().unwrap_or_default())
+            .map_err(|err| Error::Internal(Box::new(err)))?;
+        self.deserializers
+            .deserialize(&uri, None, &bytes)
+            .map_err(|err| Error::Internal(Box::new(err)))
+    }
+}
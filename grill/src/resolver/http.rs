@@ -0,0 +1,487 @@
+use super::{AsyncResolver, BoxFuture, Error};
+use crate::deserializer::Deserializers;
+use dashmap::DashMap;
+use reqwest::{header, redirect, Client, Response, StatusCode};
+use serde_json::Value;
+use std::{error::Error as StdError, fmt, sync::Arc, time::Duration};
+use uniresid::AbsoluteUri;
+use url::Url;
+
+/// The default timeout of each request made by an [`HttpResolver`].
+pub const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default maximum size, in bytes, of a document fetched by an
+/// [`HttpResolver`].
+pub const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The default maximum number of redirects an [`HttpResolver`] follows for
+/// a single request.
+pub const DEFAULT_MAX_REDIRECTS: usize = 5;
+
+/// Determines how many times, and how long after, an [`HttpResolver`] retries
+/// a request which failed to connect, timed out, or was answered with a
+/// `429` or `5xx` status.
+///
+/// The delay before each retry is the previous delay multiplied by
+/// `multiplier`, starting at `initial_backoff` and capped at `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The number of retries after the first attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum delay between retries.
+    pub max_backoff: Duration,
+    /// The factor by which the delay grows after each retry.
+    pub multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// A `RetryPolicy` which does not retry.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        multiplier: 1.0,
+    };
+
+    /// Returns the delay before the retry numbered `retry`, starting at `0`.
+    ///
+    /// The delay is clamped to `max_backoff` before it is converted to a
+    /// [`Duration`], so a large `retry` or `multiplier` can not overflow.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = self
+            .multiplier
+            .max(1.0)
+            .powi(retry.min(i32::MAX as u32) as i32);
+        let secs = self.initial_backoff.as_secs_f64() * factor;
+        if !secs.is_finite() || secs >= self.max_backoff.as_secs_f64() {
+            return self.max_backoff;
+        }
+        Duration::from_secs_f64(secs)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retries twice, after 200ms and then 400ms, waiting no longer than 5s.
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// A response with an unexpected status received by an [`HttpResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpStatusError {
+    /// The URI requested.
+    pub uri: String,
+    /// The status of the response.
+    pub status: u16,
+}
+
+impl fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "request for \"{}\" failed with status {}",
+            self.uri, self.status
+        )
+    }
+}
+
+impl StdError for HttpStatusError {}
+
+/// A redirect which an [`HttpResolver`] refused to follow, either because
+/// its target is not allowed or because too many redirects were followed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRedirectError {
+    /// The URI requested.
+    pub uri: String,
+    /// The target of the redirect.
+    pub location: String,
+    /// Why the redirect was not followed.
+    pub reason: String,
+}
+
+impl fmt::Display for HttpRedirectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "request for \"{}\" redirected to \"{}\": {}",
+            self.uri, self.location, self.reason
+        )
+    }
+}
+
+impl StdError for HttpRedirectError {}
+
+/// A response whose body exceeded the maximum size of an [`HttpResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpBodyTooLargeError {
+    /// The URI requested.
+    pub uri: String,
+    /// The maximum size, in bytes.
+    pub limit: usize,
+}
+
+impl fmt::Display for HttpBodyTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "response for \"{}\" exceeds the maximum size of {} bytes",
+            self.uri, self.limit
+        )
+    }
+}
+
+impl StdError for HttpBodyTooLargeError {}
+
+/// A document previously fetched by an [`HttpResolver`] along with its
+/// `ETag`.
+#[derive(Debug, Clone)]
+struct Cached {
+    etag: String,
+    document: Value,
+}
+
+/// An [`AsyncResolver`] which fetches documents over HTTP(S).
+///
+/// Only URIs whose host has been allowed with
+/// [`allow_host`](Self::allow_host) are fetched; all others, as well as URIs
+/// of schemes other than `http` and `https`, fail with
+/// [`ResolveError::NotFound`](crate::ResolveError::NotFound) so that another
+/// resolver may attempt them. A host of the form `"*.example.com"` allows
+/// each subdomain of `example.com`. The fragment of a URI is ignored.
+///
+/// Redirects are followed by the `HttpResolver` itself, up to
+/// [`max_redirects`](Self::max_redirects), and only to URIs which it would
+/// fetch directly, so that an allowed host can not redirect a request to one
+/// which is not, e.g. an internal address. A response body larger than
+/// [`max_body_size`](Self::max_body_size) fails with an
+/// [`HttpBodyTooLargeError`] without being read in full.
+///
+/// Each request is bounded by [`timeout`](Self::timeout) and retried
+/// according to the [`RetryPolicy`]. Documents served with an `ETag` are
+/// cached and revalidated with `If-None-Match`; a `304 Not Modified`
+/// response returns the cached document. A `404` or `410` fails with
+/// [`ResolveError::NotFound`](crate::ResolveError::NotFound); any other
+/// unsuccessful status fails with an [`HttpStatusError`].
+///
+/// Requires the `"http"` feature.
+///
+/// ## Example
+/// ```no_run
+/// use grill::{AsyncResolver, HttpResolver, RetryPolicy};
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let resolver = HttpResolver::new()
+///     .allow_host("json-schema.org")
+///     .allow_host("*.example.com")
+///     .timeout(Duration::from_secs(5))
+///     .retry_policy(RetryPolicy { max_retries: 3, ..RetryPolicy::default() });
+///
+/// let document = resolver
+///     .resolve("https://json-schema.org/draft/2020-12/schema".into())
+///     .await
+///     .unwrap();
+/// assert_eq!(document["$id"], "https://json-schema.org/draft/2020-12/schema");
+///
+/// let err = resolver.resolve("https://evil.test/schema.json".into()).await.unwrap_err();
+/// assert!(err.is_not_found());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpResolver {
+    client: Client,
+    timeout: Duration,
+    retry_policy: RetryPolicy,
+    allowed_hosts: Vec<String>,
+    max_body_size: usize,
+    max_redirects: usize,
+    deserializers: Deserializers,
+    cache: Arc<DashMap<String, Cached>>,
+}
+
+impl Default for HttpResolver {
+    fn default() -> Self {
+        Self {
+            client: Client::builder()
+                .redirect(redirect::Policy::none())
+                .build()
+                .expect("a client without a TLS backend or proxy can be built"),
+            timeout: DEFAULT_HTTP_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+            allowed_hosts: Vec::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            deserializers: Deserializers::standard(),
+            cache: Arc::new(DashMap::new()),
+        }
+    }
+}
+
+impl HttpResolver {
+    /// Creates and returns a new `HttpResolver` which allows no hosts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows documents to be fetched from `host`, e.g. `"example.com"` or
+    /// `"*.example.com"`.
+    #[must_use]
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Sets the timeout of each request. Defaults to
+    /// [`DEFAULT_HTTP_TIMEOUT`].
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the [`RetryPolicy`]. Defaults to [`RetryPolicy::default`].
+    #[must_use]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a fetched document. Defaults to
+    /// [`DEFAULT_MAX_BODY_SIZE`].
+    #[must_use]
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Sets the maximum number of redirects followed for a single request.
+    /// Defaults to [`DEFAULT_MAX_REDIRECTS`].
+    #[must_use]
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Sets the [`Deserializers`] with which documents are deserialized, e.g.
+    /// those of an [`Interrogator`](crate::Interrogator), returned by
+    /// [`Interrogator::deserializers`](crate::Interrogator::deserializers).
+    /// The deserializer of a document is chosen by its `Content-Type`, if one
+    /// is registered for it, and otherwise by the extension of its URI.
+    /// Defaults to [`Deserializers::standard`].
+    #[must_use]
+    pub fn deserializers(mut self, deserializers: Deserializers) -> Self {
+        self.deserializers = deserializers;
+        self
+    }
+
+    /// Sets the [`Client`] with which requests are made, e.g. to configure
+    /// proxies, TLS, or a connect timeout.
+    ///
+    /// The client should be built with
+    /// [`redirect::Policy::none`](reqwest::redirect::Policy::none): a client
+    /// which follows redirects itself does so without checking the allowed
+    /// hosts.
+    #[must_use]
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Returns the allowed hosts, in the order they were added.
+    pub fn allowed_hosts(&self) -> &[String] {
+        &self.allowed_hosts
+    }
+
+    /// Returns `true` if documents may be fetched from `host`.
+    pub fn is_allowed(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.allowed_hosts
+            .iter()
+            .any(|allowed| match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .map_or(false, |sub| sub.len() > 1 && sub.ends_with('.')),
+                None => *allowed == host,
+            })
+    }
+
+    /// Removes each cached document.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Returns the URL of the document at `uri`, without its fragment, if
+    /// `uri` may be fetched by this `HttpResolver`.
+    fn url(&self, uri: &str) -> Option<Url> {
+        let mut url = Url::parse(uri).ok()?;
+        if !matches!(url.scheme(), "http" | "https") || !self.is_allowed(url.host_str()?) {
+            return None;
+        }
+        url.set_fragment(None);
+        Some(url)
+    }
+
+    async fn fetch(&self, id: String) -> Result<Value, Error> {
+        let url = match self.url(&id) {
+            Some(url) => url,
+            None => return Err(Error::NotFound(id)),
+        };
+        let key = url.to_string();
+        let mut target = url;
+        let mut redirects = 0;
+        let mut retry = 0;
+        loop {
+            let cached = self.cache.get(&key).map(|c| c.clone());
+            let mut request = self.client.get(target.clone()).timeout(self.timeout);
+            if let Some(cached) = &cached {
+                request = request.header(header::IF_NONE_MATCH, &cached.etag);
+            }
+            let err = match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_redirection() && status != StatusCode::NOT_MODIFIED {
+                        target = self.redirect(&key, &target, &response, redirects)?;
+                        redirects += 1;
+                        continue;
+                    }
+                    if status == StatusCode::NOT_MODIFIED {
+                        if let Some(cached) = cached {
+                            return Ok(cached.document);
+                        }
+                    }
+                    if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+                        self.cache.remove(&key);
+                        return Err(Error::NotFound(id));
+                    }
+                    if status.is_success() {
+                        let etag = response
+                            .headers()
+                            .get(header::ETAG)
+                            .and_then(|etag| etag.to_str().ok())
+                            .map(ToString::to_string);
+                        let media_type = response
+                            .headers()
+                            .get(header::CONTENT_TYPE)
+                            .and_then(|media_type| media_type.to_str().ok())
+                            .filter(|media_type| self.deserializers.get(media_type).is_some())
+                            .map(ToString::to_string);
+                        let bytes = self.read_body(&key, response).await?;
+                        let document = AbsoluteUri::parse(&key)
+                            .map_err(|err| Error::Internal(Box::new(err)))
+                            .and_then(|uri| {
+                                self.deserializers
+                                    .deserialize(&uri, media_type.as_deref(), &bytes)
+                                    .map_err(|err| Error::Internal(Box::new(err)))
+                            })?;
+                        match etag {
+                            Some(etag) => {
+                                self.cache.insert(
+                                    key,
+                                    Cached {
+                                        etag,
+                                        document: document.clone(),
+                                    },
+                                );
+                            }
+                            None => {
+                                self.cache.remove(&key);
+                            }
+                        }
+                        return Ok(document);
+                    }
+                    let err = Error::Internal(Box::new(HttpStatusError {
+                        uri: key.clone(),
+                        status: status.as_u16(),
+                    }));
+                    if status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+                        return Err(err);
+                    }
+                    err
+                }
+                Err(err) if err.is_timeout() || err.is_connect() => Error::Internal(Box::new(err)),
+                Err(err) => return Err(Error::Internal(Box::new(err))),
+            };
+            if retry >= self.retry_policy.max_retries {
+                return Err(err);
+            }
+            tokio::time::sleep(self.retry_policy.backoff(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+impl HttpResolver {
+    /// Returns the target of the redirect `response` to a request for `url`,
+    /// having followed `followed` redirects, if it may be followed.
+    fn redirect(
+        &self,
+        key: &str,
+        url: &Url,
+        response: &Response,
+        followed: usize,
+    ) -> Result<Url, Error> {
+        let location = response
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .unwrap_or_default();
+        let error = |reason: &str| {
+            Error::Internal(Box::new(HttpRedirectError {
+                uri: key.to_string(),
+                location: location.to_string(),
+                reason: reason.to_string(),
+            }))
+        };
+        if followed >= self.max_redirects {
+            return Err(error("too many redirects"));
+        }
+        let target = url
+            .join(location)
+            .map_err(|_| error("the location is not a valid URI"))?;
+        self.url(target.as_str())
+            .ok_or_else(|| error("the location is not an allowed host"))
+    }
+
+    /// Reads the body of `response`, failing as soon as it exceeds the
+    /// maximum size.
+    async fn read_body(&self, key: &str, mut response: Response) -> Result<Vec<u8>, Error> {
+        let too_large = || {
+            Error::Internal(Box::new(HttpBodyTooLargeError {
+                uri: key.to_string(),
+                limit: self.max_body_size,
+            }))
+        };
+        if response
+            .content_length()
+            .map_or(false, |len| len > self.max_body_size as u64)
+        {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| Error::Internal(Box::new(err)))?
+        {
+            if body.len() + chunk.len() > self.max_body_size {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+impl AsyncResolver for HttpResolver {
+    fn resolve(&self, id: String) -> BoxFuture<'_, Result<Value, Error>> {
+        Box::pin(self.fetch(id))
+    }
+}
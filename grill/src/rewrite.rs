@@ -0,0 +1,162 @@
+//! Rewriting of the references and identifiers of source documents for URI
+//! migrations.
+//!
+//! [`Interrogator::rewrite_refs`](crate::Interrogator::rewrite_refs) replaces
+//! the prefix of each `"$ref"`, `"$dynamicRef"`, `"$recursiveRef"`,
+//! `"$schema"`, `"$id"`, and `"id"` across the sources of an
+//! [`Interrogator`](crate::Interrogator) according to a mapping of URI
+//! prefixes, and moves each source whose URI matches a prefix. Where more
+//! than one prefix matches, the longest is applied. Relative references are
+//! left untouched, as they resolve against the rewritten `"$id"`.
+//!
+//! [`Interrogator::rewrite_refs_dry_run`](crate::Interrogator::rewrite_refs_dry_run)
+//! reports the same changes without applying them.
+//!
+//! [`Schema`](crate::Schema)s compiled before the rewrite are not modified.
+//!
+//! ## Example
+//! ```
+//! use grill::{uri::AbsoluteUri, Interrogator};
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! let uri = AbsoluteUri::parse("https://old.example/order.json").unwrap();
+//! interrogator.source_value(
+//!     uri.clone(),
+//!     json!({
+//!         "$id": "https://old.example/order.json",
+//!         "properties": { "address": { "$ref": "https://old.example/address.json" } }
+//!     }),
+//! );
+//!
+//! let mapping = [("https://old.example/", "https://new.example/")];
+//! let report = interrogator.rewrite_refs_dry_run(&mapping).unwrap();
+//! assert_eq!(report.changes.len(), 2);
+//! assert_eq!(report.changes[1].location, "/properties/address/$ref");
+//! assert!(interrogator.source(&uri).unwrap().is_some());
+//!
+//! interrogator.rewrite_refs(&mapping).unwrap();
+//! let moved = AbsoluteUri::parse("https://new.example/order.json").unwrap();
+//! assert!(interrogator.source(&uri).unwrap().is_none());
+//! assert_eq!(
+//!     interrogator.source(&moved).unwrap().unwrap()["properties"]["address"]["$ref"],
+//!     "https://new.example/address.json"
+//! );
+//! ```
+
+use crate::pointer::escape;
+use serde_json::Value;
+use uniresid::AbsoluteUri;
+
+/// Keywords whose value is a URI which is rewritten.
+const REWRITTEN_KEYWORDS: &[&str] = &[
+    "$ref",
+    "$dynamicRef",
+    "$recursiveRef",
+    "$schema",
+    "$id",
+    "id",
+];
+
+/// Keywords whose value is instance data rather than schema.
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// A single value rewritten within a source document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// The URI of the document, prior to any move.
+    pub document: AbsoluteUri,
+    /// The JSON Pointer of the rewritten value within the document.
+    pub location: String,
+    /// The value before the rewrite.
+    pub from: String,
+    /// The value after the rewrite.
+    pub to: String,
+}
+
+/// The changes made, or which would be made, by a rewrite.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RewriteReport {
+    /// Each value rewritten, ordered by document and then by location.
+    pub changes: Vec<Change>,
+    /// The previous and new URI of each source document which was moved.
+    pub moved: Vec<(AbsoluteUri, AbsoluteUri)>,
+    /// The URI of each source document which was, or would be, replaced by
+    /// a moved document: either a source which was not itself moved or the
+    /// target of another moved document. Documents are moved in the order
+    /// of their previous URIs, so the last moved to a URI is kept.
+    pub replaced: Vec<AbsoluteUri>,
+}
+
+impl RewriteReport {
+    /// Returns `true` if the rewrite changed nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.moved.is_empty() && self.replaced.is_empty()
+    }
+}
+
+/// Returns `uri` with the longest matching prefix of `mapping` replaced, or
+/// `None` if no prefix matches.
+pub fn map_uri(uri: &str, mapping: &[(&str, &str)]) -> Option<String> {
+    mapping
+        .iter()
+        .filter(|(from, _)| uri.starts_with(*from))
+        .max_by_key(|(from, _)| from.len())
+        .map(|(from, to)| format!("{}{}", to, &uri[from.len()..]))
+}
+
+/// Rewrites `document`, the source at `uri`, in place according to
+/// `mapping`, returning each change made.
+pub fn rewrite_document(
+    uri: &AbsoluteUri,
+    document: &mut Value,
+    mapping: &[(&str, &str)],
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    rewrite(uri, document, String::new(), mapping, &mut changes);
+    changes
+}
+
+fn rewrite(
+    uri: &AbsoluteUri,
+    value: &mut Value,
+    location: String,
+    mapping: &[(&str, &str)],
+    changes: &mut Vec<Change>,
+) {
+    match value {
+        Value::Object(obj) => {
+            let mut keywords: Vec<String> = obj.keys().cloned().collect();
+            keywords.sort();
+            for keyword in keywords {
+                if DATA_KEYWORDS.contains(&keyword.as_str()) {
+                    continue;
+                }
+                let child = format!("{}/{}", location, escape(&keyword));
+                let value = match obj.get_mut(&keyword) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                match value {
+                    Value::String(s) if REWRITTEN_KEYWORDS.contains(&keyword.as_str()) => {
+                        if let Some(to) = map_uri(s, mapping) {
+                            changes.push(Change {
+                                document: uri.clone(),
+                                location: child,
+                                from: std::mem::replace(s, to.clone()),
+                                to,
+                            });
+                        }
+                    }
+                    value => rewrite(uri, value, child, mapping, changes),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                rewrite(uri, item, format!("{}/{}", location, i), mapping, changes);
+            }
+        }
+        _ => {}
+    }
+}
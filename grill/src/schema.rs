@@ -1,5 +1,5 @@
 mod functions;
-use functions::Functions;
+use functions::{Functions, KeywordSetupFn};
 
 mod meta_schema;
 
@@ -13,17 +13,50 @@ pub use parking_lot::Mutex;
 pub use sub_schema::SubSchema;
 
 use crate::{
-    applicator::{Applicators, ExecutorFn, SetupFn},
-    error::{MetaSchemaError, UnknownMetaSchema},
-    Error, Evaluation, Interrogator, Next, OutputFmt,
+    algebra::{self, Combined},
+    applicator::{Applicators, ExecutorFn},
+    backend::ValueBackend,
+    cost,
+    error::{FeatureDisabledError, MetaSchemaError, TimeoutError, UnknownMetaSchema},
+    evaluation::{probe, Options, State},
+    explain::Explanation,
+    keyword::APPLICATOR_KEYWORDS,
+    normalize, optimize, pointer,
+    report::Report,
+    source, stream,
+    uri::RawUri,
+    urn, Error, Evaluation, Feature, Interrogator, Next, OutputFmt,
 };
 use jsonptr::Pointer;
+use serde::{Deserializer, Serialize};
 use serde_json::{Map, Value};
 use std::{
+    borrow::Cow,
     collections::{HashMap, HashSet},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
-use uniresid::Uri;
+use uniresid::{AbsoluteUri, Uri};
+
+/// The source of the [`Schema::key`] of each `Schema` created.
+static NEXT_KEY: AtomicUsize = AtomicUsize::new(0);
+
+/// Describes a keyword compiled into a [`Schema`], as returned by
+/// [`Schema::keywords`] and
+/// [`Interrogator::keywords_of`](crate::Interrogator::keywords_of).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledKeyword {
+    /// The name of the keyword, if the [`Applicator`](crate::Applicator)
+    /// which compiled it reports one.
+    pub name: Option<&'static str>,
+    /// The location of the keyword within the source of the [`Schema`].
+    pub location: Pointer,
+    /// Whether the keyword applies subschemas, as listed in
+    /// [`APPLICATOR_KEYWORDS`](crate::keyword::APPLICATOR_KEYWORDS).
+    pub is_applicator: bool,
+}
 
 /// Data structure representing a single [JSON Schema](https://json-schema.org/).
 #[derive(Clone)]
@@ -35,19 +68,50 @@ pub struct Schema {
     sub_schemas: Arc<RwLock<HashMap<String, SubSchema>>>,
     functions: Functions,
     applicators: Applicators,
+    pure: Arc<RwLock<Option<bool>>>,
+    key: usize,
 }
 
 impl Schema {
-    /// Creates and returns a new `Schema`.
+    /// Creates and returns a new `Schema`, set up for evaluation.
+    ///
+    /// The keywords of the `Schema` are those of the [`Dialect`](crate::Dialect)
+    /// of its `"$schema"` or, if it declares none, of the
+    /// [default meta schema](Interrogator::default_meta_schema_uri) of
+    /// `interrogator`. References are resolved among the schemas of
+    /// `interrogator`.
+    ///
+    /// # Errors
+    /// Returns [`UnknownMetaSchema`] if no `Dialect` is registered for the
+    /// meta schema, or the error of an [`Applicator`](crate::Applicator)
+    /// which rejects the `Schema`.
     pub fn new(source: Value, interrogator: &Interrogator) -> Result<Self, Error> {
+        let schema = Self::initialized(source, None, interrogator)?;
+        schema.setup(interrogator)?;
+        Ok(schema)
+    }
+
+    /// Creates and initializes a new `Schema` of the meta schema
+    /// `meta_schema_id`, unless its source declares one, deferring setup
+    /// until the schemas it references are available.
+    pub(crate) fn initialized(
+        mut source: Value,
+        meta_schema_id: Option<Uri>,
+        interrogator: &Interrogator,
+    ) -> Result<Self, Error> {
+        if interrogator.normalizes_identifiers() {
+            interrogator.report(normalize::identifiers(&mut source));
+        }
         let schema = Schema {
             id: Arc::new(RwLock::new(None)),
-            meta_schema_id: Arc::new(RwLock::new(None)),
+            meta_schema_id: Arc::new(RwLock::new(meta_schema_id.map(Arc::new))),
             references: Arc::new(RwLock::new(Arc::new(HashSet::new()))),
             source: Arc::new(RwLock::new(Arc::new(source))),
             sub_schemas: Arc::new(RwLock::new(HashMap::new())),
             functions: Functions::new(),
             applicators: Applicators::new(),
+            pure: Arc::new(RwLock::new(None)),
+            key: NEXT_KEY.fetch_add(1, Ordering::Relaxed),
         };
         schema.initialize(interrogator)?;
         Ok(schema)
@@ -59,7 +123,10 @@ impl Schema {
     fn exec_fns(&self) -> Vec<Box<ExecutorFn>> {
         self.functions.executor_fns()
     }
-    fn setup_fns(&self) -> Vec<Box<SetupFn>> {
+    fn shared_exec_fns(&self) -> Arc<Vec<Box<ExecutorFn>>> {
+        self.functions.shared_executor_fns()
+    }
+    fn setup_fns(&self) -> Vec<KeywordSetupFn> {
         self.functions.setup_fns()
     }
     pub fn meta_schema_id(&self) -> Option<Arc<Uri>> {
@@ -69,51 +136,462 @@ impl Schema {
 
     /// Evaluates `value` against this `Schema`.
     pub fn evaluate(&self, value: &Value, output: OutputFmt) -> Result<Evaluation, Error> {
-        let next = Next::new(self.exec_fns());
-        let eval = Evaluation::new(Pointer::default(), Pointer::default(), output);
-        next.call(value, eval)
+        self.evaluate_with_options(value, output, Options::default())
+    }
+
+    /// Evaluates `value` against this `Schema` with the given [`Options`].
+    pub fn evaluate_with_options(
+        &self,
+        value: &Value,
+        output: OutputFmt,
+        options: Options,
+    ) -> Result<Evaluation, Error> {
+        check_options(&options)?;
+        let eval = Evaluation::with_state(output, State::new(options));
+        self.apply_root(value, eval)
+    }
+
+    /// Evaluates `value` against this `Schema` with the given [`Options`], as
+    /// though it were applied within each schema resource of `scope`,
+    /// outermost first.
+    pub(crate) fn evaluate_in_scope(
+        &self,
+        value: &Value,
+        output: OutputFmt,
+        options: Options,
+        scope: &[Uri],
+    ) -> Result<Evaluation, Error> {
+        check_options(&options)?;
+        let state = State::new(options);
+        for resource in scope {
+            state.enter_scope(resource.clone());
+        }
+        self.apply_root(value, Evaluation::with_state(output, state))
+    }
+
+    /// Evaluates `value` against this `Schema`, returning verbose output for
+    /// the instance subtree at `instance_pointer` and only the validity of
+    /// evaluations elsewhere. See [`Evaluation::focus`].
+    pub fn evaluate_with_focus(
+        &self,
+        value: &Value,
+        instance_pointer: &Pointer,
+    ) -> Result<Evaluation, Error> {
+        let mut eval = self.evaluate(value, OutputFmt::Verbose)?;
+        eval.focus(instance_pointer);
+        Ok(eval)
+    }
+
+    /// Evaluates `value` against this `Schema` and returns an [`Explanation`]
+    /// of the constraints checked and the branches taken at each instance
+    /// location. See [`explain`](crate::explain).
+    ///
+    /// Only the keywords of this `Schema` and its [`SubSchema`]s are
+    /// explained as branches; those of the schemas it references are listed
+    /// as checks. Use [`Interrogator::explain`] to include them.
+    pub fn explain(&self, value: &Value) -> Result<Explanation, Error> {
+        let eval = self.evaluate(value, OutputFmt::Verbose)?;
+        Ok(Explanation::new(&eval, &self.keywords()))
+    }
+
+    /// Evaluates `value` against this `Schema` with the given [`Options`]
+    /// and a user-defined `context`.
+    ///
+    /// The output structure, the options, and the context are independent
+    /// parameters. [`Applicator`](crate::Applicator)s retrieve the context
+    /// from the [`State`] of the [`Evaluation`] with [`State::context`].
+    pub fn evaluate_with_context<C: std::any::Any + Send + Sync>(
+        &self,
+        value: &Value,
+        output: OutputFmt,
+        options: Options,
+        context: C,
+    ) -> Result<Evaluation, Error> {
+        check_options(&options)?;
+        let eval = Evaluation::with_state(output, State::with_context(options, context));
+        self.apply_root(value, eval)
+    }
+
+    /// Evaluates `value`, represented by the [`ValueBackend`] `B`, against
+    /// this `Schema`.
+    ///
+    /// `value` is converted to a [`Value`] only if `B` does not use
+    /// [`Value`] as its representation.
+    pub fn evaluate_with_backend<B: ValueBackend>(
+        &self,
+        value: &B::Value,
+        output: OutputFmt,
+    ) -> Result<Evaluation, Error> {
+        self.evaluate(&B::to_json(value)?, output)
+    }
+
+    /// Evaluates a serializable Rust `value` against this `Schema`.
+    ///
+    /// `value` is converted to a [`Value`] with [`serde_json::to_value`] and
+    /// evaluated with [`evaluate`](Self::evaluate), so this costs as much as
+    /// converting it beforehand. The one exception is a `Schema` which is a
+    /// boolean or constrains no more than the `"type"` of the instance: an
+    /// object or array `value` is then evaluated as an empty object or array
+    /// without serializing its members.
+    ///
+    /// To evaluate an instance without holding its members in memory, see
+    /// [`evaluate_events`](Self::evaluate_events) and
+    /// [`evaluate_deserializer`](Self::evaluate_deserializer).
+    pub fn evaluate_serializable<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        output: OutputFmt,
+    ) -> Result<Evaluation, Error> {
+        let streamable = match self.source().as_ref() {
+            Value::Bool(_) => true,
+            Value::Object(obj) => obj
+                .keys()
+                .all(|k| STREAMABLE_KEYWORDS.contains(&k.as_str())),
+            _ => false,
+        };
+        let instance = match streamable.then(|| probe::type_of(value)).transpose()? {
+            Some("object") => Value::Object(Map::new()),
+            Some("array") => Value::Array(Vec::new()),
+            _ => serde_json::to_value(value)?,
+        };
+        self.evaluate(&instance, output)
+    }
+
+    /// Evaluates the instance described by a SAX-like sequence of
+    /// [`Event`](stream::Event)s against this `Schema` without holding the
+    /// members of an object or array instance in memory.
+    /// Only the root of the instance is evaluated; the members of an object
+    /// or array are not.
+    ///
+    /// Returns a [`RandomAccessError`](crate::error::RandomAccessError) if
+    /// the instance is an object or array and this `Schema` has a keyword
+    /// which requires random access to it. See [`stream`].
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{stream::Event, Interrogator, OutputFmt, Schema};
+    /// use serde_json::json;
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let schema = Schema::new(json!({ "minimum": 0 }), &interrogator).unwrap();
+    /// let events = vec![
+    ///     Event::StartObject,
+    ///     Event::Key("a".to_string()),
+    ///     Event::Scalar(json!(-1)),
+    ///     Event::EndObject,
+    /// ];
+    /// // "minimum" constrains numbers, not the members of an object
+    /// let eval = schema
+    ///     .evaluate_events(events.into_iter().map(Ok), OutputFmt::Basic)
+    ///     .unwrap();
+    /// assert!(eval.is_valid());
+    ///
+    /// let eval = schema
+    ///     .evaluate_events([Ok(Event::Scalar(json!(-1)))], OutputFmt::Basic)
+    ///     .unwrap();
+    /// assert!(!eval.is_valid());
+    /// ```
+    pub fn evaluate_events<I>(&self, events: I, output: OutputFmt) -> Result<Evaluation, Error>
+    where
+        I: IntoIterator<Item = Result<stream::Event, Error>>,
+    {
+        stream::evaluate_events(self, events, output)
+    }
+
+    /// Evaluates the instance read from `deserializer` against this `Schema`
+    /// without holding the members of an object or array instance in
+    /// memory.
+    /// Only the root of the instance is evaluated; the members of an object
+    /// or array are not.
+    ///
+    /// Returns a [`RandomAccessError`](crate::error::RandomAccessError) if
+    /// the instance is an object or array and this `Schema` has a keyword
+    /// which requires random access to it. See [`stream`].
+    pub fn evaluate_deserializer<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+        output: OutputFmt,
+    ) -> Result<Evaluation, Error> {
+        stream::evaluate_deserializer(self, deserializer, output)
+    }
+
+    /// Evaluates `value` against this `Schema`, returning a [`Report`] which
+    /// can outlive `value`.
+    ///
+    /// Rather than the whole instance, the `Report` retains only the
+    /// fragments of `value` at the locations of its errors and of the values
+    /// its annotations refer to, which are cloned if `value` is borrowed. See
+    /// [`Report::with_instance`].
+    ///
+    /// ## Example
+    /// ```
+    /// use grill::{report::Report, Interrogator, Schema};
+    /// use jsonptr::Pointer;
+    /// use serde_json::json;
+    /// use std::borrow::Cow;
+    ///
+    /// fn check(schema: &Schema, body: &str) -> Report {
+    ///     let instance: serde_json::Value = serde_json::from_str(body).unwrap();
+    ///     schema.evaluate_owned(Cow::Borrowed(&instance)).unwrap()
+    /// }
+    ///
+    /// let interrogator = Interrogator::new();
+    /// let schema = Schema::new(json!({ "maximum": 10 }), &interrogator).unwrap();
+    /// let report = check(&schema, "11");
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.instance_at(&Pointer::default()), Some(&json!(11)));
+    /// ```
+    pub fn evaluate_owned(&self, value: Cow<'_, Value>) -> Result<Report, Error> {
+        let eval = self.evaluate(&value, OutputFmt::Verbose)?;
+        Ok(Report::from(&eval).with_instance(value))
+    }
+
+    /// Applies this `Schema` to `value`, annotating `evaluation`.
+    ///
+    /// This should be used by [`Applicator`](crate::Applicator)s which
+    /// evaluate a nested or referenced `Schema`. If memoization is enabled
+    /// for the evaluation, the result of a prior application of this `Schema`
+    /// at the same instance location is returned instead, unless this
+    /// `Schema` contains a dynamic reference, the result of which depends on
+    /// the dynamic scope.
+    ///
+    /// If this `Schema` is the root of a schema resource, i.e. it has an id
+    /// or an `"$id"`, the resource is entered into the
+    /// [dynamic scope](State::dynamic_scope) of the evaluation for the
+    /// duration of the application.
+    pub fn apply(&self, value: &Value, evaluation: Evaluation) -> Result<Evaluation, Error> {
+        let state = evaluation.state().clone();
+        let entered = match self.resource(&state) {
+            Some(resource) => state.enter_scope(resource),
+            None => false,
+        };
+        let result = self.apply_in_scope(value, evaluation, &state);
+        if entered {
+            state.exit_scope();
+        }
+        result
+    }
+
+    /// Applies this `Schema` to `value` as the root of an evaluation,
+    /// returning [`Error::Timeout`] with the whole of the evaluation if its
+    /// deadline passed before it completed.
+    fn apply_root(&self, value: &Value, evaluation: Evaluation) -> Result<Evaluation, Error> {
+        let state = evaluation.state().clone();
+        let eval = self.apply(value, evaluation)?;
+        match state.timed_out() {
+            Some((instance_location, keyword_location)) => Err(TimeoutError {
+                instance_location,
+                keyword_location,
+                partial: Box::new(eval),
+            }
+            .into()),
+            None => Ok(eval),
+        }
+    }
+
+    fn apply_in_scope(
+        &self,
+        value: &Value,
+        evaluation: Evaluation,
+        state: &State,
+    ) -> Result<Evaluation, Error> {
+        let options = state.options();
+        if let Some(cache) = options.result_cache() {
+            if self.is_pure() {
+                let output = evaluation.output();
+                if let Some(mut cached) = cache.get(self.key(), state, &output, value) {
+                    cached.relocate(&evaluation);
+                    return Ok(cached);
+                }
+                let eval = Next::new(self.shared_exec_fns()).call(value, evaluation)?;
+                // an evaluation cut short by its deadline is incomplete
+                if !state.is_timed_out() {
+                    cache.insert(self.key(), state, &output, value, eval.clone());
+                }
+                return Ok(eval);
+            }
+        }
+        // the result of an impure schema depends on the dynamic scope, which
+        // the memo is not keyed by
+        if !state.options().is_memoized() || !self.is_pure() {
+            return Next::new(self.shared_exec_fns()).call(value, evaluation);
+        }
+        let key = (
+            self.key(),
+            state.context_key(),
+            evaluation.instance_location().clone(),
+        );
+        if let Some(memoized) = state.memoized(&key) {
+            return Ok(memoized);
+        }
+        let eval = Next::new(self.shared_exec_fns()).call(value, evaluation)?;
+        if !state.is_timed_out() {
+            state.memoize(key, eval.clone());
+        }
+        Ok(eval)
+    }
+
+    /// Returns the id, without fragment, of the schema resource this `Schema`
+    /// is the root of, if any. A relative `"$id"` is resolved against the
+    /// innermost resource of the dynamic scope of `state`.
+    pub(crate) fn resource(&self, state: &State) -> Option<Uri> {
+        let id = match self.id() {
+            Some(id) => id.as_ref().clone(),
+            None => {
+                let source = self.source();
+                let id = source.get("$id")?.as_str()?;
+                if id.starts_with('#') {
+                    return None;
+                }
+                let id = Uri::parse(id).ok()?;
+                match state.innermost_scope() {
+                    Some(base) => urn::resolve(&base, &id)?,
+                    None => id,
+                }
+            }
+        };
+        let id = id.to_string();
+        Uri::parse(id.split('#').next().unwrap_or_default()).ok()
+    }
+
+    /// Returns `true` if the result of applying this `Schema` depends solely
+    /// on the instance, i.e. neither it, its subschemas, nor the schemas they
+    /// reference, directly or transitively, contain a dynamic reference.
+    ///
+    /// The references are followed when the `Schema` is setup; until then,
+    /// only the `Schema` itself is considered.
+    fn is_pure(&self) -> bool {
+        if let Some(pure) = *self.pure.read() {
+            return pure;
+        }
+        !contains_dynamic_reference(&self.source())
+    }
+
+    /// Returns `true` if neither this `Schema` nor a document it references,
+    /// directly or transitively, contains a dynamic reference. Referenced
+    /// documents are considered whole, as may be the subschemas within them
+    /// which are reached by further references.
+    ///
+    /// Referenced schemas without a source document are read from the
+    /// schemas of `interrogator`, so they must not be locked for writing.
+    fn references_are_pure(&self, interrogator: &Interrogator) -> bool {
+        if contains_dynamic_reference(&self.source()) {
+            return false;
+        }
+        let mut queue: Vec<String> = self
+            .references_within()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let mut visited = HashSet::new();
+        while let Some(target) = queue.pop() {
+            let document = target.split('#').next().unwrap_or_default().to_string();
+            if !visited.insert(document.clone()) {
+                continue;
+            }
+            // a reference which can not be followed is assumed to be impure
+            let uri = match AbsoluteUri::parse(&document) {
+                Ok(uri) => uri,
+                Err(_) => return false,
+            };
+            let source = match interrogator.locate(&uri) {
+                Ok(Some(source)) => source,
+                Ok(None) => match interrogator.schema(uri.uri()) {
+                    Some(schema) => schema.source().as_ref().clone(),
+                    None => return false,
+                },
+                Err(_) => return false,
+            };
+            if contains_dynamic_reference(&source) {
+                return false;
+            }
+            queue.extend(
+                source::dependencies(&uri, &source)
+                    .iter()
+                    .map(ToString::to_string),
+            );
+        }
+        true
+    }
+
+    /// Computes the artifacts of this `Schema` and its [`SubSchema`]s which
+    /// are otherwise built on first evaluation. See
+    /// [`Interrogator::warm`](crate::Interrogator::warm).
+    pub(crate) fn warm(&self) {
+        self.is_pure();
+        for sub in self.sub_schemas().values() {
+            match sub {
+                SubSchema::Single(schema) => schema.warm(),
+                SubSchema::Array(schemas) => schemas.iter().for_each(Schema::warm),
+            }
+        }
+    }
+
+    /// Returns a key which uniquely identifies this `Schema` (and its clones).
+    ///
+    /// Keys are never reused, so results cached or recorded under the key of
+    /// a `Schema` which has been removed are never attributed to another.
+    pub(crate) fn key(&self) -> usize {
+        self.key
     }
 
     /// Creates and returns a new [`SubSchema`] that is nested within this `Schema`.
+    ///
+    /// `key` is the location of the subschema relative to this `Schema`, as
+    /// escaped JSON Pointer tokens without a leading `'/'`, e.g. `"oneOf/0"`.
+    /// Unless it declares its own, each subschema is identified by the id of
+    /// this `Schema` with `key`, and its index if `source` is an array,
+    /// appended to the JSON Pointer of its fragment.
     pub fn add_sub_schema(
         &self,
         key: &str,
         source: Value,
         interrogator: &Interrogator,
     ) -> Result<SubSchema, Error> {
-        let meta_schema_id = self.meta_schema_id();
-        let meta_schema = match meta_schema_id {
-            Some(ref id) => interrogator.meta_schema(id),
-            None => None,
-        };
+        let meta_schema_id = self.meta_schema_id().map(|id| id.as_ref().clone());
         let base_uri = interrogator.base_uri().as_deref().cloned();
 
         let ss = if let Value::Array(arr) = source {
             let mut subs = Vec::with_capacity(arr.len());
-            for source in arr {
+            for (i, source) in arr.into_iter().enumerate() {
                 let b = SchemaBuilder {
-                    id: None,
+                    id: self.sub_schema_id(&format!("{}/{}", key, i)),
                     source,
-                    meta_schema: meta_schema.clone(),
+                    meta_schema: None,
+                    meta_schema_id: meta_schema_id.clone(),
                     base_uri: base_uri.clone(),
                 };
-                subs.push(b.build(interrogator)?);
+                subs.push(b.initialize(interrogator)?);
             }
             SubSchema::Array(Arc::new(subs))
         } else {
             let b = SchemaBuilder {
                 source,
                 base_uri,
-                meta_schema,
-                id: None,
+                meta_schema: None,
+                meta_schema_id,
+                id: self.sub_schema_id(key),
             };
-            SubSchema::Single(b.build(interrogator)?)
+            SubSchema::Single(b.initialize(interrogator)?)
         };
         let res = ss.clone();
         let mut sub_schemas = self.sub_schemas.write();
         sub_schemas.insert(key.to_string(), ss);
         Ok(res)
     }
+
+    /// Returns the id of the subschema at `key`, relative to this `Schema`,
+    /// or `None` if this `Schema` has no absolute id or its fragment is not a
+    /// JSON Pointer.
+    fn sub_schema_id(&self, key: &str) -> Option<Uri> {
+        let id = self.id()?;
+        id.scheme()?;
+        let id = id.to_string();
+        let (base, fragment) = id.split_once('#').unwrap_or((&id, ""));
+        let parent = pointer::from_fragment(fragment).ok()?;
+        let location = Pointer::try_from(format!("{}/{}", parent.as_str(), key).as_str()).ok()?;
+        Uri::parse(&format!("{}{}", base, pointer::to_fragment(&location))).ok()
+    }
     pub fn meta_schema(&self, interrogator: &Interrogator) -> Option<MetaSchema> {
         let id = self.meta_schema_id.read();
         match id.as_ref() {
@@ -133,32 +611,57 @@ impl Schema {
         guard.clone()
     }
 
+    /// Determines the [`Dialect`](crate::Dialect) of this `Schema` and
+    /// collects the [`SetupFn`](crate::applicator::SetupFn) of each
+    /// [`Applicator`](crate::Applicator) of its vocabularies which applies to
+    /// it. The vocabularies are initialized in the order of their ids so that
+    /// initialization is deterministic.
     fn initialize(&self, interrogator: &Interrogator) -> Result<(), Error> {
-        let meta_schema = self.load_meta_schema(interrogator);
-        todo!()
+        let meta_schema_id = self.meta_schema_uri(interrogator)?;
+        let dialect = match interrogator.dialect(&meta_schema_id) {
+            Some(dialect) => dialect,
+            None => AbsoluteUri::parse(&meta_schema_id.to_string())
+                .ok()
+                .map(|id| interrogator.vocabulary_dialect(&id))
+                .transpose()?
+                .flatten()
+                .ok_or_else(|| UnknownMetaSchema {
+                    uri: meta_schema_id.clone(),
+                })?,
+        };
+        *self.meta_schema_id.write() = Some(Arc::new(meta_schema_id));
+        let mut vocabularies: Vec<_> = dialect.vocabularies.keys().collect();
+        vocabularies.sort_by_cached_key(|vocabulary| vocabulary.id.to_string());
+        let mut setup_fns = Vec::new();
+        for vocabulary in vocabularies {
+            for applicator in &vocabulary.applicators {
+                if let Some(f) = applicator.init(interrogator.clone(), self.clone())? {
+                    setup_fns.push((applicator.keyword(), f));
+                }
+            }
+        }
+        self.set_setup(setup_fns);
+        Ok(())
     }
 
-    fn load_meta_schema(&self, interrogator: &Interrogator) -> Result<MetaSchema, Error> {
-        if let Some(meta) = self.meta_schema(interrogator) {
-            return Ok(meta);
-        }
-        let source = self.source();
-        if let Some(obj) = source.as_object() {
-            if let Some(uri) = obj.get("$schema") {
-                if let Some(uri) = uri.as_str() {
-                    return match Uri::parse(uri) {
-                        Ok(uri) => interrogator
-                            .meta_schema(&uri)
-                            .ok_or_else(|| UnknownMetaSchema { uri }.into()),
-                        Err(err) => Err(MetaSchemaError::InvalidUri(err).into()),
-                    };
-                }
+    /// Returns the URI of the meta schema of this `Schema`: its `"$schema"`,
+    /// the meta schema it was created with, or the default of
+    /// `interrogator`.
+    fn meta_schema_uri(&self, interrogator: &Interrogator) -> Result<Uri, Error> {
+        match self.source().get("$schema") {
+            Some(Value::String(uri)) => {
+                return Uri::parse(uri).map_err(|err| MetaSchemaError::InvalidUri(err).into())
             }
+            Some(value) => return Err(MetaSchemaError::InvalidValueForSchema(value.clone()).into()),
+            None => {}
+        }
+        match self.meta_schema_id() {
+            Some(id) => Ok(id.as_ref().clone()),
+            None => Ok(interrogator.default_meta_schema_uri()),
         }
-        Ok(interrogator.default_meta_schema())
     }
 
-    fn set_setup(&self, fns: Vec<Box<SetupFn>>) {
+    fn set_setup(&self, fns: Vec<KeywordSetupFn>) {
         self.functions.set_setup(fns);
     }
     fn set_executors(&self, fns: Vec<Box<ExecutorFn>>) {
@@ -171,16 +674,109 @@ impl Schema {
     pub(crate) fn setup(&self, interrogator: &Interrogator) -> Result<(), Error> {
         let setup_fns = self.setup_fns();
         let mut fns = Vec::with_capacity(setup_fns.len());
-        for f in &setup_fns {
-            fns.push(f(interrogator, self)?);
+        let source = self.source();
+        let optimize = interrogator.optimizes();
+        for (keyword, f) in &setup_fns {
+            let trivial = || {
+                keyword
+                    .and_then(|k| Some(optimize::is_trivially_valid(k, source.get(k)?)))
+                    .unwrap_or(false)
+            };
+            if optimize && trivial() {
+                continue;
+            }
+            let cost = keyword
+                .and_then(|k| Some(cost::estimate(k, source.get(k)?)))
+                .unwrap_or(cost::BASE_COST);
+            let f =
+                interrogator
+                    .coverage_recorder()
+                    .recorded(self, *keyword, f(interrogator, self)?);
+            fns.push((*keyword, cost::metered(cost, f)));
+        }
+        let order = self
+            .meta_schema_id()
+            .and_then(|id| interrogator.dialect(&id))
+            .map(|dialect| dialect.order.clone())
+            .unwrap_or_default();
+        if optimize {
+            optimize::sort(&mut fns, &source, &order);
+        } else {
+            fns.sort_by_key(|(keyword, _)| order.sort_key(*keyword));
         }
-        self.set_executors(fns);
+        let keywords: Vec<_> = fns.iter().map(|(keyword, _)| *keyword).collect();
+        self.functions.set_keywords(keywords);
+        self.set_executors(order.gated(fns));
         for sub in self.sub_schemas().values() {
             sub.setup(interrogator)?;
         }
+        *self.pure.write() = Some(self.references_are_pure(interrogator));
         Ok(())
     }
 
+    /// Returns a [`CompiledKeyword`] for each keyword which will be applied
+    /// by this `Schema` and its [`SubSchema`]s, in the order they are applied.
+    ///
+    /// The keywords of a [`SubSchema`] follow those of its parent. The list is
+    /// empty until the `Schema` has been setup.
+    pub fn keywords(&self) -> Vec<CompiledKeyword> {
+        let mut keywords = Vec::new();
+        self.visit_keywords(&mut |_, keyword| keywords.push(keyword));
+        keywords
+    }
+
+    /// Calls `visit` with the owning `Schema` and a
+    /// [`CompiledKeyword`] for each keyword, in the order of
+    /// [`Schema::keywords`].
+    pub(crate) fn visit_keywords(&self, visit: &mut dyn FnMut(&Schema, CompiledKeyword)) {
+        self.walk_keywords(&Pointer::default(), visit);
+    }
+
+    fn walk_keywords(&self, location: &Pointer, visit: &mut dyn FnMut(&Schema, CompiledKeyword)) {
+        for name in self.functions.keywords().iter() {
+            let mut keyword_location = location.clone();
+            if let Some(name) = name {
+                keyword_location.push_back((*name).into());
+            }
+            visit(
+                self,
+                CompiledKeyword {
+                    name: *name,
+                    location: keyword_location,
+                    is_applicator: name.map_or(false, |n| APPLICATOR_KEYWORDS.contains(&n)),
+                },
+            );
+        }
+        let mut subs: Vec<_> = self.sub_schemas().into_iter().collect();
+        subs.sort_by(|a, b| a.0.cmp(&b.0));
+        for (key, sub) in subs {
+            let mut location = location.clone();
+            location.push_back(key.as_str().into());
+            match sub {
+                SubSchema::Single(schema) => schema.walk_keywords(&location, visit),
+                SubSchema::Array(schemas) => {
+                    for (i, schema) in schemas.iter().enumerate() {
+                        let mut location = location.clone();
+                        location.push_back(i.to_string().into());
+                        schema.walk_keywords(&location, visit);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns a schema accepting the instances which both `self` and `other`
+    /// accept. Experimental; see [`algebra`](crate::algebra).
+    pub fn intersect(&self, other: &Schema) -> Combined {
+        algebra::intersect(self, other)
+    }
+
+    /// Returns a schema accepting the instances which either `self` or
+    /// `other` accept. Experimental; see [`algebra`](crate::algebra).
+    pub fn union(&self, other: &Schema) -> Combined {
+        algebra::union(self, other)
+    }
+
     pub fn source(&self) -> Arc<Value> {
         let source = self.source.read();
         source.clone()
@@ -273,6 +869,20 @@ impl Schema {
         guard.clone()
     }
 
+    /// Returns the `"$id"` of the source of the schema exactly as written,
+    /// along with its parsed [`Uri`], if it has one. Unlike [`id`](Self::id),
+    /// it is not resolved against the id of an enclosing resource. See
+    /// [`uri`](crate::uri) for where grill normalizes URIs.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidUri`] if the `"$id"` can not be parsed.
+    pub fn raw_id(&self) -> Result<Option<RawUri>, Error> {
+        match self.source().get("$id").and_then(Value::as_str) {
+            Some(id) => Ok(Some(RawUri::parse(id)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Sets the id of the schema, returning the previous value if it exists.
     pub fn set_id(&self, id: Uri) -> Option<Arc<Uri>> {
         let mut guard = self.id.write();
@@ -301,6 +911,17 @@ impl Schema {
         references.clone()
     }
 
+    /// Returns the URIs referenced by this `Schema` and its [`SubSchema`]s.
+    pub(crate) fn references_within(&self) -> HashSet<Uri> {
+        let mut references: HashSet<Uri> = self.references().iter().cloned().collect();
+        for sub in self.sub_schemas().values() {
+            for schema in sub.schemas() {
+                references.extend(schema.references_within());
+            }
+        }
+        references
+    }
+
     /// sets schema's `dialect`, returning the previous value if it exists.
     pub fn set_meta_schema(&self, meta: &MetaSchema) -> Option<Arc<Uri>> {
         let id = meta.id();
@@ -330,11 +951,60 @@ impl Schema {
         *meta_schema_id = new_meta_schema_id;
         *references = new_references;
         *source = new_source;
+        *self.pure.write() = None;
         applicators.update(new_current, new_pending);
         functions.update(new_setup_fns, new_exec_fns);
     }
 }
 
+/// Keywords which can be evaluated while a value is serialized; each other
+/// keyword requires a [`Value`].
+pub(crate) const STREAMABLE_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "type",
+];
+
+/// Keywords whose target depends on the dynamic scope of the evaluation.
+const DYNAMIC_REFERENCE_KEYWORDS: &[&str] = &["$dynamicRef", "$recursiveRef"];
+
+pub(crate) fn contains_dynamic_reference(source: &Value) -> bool {
+    match source {
+        Value::Object(obj) => obj.iter().any(|(k, v)| {
+            DYNAMIC_REFERENCE_KEYWORDS.contains(&k.as_str()) || contains_dynamic_reference(v)
+        }),
+        Value::Array(items) => items.iter().any(contains_dynamic_reference),
+        _ => false,
+    }
+}
+
+/// Returns an error if `options` request a capability whose feature is not
+/// enabled.
+fn check_options(options: &Options) -> Result<(), FeatureDisabledError> {
+    if options.is_parallel() && !Feature::Rayon.is_enabled() {
+        return Err(FeatureDisabledError {
+            feature: Feature::Rayon,
+            capability: "parallel evaluation of subschemas".to_string(),
+        });
+    }
+    if options.compares_numbers_exactly() && !Feature::BigNum.is_enabled() {
+        return Err(FeatureDisabledError {
+            feature: Feature::BigNum,
+            capability: "exact comparison of numbers".to_string(),
+        });
+    }
+    Ok(())
+}
+
 impl std::fmt::Debug for Schema {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Schema")
@@ -358,4 +1028,70 @@ impl std::hash::Hash for Schema {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::draft::HYPER_SCHEMA_2020_12_URI;
+    use serde_json::json;
+
+    fn is_valid(schema: &Schema, instance: Value) -> bool {
+        schema
+            .evaluate(&instance, OutputFmt::Basic)
+            .unwrap()
+            .is_valid()
+    }
+
+    #[test]
+    fn applies_the_keywords_of_the_default_dialect() {
+        let interrogator = Interrogator::new();
+        let schema = Schema::new(json!({ "maximum": 10, "x-unknown": 1 }), &interrogator).unwrap();
+        assert_eq!(
+            schema.meta_schema_id().as_deref(),
+            Some(&*HYPER_SCHEMA_2020_12_URI)
+        );
+        let names: Vec<_> = schema.keywords().into_iter().map(|k| k.name).collect();
+        assert_eq!(names, vec![Some("maximum")]);
+        assert!(is_valid(&schema, json!(10)));
+        assert!(!is_valid(&schema, json!(11)));
+    }
+
+    #[test]
+    fn applies_the_keywords_of_the_declared_dialect() {
+        let interrogator = Interrogator::new();
+        // "const" is not a keyword of draft 04
+        let source = json!({ "$schema": "http://json-schema.org/draft-04/schema", "const": 1 });
+        let schema = Schema::new(source, &interrogator).unwrap();
+        assert!(is_valid(&schema, json!(2)));
+
+        let source = json!({ "$schema": "http://json-schema.org/draft-07/schema#", "const": 1 });
+        let schema = Schema::new(source, &interrogator).unwrap();
+        assert!(!is_valid(&schema, json!(2)));
+    }
+
+    #[test]
+    fn subschemas_share_the_dialect_of_their_parent() {
+        let interrogator = Interrogator::new();
+        let source = json!({
+            "$schema": "http://json-schema.org/draft-04/schema#",
+            "oneOf": [{ "const": 1 }, { "maximum": 0 }]
+        });
+        let schema = Schema::new(source, &interrogator).unwrap();
+        assert!(is_valid(&schema, json!(5)));
+        assert!(!is_valid(&schema, json!(-1)));
+    }
+
+    #[test]
+    fn rejects_an_unknown_meta_schema() {
+        let interrogator = Interrogator::new();
+        let source = json!({ "$schema": "https://example.com/unknown" });
+        let err = Schema::new(source, &interrogator).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::MetaSchema(MetaSchemaError::UnknownMetaSchema(_))
+        ));
+        let err = Schema::new(json!({ "$schema": 4 }), &interrogator).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::MetaSchema(MetaSchemaError::InvalidValueForSchema(_))
+        ));
+    }
+}
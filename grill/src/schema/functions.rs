@@ -2,10 +2,15 @@ use crate::applicator::{ExecutorFn, SetupFn};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use std::sync::Arc;
 
+/// A [`SetupFn`] paired with the keyword of the
+/// [`Applicator`](crate::Applicator) which returned it, if known.
+pub(super) type KeywordSetupFn = (Option<&'static str>, Box<SetupFn>);
+
 #[derive(Clone)]
 pub(super) struct Functions {
-    setup_fns: Arc<RwLock<Arc<Vec<Box<SetupFn>>>>>,
+    setup_fns: Arc<RwLock<Arc<Vec<KeywordSetupFn>>>>,
     executor_fns: Arc<RwLock<Arc<Vec<Box<ExecutorFn>>>>>,
+    keywords: Arc<RwLock<Arc<Vec<Option<&'static str>>>>>,
 }
 
 impl Functions {
@@ -13,19 +18,36 @@ impl Functions {
         Self {
             setup_fns: Arc::new(RwLock::new(Arc::new(Vec::new()))),
             executor_fns: Arc::new(RwLock::new(Arc::new(Vec::new()))),
+            keywords: Arc::new(RwLock::new(Arc::new(Vec::new()))),
         }
     }
 
+    /// Sets the keyword of each executor, in the order they are applied.
+    pub(super) fn set_keywords(&self, keywords: Vec<Option<&'static str>>) {
+        let mut kw = self.keywords.write();
+        *kw = Arc::new(keywords);
+    }
+
+    pub(super) fn keywords(&self) -> Arc<Vec<Option<&'static str>>> {
+        self.keywords.read().clone()
+    }
+
     pub(super) fn set_executors(&self, fns: Vec<Box<ExecutorFn>>) {
         let mut exec = self.executor_fns.write();
         *exec = Arc::new(fns);
     }
 
-    pub(super) fn set_setup(&self, fns: Vec<Box<SetupFn>>) {
+    pub(super) fn set_setup(&self, fns: Vec<KeywordSetupFn>) {
         let mut setup = self.setup_fns.write();
         *setup = Arc::new(fns);
     }
 
+    /// Returns the executors, shared rather than copied so that applying a
+    /// schema does not clone each boxed executor.
+    pub(super) fn shared_executor_fns(&self) -> Arc<Vec<Box<ExecutorFn>>> {
+        self.executor_fns.read().clone()
+    }
+
     pub(super) fn executor_fns(&self) -> Vec<Box<ExecutorFn>> {
         let v = {
             let guard = self.executor_fns.read();
@@ -34,7 +56,7 @@ impl Functions {
         v.to_vec()
     }
 
-    pub(super) fn setup_fns(&self) -> Vec<Box<SetupFn>> {
+    pub(super) fn setup_fns(&self) -> Vec<KeywordSetupFn> {
         let v = {
             let guard = self.setup_fns.read();
             guard.clone()
@@ -50,13 +72,13 @@ impl Functions {
 }
 
 pub(super) struct GuardedFunctions<'a> {
-    setup_fns: RwLockWriteGuard<'a, Arc<Vec<Box<SetupFn>>>>,
+    setup_fns: RwLockWriteGuard<'a, Arc<Vec<KeywordSetupFn>>>,
     exeec_fns: RwLockWriteGuard<'a, Arc<Vec<Box<ExecutorFn>>>>,
 }
 impl GuardedFunctions<'_> {
     pub(super) fn update(
         &mut self,
-        setup_fns: Vec<Box<SetupFn>>,
+        setup_fns: Vec<KeywordSetupFn>,
         executor_fns: Vec<Box<ExecutorFn>>,
     ) {
         *self.setup_fns = Arc::new(setup_fns);
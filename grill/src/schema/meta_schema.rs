@@ -12,6 +12,14 @@ pub struct MetaSchema {
 }
 
 impl MetaSchema {
+    /// Creates a `MetaSchema` of `schema` which evaluates schemas with
+    /// `dialect`.
+    pub(crate) fn new(schema: Schema, dialect: Dialect) -> Self {
+        Self {
+            schema,
+            dialect: Arc::new(RwLock::new(dialect)),
+        }
+    }
     pub fn id(&self) -> Option<Arc<Uri>> {
         self.schema.id()
     }
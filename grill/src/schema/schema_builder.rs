@@ -6,6 +6,7 @@ use crate::{Error, Interrogator, MetaSchema, Schema};
 pub struct SchemaBuilder {
     pub(crate) source: Value,
     pub(crate) meta_schema: Option<MetaSchema>,
+    pub(crate) meta_schema_id: Option<Uri>,
     pub(crate) base_uri: Option<AbsoluteUri>,
     pub(crate) id: Option<Uri>,
 }
@@ -17,6 +18,7 @@ impl SchemaBuilder {
             source,
             id: None,
             meta_schema: None,
+            meta_schema_id: None,
         }
     }
     #[must_use]
@@ -43,9 +45,27 @@ impl SchemaBuilder {
         self.base_uri = Some(base_uri);
         self
     }
-    /// Builds the [`Schema`] with the provided defaults.
+    /// Builds the [`Schema`] with the provided defaults and sets it up for
+    /// evaluation. See [`Schema::new`].
     pub fn build(self, interrogator: &Interrogator) -> Result<Schema, Error> {
-        let schema = Schema::new(self.source, interrogator)?;
+        let schema = self.initialize(interrogator)?;
+        schema.setup(interrogator)?;
+        Ok(schema)
+    }
+
+    /// Builds and initializes the [`Schema`] with the provided defaults
+    /// without setting it up.
+    pub(crate) fn initialize(self, interrogator: &Interrogator) -> Result<Schema, Error> {
+        let meta_schema_id = self.meta_schema_id.or_else(|| {
+            let id = self.meta_schema?.id()?;
+            Some(id.as_ref().clone())
+        });
+        let schema = Schema::initialized(self.source, meta_schema_id, interrogator)?;
+        if schema.id().is_none() {
+            if let Some(id) = self.id {
+                schema.set_id(id);
+            }
+        }
         if let Some(base_uri) = self.base_uri {
             if let Some(id) = schema.id() {
                 if id.scheme().is_none() {
@@ -54,11 +74,6 @@ impl SchemaBuilder {
                 }
             }
         }
-        if let Some(ref meta_schema) = self.meta_schema {
-            if schema.meta_schema(interrogator).is_none() {
-                schema.set_meta_schema(meta_schema);
-            }
-        }
         Ok(schema)
     }
 }
@@ -15,6 +15,13 @@ impl SubSchema {
     pub fn is_array(&self) -> bool {
         matches!(self, SubSchema::Array(_))
     }
+    /// Returns the schemas of this `SubSchema`.
+    pub(crate) fn schemas(&self) -> &[Schema] {
+        match self {
+            SubSchema::Single(schema) => std::slice::from_ref(schema),
+            SubSchema::Array(schemas) => schemas,
+        }
+    }
     pub(crate) fn setup(&self, interrogator: &Interrogator) -> Result<(), Error> {
         match self {
             SubSchema::Single(schema) => schema.setup(interrogator),
@@ -0,0 +1,350 @@
+//! Simplification of the logical structure of a schema.
+//!
+//! [`simplify`] produces a schema which accepts exactly the instances the
+//! original accepts, with fewer and flatter subschemas. It is intended to be
+//! run before bundling or code generation. The following rewrites are
+//! applied, from the innermost subschema outward:
+//!
+//! - `{}` becomes `true`.
+//! - `true` members of `"allOf"` are dropped, as are `false` members of
+//!   `"anyOf"` and `"oneOf"`.
+//! - Members of `"allOf"` which consist solely of an `"allOf"` are merged
+//!   into the parent.
+//! - `"anyOf"` and `"oneOf"` with a single member become a member of
+//!   `"allOf"`, and a schema consisting solely of an `"allOf"` with a single
+//!   member is replaced by the member.
+//! - Constant branches are folded: `"if": true` is replaced by its
+//!   `"then"`, `"if": false` by its `"else"`, `"not": false` is dropped,
+//!   and a schema with an `"allOf"` containing `false`, a `"not": true`, or
+//!   an `"anyOf"` or `"oneOf"` left without members becomes `false`.
+//! - `true` values of keywords which otherwise accept every instance, e.g.
+//!   `"additionalProperties": true`, are dropped.
+//!
+//! Subschemas which are identified by `"$id"` or an anchor, or which hold
+//! `"$defs"`, keep those keywords so that references into them still
+//! resolve, and a subschema keeps its `"$schema"`, and so its dialect, even
+//! when folded to `false`.
+//!
+//! Some subschemas are left as they are, although their own subschemas may
+//! still be simplified:
+//! - those of a schema with `"unevaluatedProperties"` or
+//!   `"unevaluatedItems"`, including the schema itself, as those keywords
+//!   depend on the annotations of the subschemas, which the rewrites do not
+//!   preserve, and
+//! - those which are the target of a reference within the schema, or which
+//!   contain one other than by `"$defs"` or `"definitions"`, so that the
+//!   reference still resolves to the same subschema.
+//!
+//! Otherwise, annotations, which do not affect validity, may differ from
+//! those of the original schema.
+//!
+//! ## Example
+//! ```
+//! use grill::simplify::simplify_value;
+//! use serde_json::json;
+//!
+//! let source = json!({
+//!     "allOf": [
+//!         true,
+//!         { "allOf": [{ "type": "object" }, { "required": ["id"] }] }
+//!     ],
+//!     "anyOf": [false, { "maxProperties": 4 }],
+//!     "additionalProperties": true
+//! });
+//! assert_eq!(
+//!     simplify_value(&source),
+//!     json!({
+//!         "allOf": [
+//!             { "type": "object" },
+//!             { "required": ["id"] },
+//!             { "maxProperties": 4 }
+//!         ]
+//!     })
+//! );
+//! assert_eq!(simplify_value(&json!({ "not": { "allOf": [{}] } })), json!(false));
+//! ```
+
+use crate::{
+    fragment,
+    lint::{ARRAY_KEYWORDS, OBJECT_KEYWORDS, SCHEMA_KEYWORDS},
+    pointer, Schema,
+};
+use serde_json::{Map, Value};
+
+/// Keywords which identify a schema or hold definitions which may be the
+/// target of a reference.
+const IDENTITY_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "id",
+    "$anchor",
+    "$dynamicAnchor",
+    "$recursiveAnchor",
+    "$defs",
+    "definitions",
+];
+
+/// Keywords whose value is a reference to a schema.
+const REFERENCE_KEYWORDS: &[&str] = &["$ref", "$dynamicRef", "$recursiveRef"];
+
+/// Keywords whose result depends on the annotations of adjacent subschemas.
+const UNEVALUATED_KEYWORDS: &[&str] = &["unevaluatedItems", "unevaluatedProperties"];
+
+/// Keywords which hold definitions, which the rewrites never move or drop.
+const DEFINITION_KEYWORDS: &[&str] = &["$defs", "definitions"];
+
+/// Keywords whose value, when `true`, has no effect on validity.
+const TRIVIAL_WHEN_TRUE: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "items",
+    "propertyNames",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Returns a simplified copy of the source of `schema`.
+pub fn simplify(schema: &Schema) -> Value {
+    simplify_value(&schema.source())
+}
+
+/// Returns a simplified copy of the schema `source`.
+pub fn simplify_value(source: &Value) -> Value {
+    let mut targets = Vec::new();
+    collect_targets(source, source, &mut targets);
+    let mut source = source.clone();
+    simplify_in_place(&mut source, "", &targets, false);
+    source
+}
+
+/// Simplifies `value`, at `location`, and its subschemas. `preserve` is set
+/// if `value` is a subschema of a schema with an `UNEVALUATED_KEYWORDS`.
+fn simplify_in_place(value: &mut Value, location: &str, targets: &[String], preserve: bool) {
+    let obj = match value.as_object_mut() {
+        Some(obj) => obj,
+        None => return,
+    };
+    let preserve = preserve || UNEVALUATED_KEYWORDS.iter().any(|k| obj.contains_key(*k));
+    let child = |tokens: &[&str]| {
+        let mut location = location.to_string();
+        for token in tokens {
+            location.push('/');
+            location.push_str(&pointer::escape(token));
+        }
+        location
+    };
+    for keyword in SCHEMA_KEYWORDS {
+        if let Some(sub) = obj.get_mut(*keyword) {
+            simplify_in_place(sub, &child(&[keyword]), targets, preserve);
+        }
+    }
+    for keyword in ARRAY_KEYWORDS {
+        if let Some(subs) = obj.get_mut(*keyword).and_then(Value::as_array_mut) {
+            for (i, sub) in subs.iter_mut().enumerate() {
+                let location = child(&[keyword, &i.to_string()]);
+                simplify_in_place(sub, &location, targets, preserve);
+            }
+        }
+    }
+    for keyword in OBJECT_KEYWORDS {
+        if let Some(subs) = obj.get_mut(*keyword).and_then(Value::as_object_mut) {
+            for (name, sub) in subs.iter_mut() {
+                simplify_in_place(sub, &child(&[keyword, name]), targets, preserve);
+            }
+        }
+    }
+    if preserve || is_pinned(location, targets) {
+        return;
+    }
+    if let Some(replacement) = reduce(obj) {
+        *value = replacement;
+    }
+}
+
+/// Pushes the location, as a JSON Pointer, of the target of each reference
+/// within `value` which is a fragment of `document`, e.g. `"#/$defs/a"` or
+/// `"#name"`, onto `targets`.
+fn collect_targets(document: &Value, value: &Value, targets: &mut Vec<String>) {
+    match value {
+        Value::Object(obj) => {
+            for (keyword, value) in obj {
+                let fragment = match value.as_str().and_then(|r| r.strip_prefix('#')) {
+                    Some(fragment) if REFERENCE_KEYWORDS.contains(&keyword.as_str()) => fragment,
+                    _ => {
+                        collect_targets(document, value, targets);
+                        continue;
+                    }
+                };
+                if let Ok(Some(located)) = fragment::locate(document, fragment, false) {
+                    targets.push(located.location.as_str().to_string());
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_targets(document, item, targets);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns `true` if the subschema at `location` is, or is within, the
+/// target of a reference, or contains one other than by
+/// `DEFINITION_KEYWORDS`.
+fn is_pinned(location: &str, targets: &[String]) -> bool {
+    targets.iter().any(|target| {
+        if target == location || location.starts_with(&format!("{}/", target)) {
+            return true;
+        }
+        match target
+            .strip_prefix(location)
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            Some(rest) => {
+                let next = rest.split('/').next().unwrap_or_default();
+                !DEFINITION_KEYWORDS.contains(&next)
+            }
+            None => false,
+        }
+    })
+}
+
+/// Applies the rewrites to `obj`, whose subschemas have been simplified,
+/// returning the schema which replaces it if it is no longer an object.
+fn reduce(obj: &mut Map<String, Value>) -> Option<Value> {
+    let mut all_of = match obj.remove("allOf") {
+        Some(Value::Array(members)) => members,
+        Some(other) => {
+            obj.insert("allOf".into(), other);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    if let Some(Value::Array(members)) = obj.remove("anyOf") {
+        if !members.iter().any(is_true) {
+            let mut members: Vec<Value> = members.into_iter().filter(|m| !is_false(m)).collect();
+            match members.len() {
+                0 => return fold_false(obj),
+                1 => all_of.append(&mut members),
+                _ => {
+                    obj.insert("anyOf".into(), members.into());
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Array(members)) = obj.remove("oneOf") {
+        let mut members: Vec<Value> = members.into_iter().filter(|m| !is_false(m)).collect();
+        if members.iter().filter(|m| is_true(m)).count() > 1 {
+            return fold_false(obj);
+        }
+        match members.len() {
+            0 => return fold_false(obj),
+            1 => all_of.append(&mut members),
+            _ => {
+                obj.insert("oneOf".into(), members.into());
+            }
+        }
+    }
+
+    match obj.get("not") {
+        Some(Value::Bool(true)) => return fold_false(obj),
+        Some(Value::Bool(false)) => {
+            obj.remove("not");
+        }
+        _ => {}
+    }
+
+    match obj.get("if").cloned() {
+        None => {
+            obj.remove("then");
+            obj.remove("else");
+        }
+        Some(Value::Bool(condition)) => {
+            obj.remove("if");
+            let (taken, dropped) = if condition {
+                ("then", "else")
+            } else {
+                ("else", "then")
+            };
+            obj.remove(dropped);
+            if let Some(branch) = obj.remove(taken) {
+                all_of.push(branch);
+            }
+        }
+        Some(_) => {
+            for branch in ["then", "else"] {
+                if obj.get(branch).map_or(false, is_true) {
+                    obj.remove(branch);
+                }
+            }
+            if !obj.contains_key("then") && !obj.contains_key("else") {
+                obj.remove("if");
+            }
+        }
+    }
+
+    for keyword in TRIVIAL_WHEN_TRUE {
+        if obj.get(*keyword).map_or(false, is_true) {
+            obj.remove(*keyword);
+        }
+    }
+    if let Some(Value::Object(deps)) = obj.get_mut("dependentSchemas") {
+        deps.retain(|_, sub| !is_true(sub));
+        if deps.is_empty() {
+            obj.remove("dependentSchemas");
+        }
+    }
+
+    let mut flattened = Vec::with_capacity(all_of.len());
+    for member in all_of {
+        if is_false(&member) {
+            return fold_false(obj);
+        }
+        if is_true(&member) {
+            continue;
+        }
+        match member {
+            Value::Object(mut inner) if inner.len() == 1 && inner.contains_key("allOf") => {
+                match inner.remove("allOf") {
+                    Some(Value::Array(members)) => flattened.extend(members),
+                    Some(other) => flattened.push(other),
+                    None => {}
+                }
+            }
+            member => flattened.push(member),
+        }
+    }
+    if !flattened.is_empty() {
+        if obj.is_empty() && flattened.len() == 1 {
+            return flattened.pop();
+        }
+        obj.insert("allOf".into(), flattened.into());
+    }
+
+    if obj.is_empty() {
+        return Some(Value::Bool(true));
+    }
+    None
+}
+
+/// Reduces `obj`, which accepts no instance, to `false`, or to its identity
+/// keywords and an `"allOf"` of `false` if it has any.
+fn fold_false(obj: &mut Map<String, Value>) -> Option<Value> {
+    obj.retain(|k, _| IDENTITY_KEYWORDS.contains(&k.as_str()));
+    if obj.is_empty() {
+        return Some(Value::Bool(false));
+    }
+    obj.insert("allOf".into(), Value::Array(vec![Value::Bool(false)]));
+    None
+}
+
+fn is_true(value: &Value) -> bool {
+    matches!(value, Value::Bool(true))
+}
+
+fn is_false(value: &Value) -> bool {
+    matches!(value, Value::Bool(false))
+}
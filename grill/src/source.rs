@@ -0,0 +1,704 @@
+use crate::{fragment, uri::UriStr, urn, Error};
+use jsonptr::Pointer;
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use uniresid::{AbsoluteUri, Uri};
+
+/// Keywords whose value is a URI of another schema.
+const REFERENCE_KEYWORDS: &[&str] = &["$ref", "$dynamicRef", "$recursiveRef", "$schema"];
+
+/// Keywords whose value is instance data rather than schema.
+const DATA_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+/// Keywords whose value is an object of subschemas, keyed by name rather than
+/// by keyword.
+const SCHEMA_MAP_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "properties",
+    "patternProperties",
+    "dependentSchemas",
+    "dependencies",
+];
+
+/// Unparsed JSON, such as a memory-mapped file.
+type Bytes = Arc<dyn AsRef<[u8]> + Send + Sync>;
+
+/// How a source document came to be in a [`Sources`] store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Provenance {
+    /// Inserted as a parsed document, e.g. with
+    /// [`Interrogator::source_value`](crate::Interrogator::source_value).
+    #[default]
+    Added,
+    /// Inserted unparsed with [`Sources::insert_lazy`] or memory-mapped.
+    Lazy,
+    /// Retrieved by a [`Resolver`](crate::Resolver) while compiling or
+    /// vendoring.
+    Resolved,
+    /// Registered by a [plugin](crate::plugin).
+    Plugin,
+    /// Restored from a sealed snapshot.
+    Restored,
+    /// Copied from another [`Interrogator`](crate::Interrogator) with
+    /// [`Interrogator::import`](crate::Interrogator::import).
+    Imported,
+}
+
+/// A summary of a source document, as returned by
+/// [`Interrogator::sources`](crate::Interrogator::sources).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceEntry {
+    /// The URI of the document.
+    pub uri: AbsoluteUri,
+    /// The size of the document in bytes, recorded when it was inserted: that
+    /// of the unparsed document if it was inserted lazily, otherwise that of
+    /// its compact JSON serialization.
+    pub size: usize,
+    /// The id of the meta schema of the document's [`Dialect`], if the
+    /// document has been parsed and its `"$schema"` identifies a registered
+    /// dialect.
+    ///
+    /// [`Dialect`]: crate::dialect::Dialect
+    pub dialect: Option<Uri>,
+    /// How the document came to be a source.
+    pub provenance: Provenance,
+    /// Whether the document was inserted lazily and has yet to be parsed.
+    pub pending: bool,
+}
+
+/// A store of source documents, keyed by the [`AbsoluteUri`] they were
+/// retrieved from.
+///
+/// Documents may be inserted unparsed with [`insert_lazy`](Self::insert_lazy),
+/// in which case they are parsed upon first use.
+///
+/// Cloning `Sources` is cheap; clones share the same store. Use
+/// [`fork`](Self::fork) for an independent copy.
+///
+/// ## Example
+/// ```
+/// use grill::Sources;
+/// use uniresid::AbsoluteUri;
+///
+/// let sources = Sources::new();
+/// let uri = AbsoluteUri::parse("https://example.com/schema.json").unwrap();
+/// sources.insert_lazy(uri.clone(), br#"{"type": "string"}"#);
+/// assert!(sources.is_pending(&uri));
+/// assert_eq!(sources.get(&uri).unwrap().unwrap()["type"], "string");
+/// assert!(!sources.is_pending(&uri));
+/// ```
+#[derive(Clone, Default)]
+pub struct Sources {
+    store: Arc<RwLock<Arc<Store>>>,
+}
+
+#[derive(Clone, Default)]
+struct Store {
+    docs: HashMap<AbsoluteUri, Arc<Value>>,
+    lazy: HashMap<AbsoluteUri, Bytes>,
+    provenance: HashMap<AbsoluteUri, Provenance>,
+    /// The size in bytes of each document when it was inserted.
+    sizes: HashMap<AbsoluteUri, usize>,
+    /// The schema resources of each parsed document, indexed when the
+    /// document is parsed so that resolving an embedded resource does not
+    /// walk every document.
+    resources: HashMap<AbsoluteUri, Arc<[Resource]>>,
+}
+
+/// A schema resource within a source document: the document itself or a
+/// subschema identified by `"$id"`.
+#[derive(Debug, Clone)]
+struct Resource {
+    /// The id of the resource, without fragment, in the form returned by
+    /// [`urn::normalize`].
+    id: String,
+    /// The location of the resource within the document.
+    location: Pointer,
+    /// The name of each `"$dynamicAnchor"` the resource declares.
+    dynamic_anchors: Vec<String>,
+}
+
+/// Returns the schema resources of `document`, retrieved from `uri`.
+fn index(uri: &AbsoluteUri, document: &Value) -> Arc<[Resource]> {
+    let resource = |id: &str, location: Pointer| {
+        let dynamic_anchors = document
+            .pointer(location.as_str())
+            .map(|r| fragment::dynamic_anchors(r).into_keys().collect())
+            .unwrap_or_default();
+        Resource {
+            id: urn::normalize(id).into_owned(),
+            location,
+            dynamic_anchors,
+        }
+    };
+    let root = resource(&uri.to_string(), Pointer::default());
+    let embedded = fragment::resources(document, uri.uri())
+        .into_iter()
+        .map(|(id, location)| resource(&id, location));
+    std::iter::once(root).chain(embedded).collect()
+}
+
+impl Sources {
+    /// Creates and returns a new, empty `Sources` store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an independent copy of this `Sources` store.
+    ///
+    /// Forking is O(1); the documents are shared until either store is next
+    /// modified, at which point that store copies its index of documents.
+    /// The documents themselves are never copied.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        Self {
+            store: Arc::new(RwLock::new(self.read())),
+        }
+    }
+
+    fn read(&self) -> Arc<Store> {
+        self.store.read().clone()
+    }
+
+    fn write<T>(&self, f: impl FnOnce(&mut Store) -> T) -> T {
+        let mut store = self.store.write();
+        f(Arc::make_mut(&mut store))
+    }
+
+    /// Returns the source document at `uri` if it exists, parsing it first if
+    /// it was inserted lazily.
+    ///
+    /// # Errors
+    /// Returns [`Error::Serde`] if the document was inserted lazily and is
+    /// not valid JSON.
+    pub fn get(&self, uri: &AbsoluteUri) -> Result<Option<Arc<Value>>, Error> {
+        let bytes = {
+            let store = self.store.read();
+            if let Some(doc) = store.docs.get(uri) {
+                return Ok(Some(doc.clone()));
+            }
+            match store.lazy.get(uri) {
+                Some(bytes) => bytes.clone(),
+                None => return Ok(None),
+            }
+        };
+        let doc = Arc::new(serde_json::from_slice::<Value>((*bytes).as_ref())?);
+        let resources = index(uri, &doc);
+        Ok(Some(self.write(|store| {
+            store.lazy.remove(uri);
+            // another thread may have parsed the document in the meantime
+            store.resources.entry(uri.clone()).or_insert(resources);
+            store.docs.entry(uri.clone()).or_insert(doc).clone()
+        })))
+    }
+
+    /// Inserts `document` at `uri`, returning the previous document if one
+    /// existed and had been parsed.
+    pub fn insert(&self, uri: AbsoluteUri, document: Value) -> Option<Arc<Value>> {
+        self.insert_from(uri, document, Provenance::Added)
+    }
+
+    /// Inserts `document` at `uri`, recording its `provenance`.
+    pub(crate) fn insert_from(
+        &self,
+        uri: AbsoluteUri,
+        document: Value,
+        provenance: Provenance,
+    ) -> Option<Arc<Value>> {
+        let resources = index(&uri, &document);
+        let size = serialized_size(&document);
+        self.write(|store| {
+            store.lazy.remove(&uri);
+            store.provenance.insert(uri.clone(), provenance);
+            store.sizes.insert(uri.clone(), size);
+            store.resources.insert(uri.clone(), resources);
+            store.docs.insert(uri, Arc::new(document))
+        })
+    }
+
+    /// Inserts the unparsed JSON `bytes` at `uri`. The document is parsed
+    /// upon first retrieval.
+    pub fn insert_lazy(&self, uri: AbsoluteUri, bytes: impl AsRef<[u8]> + Send + Sync + 'static) {
+        self.write(|store| {
+            store.docs.remove(&uri);
+            store.resources.remove(&uri);
+            store.provenance.insert(uri.clone(), Provenance::Lazy);
+            store.sizes.insert(uri.clone(), bytes.as_ref().len());
+            store.lazy.insert(uri, Arc::new(bytes));
+        });
+    }
+
+    /// Memory-maps the file at `path` and inserts it at `uri`. The document
+    /// is parsed upon first retrieval.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while it is mapped; see
+    /// [`memmap2::Mmap`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn insert_mmap(&self, uri: AbsoluteUri, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = fs::File::open(path)?;
+        let mmap = memmap2::Mmap::map(&file)?;
+        self.insert_lazy(uri, mmap);
+        Ok(())
+    }
+
+    /// Removes the source document at `uri`, returning `true` if one existed.
+    pub fn remove(&self, uri: &AbsoluteUri) -> bool {
+        self.write(|store| {
+            store.provenance.remove(uri);
+            store.sizes.remove(uri);
+            store.resources.remove(uri);
+            store.docs.remove(uri).is_some() | store.lazy.remove(uri).is_some()
+        })
+    }
+
+    /// Returns the [`Provenance`] of the source document at `uri`, if it
+    /// exists.
+    pub fn provenance(&self, uri: &AbsoluteUri) -> Option<Provenance> {
+        if !self.contains(uri) {
+            return None;
+        }
+        let store = self.store.read();
+        Some(store.provenance.get(uri).copied().unwrap_or_default())
+    }
+
+    /// Returns `true` if a source document exists at `uri`.
+    pub fn contains(&self, uri: &AbsoluteUri) -> bool {
+        let store = self.store.read();
+        store.docs.contains_key(uri) || store.lazy.contains_key(uri)
+    }
+
+    /// Returns `true` if the source document at `uri` was inserted lazily
+    /// and has yet to be parsed.
+    pub fn is_pending(&self, uri: &AbsoluteUri) -> bool {
+        self.store.read().lazy.contains_key(uri)
+    }
+
+    /// Returns the number of source documents, including those yet to be
+    /// parsed.
+    pub fn len(&self) -> usize {
+        let store = self.store.read();
+        store.docs.len() + store.lazy.len()
+    }
+
+    /// Returns `true` if there are no source documents.
+    pub fn is_empty(&self) -> bool {
+        let store = self.store.read();
+        store.docs.is_empty() && store.lazy.is_empty()
+    }
+
+    /// Writes each source document as pretty-printed JSON to a file within
+    /// `dir`, at the path given by [`export_path`]. Returns the path of each
+    /// file written, ordered by URI.
+    pub fn export(&self, dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let mut written = Vec::with_capacity(self.len());
+        for (path, doc) in self.exportable()? {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, doc)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Writes each source document as pretty-printed JSON to a tar archive,
+    /// at the path given by [`export_path`], returning `writer`.
+    #[cfg(feature = "tar")]
+    pub fn export_tar<W: io::Write>(&self, writer: W) -> io::Result<W> {
+        let mut builder = tar::Builder::new(writer);
+        for (path, doc) in self.exportable()? {
+            let mut header = tar::Header::new_gnu();
+            let size = crate::number::usize_to_u64(doc.len(), "source document")
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, doc.as_slice())?;
+        }
+        builder.into_inner()
+    }
+
+    /// Returns a [`SourceEntry`] for each source document whose URI starts
+    /// with `prefix`, ordered by URI. Documents inserted lazily are not
+    /// parsed. The `dialect` of each entry is the `"$schema"` of the
+    /// document, if any.
+    pub(crate) fn entries(&self, prefix: &str) -> Vec<SourceEntry> {
+        let store = self.read();
+        let matches = |uri: &&AbsoluteUri| uri.to_string().starts_with(prefix);
+        let entry = |uri: &AbsoluteUri, dialect: Option<Uri>, pending: bool| SourceEntry {
+            uri: uri.clone(),
+            size: store.sizes.get(uri).copied().unwrap_or_default(),
+            dialect,
+            provenance: store.provenance.get(uri).copied().unwrap_or_default(),
+            pending,
+        };
+        let mut entries: Vec<SourceEntry> = store
+            .docs
+            .iter()
+            .filter(|(uri, _)| matches(uri))
+            .map(|(uri, doc)| {
+                let dialect = doc
+                    .get("$schema")
+                    .and_then(Value::as_str)
+                    .and_then(|id| Uri::parse(id).ok());
+                entry(uri, dialect, false)
+            })
+            .chain(
+                store
+                    .lazy
+                    .keys()
+                    .filter(matches)
+                    .map(|uri| entry(uri, None, true)),
+            )
+            .collect();
+        entries.sort_by_cached_key(|entry| entry.uri.to_string());
+        entries
+    }
+
+    /// Returns each source document, keyed by URI. Documents inserted lazily
+    /// are parsed.
+    pub(crate) fn documents(&self) -> Result<Vec<(AbsoluteUri, Arc<Value>)>, Error> {
+        self.parse_pending()?;
+        Ok(self
+            .read()
+            .docs
+            .iter()
+            .map(|(uri, doc)| (uri.clone(), doc.clone()))
+            .collect())
+    }
+
+    /// Returns each document, as with [`documents`](Self::documents), along
+    /// with the URI of each document inserted lazily which could not be
+    /// parsed and is left as it was.
+    pub(crate) fn documents_lenient(&self) -> (Vec<(AbsoluteUri, Arc<Value>)>, Vec<AbsoluteUri>) {
+        let pending: Vec<AbsoluteUri> = self.read().lazy.keys().cloned().collect();
+        let unparsed = pending
+            .into_iter()
+            .filter(|uri| self.get(uri).is_err())
+            .collect();
+        let documents = self
+            .read()
+            .docs
+            .iter()
+            .map(|(uri, doc)| (uri.clone(), doc.clone()))
+            .collect();
+        (documents, unparsed)
+    }
+
+    /// Returns the schema resource identified by `id`, which has no fragment:
+    /// either a source document retrieved from `id` or a resource embedded
+    /// within one. URNs are compared in the form returned by
+    /// [`urn::normalize`]. Documents inserted lazily are parsed.
+    pub(crate) fn resource(&self, id: &str) -> Result<Option<Arc<Value>>, Error> {
+        let (uri, location) = match self.locate(id)? {
+            Some(located) => located,
+            None => return Ok(None),
+        };
+        let document = match self.read().docs.get(&uri) {
+            Some(document) => document.clone(),
+            None => return Ok(None),
+        };
+        if location == Pointer::default() {
+            return Ok(Some(document));
+        }
+        Ok(document.pointer(location.as_str()).cloned().map(Arc::new))
+    }
+
+    /// Returns the URI of the source document containing the schema resource
+    /// identified by `id`, which has no fragment, and the location of the
+    /// resource within it. See [`resource`](Self::resource).
+    pub(crate) fn locate(&self, id: &str) -> Result<Option<(AbsoluteUri, Pointer)>, Error> {
+        self.parse_pending()?;
+        let id = urn::normalize(id);
+        let store = self.read();
+        for (uri, resources) in &store.resources {
+            if !store.docs.contains_key(uri) {
+                continue;
+            }
+            if let Some(resource) = resources.iter().find(|r| r.id == id) {
+                return Ok(Some((uri.clone(), resource.location.clone())));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the id, without fragment, of each schema resource which
+    /// declares the `"$dynamicAnchor"` `name`. Documents inserted lazily are
+    /// parsed.
+    pub(crate) fn dynamic_anchor_resources(&self, name: &str) -> Result<Vec<Uri>, Error> {
+        self.parse_pending()?;
+        let store = self.read();
+        let mut resources = Vec::new();
+        for resource in store.resources.values().flat_map(|r| r.iter()) {
+            if !resource.dynamic_anchors.iter().any(|anchor| anchor == name) {
+                continue;
+            }
+            if let Ok(id) = Uri::parse(&resource.id) {
+                if !resources.contains(&id) {
+                    resources.push(id);
+                }
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Parses each document inserted lazily.
+    fn parse_pending(&self) -> Result<(), Error> {
+        let pending: Vec<AbsoluteUri> = self.read().lazy.keys().cloned().collect();
+        for uri in &pending {
+            self.get(uri)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the relative path and serialized form of each document,
+    /// ordered by URI. Documents inserted lazily are parsed.
+    fn exportable(&self) -> io::Result<Vec<(PathBuf, Vec<u8>)>> {
+        let pending: Vec<AbsoluteUri> = self.read().lazy.keys().cloned().collect();
+        for uri in &pending {
+            self.get(uri)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        }
+        let mut docs: Vec<(String, PathBuf, Arc<Value>)> = self
+            .read()
+            .docs
+            .iter()
+            .map(|(uri, doc)| (uri.to_string(), export_path(uri), doc.clone()))
+            .collect();
+        docs.sort_by(|a, b| a.0.cmp(&b.0));
+        docs.into_iter()
+            .map(|(_, path, doc)| Ok((path, serde_json::to_vec_pretty(doc.as_ref())?)))
+            .collect()
+    }
+}
+
+/// Returns the relative path a source document retrieved from `uri` is
+/// exported to.
+///
+/// The path consists of the host (or the scheme, if the URI has no
+/// authority), suffixed with `_<port>` if a port is present, followed by the
+/// segments of the URI's path. Paths which end in `/` are written to
+/// `index.json`, and `.json` is appended to a final segment without an
+/// extension. Segments of `.` and `..` are replaced with `_`. The query and
+/// fragment are not included.
+///
+/// Distinct URIs are exported to distinct paths. Unless the URI is an
+/// `https` URI without a port or query whose host and segments are written
+/// unchanged, and whose final segment has an extension, the file name is
+/// suffixed with `~` and a hash of the URI, without its fragment, as the
+/// path alone could be shared with another URI.
+///
+/// ## Example
+/// ```
+/// use grill::source::export_path;
+/// use std::path::PathBuf;
+/// use uniresid::AbsoluteUri;
+///
+/// let uri = AbsoluteUri::parse("https://example.com/schemas/user.json").unwrap();
+/// assert_eq!(
+///     export_path(&uri),
+///     ["example.com", "schemas", "user.json"].iter().collect::<PathBuf>()
+/// );
+///
+/// let uri = AbsoluteUri::parse("https://example.com:8080/schemas/user").unwrap();
+/// let path = export_path(&uri);
+/// assert!(path.starts_with("example.com_8080/schemas"));
+/// let name = path.file_name().unwrap().to_str().unwrap();
+/// assert!(name.starts_with("user~") && name.ends_with(".json"));
+/// ```
+pub fn export_path(uri: &AbsoluteUri) -> PathBuf {
+    let host = match uri.host_to_string() {
+        Ok(Some(host)) if !host.is_empty() => Some(host),
+        _ => None,
+    };
+    let mut root = host.clone().unwrap_or_else(|| uri.scheme().to_string());
+    if let Some(port) = uri.port() {
+        root = format!("{}_{}", root, port);
+    }
+    let mut path = PathBuf::from(sanitize(&root));
+    let segments: Vec<String> = uri
+        .path()
+        .iter()
+        .map(|s| String::from_utf8_lossy(s).into_owned())
+        .collect();
+    // the empty segment before the leading '/' of an absolute path
+    let segments = match segments.split_first() {
+        Some((first, rest)) if first.is_empty() => rest,
+        _ => &segments[..],
+    };
+    let (last, parents) = match segments.split_last() {
+        Some((last, parents)) => (last.as_str(), parents),
+        None => ("", &[][..]),
+    };
+    for segment in parents.iter().filter(|s| !s.is_empty()) {
+        path.push(sanitize(segment));
+    }
+    let split = last
+        .rsplit_once('.')
+        .filter(|(stem, extension)| !stem.is_empty() && !extension.is_empty());
+    let exact = uri.scheme() == "https"
+        && uri.port().is_none()
+        && uri.query().is_none()
+        && host.map_or(false, |host| sanitize(&host) == host)
+        && parents.iter().all(|s| !s.is_empty() && sanitize(s) == *s)
+        && !last.contains('~')
+        && sanitize(last) == last
+        && split.is_some();
+    if exact {
+        path.push(last);
+        return path;
+    }
+    let (stem, extension) = match split {
+        _ if last.is_empty() => ("index", "json"),
+        Some(split) => split,
+        None => (last, "json"),
+    };
+    let uri = without_fragment(uri).to_string();
+    path.push(format!(
+        "{}~{:016x}.{}",
+        sanitize(stem),
+        fnv1a(uri.as_bytes()),
+        sanitize(extension)
+    ));
+    path
+}
+
+fn sanitize(segment: &str) -> String {
+    match segment {
+        "." | ".." => "_".to_string(),
+        s => s.replace(['/', '\\', ':'], "_"),
+    }
+}
+
+/// Returns the 64-bit FNV-1a hash of `bytes`, which, unlike the hashers of
+/// `std`, is stable across platforms and releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Returns the URI, without fragment, of each document other than `uri`
+/// which `document` references, ordered by URI. Documents embedded within
+/// `document` by `"$id"`, or `"id"` as in draft 4, are not included.
+pub fn dependencies(uri: &AbsoluteUri, document: &Value) -> Vec<AbsoluteUri> {
+    let mut embedded = Vec::new();
+    let mut referenced = Vec::new();
+    walk(document, uri.uri(), &mut embedded, &mut referenced);
+    let this = without_fragment(uri);
+    let mut dependencies: Vec<AbsoluteUri> = Vec::new();
+    for reference in referenced {
+        let mut reference = match AbsoluteUri::parse(&reference.to_string()) {
+            Ok(reference) => reference,
+            Err(_) => continue,
+        };
+        reference.set_fragment(None);
+        if reference != this
+            && !embedded.contains(&reference.to_string())
+            && !dependencies.contains(&reference)
+        {
+            dependencies.push(reference);
+        }
+    }
+    dependencies.sort_by_key(ToString::to_string);
+    dependencies
+}
+
+fn walk(value: &Value, base: &Uri, embedded: &mut Vec<String>, referenced: &mut Vec<Uri>) {
+    match value {
+        Value::Object(obj) => {
+            let mut base = base.clone();
+            let id = obj
+                .get("$id")
+                .or_else(|| obj.get("id"))
+                .and_then(Value::as_str);
+            if let Some(id) = id {
+                if let Ok(id) = Uri::parse(id) {
+                    base = base.resolve(&id);
+                    let id = base.to_string();
+                    embedded.push(id.split('#').next().unwrap_or_default().to_string());
+                }
+            }
+            for (keyword, value) in obj {
+                if DATA_KEYWORDS.contains(&keyword.as_str()) {
+                    continue;
+                }
+                match value {
+                    Value::String(reference) if REFERENCE_KEYWORDS.contains(&keyword.as_str()) => {
+                        // a fragment refers to the resource it appears in,
+                        // which is never a dependency, so is not resolved;
+                        // any other reference is parsed as `"$ref"` parses
+                        // it, so that the two agree on what is referenced
+                        if matches!(UriStr::parse(reference), Ok(r) if r.is_fragment_only()) {
+                            continue;
+                        }
+                        if let Ok(reference) = Uri::parse(reference) {
+                            referenced.push(base.resolve(&reference));
+                        }
+                    }
+                    // names of subschemas, such as a property named "enum",
+                    // are not keywords
+                    Value::Object(subschemas)
+                        if SCHEMA_MAP_KEYWORDS.contains(&keyword.as_str()) =>
+                    {
+                        for subschema in subschemas.values() {
+                            walk(subschema, &base, embedded, referenced);
+                        }
+                    }
+                    value => walk(value, &base, embedded, referenced),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                walk(item, base, embedded, referenced);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the size in bytes of the compact JSON serialization of
+/// `document`, without buffering it.
+fn serialized_size(document: &Value) -> usize {
+    struct Counter(usize);
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut counter = Counter(0);
+    match serde_json::to_writer(&mut counter, document) {
+        Ok(()) => counter.0,
+        Err(_) => 0,
+    }
+}
+
+pub(crate) fn without_fragment(uri: &AbsoluteUri) -> AbsoluteUri {
+    let mut uri = uri.clone();
+    uri.set_fragment(None);
+    uri
+}
+
+impl fmt::Debug for Sources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sources")
+            .field("len", &self.len())
+            .field("pending", &self.store.read().lazy.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,246 @@
+//! Serializable snapshots of the state of an
+//! [`Interrogator`](crate::Interrogator) which are sealed with a content
+//! digest and, optionally, a signature.
+//!
+//! Compiled [`Schema`](crate::Schema)s consist of closures and can not be
+//! serialized; a [`Snapshot`] instead captures the source documents and
+//! settings from which they are compiled. Before a snapshot is written to
+//! shared storage (e.g. a cache bucket), it is sealed with
+//! [`Interrogator::seal`](crate::Interrogator::seal), which computes the
+//! SHA-256 digest of its canonical serialization and, if a [`Signer`] is
+//! provided, signs the digest.
+//!
+//! [`Interrogator::restore`](crate::Interrogator::restore) recomputes the
+//! digest and, if a [`Verifier`] is provided, verifies the signature before
+//! any of the snapshot is applied. A [`Sealed`] snapshot which has been
+//! modified fails with [`Error::StateVerification`](crate::Error::StateVerification).
+//!
+//! The digest alone detects corruption; only a signature detects tampering,
+//! as whoever can modify the snapshot can also recompute its digest.
+//!
+//! Requires the `"state"` feature.
+//!
+//! ## Example
+//! ```
+//! use grill::{state::{Signer, Verifier}, uri::AbsoluteUri, Interrogator};
+//! use serde_json::json;
+//!
+//! struct Key(&'static [u8]);
+//! impl Signer for Key {
+//!     fn key_id(&self) -> String {
+//!         "example".to_string()
+//!     }
+//!     fn sign(&self, digest: &[u8]) -> Vec<u8> {
+//!         digest.iter().zip(self.0.iter().cycle()).map(|(d, k)| d ^ k).collect()
+//!     }
+//! }
+//! impl Verifier for Key {
+//!     fn verify(&self, key_id: &str, digest: &[u8], signature: &[u8]) -> bool {
+//!         key_id == "example" && self.sign(digest) == signature
+//!     }
+//! }
+//!
+//! let key = Key(b"secret");
+//! let interrogator = Interrogator::new();
+//! let uri = AbsoluteUri::parse("https://example.com/schema.json").unwrap();
+//! interrogator.source_value(uri.clone(), json!({ "type": "string" }));
+//!
+//! let sealed = interrogator.seal(Some(&key)).unwrap();
+//! let restored = Interrogator::new();
+//! restored.restore(&sealed, Some(&key)).unwrap();
+//! assert_eq!(restored.source(&uri).unwrap().unwrap()["type"], "string");
+//!
+//! let mut tampered = sealed.clone();
+//! tampered.snapshot.sources.insert(uri.to_string(), json!(true));
+//! assert!(Interrogator::new().restore(&tampered, Some(&key)).unwrap_err().is_state_verification());
+//! ```
+
+use crate::error::StateVerificationError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// The version of the [`Snapshot`] format.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The source documents and settings of an
+/// [`Interrogator`](crate::Interrogator).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    /// The version of the snapshot format; see [`SNAPSHOT_VERSION`].
+    pub version: u32,
+    /// The source documents, keyed by the URI they were retrieved from.
+    pub sources: BTreeMap<String, Value>,
+    /// The URI of the default meta schema.
+    pub default_meta_schema: String,
+    /// The base URI, if set.
+    pub base_uri: Option<String>,
+    /// Whether identifiers are normalized to Unicode Normalization Form C.
+    pub normalize_identifiers: bool,
+    /// Whether fragments which combine an anchor with a JSON Pointer are
+    /// resolved leniently.
+    pub lenient_fragments: bool,
+}
+
+impl Snapshot {
+    /// Returns the SHA-256 digest of the canonical serialization of this
+    /// `Snapshot`.
+    ///
+    /// The keys of every object, including those within source documents,
+    /// are serialized in sorted order, so the digest is stable regardless of
+    /// the order documents were added in or whether `serde_json`'s
+    /// `preserve_order` feature is enabled.
+    pub fn digest(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let mut bytes = Vec::new();
+        write_canonical(&serde_json::to_value(self)?, &mut bytes)?;
+        Ok(Sha256::digest(&bytes).to_vec())
+    }
+}
+
+/// Writes `value` to `out` as compact JSON with the keys of each object in
+/// sorted order.
+fn write_canonical(value: &Value, out: &mut Vec<u8>) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push(b'{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, key)?;
+                out.push(b':');
+                write_canonical(value, out)?;
+            }
+            out.push(b'}');
+        }
+        scalar => serde_json::to_writer(&mut *out, scalar)?,
+    }
+    Ok(())
+}
+
+/// A [`Snapshot`] sealed with its digest and an optional [`Signature`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sealed {
+    /// The sealed snapshot.
+    pub snapshot: Snapshot,
+    /// The hex-encoded SHA-256 digest of `snapshot`.
+    pub digest: String,
+    /// The signature of the digest, if the snapshot was signed.
+    pub signature: Option<Signature>,
+}
+
+impl Sealed {
+    /// Seals `snapshot`, signing its digest with `signer` if provided.
+    pub fn new(snapshot: Snapshot, signer: Option<&dyn Signer>) -> Result<Self, serde_json::Error> {
+        let digest = snapshot.digest()?;
+        let signature = signer.map(|signer| Signature {
+            key_id: signer.key_id(),
+            value: to_hex(&signer.sign(&digest)),
+        });
+        Ok(Self {
+            snapshot,
+            digest: to_hex(&digest),
+            signature,
+        })
+    }
+
+    /// Verifies the digest of the snapshot and, if `verifier` is provided,
+    /// its signature.
+    ///
+    /// A `Sealed` snapshot without a signature fails verification if a
+    /// `verifier` is provided.
+    pub fn verify(&self, verifier: Option<&dyn Verifier>) -> Result<(), StateVerificationError> {
+        let digest = self
+            .snapshot
+            .digest()
+            .map_err(|_| StateVerificationError::Malformed)?;
+        let actual = to_hex(&digest);
+        if actual != self.digest {
+            return Err(StateVerificationError::DigestMismatch {
+                expected: self.digest.clone(),
+                actual,
+            });
+        }
+        let verifier = match verifier {
+            Some(verifier) => verifier,
+            None => return Ok(()),
+        };
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or(StateVerificationError::MissingSignature)?;
+        let value = from_hex(&signature.value).ok_or(StateVerificationError::Malformed)?;
+        if verifier.verify(&signature.key_id, &digest, &value) {
+            Ok(())
+        } else {
+            Err(StateVerificationError::InvalidSignature {
+                key_id: signature.key_id.clone(),
+            })
+        }
+    }
+}
+
+/// The signature of the digest of a [`Sealed`] snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Signature {
+    /// Identifies the key the digest was signed with.
+    pub key_id: String,
+    /// The hex-encoded signature.
+    pub value: String,
+}
+
+/// Signs the digest of a [`Snapshot`].
+///
+/// grill does not depend on a cryptography library; implement `Signer` with
+/// the signature scheme of your choosing (e.g. HMAC-SHA256 or Ed25519).
+pub trait Signer: Send + Sync {
+    /// Returns the identifier of the key used to sign, recorded alongside
+    /// the signature so that keys can be rotated.
+    fn key_id(&self) -> String;
+
+    /// Signs `digest`, returning the signature.
+    fn sign(&self, digest: &[u8]) -> Vec<u8>;
+}
+
+/// Verifies the signature of the digest of a [`Snapshot`].
+pub trait Verifier: Send + Sync {
+    /// Returns `true` if `signature` is a valid signature of `digest` by the
+    /// key identified by `key_id`.
+    fn verify(&self, key_id: &str, digest: &[u8], signature: &[u8]) -> bool;
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            let _ = write!(s, "{:02x}", b);
+            s
+        })
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
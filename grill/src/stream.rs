@@ -0,0 +1,363 @@
+//! Evaluation of instances which are read from a stream rather than held in
+//! memory as a [`Value`].
+//!
+//! [`Schema::evaluate_events`] accepts a SAX-like sequence of [`Event`]s and
+//! [`Schema::evaluate_deserializer`] accepts a serde [`Deserializer`], e.g.
+//! a `serde_json::Deserializer` reading from a file.
+//!
+//! Only the root of the instance is evaluated. A scalar root is read into a
+//! [`Value`] and evaluated against every keyword as usual. The members of an
+//! object or array root are read and checked for well-formedness, but are
+//! neither retained nor evaluated, so the instance need not fit in memory;
+//! the root is evaluated as if it were empty.
+//!
+//! For an object or array root, only the keywords of the [`Schema`] whose
+//! result does not depend on the members are therefore supported: those
+//! which only constrain scalars, e.g. `"maximum"` and `"format"`, `"type"`,
+//! those which are annotations, e.g. `"title"`, and those which declare
+//! rather than apply subschemas, e.g. `"$defs"`. If the `Schema` has any
+//! other keyword, e.g. `"properties"`, `"items"`, `"required"`,
+//! `"minItems"`, `"const"` or `"$ref"`, evaluation fails with a
+//! [`RandomAccessError`] naming the keyword before the members are read.
+//! Use [`check`] to find out ahead of time.
+//!
+//! ## Example
+//! ```
+//! use grill::{Interrogator, OutputFmt, Schema};
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! let schema = Schema::new(json!({ "maximum": 10 }), &interrogator).unwrap();
+//!
+//! let mut deserializer = serde_json::Deserializer::from_str("[1, 2, 3]");
+//! let eval = schema
+//!     .evaluate_deserializer(&mut deserializer, OutputFmt::Basic)
+//!     .unwrap();
+//! assert!(eval.is_valid());
+//!
+//! let mut deserializer = serde_json::Deserializer::from_str("11");
+//! let eval = schema
+//!     .evaluate_deserializer(&mut deserializer, OutputFmt::Basic)
+//!     .unwrap();
+//! assert!(!eval.is_valid());
+//!
+//! let schema = Schema::new(json!({ "const": [1, 2, 3] }), &interrogator).unwrap();
+//! let mut deserializer = serde_json::Deserializer::from_str("[1, 2, 3]");
+//! let err = schema
+//!     .evaluate_deserializer(&mut deserializer, OutputFmt::Basic)
+//!     .unwrap_err();
+//! assert!(err.is_random_access());
+//! ```
+
+use crate::{
+    deserializer::DeserializeError, error::RandomAccessError, schema::STREAMABLE_KEYWORDS, Error,
+    Evaluation, OutputFmt, Schema,
+};
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserializer,
+};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Keywords which only constrain scalars and therefore have no effect on
+/// an object or an array.
+const SCALAR_KEYWORDS: &[&str] = &[
+    "maximum",
+    "minimum",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "multipleOf",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "format",
+    "formatMaximum",
+    "formatMinimum",
+    "formatExclusiveMaximum",
+    "formatExclusiveMinimum",
+    "contentEncoding",
+    "contentMediaType",
+    "contentSchema",
+];
+
+/// Keywords which declare subschemas or identify resources without applying
+/// anything to the instance.
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "$anchor",
+    "$dynamicAnchor",
+    "$recursiveAnchor",
+    "$vocabulary",
+    "$defs",
+    "definitions",
+];
+
+/// The key of the map which `serde_json` reads a number as when its
+/// `"arbitrary_precision"` feature is enabled.
+#[cfg(feature = "big_num")]
+const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+/// An event of a SAX-like event source describing a JSON instance.
+///
+/// Members of an object are described by a [`Key`](Event::Key) followed by
+/// the events of the value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The start of an object.
+    StartObject,
+    /// The end of an object.
+    EndObject,
+    /// The start of an array.
+    StartArray,
+    /// The end of an array.
+    EndArray,
+    /// The key of a member of an object.
+    Key(String),
+    /// A `null`, boolean, number, or string.
+    Scalar(Value),
+}
+
+/// Returns an error naming the first keyword of `schema` which requires
+/// random access to an object or array instance, if any.
+///
+/// ## Example
+/// ```
+/// use grill::{stream, Interrogator, Schema};
+/// use serde_json::json;
+///
+/// let interrogator = Interrogator::new();
+/// let schema = Schema::new(json!({ "title": "ids", "maximum": 10 }), &interrogator).unwrap();
+/// assert!(stream::check(&schema).is_ok());
+///
+/// let schema = Schema::new(json!({ "enum": [[1], [2]] }), &interrogator).unwrap();
+/// assert!(stream::check(&schema).unwrap_err().is_random_access());
+/// ```
+pub fn check(schema: &Schema) -> Result<(), Error> {
+    let source = schema.source();
+    let obj = match source.as_ref() {
+        Value::Object(obj) => obj,
+        _ => return Ok(()),
+    };
+    let keyword = obj.keys().find(|k| {
+        let k = k.as_str();
+        !STREAMABLE_KEYWORDS.contains(&k)
+            && !SCALAR_KEYWORDS.contains(&k)
+            && !DECLARATION_KEYWORDS.contains(&k)
+    });
+    match keyword {
+        Some(keyword) => Err(RandomAccessError {
+            schema_id: schema.id(),
+            keyword: keyword.clone(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Evaluates the instance described by `events` against `schema`.
+pub(crate) fn evaluate_events<I>(
+    schema: &Schema,
+    events: I,
+    output: OutputFmt,
+) -> Result<Evaluation, Error>
+where
+    I: IntoIterator<Item = Result<Event, Error>>,
+{
+    let mut events = events.into_iter();
+    let root = match events.next().transpose()? {
+        Some(Event::Scalar(value)) => value,
+        Some(Event::StartObject) => {
+            check(schema)?;
+            drain(&mut events, vec![Frame::Object { expects_key: true }])?;
+            Value::Object(Map::new())
+        }
+        Some(Event::StartArray) => {
+            check(schema)?;
+            drain(&mut events, vec![Frame::Array])?;
+            Value::Array(Vec::new())
+        }
+        Some(event) => return Err(malformed(format!("unexpected {:?}", event))),
+        None => return Err(malformed("no events")),
+    };
+    if let Some(event) = events.next().transpose()? {
+        return Err(malformed(format!(
+            "unexpected {:?} after the end of the instance",
+            event
+        )));
+    }
+    schema.evaluate(&root, output)
+}
+
+/// Evaluates the instance read from `deserializer` against `schema`.
+pub(crate) fn evaluate_deserializer<'de, D>(
+    schema: &Schema,
+    deserializer: D,
+    output: OutputFmt,
+) -> Result<Evaluation, Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut rejected = None;
+    let root = Root {
+        schema,
+        rejected: &mut rejected,
+    }
+    .deserialize(deserializer);
+    match (root, rejected) {
+        (_, Some(err)) => Err(err),
+        (Ok(root), None) => schema.evaluate(&root, output),
+        (Err(err), None) => Err(malformed(err.to_string())),
+    }
+}
+
+fn malformed(message: impl Into<String>) -> Error {
+    DeserializeError::new(message).into()
+}
+
+/// A container which is open while draining events.
+enum Frame {
+    Object { expects_key: bool },
+    Array,
+}
+
+/// Consumes the events of the members of the open containers of `stack`,
+/// checking that they describe well-formed JSON, until each is closed.
+fn drain<I>(events: &mut I, mut stack: Vec<Frame>) -> Result<(), Error>
+where
+    I: Iterator<Item = Result<Event, Error>>,
+{
+    while let Some(top) = stack.last() {
+        let expects_key = matches!(top, Frame::Object { expects_key: true });
+        let in_array = matches!(top, Frame::Array);
+        let event = events
+            .next()
+            .transpose()?
+            .ok_or_else(|| malformed("the events ended before the end of the instance"))?;
+        match event {
+            Event::Key(_) if expects_key => expect_key(&mut stack, false),
+            Event::EndObject if expects_key => {
+                stack.pop();
+                expect_key(&mut stack, true);
+            }
+            Event::EndArray if in_array => {
+                stack.pop();
+                expect_key(&mut stack, true);
+            }
+            Event::Scalar(_) if !expects_key => expect_key(&mut stack, true),
+            Event::StartObject if !expects_key => stack.push(Frame::Object { expects_key: true }),
+            Event::StartArray if !expects_key => stack.push(Frame::Array),
+            event => return Err(malformed(format!("unexpected {:?}", event))),
+        }
+    }
+    Ok(())
+}
+
+/// Sets whether the innermost container of `stack`, if it is an object,
+/// expects a key, i.e. whether the value of its last member is complete.
+fn expect_key(stack: &mut [Frame], expects: bool) {
+    if let Some(Frame::Object { expects_key }) = stack.last_mut() {
+        *expects_key = expects;
+    }
+}
+
+/// A [`DeserializeSeed`] which reads a scalar into a [`Value`] and drains an
+/// object or array, yielding an empty one of the same type, if `schema` can
+/// be evaluated against it without random access.
+struct Root<'a> {
+    schema: &'a Schema,
+    /// The error raised by [`check`], which a [`Deserializer`] can not carry.
+    rejected: &'a mut Option<Error>,
+}
+
+impl Root<'_> {
+    fn check<E: de::Error>(&mut self) -> Result<(), E> {
+        check(self.schema).map_err(|err| {
+            let msg = err.to_string();
+            *self.rejected = Some(err);
+            E::custom(msg)
+        })
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Root<'_> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Root<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Value, A::Error> {
+        self.check()?;
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(Value::Array(Vec::new()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<Value, A::Error> {
+        #[cfg(feature = "big_num")]
+        {
+            // with the "arbitrary_precision" feature of serde_json, which
+            // `big_num` enables, a number is read as a map with a single,
+            // private key whose value is the text of the number
+            let key = map.next_key::<Value>()?;
+            if key.as_ref().and_then(Value::as_str) == Some(NUMBER_TOKEN) {
+                let number = map.next_value::<String>()?;
+                if map.next_key::<IgnoredAny>()?.is_some() {
+                    return Err(de::Error::custom("unexpected key after a number"));
+                }
+                return serde_json::from_str(&number).map_err(de::Error::custom);
+            }
+            self.check()?;
+            if key.is_some() {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        #[cfg(not(feature = "big_num"))]
+        self.check()?;
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(Value::Object(Map::new()))
+    }
+}
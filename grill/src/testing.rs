@@ -0,0 +1,237 @@
+//! Utilities for regression testing the [`Evaluation`]s produced by custom
+//! keywords and dialects against golden files.
+//!
+//! [`snapshot`] flattens an [`Evaluation`] into a stable [`Value`]: each
+//! output unit becomes an entry of `"units"`, sorted by keyword location and
+//! then instance location, with the keys of every object sorted, whether or
+//! not `serde_json`'s `preserve_order` feature is enabled. Locations are
+//! rendered as JSON Pointers; `"absoluteKeywordLocation"`, which varies with
+//! the base URI schemas are loaded from, is omitted unless requested with
+//! [`SnapshotOptions::absolute`].
+//!
+//! [`assert_golden`] compares a snapshot against a golden file as JSON, so
+//! the order of keys within the file does not matter. A golden file which
+//! does not exist is an error; it is written, as is any which differs, only
+//! if the environment variable named by [`UPDATE_ENV`] is set.
+//!
+//! Requires the `"test-util"` feature.
+//!
+//! ## Example
+//! ```
+//! use grill::{testing::snapshot, Evaluation, OutputFmt};
+//! use jsonptr::Pointer;
+//! use serde_json::json;
+//!
+//! let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Basic);
+//! let mut max = eval.for_keyword("maxLength");
+//! max.set_error("string is too long");
+//! eval.push(max);
+//!
+//! assert_eq!(
+//!     snapshot(&eval),
+//!     json!({
+//!         "valid": false,
+//!         "units": [
+//!             { "keywordLocation": "", "instanceLocation": "" },
+//!             {
+//!                 "keywordLocation": "/maxLength",
+//!                 "instanceLocation": "",
+//!                 "error": "string is too long"
+//!             }
+//!         ]
+//!     })
+//! );
+//! ```
+
+use crate::{evaluation::Iter, Evaluation};
+use serde_json::{Map, Value};
+use std::{fmt, fs, io, path::Path};
+
+/// The environment variable which, when set, causes [`assert_golden`] to
+/// write golden files, including those which do not yet exist, rather than
+/// compare against them.
+pub const UPDATE_ENV: &str = "GRILL_UPDATE_GOLDEN";
+
+/// Options for [`snapshot_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnapshotOptions {
+    absolute: bool,
+}
+
+impl SnapshotOptions {
+    /// Creates and returns a new `SnapshotOptions` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether `"absoluteKeywordLocation"` is included in each unit.
+    #[must_use]
+    pub fn absolute(mut self, absolute: bool) -> Self {
+        self.absolute = absolute;
+        self
+    }
+}
+
+/// Returns a stable snapshot of `evaluation` with the default
+/// [`SnapshotOptions`].
+pub fn snapshot(evaluation: &Evaluation) -> Value {
+    snapshot_with(evaluation, SnapshotOptions::default())
+}
+
+/// Returns a stable snapshot of `evaluation`.
+pub fn snapshot_with(evaluation: &Evaluation, options: SnapshotOptions) -> Value {
+    let mut units: Vec<Map<String, Value>> = Iter::new(evaluation)
+        .map(|eval| unit(eval, options))
+        .collect();
+    units.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+    let mut snapshot = Map::new();
+    snapshot.insert("valid".into(), evaluation.is_valid().into());
+    snapshot.insert(
+        "units".into(),
+        Value::Array(units.into_iter().map(Value::Object).collect()),
+    );
+    Value::Object(sort_map(snapshot))
+}
+
+fn unit(eval: &Evaluation, options: SnapshotOptions) -> Map<String, Value> {
+    let mut unit = Map::new();
+    let mut data: Vec<_> = eval.data().iter().collect();
+    data.sort_by(|a, b| a.0.cmp(b.0));
+    for (k, v) in data {
+        unit.insert(k.clone(), sorted(v));
+    }
+    unit.insert(
+        "keywordLocation".into(),
+        eval.keyword_location().to_string().into(),
+    );
+    unit.insert(
+        "instanceLocation".into(),
+        eval.instance_location().to_string().into(),
+    );
+    if options.absolute {
+        if let Some(uri) = eval.absolute_keyword_location() {
+            unit.insert("absoluteKeywordLocation".into(), uri.to_string().into());
+        }
+    }
+    if let Some(error) = eval.error() {
+        unit.insert("error".into(), error.into());
+    }
+    sort_map(unit)
+}
+
+/// Returns a copy of `value` with the keys of each object inserted in sorted
+/// order.
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(sorted).collect()),
+        Value::Object(map) => Value::Object(sort_map(map.clone())),
+        value => value.clone(),
+    }
+}
+
+fn sort_map(map: Map<String, Value>) -> Map<String, Value> {
+    let mut entries: Vec<_> = map.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.into_iter().map(|(k, v)| (k, sorted(&v))).collect()
+}
+
+fn sort_key(unit: &Map<String, Value>) -> (String, String, String) {
+    let field = |k: &str| unit.get(k).map(ToString::to_string).unwrap_or_default();
+    (
+        field("keywordLocation"),
+        field("instanceLocation"),
+        Value::Object(unit.clone()).to_string(),
+    )
+}
+
+/// Indicates that a snapshot differs from its golden file.
+#[derive(Debug, Clone)]
+pub struct GoldenMismatch {
+    /// The contents of the golden file.
+    pub expected: String,
+    /// The serialized snapshot.
+    pub actual: String,
+}
+
+impl fmt::Display for GoldenMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "snapshot differs from golden file:")?;
+        let expected: Vec<&str> = self.expected.lines().collect();
+        let actual: Vec<&str> = self.actual.lines().collect();
+        for i in 0..expected.len().max(actual.len()) {
+            match (expected.get(i), actual.get(i)) {
+                (Some(e), Some(a)) if e == a => writeln!(f, "  {}", e)?,
+                (e, a) => {
+                    if let Some(e) = e {
+                        writeln!(f, "- {}", e)?;
+                    }
+                    if let Some(a) = a {
+                        writeln!(f, "+ {}", a)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for GoldenMismatch {}
+
+/// Compares `snapshot` against the golden file at `path` as JSON, ignoring
+/// the order of object keys.
+///
+/// If the environment variable [`UPDATE_ENV`] is set, `snapshot` is written
+/// to `path` (creating parent directories) and `Ok(())` is returned.
+///
+/// # Errors
+/// Returns an error of kind [`NotFound`](io::ErrorKind::NotFound) if the
+/// golden file does not exist and [`UPDATE_ENV`] is not set, or the error
+/// of reading or writing the file. The golden file is reported as a
+/// [`GoldenMismatch`] if it is not valid JSON.
+pub fn compare_golden(
+    path: impl AsRef<Path>,
+    snapshot: &Value,
+) -> io::Result<Result<(), GoldenMismatch>> {
+    let path = path.as_ref();
+    let mut actual = serde_json::to_string_pretty(&sorted(snapshot))?;
+    actual.push('\n');
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, actual)?;
+        return Ok(Ok(()));
+    }
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("golden file does not exist; set {} to write it", UPDATE_ENV),
+        ));
+    }
+    let expected = fs::read_to_string(path)?;
+    match serde_json::from_str::<Value>(&expected) {
+        Ok(golden) if golden == *snapshot => Ok(Ok(())),
+        _ => Ok(Err(GoldenMismatch { expected, actual })),
+    }
+}
+
+/// Asserts that the snapshot of `evaluation` matches the golden file at
+/// `path`. See [`compare_golden`].
+///
+/// # Panics
+/// Panics with a line diff if the snapshot differs from the golden file, or
+/// if the golden file can not be read or written.
+#[track_caller]
+pub fn assert_golden(path: impl AsRef<Path>, evaluation: &Evaluation) {
+    let path = path.as_ref();
+    match compare_golden(path, &snapshot(evaluation)) {
+        Ok(Ok(())) => {}
+        Ok(Err(mismatch)) => panic!(
+            "{}\n{}\nset {} to update the golden file",
+            path.display(),
+            mismatch,
+            UPDATE_ENV
+        ),
+        Err(err) => panic!("failed to access golden file {}: {}", path.display(), err),
+    }
+}
@@ -0,0 +1,432 @@
+//! URIs, re-exported from [`uniresid`], and [`RawUri`], which retains the
+//! text a URI was parsed from.
+//!
+//! ## Normalization
+//! A [`Uri`] is stored as its components and displays in the form its parser
+//! produces rather than the text it was parsed from, so an `"$id"` may not
+//! survive a roundtrip through `Uri` byte-for-byte. Among the places grill
+//! normalizes URIs intentionally:
+//! - the id of a [`Schema`](crate::Schema) is a parsed `Uri`, either of its
+//!   `"$id"` or of the URI it was compiled from, and relative references are
+//!   resolved against it as defined by RFC 3986;
+//! - URNs are compared with the `"urn"` scheme and namespace identifier
+//!   lowercased (see [`urn::normalize`](crate::urn::normalize));
+//! - identifiers are converted to Unicode Normalization Form C if the
+//!   [`Interrogator`](crate::Interrogator) is set to
+//!   [normalize identifiers](crate::Interrogator::set_normalize_identifiers);
+//! - the `HttpResolver` of the `"http"` feature requests documents at their
+//!   URI as formatted by [`url::Url`](https://docs.rs/url), without a
+//!   fragment;
+//! - documents are written to paths derived from their parsed URI by
+//!   [`source::export_path`](crate::source::export_path) and, with the
+//!   `"vendor"` feature, `vendor::vendor_path`.
+//!
+//! Where the original text matters, e.g. to write an id back to a document
+//! or to compare it with one held elsewhere, use a [`RawUri`], or
+//! [`Schema::raw_id`](crate::Schema::raw_id) for the `"$id"` of a schema.
+//!
+//! ## Borrowed URIs
+//! [`UriStr`] and [`AbsoluteUriStr`] parse a URI reference without copying
+//! it, recording where each component begins and ends. They are used where
+//! many references are inspected but few need to be resolved, e.g. to pick
+//! out the fragments among the references of a `"$ref"` or of the
+//! [dependencies](crate::source::dependencies) of a document, and convert to
+//! [`Uri`] and [`AbsoluteUri`] when an owned URI is needed. Their parser is
+//! stricter than that of [`uniresid`], rejecting whitespace and malformed
+//! percent-encodings, so a reference it rejects is parsed as a [`Uri`]
+//! rather than dropped.
+
+pub use uniresid::*;
+
+use crate::error::UriSyntaxError;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{self, Display},
+    ops::Deref,
+};
+
+/// A [`Uri`] along with the exact text it was parsed from.
+///
+/// A `RawUri` dereferences to its parsed `Uri`, and two `RawUri`s are equal
+/// if their parsed `Uri`s are, as that is how grill identifies resources.
+/// It displays, serializes, and is returned from
+/// [`as_raw_str`](Self::as_raw_str) as the original text, byte-for-byte.
+///
+/// ## Example
+/// ```
+/// use grill::uri::RawUri;
+///
+/// let raw = "https://example.com/schemas/person.json?v=1#";
+/// let uri = RawUri::parse(raw).unwrap();
+/// assert_eq!(uri.as_raw_str(), raw);
+/// assert_eq!(uri.to_string(), raw);
+/// assert_eq!(serde_json::to_value(&uri).unwrap(), raw);
+/// assert_eq!(
+///     uri.normalized(),
+///     "https://example.com/schemas/person.json?v=1#"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RawUri {
+    raw: String,
+    uri: Uri,
+}
+
+impl RawUri {
+    /// Parses `raw` as a [`Uri`], retaining `raw`.
+    pub fn parse(raw: &str) -> Result<Self, Error> {
+        Ok(Self {
+            raw: raw.to_string(),
+            uri: Uri::parse(raw)?,
+        })
+    }
+
+    /// Returns the text this `RawUri` was parsed from, byte-for-byte.
+    pub fn as_raw_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Returns the parsed [`Uri`].
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+
+    /// Returns the parsed [`Uri`] as formatted by its parser, which is the
+    /// form grill uses to identify resources.
+    pub fn normalized(&self) -> String {
+        self.uri.to_string()
+    }
+
+    /// Returns `true` if the parsed [`Uri`] formats as the text it was parsed
+    /// from, i.e. if no normalization occurred.
+    pub fn is_normalized(&self) -> bool {
+        self.raw == self.uri.to_string()
+    }
+
+    /// Consumes the `RawUri`, returning the text it was parsed from and the
+    /// parsed [`Uri`].
+    pub fn into_parts(self) -> (String, Uri) {
+        (self.raw, self.uri)
+    }
+}
+
+impl Deref for RawUri {
+    type Target = Uri;
+
+    fn deref(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+impl AsRef<str> for RawUri {
+    fn as_ref(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl From<RawUri> for Uri {
+    fn from(raw: RawUri) -> Self {
+        raw.uri
+    }
+}
+
+impl PartialEq for RawUri {
+    fn eq(&self, other: &Self) -> bool {
+        self.uri == other.uri
+    }
+}
+
+impl Eq for RawUri {}
+
+impl Display for RawUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl Serialize for RawUri {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawUri {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        RawUri::parse(&raw).map_err(de::Error::custom)
+    }
+}
+
+/// A URI reference borrowed from the text it was parsed from, with the
+/// positions of its components computed once by [`parse`](Self::parse).
+///
+/// Unlike [`Uri`], parsing a `UriStr` does not allocate, which makes it
+/// suitable for inspecting the many `"$ref"`s of a large schema set, most of
+/// which are fragments of the document they appear in. Components are
+/// returned as they appear in the text, without normalization; convert to a
+/// `Uri` with [`to_uri`](Self::to_uri) to resolve or normalize it.
+///
+/// ## Example
+/// ```
+/// use grill::uri::UriStr;
+///
+/// let uri = UriStr::parse("https://example.com/schema.json?v=1#/$defs/a").unwrap();
+/// assert_eq!(uri.scheme(), Some("https"));
+/// assert_eq!(uri.authority(), Some("example.com"));
+/// assert_eq!(uri.path(), "/schema.json");
+/// assert_eq!(uri.query(), Some("v=1"));
+/// assert_eq!(uri.fragment(), Some("/$defs/a"));
+/// assert_eq!(uri.without_fragment(), "https://example.com/schema.json?v=1");
+///
+/// let reference = UriStr::parse("#/$defs/a").unwrap();
+/// assert!(reference.is_fragment_only());
+/// assert!(!reference.is_absolute());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UriStr<'a> {
+    text: &'a str,
+    /// The end of the scheme, i.e. the position of its `':'`.
+    scheme_end: Option<usize>,
+    /// The start and end of the authority, excluding its leading `"//"`.
+    authority: Option<(usize, usize)>,
+    path_end: usize,
+    /// The position of the `'?'` which begins the query.
+    query_start: Option<usize>,
+    /// The position of the `'#'` which begins the fragment.
+    fragment_start: Option<usize>,
+}
+
+impl<'a> UriStr<'a> {
+    /// Parses `text` as a URI reference, as defined by RFC 3986, without
+    /// copying it.
+    ///
+    /// Non-ASCII characters are permitted, as they are in IRIs.
+    ///
+    /// # Errors
+    /// Returns a [`UriSyntaxError`] if `text` contains whitespace or control
+    /// characters, a malformed percent-encoding, more than one `'#'`, or a
+    /// `':'` in its first segment which does not end a valid scheme.
+    pub fn parse(text: &'a str) -> Result<Self, UriSyntaxError> {
+        let error = |reason: &str| UriSyntaxError {
+            value: text.to_string(),
+            reason: reason.to_string(),
+        };
+        let bytes = text.as_bytes();
+        let mut fragment_start = None;
+        let mut query_start = None;
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'#' if fragment_start.is_some() => {
+                    return Err(error("only one '#' is permitted"));
+                }
+                b'#' => fragment_start = Some(i),
+                b'?' if query_start.is_none() && fragment_start.is_none() => query_start = Some(i),
+                b'%' if !bytes
+                    .get(i + 1..i + 3)
+                    .map_or(false, |hex| hex.iter().all(u8::is_ascii_hexdigit)) =>
+                {
+                    return Err(error("'%' must be followed by two hexadecimal digits"));
+                }
+                b if b.is_ascii_whitespace() || b.is_ascii_control() => {
+                    return Err(error("whitespace and control characters are not permitted"));
+                }
+                _ => {}
+            }
+        }
+        let hier_end = query_start.or(fragment_start).unwrap_or(bytes.len());
+        let scheme_end = match bytes[..hier_end].iter().position(|&b| b == b':') {
+            Some(colon) if !bytes[..colon].contains(&b'/') => {
+                if !is_scheme(&text[..colon]) {
+                    return Err(error("the text before the first ':' is not a valid scheme"));
+                }
+                Some(colon)
+            }
+            _ => None,
+        };
+        let hier_start = scheme_end.map_or(0, |colon| colon + 1);
+        let authority = match text[hier_start..hier_end].strip_prefix("//") {
+            Some(rest) => {
+                let start = hier_start + 2;
+                Some((start, start + rest.find('/').unwrap_or(rest.len())))
+            }
+            None => None,
+        };
+        let path_end = query_start.or(fragment_start).unwrap_or(bytes.len());
+        Ok(Self {
+            text,
+            scheme_end,
+            authority,
+            path_end,
+            query_start,
+            fragment_start,
+        })
+    }
+
+    /// Returns the text the `UriStr` was parsed from.
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the scheme, e.g. `"https"`, if present.
+    pub fn scheme(&self) -> Option<&'a str> {
+        self.scheme_end.map(|end| &self.text[..end])
+    }
+
+    /// Returns the authority, without its leading `"//"`, if present.
+    pub fn authority(&self) -> Option<&'a str> {
+        self.authority.map(|(start, end)| &self.text[start..end])
+    }
+
+    /// Returns the path, which may be empty.
+    pub fn path(&self) -> &'a str {
+        let start = match (self.authority, self.scheme_end) {
+            (Some((_, end)), _) => end,
+            (None, Some(colon)) => colon + 1,
+            (None, None) => 0,
+        };
+        &self.text[start..self.path_end]
+    }
+
+    /// Returns the query, without its leading `'?'`, if present.
+    pub fn query(&self) -> Option<&'a str> {
+        let end = self.fragment_start.unwrap_or(self.text.len());
+        self.query_start.map(|start| &self.text[start + 1..end])
+    }
+
+    /// Returns the fragment, without its leading `'#'`, if present.
+    pub fn fragment(&self) -> Option<&'a str> {
+        self.fragment_start.map(|start| &self.text[start + 1..])
+    }
+
+    /// Returns the text without the fragment, if any.
+    pub fn without_fragment(&self) -> &'a str {
+        &self.text[..self.fragment_start.unwrap_or(self.text.len())]
+    }
+
+    /// Returns `true` if the reference has a scheme.
+    pub fn is_absolute(&self) -> bool {
+        self.scheme_end.is_some()
+    }
+
+    /// Returns `true` if the reference consists only of a fragment, e.g.
+    /// `"#/$defs/a"`, and so refers to the document it appears in.
+    pub fn is_fragment_only(&self) -> bool {
+        self.fragment_start == Some(0)
+    }
+
+    /// Parses the reference as an owned [`Uri`].
+    pub fn to_uri(&self) -> Result<Uri, Error> {
+        Uri::parse(self.text)
+    }
+}
+
+impl Display for UriStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl AsRef<str> for UriStr<'_> {
+    fn as_ref(&self) -> &str {
+        self.text
+    }
+}
+
+impl<'a> TryFrom<&'a str> for UriStr<'a> {
+    type Error = UriSyntaxError;
+
+    fn try_from(text: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(text)
+    }
+}
+
+impl TryFrom<UriStr<'_>> for Uri {
+    type Error = Error;
+
+    fn try_from(uri: UriStr<'_>) -> Result<Self, Self::Error> {
+        uri.to_uri()
+    }
+}
+
+/// A [`UriStr`] which has a scheme, the borrowed analogue of
+/// [`AbsoluteUri`].
+///
+/// An `AbsoluteUriStr` dereferences to its `UriStr`.
+///
+/// ## Example
+/// ```
+/// use grill::uri::{AbsoluteUri, AbsoluteUriStr};
+///
+/// let uri = AbsoluteUriStr::parse("urn:example:person#name").unwrap();
+/// assert_eq!(uri.scheme(), Some("urn"));
+/// let owned = AbsoluteUri::parse("urn:example:person#name").unwrap();
+/// assert_eq!(uri.to_absolute_uri().unwrap(), owned);
+/// assert!(AbsoluteUriStr::parse("person.json").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AbsoluteUriStr<'a>(UriStr<'a>);
+
+impl<'a> AbsoluteUriStr<'a> {
+    /// Parses `text` as an absolute URI, without copying it.
+    ///
+    /// # Errors
+    /// Returns a [`UriSyntaxError`] if `text` is not a valid [`UriStr`] or
+    /// does not have a scheme.
+    pub fn parse(text: &'a str) -> Result<Self, UriSyntaxError> {
+        let uri = UriStr::parse(text)?;
+        if !uri.is_absolute() {
+            return Err(UriSyntaxError {
+                value: text.to_string(),
+                reason: "an absolute URI must have a scheme".to_string(),
+            });
+        }
+        Ok(Self(uri))
+    }
+
+    /// Returns the [`UriStr`].
+    pub fn as_uri_str(&self) -> UriStr<'a> {
+        self.0
+    }
+
+    /// Parses the URI as an owned [`AbsoluteUri`].
+    pub fn to_absolute_uri(&self) -> Result<AbsoluteUri, Error> {
+        AbsoluteUri::parse(self.0.text)
+    }
+}
+
+impl<'a> Deref for AbsoluteUriStr<'a> {
+    type Target = UriStr<'a>;
+
+    fn deref(&self) -> &UriStr<'a> {
+        &self.0
+    }
+}
+
+impl Display for AbsoluteUriStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0.text)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for AbsoluteUriStr<'a> {
+    type Error = UriSyntaxError;
+
+    fn try_from(text: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(text)
+    }
+}
+
+impl TryFrom<AbsoluteUriStr<'_>> for AbsoluteUri {
+    type Error = Error;
+
+    fn try_from(uri: AbsoluteUriStr<'_>) -> Result<Self, Self::Error> {
+        uri.to_absolute_uri()
+    }
+}
+
+/// Returns `true` if `scheme` is `ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`.
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
@@ -0,0 +1,122 @@
+//! Identification of schemas by URN.
+//!
+//! An `"$id"` may be a URN ([RFC 8141](https://www.rfc-editor.org/rfc/rfc8141)),
+//! e.g. `"urn:uuid:deadbeef-1234-ffff-ffff-4321feebdaed"` or
+//! `"urn:example:schema"`. A URN is not hierarchical: a reference made from
+//! a schema identified by a URN is either absolute or consists solely of a
+//! fragment, which may be a JSON Pointer (`"#/$defs/a"`) or an anchor
+//! (`"#node"`). [`resolve`] refuses relative paths against a URN rather
+//! than merging them as it would for a hierarchical URI.
+//!
+//! The `"urn"` scheme and the namespace identifier are case-insensitive, so
+//! URNs are compared in the form returned by [`normalize`]. Resources
+//! identified by a URN are located by
+//! [`Interrogator::locate`](crate::Interrogator::locate) even when embedded
+//! within a source document added under another URI.
+//!
+//! ## Example
+//! ```
+//! use grill::{uri::AbsoluteUri, Interrogator};
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! interrogator.source_value(
+//!     AbsoluteUri::parse("https://example.com/bundle.json").unwrap(),
+//!     json!({
+//!         "$defs": {
+//!             "uuid": {
+//!                 "$id": "urn:uuid:deadbeef-1234-ffff-ffff-4321feebdaed",
+//!                 "$defs": { "bar": { "$anchor": "something", "type": "string" } }
+//!             }
+//!         }
+//!     }),
+//! );
+//!
+//! let pointer = "URN:UUID:deadbeef-1234-ffff-ffff-4321feebdaed#/$defs/bar";
+//! let uri = AbsoluteUri::parse(pointer).unwrap();
+//! assert_eq!(interrogator.locate(&uri).unwrap().unwrap()["type"], "string");
+//!
+//! let anchor = "urn:uuid:deadbeef-1234-ffff-ffff-4321feebdaed#something";
+//! let uri = AbsoluteUri::parse(anchor).unwrap();
+//! assert_eq!(interrogator.locate(&uri).unwrap().unwrap()["type"], "string");
+//! ```
+
+use std::borrow::Cow;
+use uniresid::Uri;
+
+/// Returns `true` if `uri` is a URN.
+pub fn is_urn(uri: &str) -> bool {
+    uri.get(..4)
+        .map_or(false, |scheme| scheme.eq_ignore_ascii_case("urn:"))
+}
+
+/// Returns `uri` with the `"urn"` scheme and namespace identifier
+/// lowercased if it is a URN, or unchanged otherwise.
+///
+/// ## Example
+/// ```
+/// use grill::urn::normalize;
+///
+/// assert_eq!(normalize("URN:Example:Foo#/a"), "urn:example:Foo#/a");
+/// assert_eq!(normalize("https://Example.com/Foo"), "https://Example.com/Foo");
+/// ```
+pub fn normalize(uri: &str) -> Cow<'_, str> {
+    if !is_urn(uri) {
+        return Cow::Borrowed(uri);
+    }
+    let nid_end = uri[4..].find(':').map_or(uri.len(), |i| i + 4);
+    let prefix = &uri[..nid_end];
+    if !prefix.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Cow::Borrowed(uri);
+    }
+    Cow::Owned(format!(
+        "{}{}",
+        prefix.to_ascii_lowercase(),
+        &uri[nid_end..]
+    ))
+}
+
+/// Resolves `reference` against `base`.
+///
+/// If `base` is a URN, `reference` must be absolute or consist solely of a
+/// fragment; `None` is returned otherwise. Other bases are resolved as
+/// defined by RFC 3986.
+///
+/// ## Example
+/// ```
+/// use grill::{urn::resolve, Uri};
+///
+/// let base = Uri::parse("urn:example:schema").unwrap();
+/// let resolved = resolve(&base, &Uri::parse("#/$defs/a").unwrap()).unwrap();
+/// assert_eq!(resolved.to_string(), "urn:example:schema#/$defs/a");
+/// assert!(resolve(&base, &Uri::parse("other.json").unwrap()).is_none());
+/// ```
+pub fn resolve(base: &Uri, reference: &Uri) -> Option<Uri> {
+    let base_str = base.to_string();
+    if !is_urn(&base_str) {
+        return Some(base.resolve(reference));
+    }
+    let reference_str = reference.to_string();
+    if has_scheme(&reference_str) {
+        return Some(reference.clone());
+    }
+    if reference_str.is_empty() || reference_str.starts_with('#') {
+        let document = base_str.split('#').next().unwrap_or_default();
+        return Uri::parse(&format!("{}{}", document, reference_str)).ok();
+    }
+    None
+}
+
+/// Returns `true` if `uri` begins with a scheme as defined by RFC 3986.
+fn has_scheme(uri: &str) -> bool {
+    match uri.find(':') {
+        Some(end) => {
+            let scheme = &uri[..end];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,243 @@
+//! Vendoring of the external dependencies of schemas to disk.
+//!
+//! [`Interrogator::vendor`](crate::Interrogator::vendor) resolves each
+//! document referenced, directly or transitively, by a set of root
+//! documents, writes each to a directory at the path given by
+//! [`vendor_path`], and writes a [`Lockfile`] mapping the URI of each document
+//! to its path and SHA-256 digest. The lockfile and directory can be
+//! committed so that later builds need not reach the network.
+//!
+//! The path of a document is that which a [`FileResolver`](crate::FileResolver)
+//! reads it from when the scheme of its URI, with `//` if present, is mapped
+//! to the directory, so a vendor directory can also be read with, e.g.,
+//! `FileResolver::new().map("https://", dir)`.
+//!
+//! A [`LockedResolver`] resolves documents solely from a vendor directory,
+//! refusing any which is not listed in its lockfile or whose digest differs.
+//! Set with
+//! [`Interrogator::set_locked_resolver`](crate::Interrogator::set_locked_resolver),
+//! it replaces every other [`Resolver`] of the
+//! [`Interrogator`](crate::Interrogator).
+//!
+//! Requires the `"vendor"` feature.
+//!
+//! ## Example
+//! ```
+//! use grill::{
+//!     uri::AbsoluteUri,
+//!     vendor::{LockedResolver, LOCKFILE_NAME},
+//!     FileResolver, Interrogator, Resolver,
+//! };
+//! use serde_json::json;
+//!
+//! let interrogator = Interrogator::new();
+//! let root = AbsoluteUri::parse("https://example.com/order.json").unwrap();
+//! let address = AbsoluteUri::parse("https://example.com/address.json").unwrap();
+//! interrogator.source_value(root.clone(), json!({ "$ref": "address.json" }));
+//! interrogator.source_value(address, json!({ "type": "object" }));
+//!
+//! let dir = std::env::temp_dir().join("grill-vendor-example");
+//! let lockfile = interrogator.vendor(&[root], &dir).unwrap();
+//! assert_eq!(lockfile.entries.len(), 2);
+//! assert_eq!(
+//!     lockfile.entries["https://example.com/address.json"].path,
+//!     "example.com/address.json"
+//! );
+//! assert!(dir.join(LOCKFILE_NAME).exists());
+//!
+//! let files = FileResolver::new().map("https://", &dir);
+//! assert!(files.resolve("https://example.com/address.json".into()).is_ok());
+//!
+//! let offline = Interrogator::new();
+//! offline.set_locked_resolver(Some(LockedResolver::open(&dir).unwrap()));
+//! assert!(offline.resolve("https://example.com/address.json").is_ok());
+//! assert!(offline.resolve("https://example.com/other.json").is_err());
+//! ```
+
+pub use crate::error::LockError;
+pub use crate::source::dependencies;
+pub(crate) use crate::source::without_fragment;
+use crate::{resolver::relative_path, ResolveError, Resolver};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+use uniresid::AbsoluteUri;
+
+/// The name of the lockfile written to the vendor directory.
+pub const LOCKFILE_NAME: &str = "grill.lock";
+
+/// The version of the [`Lockfile`] format.
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// The vendored documents, keyed by URI.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// The version of the lockfile format; see [`LOCKFILE_VERSION`].
+    pub version: u32,
+    /// The entry of each vendored document, keyed by the URI it was
+    /// resolved from.
+    pub entries: BTreeMap<String, LockEntry>,
+}
+
+/// The location and digest of a vendored document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockEntry {
+    /// The path of the document relative to the vendor directory, with `/`
+    /// separators.
+    pub path: String,
+    /// The hex-encoded SHA-256 digest of the document as written.
+    pub sha256: String,
+}
+
+impl Lockfile {
+    /// Creates and returns a new, empty `Lockfile`.
+    pub fn new() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Reads a `Lockfile` from the file at `path`.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        serde_json::from_slice(&bytes).map_err(io::Error::from)
+    }
+
+    /// Writes this `Lockfile` as pretty-printed JSON to the file at `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut bytes = serde_json::to_vec_pretty(self)?;
+        bytes.push(b'\n');
+        fs::write(path, bytes)
+    }
+
+    /// Returns the entry for the document at `uri`, if one exists.
+    pub fn get(&self, uri: &str) -> Option<&LockEntry> {
+        self.entries.get(uri)
+    }
+}
+
+/// A [`Resolver`] which reads documents from a vendor directory, refusing
+/// any document which is not listed in the [`Lockfile`], whose path is not
+/// within the directory, or whose digest differs from that recorded.
+///
+/// A refusal is returned as a [`ResolveError::Internal`] wrapping a
+/// [`LockError`], and reported by
+/// [`Interrogator::compile_all`](crate::Interrogator::compile_all) as an
+/// [`Error::Lock`](crate::Error::Lock). Documents are never resolved
+/// otherwise while a `LockedResolver` is set, so an unlisted document fails
+/// to compile.
+#[derive(Debug, Clone)]
+pub struct LockedResolver {
+    dir: PathBuf,
+    lockfile: Lockfile,
+}
+
+impl LockedResolver {
+    /// Creates and returns a new `LockedResolver` which reads the documents
+    /// listed in `lockfile` from `dir`.
+    pub fn new(dir: impl Into<PathBuf>, lockfile: Lockfile) -> Self {
+        Self {
+            dir: dir.into(),
+            lockfile,
+        }
+    }
+
+    /// Reads the lockfile [`LOCKFILE_NAME`] within `dir` and returns a
+    /// `LockedResolver` for it.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        let lockfile = Lockfile::read(dir.join(LOCKFILE_NAME))?;
+        Ok(Self::new(dir, lockfile))
+    }
+
+    /// Returns the [`Lockfile`] of this `LockedResolver`.
+    pub fn lockfile(&self) -> &Lockfile {
+        &self.lockfile
+    }
+
+    /// Returns the vendor directory of this `LockedResolver`.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Resolver for LockedResolver {
+    fn resolve(&self, id: String) -> Result<Value, ResolveError> {
+        let refuse = |err: LockError| ResolveError::Internal(Box::new(err));
+        let uri = id.split('#').next().unwrap_or_default().to_string();
+        let entry = match self.lockfile.get(&uri) {
+            Some(entry) => entry,
+            None => return Err(refuse(LockError::Unlisted { uri })),
+        };
+        let path = match relative_path(&entry.path) {
+            Some(relative) => self.dir.join(relative),
+            None => {
+                let path = entry.path.clone();
+                return Err(refuse(LockError::InvalidPath { uri, path }));
+            }
+        };
+        let bytes = fs::read(&path).map_err(|err| {
+            let message = err.to_string();
+            refuse(LockError::Io {
+                uri: uri.clone(),
+                path,
+                message,
+            })
+        })?;
+        let actual = digest(&bytes);
+        if actual != entry.sha256 {
+            let expected = entry.sha256.clone();
+            return Err(refuse(LockError::DigestMismatch {
+                uri,
+                expected,
+                actual,
+            }));
+        }
+        serde_json::from_slice(&bytes).map_err(|err| ResolveError::Internal(Box::new(err)))
+    }
+}
+
+/// Returns the path, relative to the vendor directory, which the document at
+/// `uri` is vendored to: the URI without its scheme, `//`, and fragment. Returns
+/// `None` if the URI does not name a file within the directory, e.g. if its
+/// path ends with `/`.
+///
+/// ## Example
+/// ```
+/// use grill::{uri::AbsoluteUri, vendor::vendor_path};
+/// use std::path::Path;
+///
+/// let uri = AbsoluteUri::parse("https://example.com/schemas/user.json#/type").unwrap();
+/// assert_eq!(
+///     vendor_path(&uri).as_deref(),
+///     Some(Path::new("example.com/schemas/user.json"))
+/// );
+/// ```
+pub fn vendor_path(uri: &AbsoluteUri) -> Option<PathBuf> {
+    let uri = without_fragment(uri).to_string();
+    let rest = &uri[uri.find(':')? + 1..];
+    let rest = rest.strip_prefix("//").unwrap_or(rest);
+    relative_path(rest).map(Path::to_path_buf)
+}
+
+/// Returns the hex-encoded SHA-256 digest of `bytes`.
+pub fn digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Returns `path` with `/` separators.
+pub(crate) fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
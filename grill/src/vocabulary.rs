@@ -1,6 +1,7 @@
 use crate::Applicator;
 use uniresid::Uri;
 
+#[derive(Clone)]
 pub struct Vocabulary {
     pub id: Uri,
     pub applicators: Vec<Box<dyn Applicator>>,
@@ -8,7 +9,7 @@ pub struct Vocabulary {
 }
 
 impl Vocabulary {
-    pub fn new(&self, id: Uri) -> Self {
+    pub fn new(id: Uri) -> Self {
         Vocabulary {
             id,
             applicators: Vec::default(),
@@ -25,3 +26,10 @@ impl std::hash::Hash for Vocabulary {
         self.id.hash(state);
     }
 }
+
+impl PartialEq for Vocabulary {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+impl Eq for Vocabulary {}
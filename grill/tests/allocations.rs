@@ -0,0 +1,122 @@
+//! Counts the heap allocations of evaluating the simple assertion keywords.
+//!
+//! The allocations of a keyword are those of evaluating a schema with the
+//! keyword less those of evaluating an empty schema against the same
+//! instance, so that the cost of the evaluation itself is not counted.
+
+use grill::{Evaluation, Interrogator, OutputFmt, Schema};
+use jsonptr::Pointer;
+use serde_json::{json, Value};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the allocations of the current thread, so that tests running in
+/// parallel do not affect one another.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    // growing an allocation is not counted as another
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Returns the number of allocations made by `f`.
+fn count<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    drop(result);
+    after - before
+}
+
+/// Returns the number of allocations made by evaluating `instance` against
+/// `schema`, less those of evaluating it against an empty schema.
+fn keyword_allocations(schema: Value, instance: &Value) -> usize {
+    let interrogator = Interrogator::new();
+    let baseline = Schema::new(json!({}), &interrogator).unwrap();
+    let schema = Schema::new(schema, &interrogator).unwrap();
+    // the first evaluation computes artifacts which are cached for later ones
+    schema.evaluate(instance, OutputFmt::Basic).unwrap();
+    baseline.evaluate(instance, OutputFmt::Basic).unwrap();
+
+    let base = count(|| baseline.evaluate(instance, OutputFmt::Basic).unwrap());
+    let total = count(|| schema.evaluate(instance, OutputFmt::Basic).unwrap());
+    total.saturating_sub(base)
+}
+
+/// Returns the number of allocations of the error node of a keyword with an
+/// empty message: its keyword location and its place among the nested
+/// `Evaluation`s.
+fn error_node_allocations() -> usize {
+    let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Basic);
+    count(move || {
+        let mut kw = eval.for_keyword("maximum");
+        kw.set_error(String::new());
+        eval.push(kw);
+        eval
+    })
+}
+
+#[test]
+fn passing_keywords_do_not_allocate() {
+    let cases = [
+        (json!({ "maximum": 10 }), json!(5)),
+        (json!({ "exclusiveMaximum": 10 }), json!(5)),
+        (json!({ "minimum": 0 }), json!(5)),
+        (json!({ "exclusiveMinimum": 0 }), json!(5)),
+        (json!({ "maximum": 10.5, "minimum": 0.5 }), json!(5.25)),
+        (json!({ "const": 5 }), json!(5)),
+        (json!({ "const": { "a": [1, 2] } }), json!({ "a": [1, 2] })),
+        (json!({ "enum": ["a", 5, null] }), json!(5)),
+    ];
+    for (schema, instance) in cases {
+        let allocations = keyword_allocations(schema.clone(), &instance);
+        assert_eq!(allocations, 0, "{} allocated against {}", schema, instance);
+    }
+}
+
+#[test]
+fn failing_keywords_allocate_once_beyond_the_error_node() {
+    let node = error_node_allocations();
+    let cases = [
+        (json!({ "maximum": 1 }), json!(5)),
+        (json!({ "exclusiveMinimum": 5 }), json!(5)),
+        (json!({ "const": 4 }), json!(5)),
+        (json!({ "enum": ["a", 4, null] }), json!(5)),
+    ];
+    for (schema, instance) in cases {
+        let allocations = keyword_allocations(schema.clone(), &instance);
+        assert!(
+            allocations <= node + 1,
+            "{} allocated {} times against {}, expected at most {}",
+            schema,
+            allocations,
+            instance,
+            node + 1
+        );
+    }
+}
+
+#[test]
+fn failing_allocations_do_not_grow_with_the_schema() {
+    let small = keyword_allocations(json!({ "enum": [1] }), &json!(5));
+    let large = keyword_allocations(json!({ "enum": (0..1000).collect::<Vec<_>>() }), &json!(-5));
+    assert_eq!(small, large);
+}
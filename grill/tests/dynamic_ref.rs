@@ -0,0 +1,159 @@
+//! `"$dynamicRef"` resolution, after the cases of `dynamicRef.json` in the
+//! JSON Schema Test Suite.
+
+use grill::{evaluation::Options, uri::AbsoluteUri, Interrogator, OutputFmt, Schema};
+use serde_json::{json, Value};
+
+const BASE: &str = "https://grill.test/dynamic/";
+
+/// Sources each of `documents`, named relative to [`BASE`] with their
+/// `"$id"` set accordingly, and compiles the first.
+fn compile(documents: &[(&str, Value)]) -> Schema {
+    let interrogator = Interrogator::new();
+    for (name, document) in documents {
+        let uri = format!("{}{}", BASE, name);
+        let mut document = document.clone();
+        document["$id"] = Value::String(uri.clone());
+        interrogator.source_value(AbsoluteUri::parse(&uri).unwrap(), document);
+    }
+    let ids = interrogator
+        .compile_all([format!("{}{}", BASE, documents[0].0)])
+        .unwrap();
+    interrogator.schema(&ids[0]).unwrap()
+}
+
+fn is_valid(schema: &Schema, instance: Value) -> bool {
+    schema
+        .evaluate(&instance, OutputFmt::Basic)
+        .unwrap()
+        .is_valid()
+}
+
+/// A schema which applies the `"$dynamicAnchor"` `"T"` in scope, and accepts
+/// anything if no other is in scope.
+fn generic() -> (&'static str, Value) {
+    (
+        "generic",
+        json!({
+            "$dynamicRef": "#T",
+            "$defs": { "T": { "$dynamicAnchor": "T" } }
+        }),
+    )
+}
+
+/// A schema which specializes [`generic`] so that `"T"` is at most `10`.
+fn specialized() -> (&'static str, Value) {
+    (
+        "specialized",
+        json!({
+            "$ref": "generic",
+            "$defs": { "T": { "$dynamicAnchor": "T", "maximum": 10 } }
+        }),
+    )
+}
+
+#[test]
+fn dynamic_ref_to_an_anchor_in_the_same_resource_behaves_like_ref() {
+    let schema = compile(&[(
+        "same-resource",
+        json!({
+            "$dynamicRef": "#items",
+            "$defs": { "foo": { "$dynamicAnchor": "items", "maximum": 10 } }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!(10)));
+    assert!(!is_valid(&schema, json!(11)));
+}
+
+#[test]
+fn dynamic_ref_resolves_to_the_outermost_anchor_in_scope() {
+    let schema = compile(&[specialized(), generic()]);
+    assert!(is_valid(&schema, json!(10)));
+    assert!(!is_valid(&schema, json!(11)));
+
+    let schema = compile(&[generic(), specialized()]);
+    assert!(is_valid(&schema, json!(11)));
+}
+
+#[test]
+fn intermediate_resources_without_the_anchor_do_not_affect_resolution() {
+    let schema = compile(&[
+        (
+            "outer",
+            json!({
+                "$ref": "intermediate",
+                "$defs": { "T": { "$dynamicAnchor": "T", "maximum": 10 } }
+            }),
+        ),
+        ("intermediate", json!({ "$ref": "generic" })),
+        generic(),
+    ]);
+    assert!(is_valid(&schema, json!(10)));
+    assert!(!is_valid(&schema, json!(11)));
+}
+
+#[test]
+fn dynamic_ref_without_an_anchor_fragment_behaves_like_ref() {
+    let schema = compile(&[
+        (
+            "pointer-root",
+            json!({
+                "$ref": "pointer",
+                "$defs": { "T": { "$dynamicAnchor": "T", "maximum": 1 } }
+            }),
+        ),
+        (
+            "pointer",
+            json!({
+                "$dynamicRef": "#/$defs/T",
+                "$defs": { "T": { "$dynamicAnchor": "T", "maximum": 100 } }
+            }),
+        ),
+    ]);
+    assert!(is_valid(&schema, json!(50)));
+    assert!(!is_valid(&schema, json!(101)));
+}
+
+#[test]
+fn dynamic_ref_to_a_static_anchor_behaves_like_ref() {
+    let schema = compile(&[
+        (
+            "static-root",
+            json!({
+                "$ref": "static",
+                "$defs": { "T": { "$dynamicAnchor": "T", "maximum": 10 } }
+            }),
+        ),
+        (
+            "static",
+            json!({
+                "$dynamicRef": "#T",
+                "$defs": { "T": { "$anchor": "T" } }
+            }),
+        ),
+    ]);
+    assert!(is_valid(&schema, json!(11)));
+}
+
+#[test]
+fn memoization_respects_the_dynamic_scope() {
+    // "generic" is applied to the same instance location twice: first with
+    // itself outermost, accepting anything, then within "specialized"
+    let schema = compile(&[
+        (
+            "memo",
+            json!({ "oneOf": [{ "$ref": "generic" }, { "$ref": "specialized" }] }),
+        ),
+        generic(),
+        specialized(),
+    ]);
+    let options = Options::default().memoize(true);
+    let eval = |instance: Value| {
+        schema
+            .evaluate_with_options(&instance, OutputFmt::Basic, options.clone())
+            .unwrap()
+            .is_valid()
+    };
+    assert!(eval(json!(11)));
+    assert!(!eval(json!(5)));
+}
@@ -0,0 +1,55 @@
+//! Insertion of individually constructed schemas with
+//! `Interrogator::insert_schema`.
+
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt, Schema, Uri};
+use serde_json::{json, Value};
+
+const TARGET: &str = "https://grill.test/insert/name.json";
+
+/// Returns an `Interrogator` which has compiled a string schema at `TARGET`.
+fn interrogator() -> Interrogator {
+    let interrogator = Interrogator::new();
+    interrogator.source_value(
+        AbsoluteUri::parse(TARGET).unwrap(),
+        json!({ "$id": TARGET, "type": "string" }),
+    );
+    interrogator.compile_all([TARGET]).unwrap();
+    interrogator
+}
+
+fn is_valid(schema: &Schema, instance: Value) -> bool {
+    schema
+        .evaluate(&instance, OutputFmt::Basic)
+        .unwrap()
+        .is_valid()
+}
+
+#[test]
+fn insert_schema_with_ref() {
+    let interrogator = interrogator();
+    let id = "https://grill.test/insert/ref.json";
+    let schema = Schema::new(json!({ "$id": id, "$ref": TARGET }), &interrogator).unwrap();
+    assert!(interrogator.insert_schema(schema).unwrap().is_none());
+
+    let schema = interrogator.schema(&Uri::parse(id).unwrap()).unwrap();
+    assert!(is_valid(&schema, json!("Ada")));
+    assert!(!is_valid(&schema, json!(36)));
+}
+
+#[test]
+fn insert_schema_with_ref_to_inserted_schema() {
+    // the target has no source document, so following the reference to
+    // determine whether it is pure reads the inserted schemas
+    let interrogator = Interrogator::new();
+    let target = "https://grill.test/insert/inserted.json";
+    let schema = Schema::new(json!({ "$id": target, "type": "integer" }), &interrogator).unwrap();
+    interrogator.insert_schema(schema).unwrap();
+
+    let id = "https://grill.test/insert/ref-inserted.json";
+    let schema = Schema::new(json!({ "$id": id, "$ref": target }), &interrogator).unwrap();
+    interrogator.insert_schema(schema).unwrap();
+
+    let schema = interrogator.schema(&Uri::parse(id).unwrap()).unwrap();
+    assert!(is_valid(&schema, json!(36)));
+    assert!(!is_valid(&schema, json!("Ada")));
+}
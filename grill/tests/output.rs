@@ -0,0 +1,137 @@
+//! Property tests of the conversions between the output formats of
+//! [`grill::output`].
+
+use grill::{
+    output::{Basic, Detailed, Flag, Verbose},
+    Evaluation, OutputFmt,
+};
+use jsonptr::Pointer;
+use proptest::prelude::*;
+
+/// The shape of an [`Evaluation`] to generate.
+#[derive(Debug, Clone)]
+struct Node {
+    keyword: &'static str,
+    error: Option<String>,
+    annotation: Option<i64>,
+    nested: Vec<Node>,
+}
+
+const KEYWORDS: &[&str] = &[
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "0",
+    "1",
+    "2",
+    "maximum",
+    "const",
+    "properties",
+    "a",
+];
+
+fn node() -> impl Strategy<Value = Node> {
+    let leaf = (
+        prop::sample::select(KEYWORDS),
+        prop::option::weighted(0.3, "[a-z ]{1,12}"),
+        prop::option::weighted(0.3, any::<i64>()),
+    )
+        .prop_map(|(keyword, error, annotation)| Node {
+            keyword,
+            error,
+            annotation,
+            nested: Vec::new(),
+        });
+    leaf.prop_recursive(5, 64, 4, |inner| {
+        (
+            prop::sample::select(KEYWORDS),
+            prop::option::weighted(0.2, "[a-z ]{1,12}"),
+            prop::option::weighted(0.2, any::<i64>()),
+            prop::collection::vec(inner, 0..4),
+        )
+            .prop_map(|(keyword, error, annotation, nested)| Node {
+                keyword,
+                error,
+                annotation,
+                nested,
+            })
+    })
+}
+
+fn build(parent: &Evaluation, node: &Node) -> Evaluation {
+    let mut eval = parent.for_keyword(node.keyword);
+    if let Some(error) = &node.error {
+        eval.set_error(error.clone());
+    }
+    if let Some(annotation) = node.annotation {
+        eval.insert("annotation".to_string(), annotation).unwrap();
+    }
+    for nested in &node.nested {
+        let nested = build(&eval, nested);
+        eval.push(nested);
+    }
+    eval
+}
+
+fn evaluation(nested: &[Node]) -> Evaluation {
+    let mut eval = Evaluation::new(Pointer::default(), Pointer::default(), OutputFmt::Verbose);
+    for node in nested {
+        let node = build(&eval, node);
+        eval.push(node);
+    }
+    eval
+}
+
+fn count_errors(eval: &Evaluation) -> usize {
+    usize::from(eval.error().is_some()) + eval.nested().iter().map(count_errors).sum::<usize>()
+}
+
+fn all_invalid(node: &Detailed) -> bool {
+    node.nested.iter().all(|n| !n.unit.valid && all_invalid(n))
+}
+
+proptest! {
+    #[test]
+    fn conversions_preserve_validity(nested in prop::collection::vec(node(), 0..4)) {
+        let eval = evaluation(&nested);
+        let valid = eval.is_valid();
+        let verbose = Verbose::from(&eval);
+        prop_assert_eq!(verbose.unit.valid, valid);
+        let detailed = Detailed::from(verbose);
+        prop_assert_eq!(detailed.unit.valid, valid);
+        let basic = Basic::from(detailed.clone());
+        prop_assert_eq!(basic.valid, valid);
+        prop_assert_eq!(Basic::exhaustive(detailed).valid, valid);
+        prop_assert_eq!(Flag::from(basic).valid, valid);
+    }
+
+    #[test]
+    fn detailed_retains_only_failures_when_invalid(nested in prop::collection::vec(node(), 1..4)) {
+        let eval = evaluation(&nested);
+        let detailed = Detailed::from(Verbose::from(&eval));
+        if !eval.is_valid() {
+            prop_assert!(all_invalid(&detailed));
+        }
+    }
+
+    #[test]
+    fn exhaustive_basic_retains_every_error(nested in prop::collection::vec(node(), 1..4)) {
+        let eval = evaluation(&nested);
+        let basic = Basic::exhaustive(Detailed::from(Verbose::from(&eval)));
+        let errors = basic.units.iter().filter(|u| u.error.is_some()).count();
+        prop_assert_eq!(errors, count_errors(&eval));
+        if eval.is_valid() {
+            prop_assert!(basic.units.iter().all(|u| u.valid && !u.data.is_empty()));
+        }
+    }
+
+    #[test]
+    fn basic_explains_every_failure(nested in prop::collection::vec(node(), 1..4)) {
+        let eval = evaluation(&nested);
+        let basic = Basic::from(Detailed::from(Verbose::from(&eval)));
+        if !eval.is_valid() {
+            prop_assert!(basic.units.iter().any(|u| u.error.is_some()));
+            prop_assert!(basic.units.iter().all(|u| u.error.is_some()));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Sourcing TOML documents with the `"toml"` feature.
+
+use grill::{uri::AbsoluteUri, Interrogator};
+
+#[test]
+fn toml_source_keeps_key_order() {
+    let interrogator = Interrogator::new();
+    let uri = AbsoluteUri::parse("https://grill.test/toml/person.toml").unwrap();
+    let document = r#"
+        type = "object"
+        required = ["name"]
+
+        [properties.name]
+        type = "string"
+
+        [properties.age]
+        type = "integer"
+
+        [properties.address]
+        type = "string"
+    "#;
+    interrogator.source_str(uri.clone(), document).unwrap();
+
+    let source = interrogator.source(&uri).unwrap().unwrap();
+    let keys: Vec<&str> = source
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(keys, ["type", "required", "properties"]);
+    let properties: Vec<&str> = source["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(properties, ["name", "age", "address"]);
+}
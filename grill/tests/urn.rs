@@ -0,0 +1,120 @@
+//! Identification of schema resources by URN, after the URN cases of
+//! `ref.json` in the JSON Schema Test Suite.
+
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt, Schema};
+use serde_json::{json, Value};
+
+/// Sources each of `documents` and compiles the first.
+fn compile(documents: &[(&str, Value)]) -> Schema {
+    let interrogator = Interrogator::new();
+    for (uri, document) in documents {
+        let uri = AbsoluteUri::parse(uri).unwrap();
+        interrogator.source_value(uri, document.clone());
+    }
+    let ids = interrogator.compile_all([documents[0].0]).unwrap();
+    interrogator.schema(&ids[0]).unwrap()
+}
+
+fn is_valid(schema: &Schema, instance: Value) -> bool {
+    schema
+        .evaluate(&instance, OutputFmt::Basic)
+        .unwrap()
+        .is_valid()
+}
+
+#[test]
+fn urn_base_uri_with_json_pointer() {
+    let schema = compile(&[(
+        "https://grill.test/urn/pointer.json",
+        json!({
+            "$id": "urn:uuid:deadbeef-1234-00ff-ff00-4321feebdaed",
+            "$ref": "#/$defs/bar",
+            "$defs": { "bar": { "const": "bar" } }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!("bar")));
+    assert!(!is_valid(&schema, json!(12)));
+}
+
+#[test]
+fn urn_base_uri_with_nss_and_json_pointer() {
+    let schema = compile(&[(
+        "https://grill.test/urn/nss.json",
+        json!({
+            "$id": "urn:example:foo-bar-baz-qux",
+            "$ref": "urn:example:foo-bar-baz-qux#/$defs/bar",
+            "$defs": { "bar": { "const": "bar" } }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!("bar")));
+    assert!(!is_valid(&schema, json!(12)));
+}
+
+#[test]
+fn urn_base_uri_with_anchor() {
+    let schema = compile(&[(
+        "https://grill.test/urn/anchor.json",
+        json!({
+            "$id": "urn:uuid:deadbeef-1234-ff00-00ff-4321feebdaed",
+            "$ref": "#something",
+            "$defs": { "bar": { "$anchor": "something", "const": "bar" } }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!("bar")));
+    assert!(!is_valid(&schema, json!(12)));
+}
+
+#[test]
+fn urn_with_differently_cased_namespace_identifier() {
+    let schema = compile(&[(
+        "https://grill.test/urn/case.json",
+        json!({
+            "$id": "urn:example:root",
+            "$ref": "URN:EXAMPLE:root#/$defs/bar",
+            "$defs": { "bar": { "const": "bar" } }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!("bar")));
+    assert!(!is_valid(&schema, json!(12)));
+}
+
+#[test]
+fn embedded_urn_resource() {
+    let schema = compile(&[(
+        "https://grill.test/urn/embedded.json",
+        json!({
+            "$ref": "urn:uuid:deadbeef-4321-ffff-ffff-1234feebdaed",
+            "$defs": {
+                "bar": {
+                    "$id": "urn:uuid:deadbeef-4321-ffff-ffff-1234feebdaed",
+                    "maximum": 10
+                }
+            }
+        }),
+    )]);
+    assert!(is_valid(&schema, json!(10)));
+    assert!(!is_valid(&schema, json!(11)));
+}
+
+#[test]
+fn urn_resource_embedded_in_another_document() {
+    let schema = compile(&[
+        (
+            "https://grill.test/urn/referrer.json",
+            json!({ "$ref": "urn:example:shared#/$defs/small" }),
+        ),
+        (
+            "https://grill.test/urn/shared.json",
+            json!({
+                "$defs": {
+                    "shared": {
+                        "$id": "urn:example:shared",
+                        "$defs": { "small": { "maximum": 1 } }
+                    }
+                }
+            }),
+        ),
+    ]);
+    assert!(is_valid(&schema, json!(1)));
+    assert!(!is_valid(&schema, json!(2)));
+}
@@ -0,0 +1,246 @@
+//! Generates the `Harness` trait, a setup trait per draft, the table of
+//! suites, and the shared `Interrogator` of each suite from the directory
+//! layout of the JSON Schema Test Suite so that adding a draft to the suite
+//! does not require manual edits.
+
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A discovered draft directory, e.g. `tests/draft2020-12`.
+struct Draft {
+    /// The name of the directory, e.g. `"draft2020-12"`.
+    dir: String,
+    /// The name of the generated trait, e.g. `"Draft2020_12"`.
+    trait_name: String,
+    /// The snake-cased name used for setup methods, e.g. `"draft2020_12"`.
+    method: String,
+    has_optional: bool,
+    has_optional_format: bool,
+}
+
+impl Draft {
+    fn new(path: &Path) -> Option<Self> {
+        let dir = path.file_name()?.to_str()?.to_string();
+        let rest = dir.strip_prefix("draft")?.trim_start_matches('-');
+        let mut trait_name = String::from("Draft");
+        let mut capitalize = true;
+        for c in rest.chars() {
+            if c == '-' {
+                if rest.starts_with(|c: char| c.is_ascii_digit()) {
+                    trait_name.push('_');
+                } else {
+                    capitalize = true;
+                }
+                continue;
+            }
+            if capitalize {
+                trait_name.push(c.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                trait_name.push(c);
+            }
+        }
+        let method = if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("draft{}", rest.replace('-', "_"))
+        } else {
+            format!("draft_{}", rest.replace('-', "_"))
+        };
+        Some(Draft {
+            has_optional: path.join("optional").is_dir(),
+            has_optional_format: path.join("optional").join("format").is_dir(),
+            dir,
+            trait_name,
+            method,
+        })
+    }
+}
+
+fn discover(tests: &Path) -> Vec<Draft> {
+    let mut drafts: Vec<Draft> = fs::read_dir(tests)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| Draft::new(&path))
+        .collect();
+    drafts.sort_by(|a, b| a.dir.cmp(&b.dir));
+    drafts
+}
+
+fn generate(drafts: &[Draft]) -> String {
+    let mut out = String::new();
+    for d in drafts {
+        let _ = writeln!(
+            out,
+            "/// Setup for the `{dir}` directory of the JSON Schema Test Suite.\n\
+             ///\n\
+             /// Each method returns the [`Interrogator`] used to run the tests of\n\
+             /// the corresponding suite or `None` to skip them.\n\
+             pub trait {name} {{\n    \
+                 /// Setup for the required tests of `{dir}`.\n    \
+                 fn setup_{m}(&self) -> Option<Interrogator> {{\n        None\n    }}",
+            dir = d.dir,
+            name = d.trait_name,
+            m = d.method,
+        );
+        if d.has_optional {
+            let _ = writeln!(
+                out,
+                "    /// Setup for the tests of `{dir}/optional`. Defaults to\n    \
+                 /// [`setup_{m}`]({name}::setup_{m}).\n    \
+                 fn setup_{m}_optional(&self) -> Option<Interrogator> {{\n        \
+                     self.setup_{m}()\n    }}",
+                dir = d.dir,
+                name = d.trait_name,
+                m = d.method,
+            );
+        }
+        if d.has_optional_format {
+            let _ = writeln!(
+                out,
+                "    /// Setup for the tests of `{dir}/optional/format`. Defaults to\n    \
+                 /// [`setup_{m}_optional`]({name}::setup_{m}_optional).\n    \
+                 fn setup_{m}_optional_format(&self) -> Option<Interrogator> {{\n        \
+                     self.setup_{m}_optional()\n    }}",
+                dir = d.dir,
+                name = d.trait_name,
+                m = d.method,
+            );
+        }
+        out.push_str("}\n\n");
+    }
+
+    let bounds: Vec<&str> = drafts.iter().map(|d| d.trait_name.as_str()).collect();
+    out.push_str("/// Implemented for every type which implements the setup trait of each\n");
+    out.push_str("/// draft within the JSON Schema Test Suite.\n");
+    if bounds.is_empty() {
+        out.push_str("pub trait Harness {}\n\nimpl<T> Harness for T {}\n\n");
+    } else {
+        let bounds = bounds.join(" + ");
+        let _ = writeln!(out, "pub trait Harness: {bounds} {{}}\n");
+        let _ = writeln!(out, "impl<T> Harness for T where T: {bounds} {{}}\n");
+    }
+
+    let suites: usize = drafts
+        .iter()
+        .map(|d| 1 + usize::from(d.has_optional) + usize::from(d.has_optional_format))
+        .sum();
+    out.push_str("/// Each suite discovered within the JSON Schema Test Suite.\n");
+    out.push_str("pub const SUITES: &[Suite] = &[\n");
+    for d in drafts {
+        let _ = writeln!(
+            out,
+            "    Suite {{ draft: \"{0}\", kind: Kind::Required, dir: \"tests/{0}\" }},",
+            d.dir
+        );
+        if d.has_optional {
+            let _ = writeln!(
+                out,
+                "    Suite {{ draft: \"{0}\", kind: Kind::Optional, dir: \"tests/{0}/optional\" }},",
+                d.dir
+            );
+        }
+        if d.has_optional_format {
+            let _ = writeln!(
+                out,
+                "    Suite {{ draft: \"{0}\", kind: Kind::OptionalFormat, dir: \"tests/{0}/optional/format\" }},",
+                d.dir
+            );
+        }
+    }
+    out.push_str("];\n\n");
+
+    out.push_str("/// The shared [`Interrogator`] of each suite, indexed as [`SUITES`].\n");
+    let _ = writeln!(
+        out,
+        "static SHARED: [OnceLock<Option<Interrogator>>; {}] = [{}];\n",
+        suites,
+        vec!["OnceLock::new()"; suites].join(", ")
+    );
+
+    out.push_str("/// Returns the [`Interrogator`] provided by `harness` for `suite`, if any.\n");
+    out.push_str(
+        "pub fn setup<H: Harness>(harness: &H, suite: &Suite) -> Option<Interrogator> {\n",
+    );
+    out.push_str("    match (suite.draft, suite.kind) {\n");
+    for d in drafts {
+        let _ = writeln!(
+            out,
+            "        (\"{}\", Kind::Required) => {}::setup_{}(harness),",
+            d.dir, d.trait_name, d.method
+        );
+        if d.has_optional {
+            let _ = writeln!(
+                out,
+                "        (\"{}\", Kind::Optional) => {}::setup_{}_optional(harness),",
+                d.dir, d.trait_name, d.method
+            );
+        }
+        if d.has_optional_format {
+            let _ = writeln!(
+                out,
+                "        (\"{}\", Kind::OptionalFormat) => {}::setup_{}_optional_format(harness),",
+                d.dir, d.trait_name, d.method
+            );
+        }
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+    generate_impl_macro(drafts, &mut out);
+    out
+}
+
+/// Generates `impl_harness!`, which implements the setup trait of each
+/// draft for a type by delegating every method to a single function, so that
+/// a harness need not name the traits of drafts which may not be checked
+/// out.
+fn generate_impl_macro(drafts: &[Draft], out: &mut String) {
+    out.push_str(
+        "/// Implements the setup trait of each draft for `$harness` by calling\n\
+         /// `$setup(&harness, draft, kind)`, where `draft` is the name of the\n\
+         /// draft's directory, e.g. `\"draft2020-12\"`, and `kind` is the [`Kind`]\n\
+         /// of the suite.\n",
+    );
+    out.push_str("#[macro_export]\nmacro_rules! impl_harness {\n");
+    out.push_str("    ($harness:ty, $setup:path) => {\n");
+    for d in drafts {
+        let _ = writeln!(
+            out,
+            "        impl $crate::harness::{} for $harness {{",
+            d.trait_name
+        );
+        let mut method = |suffix: &str, kind: &str| {
+            let _ = writeln!(
+                out,
+                "            fn setup_{m}{suffix}(&self) -> Option<::grill::Interrogator> {{\n                \
+                     $setup(self, \"{dir}\", $crate::Kind::{kind})\n            }}",
+                m = d.method,
+                dir = d.dir,
+            );
+        };
+        method("", "Required");
+        if d.has_optional {
+            method("_optional", "Optional");
+        }
+        if d.has_optional_format {
+            method("_optional_format", "OptionalFormat");
+        }
+        out.push_str("        }\n");
+    }
+    out.push_str("    };\n}\n");
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let tests = manifest_dir.join("JSON-Schema-Test-Suite").join("tests");
+    println!("cargo:rerun-if-changed={}", tests.display());
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let drafts = discover(&tests);
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("harness.rs");
+    fs::write(out, generate(&drafts)).expect("failed to write generated harness");
+}
@@ -0,0 +1,87 @@
+//! Selection of a subset of the JSON Schema Test Suite with environment
+//! variables, e.g. to run only the `refRemote` tests of draft 2020-12:
+//!
+//! ```sh
+//! GRILL_SUITE_DRAFT=2020-12 GRILL_SUITE_FILTER=ref_remote cargo test -p test
+//! ```
+//!
+//! Each variable is a comma-separated list; a suite or file is selected if it
+//! matches any entry, or if the variable is unset or empty. Names are
+//! compared case-insensitively and without `-` or `_`, so `ref_remote`
+//! matches `refRemote.json` and `2020-12` matches `draft2020-12`.
+
+use std::{env, path::Path, sync::OnceLock};
+
+/// The variable listing the drafts to run, e.g. `"2019-09,2020-12"`.
+pub const DRAFT_VAR: &str = "GRILL_SUITE_DRAFT";
+
+/// The variable listing the test files to run, e.g. `"ref,ref_remote"`.
+pub const FILTER_VAR: &str = "GRILL_SUITE_FILTER";
+
+/// The drafts and test files selected to run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    drafts: Vec<String>,
+    files: Vec<String>,
+}
+
+impl Filter {
+    /// Creates and returns a `Filter` from [`DRAFT_VAR`] and [`FILTER_VAR`].
+    pub fn from_env() -> Self {
+        let list = |var: &str| -> Vec<String> {
+            env::var(var)
+                .unwrap_or_default()
+                .split(',')
+                .map(fold)
+                .filter(|entry| !entry.is_empty())
+                .collect()
+        };
+        Self {
+            drafts: list(DRAFT_VAR),
+            files: list(FILTER_VAR),
+        }
+    }
+
+    /// Returns the `Filter` read from the environment upon first use, shared
+    /// by each test thread.
+    pub fn global() -> &'static Filter {
+        static FILTER: OnceLock<Filter> = OnceLock::new();
+        FILTER.get_or_init(Filter::from_env)
+    }
+
+    /// Returns `true` if every suite and file is selected.
+    pub fn is_empty(&self) -> bool {
+        self.drafts.is_empty() && self.files.is_empty()
+    }
+
+    /// Returns `true` if the draft directory `draft`, e.g.
+    /// `"draft2020-12"`, is selected.
+    pub fn matches_draft(&self, draft: &str) -> bool {
+        let draft = fold(draft);
+        let draft = draft.strip_prefix("draft").unwrap_or(&draft);
+        self.drafts.is_empty()
+            || self
+                .drafts
+                .iter()
+                .any(|d| d.strip_prefix("draft").unwrap_or(d) == draft)
+    }
+
+    /// Returns `true` if the test file at `path` is selected.
+    pub fn matches_file(&self, path: &Path) -> bool {
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(fold)
+            .unwrap_or_default();
+        self.files.is_empty() || self.files.iter().any(|f| *f == stem)
+    }
+}
+
+/// Returns `name` lowercased, trimmed, and without `-` or `_`.
+fn fold(name: &str) -> String {
+    name.trim()
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,78 @@
+use crate::{
+    filter::Filter,
+    suite::{self, Optional},
+};
+use grill::Interrogator;
+use std::{io, path::PathBuf, sync::OnceLock};
+
+/// The kind of a [`Suite`] within a draft's directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// Tests directly within the draft's directory.
+    Required,
+    /// Tests within the draft's `optional` directory.
+    Optional,
+    /// Tests within the draft's `optional/format` directory.
+    OptionalFormat,
+}
+
+/// A directory of test files within the JSON Schema Test Suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Suite {
+    /// The name of the draft's directory, e.g. `"draft2020-12"`.
+    pub draft: &'static str,
+    /// The kind of the suite.
+    pub kind: Kind,
+    /// The path of the directory, relative to the root of the test suite.
+    pub dir: &'static str,
+}
+
+impl Suite {
+    /// Returns the absolute path to the directory of this suite.
+    pub fn path(&self) -> PathBuf {
+        suite::root().join(self.dir)
+    }
+
+    /// Returns `true` if the draft of this suite is selected by the
+    /// [`Filter`] of the environment.
+    pub fn is_selected(&self) -> bool {
+        Filter::global().matches_draft(self.draft)
+    }
+
+    /// Returns the paths of the test files of this suite.
+    ///
+    /// Files belonging to an [`Optional`] suite are only included if the
+    /// feature enabling it is active. Files not selected by the [`Filter`] of
+    /// the environment are excluded.
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = suite::json_files(&self.path())?;
+        files.retain(|path| Filter::global().matches_file(path));
+        if self.kind == Kind::Optional {
+            files.retain(|path| {
+                let name = path.file_name().and_then(|f| f.to_str());
+                Optional::ALL
+                    .iter()
+                    .all(|o| o.is_enabled() || name != Some(o.file_name()))
+            });
+        }
+        Ok(files)
+    }
+}
+
+/// Returns each suite of [`SUITES`] whose draft is selected by the
+/// [`Filter`] of the environment.
+pub fn selected() -> impl Iterator<Item = &'static Suite> {
+    SUITES.iter().filter(|suite| suite.is_selected())
+}
+
+/// Returns the [`Interrogator`] provided by `harness` for `suite`, set up
+/// upon first use and shared by each test thread thereafter.
+///
+/// The [`Interrogator`] of each suite is set up at most once per test
+/// binary, by the first harness to request it.
+pub fn shared<H: Harness>(harness: &H, suite: &Suite) -> Option<Interrogator> {
+    let index = SUITES.iter().position(|s| s == suite)?;
+    SHARED[index].get_or_init(|| setup(harness, suite)).clone()
+}
+
+include!(concat!(env!("OUT_DIR"), "/harness.rs"));
@@ -0,0 +1,18 @@
+//! Harness for running the [JSON Schema Test
+//! Suite](https://github.com/json-schema-org/JSON-Schema-Test-Suite) against
+//! grill.
+//!
+//! The suite is expected to be checked out as a git submodule at
+//! `test/JSON-Schema-Test-Suite`.
+
+pub mod suite;
+pub use suite::{Case, Group, Optional};
+
+pub mod harness;
+pub use harness::{selected, setup, shared, Harness, Kind, Suite, SUITES};
+
+pub mod filter;
+pub use filter::Filter;
+
+pub mod runner;
+pub use runner::{run, Failure, Outcome};
@@ -0,0 +1,234 @@
+//! Runs the cases of the selected suites against the [`Interrogator`]s of a
+//! [`Harness`].
+//!
+//! Each group is compiled into a [fork](Interrogator::fork) of the shared
+//! `Interrogator` of its suite so that the `"$id"`s of one group do not
+//! collide with those of another. Groups whose schema uses a keyword which
+//! is not a keyword of the `Interrogator`'s default dialect are skipped
+//! rather than failed, so that the outcome tracks conformance of the
+//! keywords grill implements.
+
+use crate::{
+    harness::{selected, shared, Harness, Suite},
+    suite::{self, Group, Optional},
+};
+use grill::{uri::AbsoluteUri, Interrogator, OutputFmt};
+use serde_json::Value;
+use std::{
+    collections::HashSet,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Keywords which identify or declare rather than apply to the instance, and
+/// annotations which never affect validity.
+const IGNORED_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "id",
+    "$anchor",
+    "$dynamicAnchor",
+    "$recursiveAnchor",
+    "$vocabulary",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Keywords whose value is instance data rather than schema.
+const DATA_KEYWORDS: &[&str] = &["const", "enum", "default", "examples"];
+
+/// Keywords whose value is an object of subschemas keyed by name.
+const NAMED_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "properties",
+    "patternProperties",
+    "dependentSchemas",
+    "dependencies",
+];
+
+/// A case whose evaluation did not match its expected validity.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// The test file of the case.
+    pub file: PathBuf,
+    /// The description of the case's group.
+    pub group: String,
+    /// The description of the case.
+    pub case: String,
+    /// Why the case failed.
+    pub reason: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} / {}: {}",
+            self.file.display(),
+            self.group,
+            self.case,
+            self.reason
+        )
+    }
+}
+
+/// The outcome of [`run`].
+#[derive(Debug, Clone, Default)]
+pub struct Outcome {
+    /// The number of cases which passed.
+    pub passed: usize,
+    /// The number of cases of groups using a keyword which is not
+    /// implemented.
+    pub skipped: usize,
+    /// The cases which failed.
+    pub failures: Vec<Failure>,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed,
+            self.failures.len(),
+            self.skipped
+        )?;
+        for failure in &self.failures {
+            writeln!(f, "  {failure}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the cases of each selected suite for which `harness` provides an
+/// [`Interrogator`].
+pub fn run<H: Harness>(harness: &H) -> io::Result<Outcome> {
+    let mut outcome = Outcome::default();
+    for suite in selected() {
+        let interrogator = match shared(harness, suite) {
+            Some(interrogator) => interrogator,
+            None => continue,
+        };
+        let supported = supported_keywords(&interrogator);
+        for file in suite.files()? {
+            for (index, group) in suite::load(&file)?.into_iter().enumerate() {
+                if unsupported_keyword(&group.schema, &supported).is_some() {
+                    outcome.skipped += group.tests.len();
+                    continue;
+                }
+                run_group(suite, &interrogator, &file, index, group, &mut outcome);
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+fn run_group(
+    suite: &Suite,
+    interrogator: &Interrogator,
+    file: &Path,
+    index: usize,
+    group: Group,
+    outcome: &mut Outcome,
+) {
+    let fail = |case: &str, reason: String| Failure {
+        file: file.to_path_buf(),
+        group: group.description.clone(),
+        case: case.to_string(),
+        reason,
+    };
+    let stem = file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let uri = format!("https://grill.test/{}/{}/{}.json", suite.dir, stem, index);
+    let interrogator = interrogator.fork();
+    // the optional ECMA-262 suite asserts the semantics of ECMA-262 patterns
+    #[cfg(feature = "ecmascript-regex")]
+    if file.file_name().and_then(|f| f.to_str()) == Some(Optional::EcmascriptRegex.file_name()) {
+        interrogator
+            .set_regex_engine(grill::pattern::RegexEngine::Ecma)
+            .expect("the \"regress\" feature of grill is enabled");
+    }
+    let schema = AbsoluteUri::parse(&uri)
+        .map_err(grill::Error::from)
+        .and_then(|absolute| {
+            interrogator.source_value(absolute, group.schema.clone());
+            interrogator.compile_all([uri.as_str()])
+        })
+        .map(|ids| interrogator.schema(&ids[0]).expect("schema was compiled"));
+    let schema = match schema {
+        Ok(schema) => schema,
+        Err(err) => {
+            for case in &group.tests {
+                let failure = fail(&case.description, format!("failed to compile: {err}"));
+                outcome.failures.push(failure);
+            }
+            return;
+        }
+    };
+    for case in &group.tests {
+        match schema.evaluate(&case.data, OutputFmt::Basic) {
+            Ok(eval) if eval.is_valid() == case.valid => outcome.passed += 1,
+            Ok(_) => {
+                let expected = if case.valid { "valid" } else { "invalid" };
+                let reason = format!("expected {} to be {}", case.data, expected);
+                outcome.failures.push(fail(&case.description, reason));
+            }
+            Err(err) => {
+                let reason = format!("failed to evaluate: {err}");
+                outcome.failures.push(fail(&case.description, reason));
+            }
+        }
+    }
+}
+
+/// Returns the names of the keywords of the default dialect of
+/// `interrogator`.
+fn supported_keywords(interrogator: &Interrogator) -> HashSet<&'static str> {
+    let dialect = match interrogator.dialect(&interrogator.default_meta_schema_uri()) {
+        Some(dialect) => dialect,
+        None => return HashSet::new(),
+    };
+    dialect
+        .vocabularies
+        .keys()
+        .flat_map(|vocabulary| vocabulary.applicators.iter())
+        .filter_map(|applicator| applicator.keyword())
+        .collect()
+}
+
+/// Returns the first keyword of `schema`, or of its subschemas, which is
+/// neither in `supported` nor ignorable.
+fn unsupported_keyword(schema: &Value, supported: &HashSet<&str>) -> Option<String> {
+    match schema {
+        Value::Object(obj) => obj.iter().find_map(|(keyword, value)| {
+            let k = keyword.as_str();
+            if !supported.contains(k) && !IGNORED_KEYWORDS.contains(&k) {
+                return Some(keyword.clone());
+            }
+            if DATA_KEYWORDS.contains(&k) {
+                return None;
+            }
+            match value {
+                Value::Object(named) if NAMED_KEYWORDS.contains(&k) => named
+                    .values()
+                    .find_map(|schema| unsupported_keyword(schema, supported)),
+                value => unsupported_keyword(value, supported),
+            }
+        }),
+        Value::Array(items) => items
+            .iter()
+            .find_map(|item| unsupported_keyword(item, supported)),
+        _ => None,
+    }
+}
@@ -0,0 +1,160 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Returns the path to the root of the JSON Schema Test Suite.
+pub fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("JSON-Schema-Test-Suite")
+}
+
+/// An optional suite of the JSON Schema Test Suite.
+///
+/// Optional suites cover behavior which implementations are not required to
+/// support. Each is gated behind a cargo feature of the same name so that
+/// conformance claims are precise about optional behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Optional {
+    /// `optional/bignum.json`; enabled by the `"bignum"` feature.
+    Bignum,
+    /// `optional/ecmascript-regex.json`; enabled by the `"ecmascript-regex"`
+    /// feature.
+    EcmascriptRegex,
+}
+
+impl Optional {
+    /// All optional suites known to the harness.
+    pub const ALL: [Optional; 2] = [Optional::Bignum, Optional::EcmascriptRegex];
+
+    /// The name of the cargo feature which enables this suite.
+    pub fn feature(self) -> &'static str {
+        match self {
+            Optional::Bignum => "bignum",
+            Optional::EcmascriptRegex => "ecmascript-regex",
+        }
+    }
+
+    /// The file name of this suite within each draft's `optional` directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Optional::Bignum => "bignum.json",
+            Optional::EcmascriptRegex => "ecmascript-regex.json",
+        }
+    }
+
+    /// Returns `true` if the feature for this suite is enabled.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Optional::Bignum => cfg!(feature = "bignum"),
+            Optional::EcmascriptRegex => cfg!(feature = "ecmascript-regex"),
+        }
+    }
+
+    /// Returns an iterator of the enabled optional suites.
+    pub fn enabled() -> impl Iterator<Item = Optional> {
+        Optional::ALL.into_iter().filter(|o| o.is_enabled())
+    }
+
+    /// Returns a summary of which optional suites are enabled, e.g.
+    /// `"optional suites: bignum (enabled), ecmascript-regex (disabled)"`.
+    pub fn report() -> String {
+        let suites: Vec<String> = Optional::ALL
+            .iter()
+            .map(|o| {
+                let state = if o.is_enabled() {
+                    "enabled"
+                } else {
+                    "disabled"
+                };
+                format!("{} ({})", o.feature(), state)
+            })
+            .collect();
+        format!("optional suites: {}", suites.join(", "))
+    }
+}
+
+impl fmt::Display for Optional {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.feature())
+    }
+}
+
+/// A group of test cases sharing a schema.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Group {
+    pub description: String,
+    pub schema: Value,
+    pub tests: Vec<Case>,
+}
+
+/// A single test case of a [`Group`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Case {
+    pub description: String,
+    pub data: Value,
+    pub valid: bool,
+}
+
+/// Reads and deserializes the [`Group`]s of the test file at `path`.
+pub fn load(path: &Path) -> io::Result<Vec<Group>> {
+    let data = fs::read(path)?;
+    serde_json::from_slice(&data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The base URI at which the suite expects the documents of its `remotes`
+/// directory to be served.
+pub const REMOTES_BASE_URI: &str = "http://localhost:1234/";
+
+/// Reads the documents of the suite's `remotes` directory, paired with the
+/// URI each is expected to be served at, e.g.
+/// `"http://localhost:1234/draft2020-12/subSchemas.json"`.
+pub fn remotes() -> io::Result<Vec<(String, Value)>> {
+    let dir = root().join("remotes");
+    let mut remotes = Vec::new();
+    if dir.is_dir() {
+        read_remotes(&dir, &dir, &mut remotes)?;
+    }
+    remotes.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(remotes)
+}
+
+fn read_remotes(root: &Path, dir: &Path, remotes: &mut Vec<(String, Value)>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_remotes(root, &path, remotes)?;
+            continue;
+        }
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let relative = path.strip_prefix(root).expect("path is within root");
+        let segments: Vec<_> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect();
+        let data = fs::read(&path)?;
+        let document = serde_json::from_slice(&data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        remotes.push((
+            format!("{}{}", REMOTES_BASE_URI, segments.join("/")),
+            document,
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the paths of the `.json` files directly within `dir`, sorted.
+pub(crate) fn json_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
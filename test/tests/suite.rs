@@ -0,0 +1,40 @@
+//! Runs the JSON Schema Test Suite against grill.
+//!
+//! Each draft is evaluated by an [`Interrogator`] whose default meta schema
+//! is that of the draft and which has the documents of the suite's
+//! `remotes` directory as sources. Select a subset of the suite with the
+//! variables of [`test::filter`].
+
+use grill::{draft, uri::AbsoluteUri, Interrogator, Uri};
+use test::{suite, Kind, Optional};
+
+struct Grill;
+
+fn setup(_: &Grill, draft: &str, _: Kind) -> Option<Interrogator> {
+    let meta_schema: &Uri = match draft {
+        "draft2020-12" => &draft::SCHEMA_2020_12_URI,
+        "draft2019-09" => &draft::SCHEMA_2019_09_URI,
+        "draft7" => &draft::SCHEMA_07_URI,
+        "draft4" => &draft::SCHEMA_04_URI,
+        _ => return None,
+    };
+    let interrogator = Interrogator::builder()
+        .default_meta_schema(meta_schema.clone())
+        .build()
+        .expect("the dialect of each draft is registered");
+    for (uri, document) in suite::remotes().expect("failed to read the remotes of the suite") {
+        let uri = AbsoluteUri::parse(&uri).expect("remote URIs are absolute");
+        interrogator.source_value(uri, document);
+    }
+    Some(interrogator)
+}
+
+test::impl_harness!(Grill, setup);
+
+#[test]
+fn json_schema_test_suite() {
+    let outcome = test::run(&Grill).expect("failed to read the suite");
+    println!("{}", Optional::report());
+    print!("{outcome}");
+    assert!(outcome.failures.is_empty(), "{outcome}");
+}